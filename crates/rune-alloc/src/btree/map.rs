@@ -492,6 +492,37 @@ impl<K, V> ExactSizeIterator for IterRaw<K, V> {
     }
 }
 
+/// A raw iterator over a sub-range of entries in a map where the caller is
+/// responsible for ensuring that it doesn't outlive the data it's iterating
+/// over.
+///
+/// See [BTreeMap::range_raw_with].
+#[doc(hidden)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct RangeRaw<K, V> {
+    inner: LeafRange<marker::Raw, K, V>,
+}
+
+impl<K, V> Iterator for RangeRaw<K, V> {
+    type Item = (*const K, *const V);
+
+    fn next(&mut self) -> Option<(*const K, *const V)> {
+        self.inner.next_checked()
+    }
+
+    fn last(mut self) -> Option<(*const K, *const V)> {
+        self.next_back()
+    }
+}
+
+impl<K, V> FusedIterator for RangeRaw<K, V> {}
+
+impl<K, V> DoubleEndedIterator for RangeRaw<K, V> {
+    fn next_back(&mut self) -> Option<(*const K, *const V)> {
+        self.inner.next_back_checked()
+    }
+}
+
 impl<K, V> Clone for IterRaw<K, V> {
     fn clone(&self) -> Self {
         IterRaw {
@@ -872,7 +903,12 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         into_ok(self.get_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn get_with<C, Q, E>(
+    /// Like [`BTreeMap::get`] but allows for custom value comparisons.
+    ///
+    /// The comparison must order keys the same way as the one the entries were
+    /// inserted with, otherwise the lookup might miss the entry.
+    #[doc(hidden)]
+    pub fn get_with<C, Q, E>(
         &self,
         cx: &mut C,
         key: &Q,
@@ -1336,7 +1372,13 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         into_ok(self.remove_entry_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn remove_entry_with<C: ?Sized, Q: ?Sized, E>(
+    /// Like [`BTreeMap::remove_entry`] but allows for custom value
+    /// comparisons.
+    ///
+    /// If the comparison orders keys differently from the one the entries were
+    /// inserted with, a different entry than expected might be removed.
+    #[doc(hidden)]
+    pub fn remove_entry_with<C: ?Sized, Q: ?Sized, E>(
         &mut self,
         cx: &mut C,
         key: &Q,
@@ -1499,7 +1541,12 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         into_ok(self.range_with(&mut (), range, infallible_cmp))
     }
 
-    pub(crate) fn range_with<C, Q, R, E>(
+    /// Like [`BTreeMap::range`] but allows for custom value comparisons.
+    ///
+    /// The bounds are located using the comparison, so with an inconsistent
+    /// ordering the range might not contain the expected entries.
+    #[doc(hidden)]
+    pub fn range_with<C, Q, R, E>(
         &self,
         cx: &mut C,
         range: R,
@@ -1522,6 +1569,43 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         })
     }
 
+    /// Perform a raw iteration over a sub-range of the btree using custom
+    /// value comparisons.
+    ///
+    /// See [`BTreeMap::range_with`].
+    ///
+    /// # Safety
+    ///
+    /// The returned iterator and the pointers it produces borrow from the map
+    /// without a lifetime. The caller must ensure that:
+    /// * The map is neither dropped nor modified while the iterator is in use,
+    ///   since either might deallocate the nodes being iterated over.
+    /// * The produced pointers are only dereferenced while the map is alive and
+    ///   unmodified, and only to create shared references.
+    #[doc(hidden)]
+    pub unsafe fn range_raw_with<C, Q, R, E>(
+        &self,
+        cx: &mut C,
+        range: R,
+        cmp: CmpFn<C, Q, E>,
+    ) -> Result<RangeRaw<K, V>, E>
+    where
+        C: ?Sized,
+        Q: ?Sized,
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        Ok(if let Some(root) = &self.root {
+            RangeRaw {
+                inner: root.raw().range_search(cx, range, cmp)?,
+            }
+        } else {
+            RangeRaw {
+                inner: LeafRange::none(),
+            }
+        })
+    }
+
     /// Constructs a mutable double-ended iterator over a sub-range of elements in the map.
     /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
     /// yield elements from min (inclusive) to max (exclusive).
@@ -1614,7 +1698,13 @@ impl<K, V, A: Allocator> BTreeMap<K, V, A> {
         into_ok(self.entry_with(&mut (), key, infallible_cmp))
     }
 
-    pub(crate) fn entry_with<C: ?Sized, E>(
+    /// Like [`BTreeMap::entry`] but allows for custom value comparisons.
+    ///
+    /// The comparison implementation must be coherent with the one used for
+    /// every other operation on the map, else the map might end up in an
+    /// inconsistent order.
+    #[doc(hidden)]
+    pub fn entry_with<C: ?Sized, E>(
         &mut self,
         cx: &mut C,
         key: K,
@@ -88,6 +88,18 @@ impl<'a, K, V> LeafRange<marker::ValMut<'a>, K, V> {
     }
 }
 
+impl<K, V> LeafRange<marker::Raw, K, V> {
+    #[inline]
+    pub(crate) fn next_checked(&mut self) -> Option<(*const K, *const V)> {
+        self.perform_next_checked(|kv| unsafe { ptr::read(kv) }.into_kv_raw())
+    }
+
+    #[inline]
+    pub(crate) fn next_back_checked(&mut self) -> Option<(*const K, *const V)> {
+        self.perform_next_back_checked(|kv| unsafe { ptr::read(kv) }.into_kv_raw())
+    }
+}
+
 impl<BorrowType: marker::BorrowType, K, V> LeafRange<BorrowType, K, V> {
     /// If possible, extract some result from the following KV and move to the edge beyond it.
     fn perform_next_checked<F, R>(&mut self, f: F) -> Option<R>
@@ -407,6 +419,28 @@ impl<'a, K: 'a, V: 'a> NodeRef<marker::Immut<'a>, K, V, marker::LeafOrInternal>
 }
 
 impl<K, V> NodeRef<marker::Raw, K, V, marker::LeafOrInternal> {
+    /// Finds the pair of leaf edges delimiting a specific range in a tree.
+    ///
+    /// The result is meaningful only if the tree is ordered by key, like the tree
+    /// in a `BTreeMap` is.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the tree outlives the returned range.
+    pub(crate) unsafe fn range_search<C: ?Sized, Q: ?Sized, R, E>(
+        self,
+        cx: &mut C,
+        range: R,
+        cmp: fn(&mut C, &Q, &Q) -> Result<Ordering, E>,
+    ) -> Result<LeafRange<marker::Raw, K, V>, E>
+    where
+        K: Borrow<Q>,
+        R: RangeBounds<Q>,
+    {
+        // SAFETY: raw handles are only ever used to read.
+        unsafe { self.find_leaf_edges_spanning_range(cx, range, cmp) }
+    }
+
     /// Finds the pair of leaf edges delimiting an entire tree.
     pub(crate) fn full_range(self) -> LazyLeafRange<marker::Raw, K, V> {
         full_range(self, self)
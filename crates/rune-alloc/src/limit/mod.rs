@@ -133,6 +133,30 @@ pub fn get() -> usize {
     self::no_std::rune_memory_get()
 }
 
+/// Split off half of the memory which remains available, deducting it from the
+/// current limit.
+///
+/// This is used to hand over part of the limit to work which runs under a
+/// limit of its own, like a task running on a different thread. If memory is
+/// not limited this returns [`usize::MAX`] and leaves the current limit
+/// unchanged.
+#[doc(hidden)]
+pub fn split() -> usize {
+    let memory = get();
+
+    // NB: Without a limit the available memory starts out at `usize::MAX` and
+    // is only reduced by live allocations, which can't span more than half of
+    // the address space.
+    if memory > usize::MAX / 2 {
+        return usize::MAX;
+    }
+
+    let half = memory / 2;
+    // NB: This can't fail, since less than what remains is taken.
+    let _ = take(half);
+    half
+}
+
 /// Take memory from the current budget.
 #[inline(never)]
 pub(crate) fn take(amount: usize) -> bool {
@@ -34,6 +34,11 @@ pub const VERSION: &str = include_str!(concat!(env!("OUT_DIR"), "/version.txt"))
 fn main() {
     rune::cli::Entry::new()
         .about(format_args!("The Rune Language Interpreter {VERSION}"))
-        .context(&mut |opts| rune_modules::with_config(opts.capture.is_none()))
+        .context(&mut |opts| {
+            let mut context = rune_modules::with_config(opts.capture.is_none())?;
+            let env = rune_modules::env::Env::snapshot().args(opts.args.iter().cloned());
+            context.install(env.module()?)?;
+            Ok(context)
+        })
         .run();
 }
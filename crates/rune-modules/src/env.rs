@@ -0,0 +1,524 @@
+//! The native `env` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.14.0", features = ["env"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::env::module(true)?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Scripts can read the environment of the host process, but can't modify it.
+//! To let scripts modify their environment, provide them with a virtual
+//! environment which is isolated from the one of the host process:
+//!
+//! ```rust
+//! use rune_modules::env::Env;
+//!
+//! let mut context = rune::Context::with_default_modules()?;
+//!
+//! let env = Env::new()
+//!     .var("HOME", "/home/rune")
+//!     .args(["--verbose"])
+//!     .current_dir("/home/rune");
+//!
+//! context.install(env.module()?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! fn main() {
+//!     let home = env::var("HOME");
+//!     dbg!(home, env::args());
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use rune::alloc::prelude::*;
+use rune::alloc::String;
+use rune::runtime::{Object, Ref, Vec, VmResult};
+use rune::{docstring, vm_try, ContextError, Module};
+
+/// Construct the `env` module.
+///
+/// If `stdio` is `true` the module exposes the environment of the host
+/// process, see [`Env::from_process`]. Otherwise the host is assumed to not
+/// want scripts to access the process, and an empty virtual environment is
+/// used, see [`Env::new`].
+pub fn module(stdio: bool) -> Result<Module, ContextError> {
+    if stdio {
+        Env::from_process().module()
+    } else {
+        Env::new().module()
+    }
+}
+
+/// A builder for the environment which is exposed to scripts.
+///
+/// # Examples
+///
+/// ```rust
+/// use rune_modules::env::Env;
+///
+/// let module = Env::new()
+///     .var("LANG", "en_US.UTF-8")
+///     .args(["input.txt"])
+///     .module()?;
+/// # Ok::<_, rune::ContextError>(())
+/// ```
+pub struct Env {
+    mode: Mode,
+    vars: BTreeMap<std::string::String, std::string::String>,
+    args: std::vec::Vec<std::string::String>,
+    current_dir: Option<PathBuf>,
+}
+
+/// How the variables of an environment are stored.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// Variables are only stored in the environment, and can be modified by
+    /// scripts.
+    Virtual,
+    /// Variables are read from the host process, unless they have been set
+    /// through [`Env::var`]. Scripts can't modify them.
+    Process,
+}
+
+impl Env {
+    /// Construct a virtual environment with no variables, no arguments and
+    /// no current directory.
+    ///
+    /// Changes made by scripts through `env::set_var` and `env::remove_var`
+    /// only affect this environment and are never visible to the host process.
+    pub fn new() -> Self {
+        Self {
+            mode: Mode::Virtual,
+            vars: BTreeMap::new(),
+            args: std::vec::Vec::new(),
+            current_dir: None,
+        }
+    }
+
+    /// Construct an environment backed by the host process.
+    ///
+    /// Arguments are taken from the arguments of the process, excluding the
+    /// name of the program itself, and the current directory is the one of
+    /// the process.
+    ///
+    /// Since modifying the environment of a running process is not safe while
+    /// other threads might be reading it, scripts can't modify this
+    /// environment. Use [`Env::snapshot`] to let them do so.
+    pub fn from_process() -> Self {
+        Self {
+            mode: Mode::Process,
+            vars: BTreeMap::new(),
+            args: std::env::args().skip(1).collect(),
+            current_dir: None,
+        }
+    }
+
+    /// Construct a virtual environment seeded with the variables, arguments
+    /// and current directory of the host process.
+    ///
+    /// Changes made by scripts only affect this environment.
+    pub fn snapshot() -> Self {
+        Self {
+            mode: Mode::Virtual,
+            vars: std::env::vars().collect(),
+            args: std::env::args().skip(1).collect(),
+            current_dir: std::env::current_dir().ok(),
+        }
+    }
+
+    /// Set a variable in the environment.
+    ///
+    /// For an environment backed by the host process this overrides the
+    /// variable of the process, without modifying the process.
+    pub fn var(
+        mut self,
+        key: impl Into<std::string::String>,
+        value: impl Into<std::string::String>,
+    ) -> Self {
+        self.vars.insert(key.into(), value.into());
+        self
+    }
+
+    /// Set the arguments exposed through `env::args`.
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<std::string::String>,
+    {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set the directory exposed through `env::current_dir`.
+    ///
+    /// If this is not set the current directory of the host process is used
+    /// for environments backed by the process, while virtual environments
+    /// raise an error.
+    pub fn current_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(path.into());
+        self
+    }
+
+    /// Construct the `env` module for this environment.
+    pub fn module(self) -> Result<Module, ContextError> {
+        let state = Arc::new(State {
+            mode: self.mode,
+            vars: Mutex::new(self.vars),
+            args: self.args,
+            current_dir: self.current_dir,
+        });
+
+        let mut module = Module::with_crate("env")?;
+
+        let s = state.clone();
+        module
+            .function("var", move |key: Ref<str>| s.var(&key))
+            .build()?
+            .docs(docstring! {
+                /// Fetch the environment variable `key`, returning `None` if it
+                /// is not set.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// env::set_var("GREETING", "Hello");
+                /// assert_eq!(env::var("GREETING"), Some("Hello"));
+                /// assert_eq!(env::var("NOT_SET_ANYWHERE"), None);
+                /// ```
+            })?;
+
+        let s = state.clone();
+        module
+            .function("vars", move || s.vars())
+            .build()?
+            .docs(docstring! {
+                /// Get all environment variables as an object.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// env::set_var("GREETING", "Hello");
+                /// let vars = env::vars();
+                /// assert_eq!(vars["GREETING"], "Hello");
+                /// ```
+            })?;
+
+        let s = state.clone();
+        module
+            .function("set_var", move |key: Ref<str>, value: Ref<str>| {
+                s.set_var(&key, &value)
+            })
+            .build()?
+            .docs(docstring! {
+                /// Set the environment variable `key` to `value`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `key` is empty or contains `=` or a NUL character,
+                /// if `value` contains a NUL character, or if the environment
+                /// is the one of the host process, which scripts can't modify.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// env::set_var("GREETING", "Hello");
+                /// assert_eq!(env::var("GREETING"), Some("Hello"));
+                /// ```
+            })?;
+
+        let s = state.clone();
+        module
+            .function("remove_var", move |key: Ref<str>| s.remove_var(&key))
+            .build()?
+            .docs(docstring! {
+                /// Remove the environment variable `key`.
+                ///
+                /// # Panics
+                ///
+                /// Panics if `key` is empty or contains `=` or a NUL character,
+                /// or if the environment is the one of the host process, which
+                /// scripts can't modify.
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// env::set_var("GREETING", "Hello");
+                /// env::remove_var("GREETING");
+                /// assert_eq!(env::var("GREETING"), None);
+                /// ```
+            })?;
+
+        let s = state.clone();
+        module
+            .function("args", move || s.args())
+            .build()?
+            .docs(docstring! {
+                /// Get the arguments which were passed to the script.
+                ///
+                /// When running a script with `rune run`, these are the
+                /// arguments specified after `--`.
+                ///
+                /// # Examples
+                ///
+                /// ```rune,no_run
+                /// for arg in env::args() {
+                ///     println!("{arg}");
+                /// }
+                /// ```
+            })?;
+
+        let s = state;
+        module
+            .function("current_dir", move || s.current_dir())
+            .build()?
+            .docs(docstring! {
+                /// Get the current working directory.
+                ///
+                /// # Examples
+                ///
+                /// ```rune,no_run
+                /// let dir = env::current_dir()?;
+                /// println!("{dir}");
+                /// ```
+            })?;
+
+        Ok(module)
+    }
+}
+
+impl Default for Env {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+struct State {
+    mode: Mode,
+    /// The variables of a virtual environment, or the variables which
+    /// override the ones of the process.
+    vars: Mutex<BTreeMap<std::string::String, std::string::String>>,
+    args: std::vec::Vec<std::string::String>,
+    current_dir: Option<PathBuf>,
+}
+
+impl State {
+    fn lock(&self) -> VmResult<MutexGuard<'_, BTreeMap<std::string::String, std::string::String>>> {
+        match self.vars.lock() {
+            Ok(vars) => VmResult::Ok(vars),
+            Err(..) => VmResult::panic("environment variables are poisoned"),
+        }
+    }
+
+    fn var(&self, key: &str) -> VmResult<Option<String>> {
+        let value = match vm_try!(self.lock()).get(key) {
+            Some(value) => Some(value.clone()),
+            None if self.mode == Mode::Process => std::env::var(key).ok(),
+            None => None,
+        };
+
+        let Some(value) = value else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(vm_try!(String::try_from(value))))
+    }
+
+    fn vars(&self) -> VmResult<Object> {
+        let mut object = Object::new();
+
+        let mut insert = |key: &str, value: &str| -> VmResult<()> {
+            let key = vm_try!(key.try_to_owned());
+            let value = vm_try!(rune::to_value(vm_try!(value.try_to_owned())));
+            vm_try!(object.insert(key, value));
+            VmResult::Ok(())
+        };
+
+        let vars = vm_try!(self.lock());
+
+        if self.mode == Mode::Process {
+            for (key, value) in std::env::vars() {
+                if !vars.contains_key(&key) {
+                    vm_try!(insert(&key, &value));
+                }
+            }
+        }
+
+        for (key, value) in vars.iter() {
+            vm_try!(insert(key, value));
+        }
+
+        VmResult::Ok(object)
+    }
+
+    fn set_var(&self, key: &str, value: &str) -> VmResult<()> {
+        vm_try!(self.check_modify(key));
+
+        if value.contains('\0') {
+            return VmResult::panic(format!(
+                "value of environment variable `{key}` contains a NUL character"
+            ));
+        }
+
+        vm_try!(self.lock()).insert(key.to_owned(), value.to_owned());
+        VmResult::Ok(())
+    }
+
+    fn remove_var(&self, key: &str) -> VmResult<()> {
+        vm_try!(self.check_modify(key));
+        vm_try!(self.lock()).remove(key);
+        VmResult::Ok(())
+    }
+
+    /// Check that the variable `key` can be modified by a script.
+    fn check_modify(&self, key: &str) -> VmResult<()> {
+        if self.mode == Mode::Process {
+            return VmResult::panic(
+                "the environment of the host process can't be modified by scripts",
+            );
+        }
+
+        if key.is_empty() || key.contains(['=', '\0']) {
+            return VmResult::panic(format!("invalid environment variable name `{key}`"));
+        }
+
+        VmResult::Ok(())
+    }
+
+    fn args(&self) -> VmResult<Vec> {
+        let mut args = vm_try!(Vec::with_capacity(self.args.len()));
+
+        for arg in &self.args {
+            vm_try!(args.push(vm_try!(rune::to_value(vm_try!(arg
+                .as_str()
+                .try_to_owned())))));
+        }
+
+        VmResult::Ok(args)
+    }
+
+    fn current_dir(&self) -> io::Result<std::string::String> {
+        let path = match &self.current_dir {
+            Some(path) => path.clone(),
+            None if self.mode == Mode::Process => std::env::current_dir()?,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "no current directory in virtual environment",
+                ))
+            }
+        };
+
+        Ok(path.to_string_lossy().into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rune::{Context, Source, Sources, Value, Vm};
+
+    use super::Env;
+
+    fn run<T>(env: Env, source: &str) -> rune::support::Result<T>
+    where
+        T: rune::FromValue,
+    {
+        let mut context = Context::with_default_modules()?;
+        context.install(env.module()?)?;
+
+        let mut sources = Sources::new();
+        sources.insert(Source::memory(source)?)?;
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+        Ok(rune::from_value(vm.call(["main"], ())?)?)
+    }
+
+    #[test]
+    fn test_virtual_env() -> rune::support::Result<()> {
+        let env = Env::new().var("A", "1").args(["x", "y"]);
+
+        let output: (
+            Option<String>,
+            Option<String>,
+            Option<String>,
+            Vec<String>,
+            bool,
+        ) = run(
+            env,
+            r#"
+            pub fn main() {
+                let a = env::var("A");
+                env::set_var("B", "2");
+                env::remove_var("A");
+                let vars = env::vars();
+                (a, env::var("A"), vars.get("B"), env::args(), env::current_dir().is_err())
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            output,
+            (
+                Some(String::from("1")),
+                None,
+                Some(String::from("2")),
+                vec![String::from("x"), String::from("y")],
+                true
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_process_env() -> rune::support::Result<()> {
+        let env = Env::from_process().var("RUNE_ENV_OVERRIDE", "1");
+
+        let output: (Option<String>, bool, Option<String>) = run(
+            env,
+            r#"
+            pub fn main() {
+                let vars = env::vars();
+                (env::var("RUNE_ENV_OVERRIDE"), env::current_dir().is_ok(), vars.get("RUNE_ENV_OVERRIDE"))
+            }
+            "#,
+        )?;
+
+        assert_eq!(
+            output,
+            (Some(String::from("1")), true, Some(String::from("1")))
+        );
+
+        let result = run::<Value>(
+            Env::from_process(),
+            r#"pub fn main() { env::set_var("RUNE_ENV_SET", "1") }"#,
+        );
+
+        assert!(result.is_err());
+        assert!(std::env::var_os("RUNE_ENV_SET").is_none());
+        Ok(())
+    }
+}
@@ -18,6 +18,14 @@
 //! # Ok::<_, rune::support::Error>(())
 //! ```
 //!
+//! Or restrict scripts to only touch paths inside of a given directory:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::fs::module_with_root("sandbox")?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
 //! Use it in Rune:
 //!
 //! ```rust,ignore
@@ -27,17 +35,597 @@
 //! }
 //! ```
 
-use rune::{ContextError, Module};
 use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::Vec;
+use rune::runtime::{Bytes, Formatter, Mut, Ref, Value, VmResult};
+use rune::{docstring, vm_try, vm_write, Any, ContextError, Module};
 use tokio::fs;
+use tokio::io::AsyncWriteExt;
 
 /// Construct the `fs` module.
+///
+/// Scripts can access any path the host process has access to. Use
+/// [`module_with_root`] to restrict this.
 pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    build(Sandbox { root: None })
+}
+
+/// Construct the `fs` module, where every path used by scripts is resolved
+/// relative to the given `root` directory.
+///
+/// Paths which would escape the root, either through `..` components, absolute
+/// paths outside of it or symbolic links pointing out of it, result in a
+/// [`PermissionDenied`] error being raised to the script.
+///
+/// [`PermissionDenied`]: io::ErrorKind::PermissionDenied
+pub fn module_with_root(root: impl Into<PathBuf>) -> Result<Module, ContextError> {
+    build(Sandbox {
+        root: Some(Arc::from(root.into())),
+    })
+}
+
+fn build(sandbox: Sandbox) -> Result<Module, ContextError> {
     let mut module = Module::with_crate("fs")?;
-    module.function("read_to_string", read_to_string).build()?;
+
+    module.ty::<Metadata>()?;
+    module.function_meta(Metadata::is_file)?;
+    module.function_meta(Metadata::is_dir)?;
+    module.function_meta(Metadata::is_symlink)?;
+    module.function_meta(Metadata::len)?;
+    module.function_meta(Metadata::is_readonly)?;
+    module.function_meta(Metadata::debug_fmt)?;
+
+    module.ty::<ReadDir>()?;
+    module
+        .function("next_entry", ReadDir::next_entry)
+        .build_associated::<ReadDir>()?;
+
+    module.ty::<DirEntry>()?;
+    module.function_meta(DirEntry::path)?;
+    module.function_meta(DirEntry::file_name)?;
+    module
+        .function("metadata", DirEntry::metadata)
+        .build_associated::<DirEntry>()?;
+    module.function_meta(DirEntry::debug_fmt)?;
+
+    let s = sandbox.clone();
+    module
+        .function("read_to_string", move |path: Ref<str>| {
+            read_to_string(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Read the entire contents of a file into a string.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// let contents = fs::read_to_string("file.txt").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("read", move |path: Ref<str>| {
+            read(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Read the entire contents of a file into bytes.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// let bytes = fs::read("image.png").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("write", move |path: Ref<str>, contents: Value| {
+            write(
+                s.clone(),
+                PathBuf::from(&*path),
+                contents_to_vec(contents),
+                false,
+            )
+        })
+        .build()?
+        .docs(docstring! {
+            /// Write a string or bytes as the entire contents of a file.
+            ///
+            /// The file is created if it does not exist, and truncated if it
+            /// does.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::write("file.txt", "Hello World").await?;
+            /// fs::write("file.bin", b"\x00\x01").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("append", move |path: Ref<str>, contents: Value| {
+            write(
+                s.clone(),
+                PathBuf::from(&*path),
+                contents_to_vec(contents),
+                true,
+            )
+        })
+        .build()?
+        .docs(docstring! {
+            /// Append a string or bytes to the end of a file.
+            ///
+            /// The file is created if it does not exist.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::append("log.txt", "another line\n").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("create_dir_all", move |path: Ref<str>| {
+            create_dir_all(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Recursively create a directory and all of its parent components
+            /// if they are missing.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::create_dir_all("target/reports/html").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("remove_file", move |path: Ref<str>| {
+            remove_file(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Remove a file.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::remove_file("file.txt").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("remove_dir_all", move |path: Ref<str>| {
+            remove_dir_all(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Remove a directory after removing all of its contents.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::remove_dir_all("target/reports").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("rename", move |from: Ref<str>, to: Ref<str>| {
+            rename(s.clone(), PathBuf::from(&*from), PathBuf::from(&*to))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Rename a file or directory, replacing the destination if it
+            /// already exists.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// fs::rename("a.txt", "b.txt").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("copy", move |from: Ref<str>, to: Ref<str>| {
+            copy(s.clone(), PathBuf::from(&*from), PathBuf::from(&*to))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Copy the contents of one file to another, returning the number
+            /// of bytes copied.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// let n = fs::copy("a.txt", "b.txt").await?;
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("metadata", move |path: Ref<str>| {
+            metadata(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Query the metadata of a file or directory.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// let meta = fs::metadata("file.txt").await?;
+            /// println!("{} bytes", meta.len());
+            /// ```
+        })?;
+
+    let s = sandbox.clone();
+    module
+        .function("read_dir", move |path: Ref<str>| {
+            read_dir(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Read the entries of a directory.
+            ///
+            /// Entries are produced by calling `next_entry` on the returned
+            /// value until it returns `None`.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// let entries = fs::read_dir(".").await?;
+            ///
+            /// while let Some(entry) = entries.next_entry().await? {
+            ///     println!("{}", entry.path());
+            /// }
+            /// ```
+        })?;
+
+    let s = sandbox;
+    module
+        .function("exists", move |path: Ref<str>| {
+            exists(s.clone(), PathBuf::from(&*path))
+        })
+        .build()?
+        .docs(docstring! {
+            /// Test if a path points at an existing file or directory.
+            ///
+            /// # Examples
+            ///
+            /// ```rune,no_run
+            /// if !fs::exists("output").await? {
+            ///     fs::create_dir_all("output").await?;
+            /// }
+            /// ```
+        })?;
+
     Ok(module)
 }
 
-async fn read_to_string(path: String) -> io::Result<String> {
-    fs::read_to_string(&path).await
+/// Restricts which paths scripts are allowed to touch.
+#[derive(Clone)]
+struct Sandbox {
+    root: Option<Arc<Path>>,
+}
+
+impl Sandbox {
+    /// Resolve a path provided by a script into the path to operate on.
+    async fn resolve(&self, path: &Path) -> io::Result<PathBuf> {
+        let Some(root) = &self.root else {
+            return Ok(path.to_owned());
+        };
+
+        // Paths which have been produced by the module itself, like the ones
+        // returned by `DirEntry::path`, are prefixed with the root.
+        let relative = path.strip_prefix(root).unwrap_or(path);
+
+        let mut resolved = root.to_path_buf();
+        let mut depth = 0usize;
+
+        for component in relative.components() {
+            match component {
+                Component::Normal(part) => {
+                    resolved.push(part);
+                    depth += 1;
+                }
+                Component::CurDir => {}
+                Component::ParentDir if depth > 0 => {
+                    resolved.pop();
+                    depth -= 1;
+                }
+                _ => return Err(outside_root(path)),
+            }
+        }
+
+        // Guard against symbolic links which point outside of the root by
+        // checking the closest ancestor which exists.
+        let root = fs::canonicalize(root).await?;
+        let mut current = resolved.as_path();
+
+        loop {
+            match fs::canonicalize(current).await {
+                Ok(real) if real.starts_with(&root) => break,
+                Ok(..) => return Err(outside_root(path)),
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    // A dangling symbolic link could be used to create a file
+                    // outside of the root.
+                    if fs::symlink_metadata(current).await.is_ok() {
+                        return Err(outside_root(path));
+                    }
+
+                    let Some(parent) = current.parent() else {
+                        break;
+                    };
+
+                    current = parent;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(resolved)
+    }
+}
+
+fn outside_root(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::PermissionDenied,
+        format!("path `{}` is outside of the sandbox", path.display()),
+    )
+}
+
+async fn read_to_string(sandbox: Sandbox, path: PathBuf) -> io::Result<String> {
+    fs::read_to_string(sandbox.resolve(&path).await?).await
+}
+
+async fn read(sandbox: Sandbox, path: PathBuf) -> VmResult<io::Result<Bytes>> {
+    let path = match sandbox.resolve(&path).await {
+        Ok(path) => path,
+        Err(error) => return VmResult::Ok(Err(error)),
+    };
+
+    let bytes = match fs::read(path).await {
+        Ok(bytes) => bytes,
+        Err(error) => return VmResult::Ok(Err(error)),
+    };
+
+    VmResult::Ok(Ok(Bytes::from_vec(vm_try!(Vec::try_from(bytes)))))
+}
+
+async fn write(
+    sandbox: Sandbox,
+    path: PathBuf,
+    contents: VmResult<std::vec::Vec<u8>>,
+    append: bool,
+) -> VmResult<io::Result<()>> {
+    let contents = vm_try!(contents);
+
+    let result = async {
+        let path = sandbox.resolve(&path).await?;
+
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)
+            .await?;
+
+        file.write_all(&contents).await?;
+        file.flush().await
+    };
+
+    VmResult::Ok(result.await)
+}
+
+/// Convert a value which is either a string or bytes into the bytes it
+/// contains, so that writes accept both `"text"` and `b"bytes"`.
+fn contents_to_vec(value: Value) -> VmResult<std::vec::Vec<u8>> {
+    if let Ok(string) = rune::from_value::<Ref<str>>(value.clone()) {
+        return VmResult::Ok(string.as_bytes().to_vec());
+    }
+
+    let bytes = vm_try!(rune::from_value::<Ref<Bytes>>(value));
+    VmResult::Ok(bytes.as_slice().to_vec())
+}
+
+async fn create_dir_all(sandbox: Sandbox, path: PathBuf) -> io::Result<()> {
+    fs::create_dir_all(sandbox.resolve(&path).await?).await
+}
+
+async fn remove_file(sandbox: Sandbox, path: PathBuf) -> io::Result<()> {
+    fs::remove_file(sandbox.resolve(&path).await?).await
+}
+
+async fn remove_dir_all(sandbox: Sandbox, path: PathBuf) -> io::Result<()> {
+    fs::remove_dir_all(sandbox.resolve(&path).await?).await
+}
+
+async fn rename(sandbox: Sandbox, from: PathBuf, to: PathBuf) -> io::Result<()> {
+    let from = sandbox.resolve(&from).await?;
+    let to = sandbox.resolve(&to).await?;
+    fs::rename(from, to).await
+}
+
+async fn copy(sandbox: Sandbox, from: PathBuf, to: PathBuf) -> io::Result<u64> {
+    let from = sandbox.resolve(&from).await?;
+    let to = sandbox.resolve(&to).await?;
+    fs::copy(from, to).await
+}
+
+async fn metadata(sandbox: Sandbox, path: PathBuf) -> io::Result<Metadata> {
+    let inner = fs::metadata(sandbox.resolve(&path).await?).await?;
+    Ok(Metadata { inner })
+}
+
+async fn read_dir(sandbox: Sandbox, path: PathBuf) -> io::Result<ReadDir> {
+    let inner = fs::read_dir(sandbox.resolve(&path).await?).await?;
+    Ok(ReadDir { inner })
+}
+
+async fn exists(sandbox: Sandbox, path: PathBuf) -> io::Result<bool> {
+    fs::try_exists(sandbox.resolve(&path).await?).await
+}
+
+/// Metadata information about a file or directory.
+#[derive(Any)]
+#[rune(item = ::fs)]
+struct Metadata {
+    inner: std::fs::Metadata,
+}
+
+impl Metadata {
+    /// Test if this metadata is for a regular file.
+    #[rune::function]
+    fn is_file(&self) -> bool {
+        self.inner.is_file()
+    }
+
+    /// Test if this metadata is for a directory.
+    #[rune::function]
+    fn is_dir(&self) -> bool {
+        self.inner.is_dir()
+    }
+
+    /// Test if this metadata is for a symbolic link.
+    #[rune::function]
+    fn is_symlink(&self) -> bool {
+        self.inner.is_symlink()
+    }
+
+    /// The size of the file in bytes.
+    #[rune::function]
+    fn len(&self) -> u64 {
+        self.inner.len()
+    }
+
+    /// Test if the file is read-only.
+    #[rune::function]
+    fn is_readonly(&self) -> bool {
+        self.inner.permissions().readonly()
+    }
+
+    #[rune::function(protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{:?}", self.inner)
+    }
+}
+
+/// A stream over the entries in a directory.
+#[derive(Any)]
+#[rune(item = ::fs)]
+struct ReadDir {
+    inner: fs::ReadDir,
+}
+
+impl ReadDir {
+    /// Get the next entry in the directory, or `None` if there are no more
+    /// entries.
+    async fn next_entry(mut this: Mut<ReadDir>) -> io::Result<Option<DirEntry>> {
+        let Some(inner) = this.inner.next_entry().await? else {
+            return Ok(None);
+        };
+
+        Ok(Some(DirEntry { inner }))
+    }
+}
+
+/// An entry in a directory.
+#[derive(Any)]
+#[rune(item = ::fs)]
+struct DirEntry {
+    inner: fs::DirEntry,
+}
+
+impl DirEntry {
+    /// The full path of the entry.
+    #[rune::function]
+    fn path(&self) -> String {
+        self.inner.path().to_string_lossy().into_owned()
+    }
+
+    /// The file name of the entry, without any leading path components.
+    #[rune::function]
+    fn file_name(&self) -> String {
+        self.inner.file_name().to_string_lossy().into_owned()
+    }
+
+    /// Query the metadata of the entry.
+    ///
+    /// This does not follow symbolic links.
+    async fn metadata(this: Ref<DirEntry>) -> io::Result<Metadata> {
+        let inner = this.inner.metadata().await?;
+        Ok(Metadata { inner })
+    }
+
+    #[rune::function(protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{:?}", self.inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::path::{Path, PathBuf};
+    use std::sync::Arc;
+
+    use super::Sandbox;
+
+    #[tokio::test]
+    async fn test_sandbox_resolve() {
+        let root = std::env::temp_dir().join(format!("rune-fs-sandbox-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("sub")).unwrap();
+
+        let sandbox = Sandbox {
+            root: Some(Arc::from(root.clone())),
+        };
+
+        let resolve = |path: &'static str| {
+            let sandbox = sandbox.clone();
+            async move { sandbox.resolve(Path::new(path)).await }
+        };
+
+        assert_eq!(resolve("a.txt").await.unwrap(), root.join("a.txt"));
+        assert_eq!(resolve("sub/../b.txt").await.unwrap(), root.join("b.txt"));
+        assert_eq!(
+            resolve("./sub/new/c.txt").await.unwrap(),
+            root.join("sub/new/c.txt")
+        );
+
+        let denied = |result: io::Result<PathBuf>| {
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+        };
+
+        denied(resolve("../a.txt").await);
+        denied(resolve("sub/../../a.txt").await);
+        denied(resolve("/etc/passwd").await);
+
+        let inside = root.join("sub").join("d.txt");
+        assert_eq!(sandbox.resolve(&inside).await.unwrap(), inside);
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink("/", root.join("escape")).unwrap();
+            denied(resolve("escape/etc/passwd").await);
+            std::os::unix::fs::symlink("/nonexistent-target", root.join("dangling")).unwrap();
+            denied(resolve("dangling").await);
+        }
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 }
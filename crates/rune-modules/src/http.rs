@@ -48,13 +48,22 @@
 //! }
 //! ```
 
+#[cfg(feature = "http-server")]
+pub mod server;
+
 use core::cmp::Ordering;
 use core::hash::Hash;
+use core::str::Utf8Error;
+use std::io;
 
 use rune::alloc::fmt::TryWrite;
 use rune::alloc::prelude::*;
-use rune::runtime::{Bytes, Formatter, Hasher, Ref, VmResult};
-use rune::{docstring, item, Any, ContextError, Module, ToConstValue, Value};
+use rune::runtime::{Bytes, Formatter, Hasher, Object, Ref, VmResult};
+use rune::{docstring, item, vm_try, Any, ContextError, Module, ToConstValue, Value};
+
+#[cfg(feature = "http-server")]
+use self::server::Server;
+use crate::time::Duration;
 
 /// A simple HTTP module for Rune.
 ///
@@ -73,12 +82,39 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
 
     module.ty::<Client>()?;
     module.function_meta(Client::new__meta)?;
+    module.function_meta(Client::builder__meta)?;
     module.function_meta(Client::get__meta)?;
     module.function_meta(Client::post__meta)?;
     module.function_meta(Client::put__meta)?;
     module.function_meta(Client::delete__meta)?;
     module.function_meta(Client::head__meta)?;
 
+    module.ty::<ClientBuilder>()?;
+    module.function_meta(ClientBuilder::new__meta)?;
+    module.function_meta(ClientBuilder::user_agent__meta)?;
+    module.function_meta(ClientBuilder::default_header__meta)?;
+    module.function_meta(ClientBuilder::timeout__meta)?;
+    module.function_meta(ClientBuilder::connect_timeout__meta)?;
+    module.function_meta(ClientBuilder::max_redirects__meta)?;
+    module.function_meta(ClientBuilder::cookie_store__meta)?;
+    module.function_meta(ClientBuilder::build__meta)?;
+
+    #[cfg(feature = "http-server")]
+    {
+        module.ty::<Server>()?;
+        module.function_meta(Server::bind__meta)?;
+        module.function_meta(Server::local_addr__meta)?;
+        module.function_meta(Server::max_body_size__meta)?;
+        module.function_meta(Server::route__meta)?;
+        module.function_meta(Server::get__meta)?;
+        module.function_meta(Server::post__meta)?;
+        module.function_meta(Server::put__meta)?;
+        module.function_meta(Server::delete__meta)?;
+        module.function_meta(Server::fallback__meta)?;
+        module.function_meta(Server::serve__meta)?;
+        module.function_meta(Server::spawn__meta)?;
+    }
+
     module.ty::<Response>()?;
     module.function_meta(Response::text__meta)?;
     module.function_meta(Response::json__meta)?;
@@ -86,6 +122,8 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.function_meta(Response::status__meta)?;
     module.function_meta(Response::version__meta)?;
     module.function_meta(Response::content_length__meta)?;
+    module.function_meta(Response::header__meta)?;
+    module.function_meta(Response::headers__meta)?;
 
     module.ty::<RequestBuilder>()?;
     module.function_meta(RequestBuilder::send__meta)?;
@@ -94,6 +132,16 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.function_meta(RequestBuilder::bearer_auth__meta)?;
     module.function_meta(RequestBuilder::fetch_mode_no_cors__meta)?;
     module.function_meta(RequestBuilder::body_bytes__meta)?;
+    module.function_meta(RequestBuilder::query__meta)?;
+    module.function_meta(RequestBuilder::json__meta)?;
+    module.function_meta(RequestBuilder::form__meta)?;
+    module.function_meta(RequestBuilder::multipart__meta)?;
+    module.function_meta(RequestBuilder::timeout__meta)?;
+
+    module.ty::<Form>()?;
+    module.function_meta(Form::new__meta)?;
+    module.function_meta(Form::text__meta)?;
+    module.function_meta(Form::file__meta)?;
 
     module.ty::<StatusCode>()?;
     module.function_meta(StatusCode::as_u16__meta)?;
@@ -1437,12 +1485,55 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
 #[derive(Debug, Any)]
 #[rune(item = ::http)]
 pub struct Error {
-    inner: reqwest::Error,
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    Reqwest(reqwest::Error),
+    Http(::http::Error),
+    Io(io::Error),
+    Json(serde_json::Error),
+    Utf8(Utf8Error),
 }
 
 impl From<reqwest::Error> for Error {
-    fn from(inner: reqwest::Error) -> Self {
-        Self { inner }
+    fn from(error: reqwest::Error) -> Self {
+        Self {
+            kind: ErrorKind::Reqwest(error),
+        }
+    }
+}
+
+impl From<::http::Error> for Error {
+    fn from(error: ::http::Error) -> Self {
+        Self {
+            kind: ErrorKind::Http(error),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Self {
+            kind: ErrorKind::Io(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(error: serde_json::Error) -> Self {
+        Self {
+            kind: ErrorKind::Json(error),
+        }
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(error: Utf8Error) -> Self {
+        Self {
+            kind: ErrorKind::Utf8(error),
+        }
     }
 }
 
@@ -1450,7 +1541,13 @@ impl Error {
     /// Write a display representation the error.
     #[rune::function(keep, instance, protocol = DISPLAY_FMT)]
     fn display_fmt(&self, f: &mut Formatter) -> VmResult<()> {
-        rune::vm_write!(f, "{}", self.inner)
+        match &self.kind {
+            ErrorKind::Reqwest(error) => rune::vm_write!(f, "{error}"),
+            ErrorKind::Http(error) => rune::vm_write!(f, "{error}"),
+            ErrorKind::Io(error) => rune::vm_write!(f, "{error}"),
+            ErrorKind::Json(error) => rune::vm_write!(f, "{error}"),
+            ErrorKind::Utf8(error) => rune::vm_write!(f, "{error}"),
+        }
     }
 }
 
@@ -1556,6 +1653,38 @@ impl Response {
     fn content_length(&self) -> Option<u64> {
         self.response.content_length()
     }
+
+    /// Get the value of a header of the response.
+    ///
+    /// Header names are case-insensitive. If the header is not valid UTF-8 or
+    /// is missing, `None` is returned.
+    ///
+    /// ```rune,no_run
+    /// let response = http::get("http://example.com").await?;
+    /// let content_type = response.header("Content-Type");
+    /// ```
+    #[rune::function(keep, instance)]
+    fn header(&self, name: &str) -> VmResult<Option<String>> {
+        header(self.response.headers(), name)
+    }
+
+    /// Get all headers of the response as an object keyed by lowercase header
+    /// names.
+    ///
+    /// Headers which are repeated are joined with `", "`, and headers which
+    /// are not valid UTF-8 are skipped.
+    ///
+    /// ```rune,no_run
+    /// let response = http::get("http://example.com").await?;
+    ///
+    /// for (name, value) in response.headers() {
+    ///     println!("{name}: {value}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn headers(&self) -> VmResult<Object> {
+        headers(self.response.headers())
+    }
 }
 
 /// An HTTP status code.
@@ -2136,6 +2265,337 @@ impl RequestBuilder {
             request: self.request.body(bytes.into_std()),
         }
     }
+
+    /// Append parameters to the query string of the request.
+    ///
+    /// The parameters can be an object, or a vector of key-value pairs in
+    /// case a key should be repeated.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.get("http://example.com/search")
+    ///     .query(#{ q: "rune", page: 2 })
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn query(self, query: Value) -> Self {
+        let request = match pairs(&query) {
+            Some(pairs) => self.request.query(&pairs),
+            None => self.request.query(&query),
+        };
+
+        Self { request }
+    }
+
+    /// Set the request body to a value encoded as JSON, the same way as
+    /// `json::to_bytes`, and set the `Content-Type` header to
+    /// `application/json`.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.post("http://example.com/users")
+    ///     .json(#{ name: "Alice", admin: false })
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn json(self, value: Value) -> Self {
+        Self {
+            request: self.request.json(&value),
+        }
+    }
+
+    /// Set the request body to an url-encoded form, and set the
+    /// `Content-Type` header to `application/x-www-form-urlencoded`.
+    ///
+    /// Like with [`RequestBuilder::query`], the form can be an object or a
+    /// vector of key-value pairs.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.post("http://example.com/login")
+    ///     .form(#{ username: "admin", password: "good password" })
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn form(self, form: Value) -> Self {
+        let request = match pairs(&form) {
+            Some(pairs) => self.request.form(&pairs),
+            None => self.request.form(&form),
+        };
+
+        Self { request }
+    }
+
+    /// Set the request body to a multipart form.
+    ///
+    /// ```rune,no_run
+    /// let client = http::Client::new();
+    ///
+    /// let form = http::Form::new()
+    ///     .text("title", "Report")
+    ///     .file("attachment", "report.txt", b"Hello World");
+    ///
+    /// let response = client.post("http://example.com/upload")
+    ///     .multipart(form)
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn multipart(self, form: Form) -> Self {
+        Self {
+            request: self.request.multipart(form.inner),
+        }
+    }
+
+    /// Set a timeout for the request, which applies from when the request
+    /// starts connecting until the response body has finished.
+    ///
+    /// This overrides the timeout of the client.
+    ///
+    /// ```rune,no_run
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::new();
+    ///
+    /// let response = client.get("http://example.com")
+    ///     .timeout(Duration::from_secs(5))
+    ///     .send()
+    ///     .await?;
+    /// ```
+    #[rune::function(keep)]
+    fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            request: self.request.timeout(timeout.into_std()),
+        }
+    }
+}
+
+/// A multipart form to send as the body of a request.
+///
+/// See [`RequestBuilder::multipart`].
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub struct Form {
+    inner: reqwest::multipart::Form,
+}
+
+impl Form {
+    /// Construct an empty multipart form.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let form = http::Form::new();
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self {
+            inner: reqwest::multipart::Form::new(),
+        }
+    }
+
+    /// Add a text field to the form.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let form = http::Form::new().text("name", "Alice");
+    /// ```
+    #[rune::function(keep)]
+    fn text(self, name: &str, value: &str) -> Self {
+        Self {
+            inner: self.inner.text(name.to_owned(), value.to_owned()),
+        }
+    }
+
+    /// Add a file to the form with the given file name and contents.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let form = http::Form::new().file("attachment", "hello.txt", b"Hello World");
+    /// ```
+    #[rune::function(keep)]
+    fn file(self, name: &str, file_name: &str, bytes: Bytes) -> Self {
+        let part = reqwest::multipart::Part::bytes(bytes.into_vec().into_std())
+            .file_name(file_name.to_owned());
+
+        Self {
+            inner: self.inner.part(name.to_owned(), part),
+        }
+    }
+}
+
+/// A builder to configure a [`Client`].
+///
+/// # Examples
+///
+/// ```rune
+/// use time::Duration;
+///
+/// let client = http::Client::builder()
+///     .user_agent("my-agent/1.0")
+///     .timeout(Duration::from_secs(30))
+///     .cookie_store(true)
+///     .build()?;
+/// ```
+#[derive(Debug, Any)]
+#[rune(item = ::http)]
+pub struct ClientBuilder {
+    inner: reqwest::ClientBuilder,
+    headers: reqwest::header::HeaderMap,
+}
+
+impl ClientBuilder {
+    /// Construct a new client builder with the default configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::ClientBuilder::new().build()?;
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self {
+            inner: reqwest::Client::builder(),
+            headers: reqwest::header::HeaderMap::new(),
+        }
+    }
+
+    /// Set the `User-Agent` header sent with every request.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().user_agent("my-agent/1.0").build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn user_agent(self, user_agent: &str) -> Self {
+        Self {
+            inner: self.inner.user_agent(user_agent),
+            ..self
+        }
+    }
+
+    /// Add a header which is sent with every request, erroring if the name or
+    /// value of the header is not valid.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder()
+    ///     .default_header("Accept", "application/json")?
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn default_header(mut self, key: &str, value: &str) -> Result<Self, Error> {
+        let key =
+            reqwest::header::HeaderName::from_bytes(key.as_bytes()).map_err(::http::Error::from)?;
+        let value = reqwest::header::HeaderValue::from_str(value).map_err(::http::Error::from)?;
+        self.headers.append(key, value);
+        Ok(self)
+    }
+
+    /// Set a timeout for requests, which applies from when a request starts
+    /// connecting until the response body has finished.
+    ///
+    /// By default there is no timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::builder().timeout(Duration::from_secs(30)).build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.timeout(timeout.into_std()),
+            ..self
+        }
+    }
+
+    /// Set a timeout for only the connect phase of requests.
+    ///
+    /// By default there is no timeout.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use time::Duration;
+    ///
+    /// let client = http::Client::builder()
+    ///     .connect_timeout(Duration::from_secs(5))
+    ///     .build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn connect_timeout(self, timeout: Duration) -> Self {
+        Self {
+            inner: self.inner.connect_timeout(timeout.into_std()),
+            ..self
+        }
+    }
+
+    /// Set the maximum number of redirects which are followed for a request,
+    /// where `0` disables following redirects.
+    ///
+    /// By default up to 10 redirects are followed.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().max_redirects(0).build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn max_redirects(self, max: usize) -> Self {
+        let policy = match max {
+            0 => reqwest::redirect::Policy::none(),
+            max => reqwest::redirect::Policy::limited(max),
+        };
+
+        Self {
+            inner: self.inner.redirect(policy),
+            ..self
+        }
+    }
+
+    /// Enable a cookie store, which stores cookies set by responses and sends
+    /// them with later requests made by the client.
+    ///
+    /// By default cookies are not stored.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().cookie_store(true).build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn cookie_store(self, enable: bool) -> Self {
+        Self {
+            inner: self.inner.cookie_store(enable),
+            ..self
+        }
+    }
+
+    /// Build the client.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().build()?;
+    /// ```
+    #[rune::function(keep)]
+    fn build(self) -> Result<Client, Error> {
+        let client = self.inner.default_headers(self.headers).build()?;
+        Ok(Client { client })
+    }
 }
 
 impl Client {
@@ -2153,6 +2613,18 @@ impl Client {
         }
     }
 
+    /// Construct a builder to configure a new http client.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// let client = http::Client::builder().cookie_store(true).build()?;
+    /// ```
+    #[rune::function(keep, path = Self::builder)]
+    fn builder() -> ClientBuilder {
+        ClientBuilder::new()
+    }
+
     /// Construct a builder to GET the given `url`.
     ///
     /// # Examples
@@ -2291,6 +2763,67 @@ async fn get(url: Ref<str>) -> Result<Response, Error> {
     })
 }
 
+/// Convert a vector of `(key, value)` tuples into pairs which are url-encoded
+/// as repeated keys, or `None` if the value is something else.
+fn pairs(value: &Value) -> Option<std::vec::Vec<(Value, Value)>> {
+    let vec = value.borrow_ref::<rune::runtime::Vec>().ok()?;
+    let mut pairs = std::vec::Vec::with_capacity(vec.len());
+
+    for pair in vec.iter() {
+        let pair = pair.borrow_tuple_ref().ok()?;
+        let [key, value] = &pair[..] else {
+            return None;
+        };
+
+        pairs.push((key.clone(), value.clone()));
+    }
+
+    Some(pairs)
+}
+
+/// Get the value of a header, or `None` if it is missing or is not valid
+/// UTF-8.
+fn header(headers: &::http::HeaderMap, name: &str) -> VmResult<Option<String>> {
+    let Some(value) = headers.get(name) else {
+        return VmResult::Ok(None);
+    };
+
+    let Ok(value) = value.to_str() else {
+        return VmResult::Ok(None);
+    };
+
+    VmResult::Ok(Some(vm_try!(value.try_to_owned())))
+}
+
+/// Convert headers into an object keyed by lowercase header names.
+///
+/// Headers which are repeated are joined with `", "`, and headers which are
+/// not valid UTF-8 are skipped.
+fn headers(headers: &::http::HeaderMap) -> VmResult<Object> {
+    let mut object = Object::new();
+
+    for name in headers.keys() {
+        let mut joined = String::new();
+
+        for value in headers.get_all(name) {
+            let Ok(value) = value.to_str() else {
+                continue;
+            };
+
+            if !joined.is_empty() {
+                vm_try!(joined.try_push_str(", "));
+            }
+
+            vm_try!(joined.try_push_str(value));
+        }
+
+        let key = vm_try!(name.as_str().try_to_owned());
+        vm_try!(object.insert(key, vm_try!(rune::to_value(joined))));
+    }
+
+    VmResult::Ok(object)
+}
+
 mod const_version {
     use rune::runtime::{ConstValue, RuntimeError, Value};
 
@@ -2359,3 +2892,152 @@ mod const_status_code {
         }
     }
 }
+
+#[cfg(all(test, feature = "http-server"))]
+mod tests {
+    use std::sync::Arc;
+
+    use rune::{Context, Source, Sources, Value, Vm};
+
+    #[tokio::test]
+    async fn test_client() -> rune::support::Result<()> {
+        let mut context = Context::with_default_modules()?;
+        context.install(super::module(true)?)?;
+        context.install(super::server::module(true)?)?;
+        context.install(crate::time::module(true)?)?;
+
+        let mut sources = Sources::new();
+
+        sources.insert(Source::memory(
+            r#"
+            use http::server::Response;
+            use time::Duration;
+
+            pub async fn main() {
+                let server = http::Server::bind("127.0.0.1:0").await?;
+
+                server.post("/echo", |request| {
+                    Response::json(#{
+                        query: request.query(),
+                        content_type: request.header("Content-Type"),
+                        agent: request.header("User-Agent"),
+                        body: request.text()?,
+                    })?.header("X-Echo", "yes")
+                });
+
+                server.get("/redirect", |request| {
+                    Response::new().status(http::StatusCode::FOUND).header("Location", "/cookie")
+                });
+
+                server.get("/cookie", |request| {
+                    Response::text(request.header("Cookie").unwrap_or("none")).header("Set-Cookie", "id=42")
+                });
+
+                server.get("/slow", async |request| {
+                    time::sleep(Duration::from_secs(5)).await;
+                    Response::new()
+                });
+
+                let base = `http://${server.local_addr()?}`;
+                let server = server.spawn();
+
+                let client = http::Client::builder()
+                    .user_agent("rune-test")
+                    .cookie_store(true)
+                    .build()?;
+
+                let response = client.post(`${base}/echo`)
+                    .query(#{ a: 1 })
+                    .json(#{ hello: "world" })
+                    .send()
+                    .await?;
+
+                let headers = response.headers();
+                let json = response.json().await?;
+
+                let form = client.post(`${base}/echo`)
+                    .form([("a", "1"), ("a", "x y")])
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+
+                let multipart = http::Form::new()
+                    .text("title", "Report")
+                    .file("attachment", "report.txt", b"Hello World");
+
+                let multipart = client.post(`${base}/echo`)
+                    .multipart(multipart)
+                    .send()
+                    .await?
+                    .json()
+                    .await?;
+
+                let first = client.get(`${base}/redirect`).send().await?.text().await?;
+                let second = client.get(`${base}/cookie`).send().await?.text().await?;
+
+                let no_redirects = http::Client::builder().max_redirects(0).build()?;
+                let redirect = no_redirects.get(`${base}/redirect`).send().await?;
+                let redirect = (redirect.status().as_u16(), redirect.header("location"));
+
+                let timeout = client.get(`${base}/slow`)
+                    .timeout(Duration::from_millis(50))
+                    .send()
+                    .await
+                    .is_err();
+
+                server.shutdown();
+                Ok((headers, json, form, multipart, first, second, redirect, timeout))
+            }
+            "#,
+        )?)?;
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+        let output = vm.async_call(["main"], ()).await?;
+
+        type Output = (Value, Value, Value, Value, String, String, Value, bool);
+        let output: Result<Output, Value> = rune::from_value(output)?;
+
+        let Ok((headers, json, form, multipart, first, second, redirect, timeout)) = output else {
+            panic!("script returned an error");
+        };
+
+        let headers = serde_json::to_value(&headers)?;
+        assert_eq!(headers["x-echo"], "yes");
+        assert_eq!(headers["content-type"], "application/json");
+
+        assert_eq!(
+            serde_json::to_value(&json)?,
+            serde_json::json!({
+                "query": { "a": "1" },
+                "content_type": "application/json",
+                "agent": "rune-test",
+                "body": "{\"hello\":\"world\"}",
+            })
+        );
+
+        let form = serde_json::to_value(&form)?;
+        assert_eq!(form["content_type"], "application/x-www-form-urlencoded");
+        assert_eq!(form["body"], "a=1&a=x+y");
+
+        let multipart = serde_json::to_value(&multipart)?;
+        let content_type = multipart["content_type"].as_str().unwrap();
+        let body = multipart["body"].as_str().unwrap();
+        assert!(content_type.starts_with("multipart/form-data; boundary="));
+        assert!(body.contains("name=\"title\"\r\n\r\nReport\r\n"));
+        assert!(body.contains("name=\"attachment\"; filename=\"report.txt\""));
+        assert!(body.contains("\r\n\r\nHello World\r\n"));
+
+        assert_eq!(first, "none");
+        assert_eq!(second, "id=42");
+
+        assert_eq!(
+            serde_json::to_value(&redirect)?,
+            serde_json::json!([302, "/cookie"])
+        );
+
+        assert!(timeout);
+        Ok(())
+    }
+}
@@ -0,0 +1,1046 @@
+//! The HTTP server of the `http` module.
+//!
+//! A [`Server`] binds a local address and routes incoming requests to
+//! functions defined in scripts. Each handler is called with a [`Request`] and
+//! returns a [`Response`].
+
+use core::convert::Infallible;
+use std::io;
+use std::sync::Arc;
+use std::time::Duration;
+
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::Incoming;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper_util::rt::TokioIo;
+use tokio::net::TcpListener;
+use tokio::runtime;
+use tokio::task;
+
+use rune::alloc::prelude::TryToOwned;
+use rune::alloc::String;
+use rune::runtime::{
+    Bytes, FromValue, Function, Object, Ref, RuntimeError, SyncFunction, TypeHash, Value, VmResult,
+};
+use rune::{vm_panic, vm_try, Any, ContextError, Module};
+
+use super::{header, headers, Error, StatusCode};
+
+/// Types used by the HTTP server in the `http` module.
+///
+/// Requests are routed by an [`http::Server`] to handlers, which are called
+/// with a [`Request`] and return a [`Response`].
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// use http::server::Response;
+///
+/// let server = http::Server::bind("127.0.0.1:8080").await?;
+///
+/// server.get("/hello/:name", |request| {
+///     let name = request.param("name").unwrap();
+///     Response::text(`Hello ${name}!`)
+/// });
+///
+/// server.serve().await?;
+/// ```
+#[rune::module(::http::server)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut module = Module::from_meta(self::module_meta)?;
+
+    module.ty::<Request>()?;
+    module.function_meta(Request::method__meta)?;
+    module.function_meta(Request::path__meta)?;
+    module.function_meta(Request::query__meta)?;
+    module.function_meta(Request::param__meta)?;
+    module.function_meta(Request::header__meta)?;
+    module.function_meta(Request::headers__meta)?;
+    module.function_meta(Request::bytes__meta)?;
+    module.function_meta(Request::text__meta)?;
+    module.function_meta(Request::json__meta)?;
+
+    module.ty::<Response>()?;
+    module.function_meta(Response::new__meta)?;
+    module.function_meta(Response::text__meta)?;
+    module.function_meta(Response::json__meta)?;
+    module.function_meta(Response::bytes__meta)?;
+    module.function_meta(Response::status__meta)?;
+    module.function_meta(Response::header__meta)?;
+
+    module.ty::<ServerHandle>()?;
+    module.function_meta(ServerHandle::shutdown__meta)?;
+    module.function_meta(ServerHandle::is_finished__meta)?;
+
+    Ok(module)
+}
+
+/// An HTTP server which routes requests to functions defined in scripts.
+///
+/// Handlers are called with a [`Request`] and must return a [`Response`], or a
+/// result containing one so that errors can be propagated with `?`. They are
+/// run on the Tokio runtime of the server and can be async. Since requests are
+/// handled concurrently, closures used as handlers can only capture constant
+/// values.
+///
+/// Unless a [fallback][Server::fallback] is set, requests which do not match
+/// any route are answered with `404 Not Found`, or `405 Method Not Allowed` if
+/// the path matches a route for a different method. If a handler errors or
+/// returns an `Err`, the request is answered with `500 Internal Server Error`.
+/// Errors are logged through `tracing` and are not sent to the client.
+///
+/// Request bodies larger than [`Server::max_body_size`] are answered with
+/// `413 Payload Too Large` without calling the handler.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// use http::server::Response;
+///
+/// let server = http::Server::bind("127.0.0.1:0").await?;
+///
+/// server.post("/echo", |request| {
+///     Response::bytes(request.bytes())
+/// });
+///
+/// let url = `http://${server.local_addr()?}/echo`;
+/// let server = server.spawn();
+///
+/// let client = http::Client::new();
+/// let response = client.post(url).body_bytes(b"ping").send().await?;
+/// assert_eq!(response.bytes().await?, b"ping");
+///
+/// server.shutdown();
+/// ```
+#[derive(Any)]
+#[rune(item = ::http)]
+pub struct Server {
+    listener: TcpListener,
+    router: Router,
+}
+
+impl Server {
+    /// Bind a server to the given local address.
+    ///
+    /// Binding to port `0` picks any free port, which can be looked up through
+    /// [`Server::local_addr`].
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    /// ```
+    #[rune::function(keep, path = Self::bind)]
+    async fn bind(addr: Ref<str>) -> Result<Self, Error> {
+        let listener = TcpListener::bind(addr.as_ref()).await?;
+
+        Ok(Self {
+            listener,
+            router: Router::new(),
+        })
+    }
+
+    /// Get the local address the server is bound to.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    /// assert_eq!(server.local_addr()?, "127.0.0.1:8080");
+    /// ```
+    #[rune::function(keep, instance, vm_result)]
+    fn local_addr(&self) -> Result<String, Error> {
+        let addr = self.listener.local_addr()?;
+        Ok(String::try_from(addr.to_string()).vm?)
+    }
+
+    /// Set the maximum size in bytes of request bodies, which defaults to 2
+    /// MiB.
+    ///
+    /// Requests with larger bodies are answered with `413 Payload Too Large`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    /// server.max_body_size(64 * 1024);
+    /// ```
+    #[rune::function(keep, instance)]
+    fn max_body_size(&mut self, limit: usize) {
+        self.router.max_body_size = limit;
+    }
+
+    /// Route requests with the given method and path to a handler.
+    ///
+    /// Segments of the path which start with `:` match any segment, which
+    /// can be accessed through [`Request::param`] by the handler.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.route("OPTIONS", "/users/:id", |request| {
+    ///     Response::new().header("Allow", "GET")?
+    /// })?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn route(
+        &mut self,
+        method: &str,
+        path: &str,
+        handler: Function,
+    ) -> VmResult<Result<(), Error>> {
+        let method = match ::http::Method::from_bytes(method.as_bytes()) {
+            Ok(method) => method,
+            Err(error) => return VmResult::Ok(Err(Error::from(::http::Error::from(error)))),
+        };
+
+        vm_try!(self.router.push(Some(method), path, handler));
+        VmResult::Ok(Ok(()))
+    }
+
+    /// Route `GET` requests with the given path to a handler.
+    ///
+    /// See [`Server::route`] for how paths are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.get("/users/:id", |request| {
+    ///     Response::json(#{ id: request.param("id") })?
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn get(&mut self, path: &str, handler: Function) -> VmResult<()> {
+        self.router.push(Some(::http::Method::GET), path, handler)
+    }
+
+    /// Route `POST` requests with the given path to a handler.
+    ///
+    /// See [`Server::route`] for how paths are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.post("/webhook", async |request| {
+    ///     let event = request.json()?;
+    ///     dbg!(event);
+    ///     Response::new().status(http::StatusCode::NO_CONTENT)
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn post(&mut self, path: &str, handler: Function) -> VmResult<()> {
+        self.router.push(Some(::http::Method::POST), path, handler)
+    }
+
+    /// Route `PUT` requests with the given path to a handler.
+    ///
+    /// See [`Server::route`] for how paths are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.put("/users/:id", |request| {
+    ///     Response::new().status(http::StatusCode::NO_CONTENT)
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn put(&mut self, path: &str, handler: Function) -> VmResult<()> {
+        self.router.push(Some(::http::Method::PUT), path, handler)
+    }
+
+    /// Route `DELETE` requests with the given path to a handler.
+    ///
+    /// See [`Server::route`] for how paths are matched.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.delete("/users/:id", |request| {
+    ///     Response::new().status(http::StatusCode::NO_CONTENT)
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn delete(&mut self, path: &str, handler: Function) -> VmResult<()> {
+        self.router
+            .push(Some(::http::Method::DELETE), path, handler)
+    }
+
+    /// Set the handler for requests which do not match any route.
+    ///
+    /// The fallback handler receives requests with any method and path.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.fallback(|request| {
+    ///     Response::text(`nothing at ${request.path()}`).status(http::StatusCode::NOT_FOUND)
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn fallback(&mut self, handler: Function) -> VmResult<()> {
+        self.router.fallback = Some(vm_try!(handler.into_sync()));
+        VmResult::Ok(())
+    }
+
+    /// Serve requests until the task serving them is cancelled.
+    ///
+    /// Errors while accepting connections are logged and retried, so that
+    /// running out of file descriptors only stops the server temporarily.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    /// server.get("/", |request| Response::text("Hello World"));
+    /// server.serve().await?;
+    /// ```
+    #[rune::function(keep)]
+    async fn serve(self) -> Result<(), Error> {
+        accept(self.listener, Arc::new(self.router)).await
+    }
+
+    /// Serve requests in the background, returning a handle which can be used
+    /// to shut down the server.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// server.get("/", |request| Response::text("Hello World"));
+    ///
+    /// let url = `http://${server.local_addr()?}/`;
+    /// let server = server.spawn();
+    ///
+    /// let response = http::get(url).await?;
+    /// assert_eq!(response.text().await?, "Hello World");
+    ///
+    /// server.shutdown();
+    /// ```
+    #[rune::function(keep)]
+    fn spawn(self) -> VmResult<ServerHandle> {
+        let Ok(runtime) = runtime::Handle::try_current() else {
+            vm_panic!("servers can only be spawned inside of a Tokio runtime");
+        };
+
+        let inner = runtime.spawn(accept(self.listener, Arc::new(self.router)));
+        VmResult::Ok(ServerHandle { inner })
+    }
+}
+
+/// A handle to a server running in the background.
+///
+/// Dropping the handle leaves the server running.
+#[derive(Any)]
+#[rune(item = ::http::server)]
+pub struct ServerHandle {
+    inner: task::JoinHandle<Result<(), Error>>,
+}
+
+impl ServerHandle {
+    /// Shut down the server, closing the address it is bound to.
+    ///
+    /// Requests which are already being handled run to completion.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let server = http::Server::bind("127.0.0.1:0").await?;
+    /// let server = server.spawn();
+    /// server.shutdown();
+    /// ```
+    #[rune::function(keep)]
+    fn shutdown(&self) {
+        self.inner.abort();
+    }
+
+    /// Test if the server has stopped because it was shut down.
+    #[rune::function(keep)]
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+}
+
+/// An incoming request which is passed to a handler.
+#[derive(Any)]
+#[rune(item = ::http::server)]
+pub struct Request {
+    parts: ::http::request::Parts,
+    params: Params,
+    body: bytes::Bytes,
+}
+
+impl Request {
+    /// Get the method of the request, like `"GET"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.fallback(|request| {
+    ///     Response::text(request.method())
+    /// });
+    /// ```
+    #[rune::function(keep, instance, vm_result)]
+    fn method(&self) -> String {
+        self.parts.method.as_str().try_to_owned().vm?
+    }
+
+    /// Get the path of the request, without the query string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.fallback(|request| {
+    ///     Response::text(request.path())
+    /// });
+    /// ```
+    #[rune::function(keep, instance, vm_result)]
+    fn path(&self) -> String {
+        self.parts.uri.path().try_to_owned().vm?
+    }
+
+    /// Get the decoded parameters of the query string as an object.
+    ///
+    /// If a parameter is repeated, the last value is used.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.get("/search", |request| {
+    ///     let query = request.query();
+    ///     Response::text(query.get("q").unwrap_or(""))
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn query(&self) -> VmResult<Object> {
+        let mut object = Object::new();
+
+        let query = self.parts.uri.query().unwrap_or_default();
+
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let key = vm_try!(key.as_ref().try_to_owned());
+            let value = vm_try!(rune::to_value(vm_try!(value.as_ref().try_to_owned())));
+            vm_try!(object.insert(key, value));
+        }
+
+        VmResult::Ok(object)
+    }
+
+    /// Get a parameter captured by a `:name` segment of the route which
+    /// matched the request.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.get("/users/:id", |request| {
+    ///     Response::text(request.param("id").unwrap())
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn param(&self, name: &str) -> VmResult<Option<String>> {
+        let Some((_, value)) = self.params.iter().find(|(key, _)| key.as_ref() == name) else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(vm_try!(value.as_ref().try_to_owned())))
+    }
+
+    /// Get the value of a header of the request.
+    ///
+    /// Header names are case-insensitive. If the header is not valid UTF-8 or
+    /// is missing, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.get("/", |request| {
+    ///     Response::text(request.header("User-Agent").unwrap_or("unknown"))
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn header(&self, name: &str) -> VmResult<Option<String>> {
+        header(&self.parts.headers, name)
+    }
+
+    /// Get all headers of the request as an object keyed by lowercase header
+    /// names.
+    ///
+    /// Headers which are repeated are joined with `", "`, and headers which
+    /// are not valid UTF-8 are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.get("/", |request| {
+    ///     Response::json(request.headers())?
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn headers(&self) -> VmResult<Object> {
+        headers(&self.parts.headers)
+    }
+
+    /// Get the body of the request as bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.post("/echo", |request| {
+    ///     Response::bytes(request.bytes())
+    /// });
+    /// ```
+    #[rune::function(keep, instance, vm_result)]
+    fn bytes(&self) -> Bytes {
+        Bytes::from_vec(rune::alloc::Vec::try_from(self.body.as_ref()).vm?)
+    }
+
+    /// Get the body of the request as text, erroring if it is not valid UTF-8.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.post("/echo", |request| {
+    ///     Response::text(request.text()?)
+    /// });
+    /// ```
+    #[rune::function(keep, instance, vm_result)]
+    fn text(&self) -> Result<String, Error> {
+        let text = core::str::from_utf8(&self.body)?;
+        Ok(text.try_to_owned().vm?)
+    }
+
+    /// Get the body of the request as a Rune value decoded from JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use http::server::Response;
+    ///
+    /// let server = http::Server::bind("127.0.0.1:8080").await?;
+    ///
+    /// server.post("/users", |request| {
+    ///     let user = request.json()?;
+    ///     Response::json(#{ name: user.name })?
+    /// });
+    /// ```
+    #[rune::function(keep, instance)]
+    fn json(&self) -> Result<Value, Error> {
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+/// A response returned by a handler.
+///
+/// Responses are constructed with a body through [`Response::text`],
+/// [`Response::json`] or [`Response::bytes`], or without a body through
+/// [`Response::new`], and can then be modified by chaining calls.
+#[derive(Debug, Any)]
+#[rune(item = ::http::server)]
+pub struct Response {
+    inner: ::http::Response<bytes::Bytes>,
+}
+
+impl Response {
+    /// Construct an empty `200 OK` response.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    ///
+    /// let response = Response::new().status(http::StatusCode::ACCEPTED);
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    fn new() -> Self {
+        Self {
+            inner: ::http::Response::default(),
+        }
+    }
+
+    /// Construct a `200 OK` response with a plain text body.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    ///
+    /// let response = Response::text("Hello World");
+    /// ```
+    #[rune::function(keep, path = Self::text)]
+    fn text(text: &str) -> Self {
+        Self::with_body(text.as_bytes(), "text/plain; charset=utf-8")
+    }
+
+    /// Construct a `200 OK` response with a body encoded as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    ///
+    /// let response = Response::json(#{ "hello": "world" })?;
+    /// ```
+    #[rune::function(keep, path = Self::json)]
+    fn json(value: Value) -> Result<Self, Error> {
+        let body = serde_json::to_vec(&value)?;
+        Ok(Self::with_body(&body, "application/json"))
+    }
+
+    /// Construct a `200 OK` response with a binary body.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    ///
+    /// let response = Response::bytes(b"\x00\x01");
+    /// ```
+    #[rune::function(keep, path = Self::bytes)]
+    fn bytes(bytes: &[u8]) -> Self {
+        Self::with_body(bytes, "application/octet-stream")
+    }
+
+    /// Set the status code of the response.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    /// use http::StatusCode;
+    ///
+    /// let response = Response::text("missing").status(StatusCode::NOT_FOUND);
+    /// ```
+    #[rune::function(keep)]
+    fn status(mut self, status: StatusCode) -> Self {
+        *self.inner.status_mut() = status.inner;
+        self
+    }
+
+    /// Set a header of the response, replacing any existing value.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use http::server::Response;
+    ///
+    /// let response = Response::text("<p>Hello</p>").header("Content-Type", "text/html")?;
+    /// ```
+    #[rune::function(keep)]
+    fn header(mut self, key: &str, value: &str) -> Result<Self, Error> {
+        let key = ::http::HeaderName::from_bytes(key.as_bytes()).map_err(::http::Error::from)?;
+        let value = ::http::HeaderValue::from_str(value).map_err(::http::Error::from)?;
+        self.inner.headers_mut().insert(key, value);
+        Ok(self)
+    }
+
+    fn with_body(body: &[u8], content_type: &'static str) -> Self {
+        let mut inner = ::http::Response::new(bytes::Bytes::copy_from_slice(body));
+
+        inner.headers_mut().insert(
+            ::http::header::CONTENT_TYPE,
+            ::http::HeaderValue::from_static(content_type),
+        );
+
+        Self { inner }
+    }
+}
+
+/// The default maximum size of request bodies.
+const DEFAULT_MAX_BODY_SIZE: usize = 2 * 1024 * 1024;
+
+/// The parameters captured by the `:name` segments of a route.
+type Params = Vec<(Box<str>, Box<str>)>;
+
+/// The routes of a server.
+struct Router {
+    routes: Vec<Route>,
+    fallback: Option<SyncFunction>,
+    max_body_size: usize,
+}
+
+struct Route {
+    method: Option<::http::Method>,
+    segments: Vec<Segment>,
+    handler: SyncFunction,
+}
+
+enum Segment {
+    Literal(Box<str>),
+    Param(Box<str>),
+}
+
+impl Router {
+    fn new() -> Self {
+        Self {
+            routes: Vec::new(),
+            fallback: None,
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+        }
+    }
+
+    fn push(
+        &mut self,
+        method: Option<::http::Method>,
+        path: &str,
+        handler: Function,
+    ) -> VmResult<()> {
+        let handler = vm_try!(handler.into_sync());
+
+        let segments = split(path)
+            .map(|segment| match segment.strip_prefix(':') {
+                Some(name) => Segment::Param(name.into()),
+                None => Segment::Literal(segment.into()),
+            })
+            .collect();
+
+        self.routes.push(Route {
+            method,
+            segments,
+            handler,
+        });
+
+        VmResult::Ok(())
+    }
+
+    /// Find the handler for the given request, along with the parameters
+    /// captured by its route.
+    fn find(
+        &self,
+        method: &::http::Method,
+        path: &str,
+    ) -> Result<(&SyncFunction, Params), ::http::StatusCode> {
+        let mut status = ::http::StatusCode::NOT_FOUND;
+
+        'routes: for route in &self.routes {
+            let mut params = Vec::new();
+            let mut actual = split(path);
+
+            for segment in &route.segments {
+                let Some(actual) = actual.next() else {
+                    continue 'routes;
+                };
+
+                match segment {
+                    Segment::Literal(literal) if literal.as_ref() == actual => {}
+                    Segment::Literal(..) => continue 'routes,
+                    Segment::Param(name) => params.push((name.clone(), actual.into())),
+                }
+            }
+
+            if actual.next().is_some() {
+                continue;
+            }
+
+            if route.method.as_ref().is_some_and(|m| m != method) {
+                status = ::http::StatusCode::METHOD_NOT_ALLOWED;
+                continue;
+            }
+
+            return Ok((&route.handler, params));
+        }
+
+        match &self.fallback {
+            Some(handler) => Ok((handler, Vec::new())),
+            None => Err(status),
+        }
+    }
+
+    /// Dispatch a request to its handler.
+    async fn dispatch(
+        &self,
+        request: hyper::Request<Incoming>,
+    ) -> ::http::Response<Full<bytes::Bytes>> {
+        let (parts, body) = request.into_parts();
+
+        let (handler, params) = match self.find(&parts.method, parts.uri.path()) {
+            Ok(found) => found,
+            Err(status) => return canonical(status),
+        };
+
+        let body = match Limited::new(body, self.max_body_size).collect().await {
+            Ok(body) => body.to_bytes(),
+            Err(error) if error.is::<LengthLimitError>() => {
+                return canonical(::http::StatusCode::PAYLOAD_TOO_LARGE);
+            }
+            Err(..) => return canonical(::http::StatusCode::BAD_REQUEST),
+        };
+
+        let method = parts.method.clone();
+        let path = parts.uri.path().to_owned();
+
+        let request = Request {
+            parts,
+            params,
+            body,
+        };
+
+        match handler.async_send_call::<Reply>((request,)).await {
+            VmResult::Ok(Reply::Response(response)) => response.inner.map(Full::new),
+            VmResult::Ok(Reply::Error) => canonical(::http::StatusCode::INTERNAL_SERVER_ERROR),
+            VmResult::Err(error) => {
+                tracing::error!("Handler for {method} {path} errored: {error}");
+                canonical(::http::StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+}
+
+/// The value returned by a handler, which is either a response or a result
+/// containing one.
+enum Reply {
+    Response(Response),
+    Error,
+}
+
+impl FromValue for Reply {
+    fn from_value(value: Value) -> Result<Self, RuntimeError> {
+        if value.type_hash() == Response::HASH {
+            return Ok(Self::Response(rune::from_value(value)?));
+        }
+
+        match value.downcast::<Result<Value, Value>>()? {
+            Ok(value) => Ok(Self::Response(rune::from_value(value)?)),
+            Err(..) => Ok(Self::Error),
+        }
+    }
+}
+
+/// Accept connections and serve requests on them.
+async fn accept(listener: TcpListener, router: Arc<Router>) -> Result<(), Error> {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(error) if is_connection_error(&error) => continue,
+            Err(error) => {
+                // NB: Errors like running out of file descriptors go away once
+                // other connections are closed, so back off and retry.
+                tracing::error!("Failed to accept connection: {error}");
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+
+        let router = router.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let router = router.clone();
+                async move { Ok::<_, Infallible>(router.dispatch(request).await) }
+            });
+
+            // NB: Errors on a single connection, like a client hanging up,
+            // should not affect the rest of the server.
+            _ = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+/// Test if an error from accepting is specific to the connection being
+/// accepted, in which case the next connection can be accepted immediately.
+fn is_connection_error(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::ConnectionRefused
+            | io::ErrorKind::ConnectionAborted
+            | io::ErrorKind::ConnectionReset
+    )
+}
+
+/// Split a path into its non-empty segments.
+fn split(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
+/// Construct a response with the canonical reason of the status as its body.
+fn canonical(status: ::http::StatusCode) -> ::http::Response<Full<bytes::Bytes>> {
+    let reason = status.canonical_reason().unwrap_or_default();
+    let mut response =
+        ::http::Response::new(Full::new(bytes::Bytes::from_static(reason.as_bytes())));
+    *response.status_mut() = status;
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rune::{Context, Source, Sources, Value, Vm};
+
+    #[tokio::test]
+    async fn test_server() -> rune::support::Result<()> {
+        let mut context = Context::with_default_modules()?;
+        context.install(super::super::module(true)?)?;
+        context.install(super::module(true)?)?;
+        context.install(crate::json::module(true)?)?;
+
+        let mut sources = Sources::new();
+
+        sources.insert(Source::memory(
+            r#"
+            use http::server::Response;
+            use http::StatusCode;
+
+            pub async fn main() {
+                let server = http::Server::bind("127.0.0.1:0").await?;
+
+                server.get("/users/:id", |request| {
+                    Response::text(`user ${request.param("id").unwrap()}`)
+                });
+
+                server.post("/echo", async |request| {
+                    let response = Response::json(#{
+                        method: request.method(),
+                        query: request.query(),
+                        header: request.header("X-Test"),
+                        body: request.json()?,
+                    })?;
+
+                    response.status(StatusCode::CREATED).header("X-Reply", "yes")
+                });
+
+                server.route("PUT", "/fail", |request| Err("oops"))?;
+                server.get("/panic", |request| panic!("secret"));
+                server.max_body_size(16);
+
+                let base = `http://${server.local_addr()?}`;
+                let server = server.spawn();
+                let client = http::Client::new();
+
+                let user = client.get(`${base}/users/42`).send().await?;
+                let user = (user.status().as_u16(), user.text().await?);
+
+                let echo = client.post(`${base}/echo?a=1&b=x%20y`)
+                    .header("X-Test", "hello")
+                    .body_bytes(b"[1, 2]")
+                    .send()
+                    .await?;
+
+                let echo = (echo.status().as_u16(), echo.json().await?);
+
+                let missing = client.get(`${base}/users`).send().await?.status().as_u16();
+                let wrong = client.get(`${base}/echo`).send().await?.status().as_u16();
+                let fail = client.put(`${base}/fail`).send().await?.status().as_u16();
+
+                let panic = client.get(`${base}/panic`).send().await?;
+                let panic = (panic.status().as_u16(), panic.text().await?);
+
+                let large = client.post(`${base}/echo`)
+                    .body_bytes(b"[1, 2, 3, 4, 5, 6, 7, 8, 9]")
+                    .send()
+                    .await?
+                    .status()
+                    .as_u16();
+
+                server.shutdown();
+                Ok((user, echo, missing, wrong, fail, panic, large))
+            }
+            "#,
+        )?)?;
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+        let output = vm.async_call(["main"], ()).await?;
+
+        type Output = (
+            (u16, String),
+            (u16, Value),
+            u16,
+            u16,
+            u16,
+            (u16, String),
+            u16,
+        );
+
+        let output: Result<Output, Value> = rune::from_value(output)?;
+
+        let Ok((user, echo, missing, wrong, fail, panic, large)) = output else {
+            panic!("script returned an error");
+        };
+
+        assert_eq!(user, (200, String::from("user 42")));
+        assert_eq!(echo.0, 201);
+
+        assert_eq!(
+            serde_json::to_value(&echo.1)?,
+            serde_json::json!({
+                "method": "POST",
+                "query": { "a": "1", "b": "x y" },
+                "header": "hello",
+                "body": [1, 2],
+            })
+        );
+
+        assert_eq!(missing, 404);
+        assert_eq!(wrong, 405);
+        assert_eq!(fail, 500);
+        assert_eq!(panic, (500, String::from("Internal Server Error")));
+        assert_eq!(large, 413);
+        Ok(())
+    }
+}
@@ -27,6 +27,7 @@
 //! See each module for documentation:
 //! * [base64]
 //! * [core]
+//! * [env]
 //! * [fmt]
 //! * [fs]
 //! * [http]
@@ -35,6 +36,7 @@
 //! * [macros]
 //! * [process]
 //! * [rand]
+//! * [regex]
 //! * [signal]
 //! * [test]
 //! * [time]
@@ -45,21 +47,31 @@
 //! ## Features
 //!
 //! * `core` for the [core module][toml]
+//! * `env` for the [env module][env]
 //! * `fmt` for the [fmt module][fmt]
 //! * `fs` for the [fs module][fs]
 //! * `full` includes all modules.
 //! * `http` for the [http module][http]
+//! * `http-server` for the server of the [http module][http], which
+//!   also enables `http`.
 //! * `io` for the [io module][io]
 //! * `json` for the [json module][json]
 //! * `macros` for the [macros module][macros]
-//! * `process` for the [process module][process]
+//! * `process` for the [process module][process], this also enables the
+//!   `tokio` feature of `rune` for its buffered readers and writers.
 //! * `rand` for the [rand module][rand]
+//! * `regex` for the [regex module][regex]
 //! * `signal` for the [signal module][signal]
 //! * `test` for the [test module][test]
 //! * `time` for the [time module][time]
 //! * `toml` for the [toml module][toml]
 //!
+//! Note that the [env module][env] is not installed by `with_config` or
+//! `default_context`, since the arguments it exposes are specific to how the
+//! host runs a script. It has to be installed explicitly.
+//!
 //! [core]: https://docs.rs/rune-modules/0/rune_modules/core/
+//! [env]: https://docs.rs/rune-modules/0/rune_modules/env/
 //! [fmt]: https://docs.rs/rune-modules/0/rune_modules/fmt/
 //! [fs]: https://docs.rs/rune-modules/0/rune_modules/fs/
 //! [http]: https://docs.rs/rune-modules/0/rune_modules/http/
@@ -68,6 +80,7 @@
 //! [macros]: https://docs.rs/rune-modules/0/rune_modules/macros/
 //! [process]: https://docs.rs/rune-modules/0/rune_modules/process/
 //! [rand]: https://docs.rs/rune-modules/0/rune_modules/rand/
+//! [regex]: https://docs.rs/rune-modules/0/rune_modules/regex/
 //! [signal]: https://docs.rs/rune-modules/0/rune_modules/signal/
 //! [test]: https://docs.rs/rune-modules/0/rune_modules/test/
 //! [time]: https://docs.rs/rune-modules/0/rune_modules/time/
@@ -78,7 +91,7 @@
 // https://github.com/livioribeiro/cargo-readme/issues/55
 
 macro_rules! entry {
-    ($({$ident:ident, $name:literal $(, $module:ident)*}),* $(,)?) => {
+    ($({$ident:ident, $name:literal $(, $(#[$meta:meta])* $module:ident)*}),* $(,)?) => {
         /// Construct a a default rune context with all enabled modules provided
         /// based on the [default rune
         /// context](rune::Context::with_default_modules).
@@ -91,7 +104,10 @@ macro_rules! entry {
                 #[cfg(feature = $name)]
                 {
                     context.install(self::$ident::module(stdio)?)?;
-                    $(context.install(self::$ident::$module::module(stdio)?)?;)*
+                    $(
+                        $(#[$meta])*
+                        context.install(self::$ident::$module::module(stdio)?)?;
+                    )*
                 }
             )*
 
@@ -108,6 +124,9 @@ macro_rules! entry {
 #[cfg(feature = "base64")]
 pub mod base64;
 
+#[cfg(feature = "env")]
+pub mod env;
+
 #[cfg(feature = "fs")]
 pub mod fs;
 
@@ -123,6 +142,9 @@ pub mod process;
 #[cfg(feature = "rand")]
 pub mod rand;
 
+#[cfg(feature = "regex")]
+pub mod regex;
+
 #[cfg(feature = "signal")]
 pub mod signal;
 
@@ -138,10 +160,11 @@ pub mod toml;
 entry! {
     {base64, "base64"},
     {fs, "fs"},
-    {http, "http"},
+    {http, "http", #[cfg(feature = "http-server")] server},
     {json, "json"},
     {process, "process"},
     {rand, "rand"},
+    {regex, "regex"},
     {signal, "signal"},
     {test, "test"},
     {time, "time"},
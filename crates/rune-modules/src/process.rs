@@ -35,6 +35,7 @@
 use rune::alloc::clone::TryClone;
 use rune::alloc::fmt::TryWrite;
 use rune::alloc::Vec;
+use rune::modules::io::{BufReader, BufWriter};
 use rune::runtime::{Bytes, Formatter, Mut, Value, VmResult};
 use rune::{vm_try, vm_write, Any, ContextError, Module};
 
@@ -98,14 +99,17 @@ pub fn module(_stdio: bool) -> Result<Module, ContextError> {
     module.ty::<ChildStdin>()?;
     module.function_meta(ChildStdin::debug_fmt__meta)?;
     module.function_meta(ChildStdin::try_into_stdio__meta)?;
+    module.function_meta(ChildStdin::into_buf_writer__meta)?;
 
     module.ty::<ChildStdout>()?;
     module.function_meta(ChildStdout::debug_fmt__meta)?;
     module.function_meta(ChildStdout::try_into_stdio__meta)?;
+    module.function_meta(ChildStdout::into_buf_reader__meta)?;
 
     module.ty::<ChildStderr>()?;
     module.function_meta(ChildStderr::debug_fmt__meta)?;
     module.function_meta(ChildStderr::try_into_stdio__meta)?;
+    module.function_meta(ChildStderr::into_buf_reader__meta)?;
 
     Ok(module)
 }
@@ -794,3 +798,130 @@ macro_rules! stdio_stream {
 stdio_stream!(ChildStdin, "stdin");
 stdio_stream!(ChildStdout, "stdout");
 stdio_stream!(ChildStderr, "stderr");
+
+impl ChildStdin {
+    /// Convert into a buffered writer, which allows writing to the child.
+    ///
+    /// The writer has to be shut down for the child to see the end of its
+    /// input.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("cat");
+    /// command.stdin(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let writer = child.stdin().unwrap().into_buf_writer();
+    /// writer.write_all("Hello World\n").await?;
+    /// writer.shutdown().await?;
+    /// ```
+    #[rune::function(keep, instance)]
+    fn into_buf_writer(self) -> BufWriter {
+        BufWriter::new(self.inner)
+    }
+}
+
+impl ChildStdout {
+    /// Convert into a buffered reader, which allows reading the output of the
+    /// child as it is produced.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("ls");
+    /// command.stdout(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let lines = child.stdout().unwrap().into_buf_reader().lines();
+    ///
+    /// while let Some(line) = lines.next_line().await? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn into_buf_reader(self) -> BufReader {
+        BufReader::new(self.inner)
+    }
+}
+
+impl ChildStderr {
+    /// Convert into a buffered reader, which allows reading the errors of the
+    /// child as they are produced.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("ls");
+    /// command.stderr(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let lines = child.stderr().unwrap().into_buf_reader().lines();
+    ///
+    /// while let Some(line) = lines.next_line().await? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance)]
+    fn into_buf_reader(self) -> BufReader {
+        BufReader::new(self.inner)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::sync::Arc;
+
+    use rune::{Context, Source, Sources, Vm};
+
+    #[tokio::test]
+    async fn test_buffered_pipes() -> rune::support::Result<()> {
+        let mut context = Context::with_default_modules()?;
+        context.install(super::module(true)?)?;
+
+        let mut sources = Sources::new();
+
+        sources.insert(Source::memory(
+            r#"
+            use process::{Command, Stdio};
+
+            pub async fn main() {
+                let command = Command::new("cat");
+                command.stdin(Stdio::piped());
+                command.stdout(Stdio::piped());
+
+                let child = command.spawn()?;
+                let writer = child.stdin().unwrap().into_buf_writer();
+                let lines = child.stdout().unwrap().into_buf_reader().lines();
+
+                writer.write_all("first\n").await?;
+                writer.write_all(b"second\r\n").await?;
+                writer.shutdown().await?;
+
+                let output = [];
+
+                while let Some(line) = lines.next_line().await? {
+                    output.push(line);
+                }
+
+                child.wait().await?;
+                Ok(output)
+            }
+            "#,
+        )?)?;
+
+        let unit = rune::prepare(&mut sources).with_context(&context).build()?;
+        let mut vm = Vm::new(Arc::new(context.runtime()?), Arc::new(unit));
+        let output = vm.async_call(["main"], ()).await?;
+
+        let output: Result<Vec<String>, rune::Value> = rune::from_value(output)?;
+        assert_eq!(output.ok(), Some(vec!["first".into(), "second".into()]));
+        Ok(())
+    }
+}
@@ -0,0 +1,488 @@
+//! The native `regex` module for the [Rune Language].
+//!
+//! [Rune Language]: https://rune-rs.github.io
+//!
+//! ## Usage
+//!
+//! Add the following to your `Cargo.toml`:
+//!
+//! ```toml
+//! rune-modules = { version = "0.14.0", features = ["regex"] }
+//! ```
+//!
+//! Install it into your context:
+//!
+//! ```rust
+//! let mut context = rune::Context::with_default_modules()?;
+//! context.install(rune_modules::regex::module(true)?)?;
+//! # Ok::<_, rune::support::Error>(())
+//! ```
+//!
+//! Use it in Rune:
+//!
+//! ```rust,ignore
+//! use regex::Regex;
+//!
+//! fn main() {
+//!     let re = Regex::new("[0-9]+")?;
+//!     dbg(re.find("abc 123"));
+//! }
+//! ```
+
+use rune::alloc::fmt::TryWrite;
+use rune::alloc::prelude::*;
+use rune::alloc::String;
+use rune::runtime::{Formatter, Function, Object, Ref, Value, VmResult};
+use rune::{vm_try, vm_write, Any, ContextError, Module};
+
+/// Module for matching strings against regular expressions.
+///
+/// # Examples
+///
+/// ```rune
+/// use regex::Regex;
+///
+/// let re = Regex::new("(?<year>[0-9]{4})-(?<month>[0-9]{2})")?;
+/// let caps = re.captures("released 2024-06")?;
+/// assert_eq!(caps["year"], "2024");
+/// assert_eq!(caps["month"], "06");
+/// ```
+#[rune::module(::regex)]
+pub fn module(_stdio: bool) -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.ty::<Error>()?;
+    m.function_meta(Error::display)?;
+    m.function_meta(Error::debug)?;
+
+    m.ty::<Regex>()?;
+    m.function_meta(Regex::new)?;
+    m.function_meta(Regex::as_str)?;
+    m.function_meta(Regex::is_match)?;
+    m.function_meta(Regex::find)?;
+    m.function_meta(Regex::find_iter)?;
+    m.function_meta(Regex::captures)?;
+    m.function_meta(Regex::replace_all)?;
+    m.function_meta(Regex::split)?;
+    m.function_meta(Regex::display)?;
+    m.function_meta(Regex::debug)?;
+
+    m.ty::<Match>()?;
+    m.function_meta(Match::start)?;
+    m.function_meta(Match::end)?;
+    m.function_meta(Match::as_str)?;
+    m.function_meta(Match::display)?;
+    m.function_meta(Match::debug)?;
+
+    m.ty::<Matches>()?;
+    m.function_meta(Matches::next__meta)?;
+    m.implement_trait::<Matches>(rune::item!(::std::iter::Iterator))?;
+
+    m.ty::<Split>()?;
+    m.function_meta(Split::next__meta)?;
+    m.implement_trait::<Split>(rune::item!(::std::iter::Iterator))?;
+
+    Ok(m)
+}
+
+#[derive(Any)]
+#[rune(item = ::regex)]
+/// Error raised when a regular expression fails to compile.
+struct Error {
+    error: regex::Error,
+}
+
+impl Error {
+    #[rune::function(protocol = DISPLAY_FMT)]
+    pub(crate) fn display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{}", self.error)
+    }
+
+    #[rune::function(protocol = DEBUG_FMT)]
+    pub(crate) fn debug(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{:?}", self.error)
+    }
+}
+
+impl From<regex::Error> for Error {
+    fn from(error: regex::Error) -> Self {
+        Self { error }
+    }
+}
+
+/// A compiled regular expression.
+///
+/// See the documentation of the [regex crate] for the supported syntax.
+///
+/// [regex crate]: https://docs.rs/regex
+///
+/// # Examples
+///
+/// ```rune
+/// use regex::Regex;
+///
+/// let re = Regex::new("^[a-z]+$")?;
+/// assert!(re.is_match("hello"));
+/// assert!(!re.is_match("Hello"));
+/// ```
+#[derive(Any)]
+#[rune(item = ::regex)]
+struct Regex {
+    inner: regex::Regex,
+}
+
+impl Regex {
+    /// Compile a regular expression.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the pattern is not a valid regular expression.
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// assert!(Regex::new("a(b").is_err());
+    /// ```
+    #[rune::function(path = Self::new)]
+    fn new(pattern: &str) -> Result<Self, Error> {
+        Ok(Self {
+            inner: regex::Regex::new(pattern)?,
+        })
+    }
+
+    /// Get the pattern this regular expression was compiled from.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("[0-9]+")?;
+    /// assert_eq!(re.as_str(), "[0-9]+");
+    /// ```
+    #[rune::function(vm_result)]
+    fn as_str(&self) -> String {
+        self.inner.as_str().try_to_owned().vm?
+    }
+
+    /// Test if the regular expression matches anywhere in the given string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("[0-9]{3}")?;
+    /// assert!(re.is_match("abc 123"));
+    /// assert!(!re.is_match("abc 12"));
+    /// ```
+    #[rune::function]
+    fn is_match(&self, haystack: &str) -> bool {
+        self.inner.is_match(haystack)
+    }
+
+    /// Find the leftmost-first match in the given string, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("[0-9]+")?;
+    /// let m = re.find("abc 123 456")?;
+    ///
+    /// assert_eq!(m.as_str(), "123");
+    /// assert_eq!(m.start(), 4);
+    /// assert_eq!(m.end(), 7);
+    ///
+    /// assert!(re.find("abc").is_none());
+    /// ```
+    #[rune::function]
+    fn find(&self, haystack: &str) -> VmResult<Option<Match>> {
+        let Some(m) = self.inner.find(haystack) else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(vm_try!(Match::new(m))))
+    }
+
+    /// Iterate over all successive non-overlapping matches in the given
+    /// string.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("[0-9]+")?;
+    /// let numbers = re.find_iter("1, 22 and 333").map(|m| m.as_str()).collect::<Vec>();
+    /// assert_eq!(numbers, ["1", "22", "333"]);
+    /// ```
+    #[rune::function]
+    fn find_iter(&self, haystack: Ref<str>) -> Matches {
+        Matches::new(self.inner.clone(), haystack)
+    }
+
+    /// Match the regular expression against the given string and return its
+    /// named capture groups as an object, or `None` if there is no match.
+    ///
+    /// Groups which did not participate in the match are left out of the
+    /// object.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("(?<key>[a-z]+)=(?<value>[0-9]+)?")?;
+    ///
+    /// let caps = re.captures("id=42")?;
+    /// assert_eq!(caps["key"], "id");
+    /// assert_eq!(caps.get("value"), Some("42"));
+    ///
+    /// let caps = re.captures("id=")?;
+    /// assert_eq!(caps.get("value"), None);
+    ///
+    /// assert!(re.captures("42").is_none());
+    /// ```
+    #[rune::function]
+    fn captures(&self, haystack: &str) -> VmResult<Option<Object>> {
+        let Some(caps) = self.inner.captures(haystack) else {
+            return VmResult::Ok(None);
+        };
+
+        let mut object = Object::new();
+
+        for name in self.inner.capture_names().flatten() {
+            let Some(m) = caps.name(name) else {
+                continue;
+            };
+
+            let key = vm_try!(name.try_to_owned());
+            let value = vm_try!(rune::to_value(vm_try!(m.as_str().try_to_owned())));
+            vm_try!(object.insert(key, value));
+        }
+
+        VmResult::Ok(Some(object))
+    }
+
+    /// Replace all non-overlapping matches in the given string.
+    ///
+    /// The replacement can either be a string, in which `$name` and `$1`
+    /// refer to capture groups, or a function which is called with the text
+    /// of each match and returns its replacement.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("(?<first>[a-z]+) (?<last>[a-z]+)")?;
+    /// assert_eq!(re.replace_all("john smith", "$last $first"), "smith john");
+    ///
+    /// let re = Regex::new("[0-9]+")?;
+    /// let doubled = re.replace_all("1 and 20", |m| (m.parse::<i64>()? * 2).to_string());
+    /// assert_eq!(doubled, "2 and 40");
+    /// ```
+    #[rune::function]
+    fn replace_all(&self, haystack: &str, replacement: Value) -> VmResult<String> {
+        let function = match rune::from_value::<Function>(replacement.clone()) {
+            Ok(function) => function,
+            Err(..) => {
+                let replacement = vm_try!(rune::from_value::<Ref<str>>(replacement));
+                let output = self.inner.replace_all(haystack, &*replacement);
+                return VmResult::Ok(vm_try!(output.try_to_owned()));
+            }
+        };
+
+        let mut output = String::new();
+        let mut last = 0;
+
+        for m in self.inner.find_iter(haystack) {
+            vm_try!(output.try_push_str(&haystack[last..m.start()]));
+            let text = vm_try!(m.as_str().try_to_owned());
+            let replaced = vm_try!(function.call::<String>((text,)));
+            vm_try!(output.try_push_str(&replaced));
+            last = m.end();
+        }
+
+        vm_try!(output.try_push_str(&haystack[last..]));
+        VmResult::Ok(output)
+    }
+
+    /// Split the given string on every match of the regular expression.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use regex::Regex;
+    ///
+    /// let re = Regex::new("[ \t]+")?;
+    /// let fields = re.split("a b \t  c\td").collect::<Vec>();
+    /// assert_eq!(fields, ["a", "b", "c", "d"]);
+    /// ```
+    #[rune::function]
+    fn split(&self, haystack: Ref<str>) -> Split {
+        Split {
+            matches: Matches::new(self.inner.clone(), haystack),
+            last: 0,
+            done: false,
+        }
+    }
+
+    #[rune::function(protocol = DISPLAY_FMT)]
+    fn display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{}", self.inner)
+    }
+
+    #[rune::function(protocol = DEBUG_FMT)]
+    fn debug(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{:?}", self.inner)
+    }
+}
+
+/// A single match of a regular expression in a string.
+#[derive(Any)]
+#[rune(item = ::regex)]
+struct Match {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Match {
+    fn new(m: regex::Match<'_>) -> rune::alloc::Result<Self> {
+        Ok(Self {
+            start: m.start(),
+            end: m.end(),
+            text: m.as_str().try_to_owned()?,
+        })
+    }
+
+    /// The byte offset in the haystack where the match starts.
+    #[rune::function]
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    /// The byte offset in the haystack where the match ends.
+    #[rune::function]
+    fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The matched text.
+    #[rune::function(vm_result)]
+    fn as_str(&self) -> String {
+        self.text.try_clone().vm?
+    }
+
+    #[rune::function(protocol = DISPLAY_FMT)]
+    fn display(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(f, "{}", self.text)
+    }
+
+    #[rune::function(protocol = DEBUG_FMT)]
+    fn debug(&self, f: &mut Formatter) -> VmResult<()> {
+        vm_write!(
+            f,
+            "Match {{ start: {}, end: {}, text: {:?} }}",
+            self.start,
+            self.end,
+            self.text
+        )
+    }
+}
+
+/// An iterator over all non-overlapping matches in a string.
+#[derive(Any)]
+#[rune(item = ::regex)]
+struct Matches {
+    regex: regex::Regex,
+    haystack: Ref<str>,
+    at: usize,
+    last_end: Option<usize>,
+}
+
+impl Matches {
+    fn new(regex: regex::Regex, haystack: Ref<str>) -> Self {
+        Self {
+            regex,
+            haystack,
+            at: 0,
+            last_end: None,
+        }
+    }
+
+    /// Find the next match, skipping empty matches which immediately follow
+    /// the previous match.
+    fn next_match(&mut self) -> Option<(usize, usize)> {
+        loop {
+            if self.at > self.haystack.len() {
+                return None;
+            }
+
+            let m = self.regex.find_at(&self.haystack, self.at)?;
+
+            if m.is_empty() {
+                self.at = match self.haystack[m.end()..].chars().next() {
+                    Some(c) => m.end() + c.len_utf8(),
+                    None => m.end() + 1,
+                };
+
+                if self.last_end == Some(m.end()) {
+                    continue;
+                }
+            } else {
+                self.at = m.end();
+            }
+
+            self.last_end = Some(m.end());
+            return Some((m.start(), m.end()));
+        }
+    }
+
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> VmResult<Option<Match>> {
+        let Some((start, end)) = self.next_match() else {
+            return VmResult::Ok(None);
+        };
+
+        VmResult::Ok(Some(Match {
+            start,
+            end,
+            text: vm_try!(self.haystack[start..end].try_to_owned()),
+        }))
+    }
+}
+
+/// An iterator over the substrings of a string separated by matches of a
+/// regular expression.
+#[derive(Any)]
+#[rune(item = ::regex)]
+struct Split {
+    matches: Matches,
+    last: usize,
+    done: bool,
+}
+
+impl Split {
+    #[rune::function(keep, protocol = NEXT)]
+    fn next(&mut self) -> VmResult<Option<String>> {
+        if self.done {
+            return VmResult::Ok(None);
+        }
+
+        let (start, end) = match self.matches.next_match() {
+            Some((start, end)) => (start, end),
+            None => {
+                self.done = true;
+                (self.matches.haystack.len(), self.matches.haystack.len())
+            }
+        };
+
+        let piece = vm_try!(self.matches.haystack[self.last..start].try_to_owned());
+        self.last = end;
+        VmResult::Ok(Some(piece))
+    }
+}
@@ -87,6 +87,9 @@ impl Parse for Block {
 #[derive(Debug, TryClone, PartialEq, Eq, ToTokens)]
 #[non_exhaustive]
 pub struct EmptyBlock {
+    /// Leading `#![...]` attributes, which apply to the whole block.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
     /// Statements in the block.
     pub statements: Vec<ast::Stmt>,
     /// The unique identifier for the block expression.
@@ -96,6 +99,12 @@ pub struct EmptyBlock {
 
 impl Parse for EmptyBlock {
     fn parse(parser: &mut Parser<'_>) -> Result<Self> {
+        let mut attributes = Vec::new();
+
+        while parser.peek::<ast::attribute::OuterAttribute>()? {
+            attributes.try_push(parser.parse()?)?;
+        }
+
         let mut statements = Vec::new();
 
         while !parser.is_eof()? {
@@ -103,6 +112,7 @@ impl Parse for EmptyBlock {
         }
 
         Ok(Self {
+            attributes,
             statements,
             id: ItemId::ROOT,
         })
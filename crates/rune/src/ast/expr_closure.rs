@@ -14,12 +14,16 @@ fn ast_parse() {
 
     let expr = rt::<ast::ExprClosure>("#[retry(n=3)] async || 43");
     assert_eq!(expr.attributes.len(), 1);
+
+    let expr = rt::<ast::ExprClosure>("|x: i64| -> i64 { x }");
+    assert!(matches!(expr.output, Some((_, ast::Type::Path(..)))));
 }
 
 /// A closure expression.
 ///
 /// * `|| <expr>`.
 /// * `async || <expr>`.
+/// * `|<args>| -> <type> <expr>`.
 #[derive(Debug, TryClone, PartialEq, Eq, Parse, ToTokens, Spanned)]
 #[rune(parse = "meta_only")]
 #[non_exhaustive]
@@ -35,6 +39,9 @@ pub struct ExprClosure {
     pub move_token: Option<T![move]>,
     /// Arguments to the closure.
     pub args: ExprClosureArgs,
+    /// The optional return type of the closure.
+    #[rune(iter)]
+    pub output: Option<(T![->], ast::Type)>,
     /// The body of the closure.
     pub body: Box<ast::Expr>,
     /// Opaque identifier for the closure.
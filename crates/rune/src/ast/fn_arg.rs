@@ -6,6 +6,8 @@ fn ast_parse() {
     rt::<ast::FnArg>("self");
     rt::<ast::FnArg>("_");
     rt::<ast::FnArg>("abc");
+    rt::<ast::FnArg>("abc: i64");
+    rt::<ast::FnArg>("(a, b): (i64, String)");
 }
 
 /// A single argument in a closure.
@@ -16,13 +18,40 @@ pub enum FnArg {
     SelfValue(T![self]),
     /// Function argument is a pattern binding.
     Pat(ast::Pat),
+    /// Function argument is a pattern binding with a type annotation.
+    Typed(FnArgTyped),
 }
 
 impl Parse for FnArg {
     fn parse(p: &mut Parser<'_>) -> Result<Self> {
-        Ok(match p.nth(0)? {
-            K![self] => Self::SelfValue(p.parse()?),
-            _ => Self::Pat(p.parse()?),
-        })
+        if let K![self] = p.nth(0)? {
+            return Ok(Self::SelfValue(p.parse()?));
+        }
+
+        let pat = ast::Pat::parse_annotated(p)?;
+
+        if let K![:] = p.nth(0)? {
+            return Ok(Self::Typed(FnArgTyped {
+                pat,
+                colon: p.parse()?,
+                ty: p.parse()?,
+            }));
+        }
+
+        Ok(Self::Pat(pat))
     }
 }
+
+/// A function argument with a type annotation.
+///
+/// * `<pat>: <type>`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct FnArgTyped {
+    /// The pattern binding.
+    pub pat: ast::Pat,
+    /// The colon separating the pattern from its type.
+    pub colon: T![:],
+    /// The type of the argument.
+    pub ty: ast::Type,
+}
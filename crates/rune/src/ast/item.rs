@@ -19,12 +19,16 @@ pub enum Item {
     Struct(ast::ItemStruct),
     /// An impl declaration.
     Impl(ast::ItemImpl),
+    /// A trait declaration.
+    Trait(ast::ItemTrait),
     /// A module declaration.
     Mod(ast::ItemMod),
     /// A const declaration.
     Const(ast::ItemConst),
     /// A macro call expanding into an item.
     MacroCall(ast::MacroCall),
+    /// A declarative macro definition.
+    MacroRules(ast::ItemMacroRules),
 }
 
 impl Item {
@@ -36,9 +40,11 @@ impl Item {
             Self::Enum(item) => &item.attributes,
             Self::Struct(item) => &item.attributes,
             Self::Impl(item) => &item.attributes,
+            Self::Trait(item) => &item.attributes,
             Self::Mod(item) => &item.attributes,
             Self::Const(item) => &item.attributes,
             Self::MacroCall(item) => &item.attributes,
+            Self::MacroRules(item) => &item.attributes,
         }
     }
     /// Get the item's attributes mutably
@@ -49,9 +55,11 @@ impl Item {
             Self::Enum(item) => &mut item.attributes,
             Self::Struct(item) => &mut item.attributes,
             Self::Impl(item) => &mut item.attributes,
+            Self::Trait(item) => &mut item.attributes,
             Self::Mod(item) => &mut item.attributes,
             Self::Const(item) => &mut item.attributes,
             Self::MacroCall(item) => &mut item.attributes,
+            Self::MacroRules(item) => &mut item.attributes,
         }
     }
 
@@ -72,10 +80,12 @@ impl Item {
             K![enum] => true,
             K![struct] => true,
             K![impl] => true,
+            K![trait] => true,
             K![async] => matches!(p.nth(1), K![fn]),
             K![fn] => true,
             K![mod] => true,
             K![const] => true,
+            K![ident] => matches!((p.nth(1), p.nth(2)), (K![!], K![ident])),
             _ => false,
         }
     }
@@ -88,11 +98,20 @@ impl Item {
         path: Option<ast::Path>,
     ) -> Result<Self> {
         let item = if let Some(path) = path {
-            Self::MacroCall(ast::MacroCall::parse_with_meta_path(
-                p,
-                take(&mut attributes),
-                path,
-            )?)
+            if matches!((p.nth(0)?, p.nth(1)?), (K![!], K![ident])) {
+                Self::MacroRules(ast::ItemMacroRules::parse_with_meta_path(
+                    p,
+                    take(&mut attributes),
+                    take(&mut visibility),
+                    path,
+                )?)
+            } else {
+                Self::MacroCall(ast::MacroCall::parse_with_meta_path(
+                    p,
+                    take(&mut attributes),
+                    path,
+                )?)
+            }
         } else {
             let mut const_token = p.parse::<Option<T![const]>>()?;
             let mut async_token = p.parse::<Option<T![async]>>()?;
@@ -117,6 +136,11 @@ impl Item {
                     p,
                     take(&mut attributes),
                 )?),
+                K![trait] => Self::Trait(ast::ItemTrait::parse_with_meta(
+                    p,
+                    take(&mut attributes),
+                    take(&mut visibility),
+                )?),
                 K![fn] => Self::Fn(ast::ItemFn::parse_with_meta(
                     p,
                     take(&mut attributes),
@@ -144,7 +168,7 @@ impl Item {
                 _ => {
                     return Err(compile::Error::expected(
                         p.tok_at(0)?,
-                        "`fn`, `mod`, `struct`, `enum`, `use`, or macro call",
+                        "`fn`, `mod`, `struct`, `enum`, `trait`, `use`, or macro call",
                     ))
                 }
             };
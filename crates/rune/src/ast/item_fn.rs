@@ -40,6 +40,13 @@ fn ast_parse() {
     let item_with_type = rt::<ast::ItemFn>("pub async fn hello(foo, bar) -> Type {}");
     assert!(item_with_type.arrow.is_some());
     assert!(item_with_type.fn_type.is_some());
+
+    let item = rt::<ast::ItemFn>("fn hello(foo: i64, bar: String) -> std::vec::Vec {}");
+    assert!(matches!(
+        item.args.first(),
+        Some((ast::FnArg::Typed(..), _))
+    ));
+    assert!(matches!(item.fn_type, Some(ast::Type::Path(..))));
 }
 
 /// A function item.
@@ -68,9 +75,9 @@ pub struct ItemFn {
     /// The arrow token.
     #[rune(option)]
     pub arrow: Option<ast::Arrow>,
-    /// The function type.
+    /// The return type of the function.
     #[rune(option)]
-    pub fn_type: Option<ast::Ident>,
+    pub fn_type: Option<ast::Type>,
     /// The body of the function.
     pub body: ast::Block,
     /// Opaque identifier for fn item.
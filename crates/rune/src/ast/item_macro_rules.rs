@@ -0,0 +1,73 @@
+use crate::ast::prelude::*;
+
+use super::macro_call::parse_delimited;
+
+#[test]
+#[cfg(not(miri))]
+fn ast_parse() {
+    rt::<ast::ItemMacroRules>("macro_rules! foo {}");
+    rt::<ast::ItemMacroRules>("macro_rules! foo { ($a:expr) => { $a + 1 }; }");
+    rt::<ast::ItemMacroRules>("#[doc = \"a\"] macro_rules! foo { () => () }");
+    rt::<ast::ItemMacroRules>("pub macro_rules! foo { () => () }");
+}
+
+/// A declarative macro definition.
+///
+/// * `<vis>? macro_rules! <name> { (<matcher>) => { <transcriber> }; ... }`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct ItemMacroRules {
+    /// Attributes associated with the macro definition.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the macro.
+    #[rune(option)]
+    pub visibility: ast::Visibility,
+    /// The `macro_rules` path.
+    pub path: ast::Path,
+    /// Bang operator `!`.
+    pub bang: T![!],
+    /// The name of the macro being defined.
+    pub name: ast::Ident,
+    /// Opening token.
+    pub open: ast::Token,
+    /// The tokens making up the rules of the macro.
+    #[rune(iter)]
+    pub rules: TokenStream,
+    /// Closing token.
+    pub close: ast::Token,
+}
+
+impl ItemMacroRules {
+    /// Parse a macro definition with the given attributes, visibility and the
+    /// already parsed `macro_rules` path.
+    pub(crate) fn parse_with_meta_path(
+        parser: &mut Parser<'_>,
+        attributes: Vec<ast::Attribute>,
+        visibility: ast::Visibility,
+        path: ast::Path,
+    ) -> Result<Self> {
+        let bang = parser.parse()?;
+        let name = parser.parse()?;
+        let (open, rules, close) = parse_delimited(parser)?;
+
+        Ok(Self {
+            attributes,
+            visibility,
+            path,
+            bang,
+            name,
+            open,
+            rules,
+            close,
+        })
+    }
+
+    /// Get the descriptive span of this item, e.g. `macro_rules! name`
+    /// instead of the span for the whole definition.
+    pub(crate) fn descriptive_span(&self) -> Span {
+        self.path.span().join(self.name.span())
+    }
+}
+
+item_parse!(MacroRules, ItemMacroRules, "macro definition");
@@ -0,0 +1,159 @@
+use crate::ast::prelude::*;
+
+#[test]
+#[cfg(not(miri))]
+fn ast_parse() {
+    rt::<ast::ItemTrait>("trait Shape {}");
+    rt::<ast::ItemTrait>("pub trait Shape { fn area(self); }");
+    rt::<ast::ItemTrait>(
+        "trait Shape { fn area(self); fn describe(self) { `area: ${self.area()}` } }",
+    );
+    rt::<ast::ItemTrait>("#[doc = \"A shape.\"] trait Shape { #[doc = \"Area.\"] fn area(self); }");
+
+    let item = rt::<ast::TraitFn>("fn area(self);");
+    assert!(item.body.is_none());
+    assert!(item.semi_token.is_some());
+
+    let item = rt::<ast::TraitFn>("async fn area(self) -> f64 { 0.0 }");
+    assert!(item.body.is_some());
+    assert!(item.semi_token.is_none());
+
+    assert!(crate::parse::parse_all::<ast::TraitFn>(
+        "fn area(self)",
+        crate::SourceId::EMPTY,
+        false
+    )
+    .is_err());
+}
+
+/// A trait item.
+///
+/// * `trait <name> { <fn>* }`.
+#[derive(Debug, TryClone, PartialEq, Eq, Parse, ToTokens, Spanned)]
+#[rune(parse = "meta_only")]
+#[non_exhaustive]
+pub struct ItemTrait {
+    /// The attributes of the trait.
+    #[rune(iter, meta)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The visibility of the trait.
+    #[rune(option, meta)]
+    pub visibility: ast::Visibility,
+    /// The `trait` keyword.
+    pub trait_token: T![trait],
+    /// The name of the trait.
+    pub name: ast::Ident,
+    /// The open brace.
+    pub open: T!['{'],
+    /// The functions of the trait.
+    #[rune(iter, parse_with = "parse_functions")]
+    pub functions: Vec<TraitFn>,
+    /// The close brace.
+    pub close: T!['}'],
+}
+
+item_parse!(Trait, ItemTrait, "trait item");
+
+fn parse_functions(p: &mut Parser<'_>) -> Result<Vec<TraitFn>> {
+    let mut functions = Vec::new();
+
+    while !p.peek::<ast::CloseBrace>()? {
+        functions.try_push(p.parse()?)?;
+    }
+
+    Ok(functions)
+}
+
+/// A function declared in a trait.
+///
+/// * `fn <name>(<args>);` for a required function.
+/// * `fn <name>(<args>) <block>` for a function with a default
+///   implementation.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub struct TraitFn {
+    /// The attributes for the function.
+    #[rune(iter)]
+    pub attributes: Vec<ast::Attribute>,
+    /// The optional `async` keyword.
+    #[rune(iter)]
+    pub async_token: Option<T![async]>,
+    /// The `fn` token.
+    pub fn_token: T![fn],
+    /// The name of the function.
+    pub name: ast::Ident,
+    /// The arguments of the function.
+    pub args: ast::Parenthesized<ast::FnArg, T![,]>,
+    /// The arrow token.
+    #[rune(iter)]
+    pub arrow: Option<ast::Arrow>,
+    /// The return type of the function.
+    #[rune(iter)]
+    pub fn_type: Option<ast::Type>,
+    /// The default implementation of the function.
+    #[rune(iter)]
+    pub body: Option<ast::Block>,
+    /// The terminating semicolon of a function without a default
+    /// implementation.
+    #[rune(iter)]
+    pub semi_token: Option<T![;]>,
+}
+
+impl TraitFn {
+    /// Convert a function with a default implementation into a function item
+    /// which can be added to an implementation.
+    pub(crate) fn to_item_fn(&self) -> alloc::Result<Option<ast::ItemFn>> {
+        let Some(body) = &self.body else {
+            return Ok(None);
+        };
+
+        Ok(Some(ast::ItemFn {
+            attributes: self.attributes.try_clone()?,
+            visibility: ast::Visibility::Inherited,
+            const_token: None,
+            async_token: self.async_token,
+            fn_token: self.fn_token,
+            name: self.name,
+            args: self.args.try_clone()?,
+            arrow: self.arrow,
+            fn_type: self.fn_type.try_clone()?,
+            body: body.try_clone()?,
+            id: ItemId::default(),
+        }))
+    }
+}
+
+impl Parse for TraitFn {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        let attributes = p.parse()?;
+        let async_token = p.parse()?;
+        let fn_token = p.parse()?;
+        let name = p.parse()?;
+        let args = p.parse()?;
+        let arrow = p.parse::<Option<ast::Arrow>>()?;
+
+        let fn_type = if arrow.is_some() {
+            Some(p.parse()?)
+        } else {
+            None
+        };
+
+        let (body, semi_token) = if p.peek::<T![;]>()? {
+            (None, Some(p.parse()?))
+        } else {
+            (Some(p.parse()?), None)
+        };
+
+        Ok(Self {
+            attributes,
+            async_token,
+            fn_token,
+            name,
+            args,
+            arrow,
+            fn_type,
+            body,
+            semi_token,
+        })
+    }
+}
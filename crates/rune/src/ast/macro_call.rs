@@ -55,49 +55,7 @@ impl MacroCall {
         path: ast::Path,
     ) -> Result<Self> {
         let bang = parser.parse()?;
-
-        let mut level = 1;
-        let open = parser.next()?;
-
-        let delim = match open.kind {
-            ast::Kind::Open(delim) => delim,
-            _ => {
-                return Err(compile::Error::expected(open, Expectation::OpenDelimiter));
-            }
-        };
-
-        let close;
-
-        let mut stream = Vec::new();
-
-        loop {
-            let token = parser.next()?;
-
-            match token.kind {
-                ast::Kind::Open(..) => level += 1,
-                ast::Kind::Close(actual) => {
-                    level -= 1;
-
-                    if level == 0 {
-                        if actual != delim {
-                            return Err(compile::Error::new(
-                                open,
-                                ErrorKind::ExpectedMacroCloseDelimiter {
-                                    actual: token.kind,
-                                    expected: ast::Kind::Close(delim),
-                                },
-                            ));
-                        }
-
-                        close = token;
-                        break;
-                    }
-                }
-                _ => (),
-            }
-
-            stream.try_push(token)?;
-        }
+        let (open, input, close) = parse_delimited(parser)?;
 
         Ok(Self {
             id: Default::default(),
@@ -105,7 +63,7 @@ impl MacroCall {
             bang,
             path,
             open,
-            input: TokenStream::from(stream),
+            input,
             close,
         })
     }
@@ -118,3 +76,54 @@ impl Parse for MacroCall {
         Self::parse_with_meta_path(parser, attributes, path)
     }
 }
+
+/// Parse a delimited group of tokens, returning the opening token, the tokens
+/// inside of the group and the closing token.
+pub(crate) fn parse_delimited(
+    parser: &mut Parser<'_>,
+) -> Result<(ast::Token, TokenStream, ast::Token)> {
+    let mut level = 1;
+    let open = parser.next()?;
+
+    let delim = match open.kind {
+        ast::Kind::Open(delim) => delim,
+        _ => {
+            return Err(compile::Error::expected(open, Expectation::OpenDelimiter));
+        }
+    };
+
+    let close;
+
+    let mut stream = Vec::new();
+
+    loop {
+        let token = parser.next()?;
+
+        match token.kind {
+            ast::Kind::Open(..) => level += 1,
+            ast::Kind::Close(actual) => {
+                level -= 1;
+
+                if level == 0 {
+                    if actual != delim {
+                        return Err(compile::Error::new(
+                            open,
+                            ErrorKind::ExpectedMacroCloseDelimiter {
+                                actual: token.kind,
+                                expected: ast::Kind::Close(delim),
+                            },
+                        ));
+                    }
+
+                    close = token;
+                    break;
+                }
+            }
+            _ => (),
+        }
+
+        stream.try_push(token)?;
+    }
+
+    Ok((open, TokenStream::from(stream), close))
+}
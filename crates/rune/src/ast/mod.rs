@@ -143,8 +143,10 @@ mod item_const;
 mod item_enum;
 mod item_fn;
 mod item_impl;
+mod item_macro_rules;
 mod item_mod;
 mod item_struct;
+mod item_trait;
 mod item_use;
 mod label;
 mod lit;
@@ -165,6 +167,7 @@ pub(crate) mod spanned;
 mod stmt;
 mod to_ast;
 mod token;
+mod ty;
 pub(super) mod unescape;
 mod utils;
 mod vis;
@@ -203,7 +206,7 @@ pub use self::expr_while::ExprWhile;
 pub use self::expr_yield::ExprYield;
 pub use self::fields::Fields;
 pub use self::file::{File, Shebang};
-pub use self::fn_arg::FnArg;
+pub use self::fn_arg::{FnArg, FnArgTyped};
 pub use self::grouped::{AngleBracketed, Braced, Bracketed, Parenthesized};
 pub use self::ident::Ident;
 pub use self::item::Item;
@@ -211,8 +214,10 @@ pub use self::item_const::ItemConst;
 pub use self::item_enum::{ItemEnum, ItemVariant};
 pub use self::item_fn::ItemFn;
 pub use self::item_impl::ItemImpl;
+pub use self::item_macro_rules::ItemMacroRules;
 pub use self::item_mod::{ItemInlineBody, ItemMod, ItemModBody};
 pub use self::item_struct::{Field, ItemStruct};
+pub use self::item_trait::{ItemTrait, TraitFn};
 pub use self::item_use::{ItemUse, ItemUsePath, ItemUseSegment};
 pub use self::label::Label;
 pub use self::lit::Lit;
@@ -238,6 +243,7 @@ pub use self::token::{
     BuiltIn, CopySource, Delimiter, LitSource, Number, NumberBase, NumberSize, NumberSource,
     NumberSuffix, NumberText, NumberValue, StrSource, StrText, Token,
 };
+pub use self::ty::Type;
 pub use self::vis::Visibility;
 
 macro_rules! decl_tokens {
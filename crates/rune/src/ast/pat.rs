@@ -39,8 +39,14 @@ pub enum Pat {
     Rest(PatRest),
 }
 
-impl Parse for Pat {
-    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+impl Pat {
+    /// Parse a pattern which can be followed by a type annotation, like in
+    /// `let <pat>: <type>`, where a `:` can't introduce a binding.
+    pub(crate) fn parse_annotated(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, false)
+    }
+
+    fn parse_with(p: &mut Parser<'_>, binding: bool) -> Result<Self> {
         let attributes = p.parse::<Vec<ast::Attribute>>()?;
 
         match p.nth(0)? {
@@ -70,7 +76,7 @@ impl Parse for Pat {
             }
             K![str] => {
                 return Ok(match p.nth(1)? {
-                    K![:] => Self::Binding(PatBinding {
+                    K![:] if binding => Self::Binding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::LitStr(p.parse()?),
                         colon: p.parse()?,
@@ -148,7 +154,7 @@ impl Parse for Pat {
                         ident: ast::ObjectIdent::Named(path),
                         items: p.parse()?,
                     }),
-                    K![:] => Self::Binding(PatBinding {
+                    K![:] if binding => Self::Binding(PatBinding {
                         attributes,
                         key: ast::ObjectKey::Path(path),
                         colon: p.parse()?,
@@ -164,6 +170,12 @@ impl Parse for Pat {
     }
 }
 
+impl Parse for Pat {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Self::parse_with(p, true)
+    }
+}
+
 impl Peek for Pat {
     fn peek(p: &mut Peeker<'_>) -> bool {
         match p.nth(0) {
@@ -0,0 +1,38 @@
+use crate::ast::prelude::*;
+
+#[test]
+#[cfg(not(miri))]
+fn ast_parse() {
+    rt::<ast::Type>("i64");
+    rt::<ast::Type>("std::string::String");
+    rt::<ast::Type>("()");
+    rt::<ast::Type>("(i64, String)");
+}
+
+/// A type annotation.
+///
+/// * `<path>`.
+/// * `(<type>,*)`.
+#[derive(Debug, TryClone, PartialEq, Eq, ToTokens, Spanned)]
+#[non_exhaustive]
+pub enum Type {
+    /// A type referenced by path, like `std::string::String`.
+    Path(ast::Path),
+    /// A tuple type, like `(i64, String)` or the unit type `()`.
+    Tuple(ast::Parenthesized<Type, T![,]>),
+}
+
+impl Parse for Type {
+    fn parse(p: &mut Parser<'_>) -> Result<Self> {
+        Ok(match p.nth(0)? {
+            K!['('] => Self::Tuple(p.parse()?),
+            _ => Self::Path(p.parse()?),
+        })
+    }
+}
+
+impl Peek for Type {
+    fn peek(p: &mut Peeker<'_>) -> bool {
+        matches!(p.nth(0), K!['(']) || ast::Path::peek(p)
+    }
+}
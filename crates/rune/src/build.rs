@@ -198,6 +198,54 @@ impl compile::CompileVisitor for CompileVisitorGroup<'_> {
         Ok(())
     }
 
+    fn visit_fn_arg(&mut self, location: &dyn Located) -> Result<(), MetaError> {
+        for v in self.visitors.iter_mut() {
+            v.visit_fn_arg(location)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_closure_capture(
+        &mut self,
+        location: &dyn Located,
+        name: &str,
+    ) -> Result<(), MetaError> {
+        for v in self.visitors.iter_mut() {
+            v.visit_closure_capture(location, name)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_field(
+        &mut self,
+        location: &dyn Located,
+        item: &Item,
+        hash: crate::Hash,
+        field: &str,
+    ) -> Result<(), MetaError> {
+        for v in self.visitors.iter_mut() {
+            v.visit_field(location, item, hash, field)?;
+        }
+
+        Ok(())
+    }
+
+    fn visit_field_use(
+        &mut self,
+        location: &dyn Located,
+        hash: Option<crate::Hash>,
+        field: &str,
+        shorthand: bool,
+    ) -> Result<(), MetaError> {
+        for v in self.visitors.iter_mut() {
+            v.visit_field_use(location, hash, field, shorthand)?;
+        }
+
+        Ok(())
+    }
+
     fn visit_mod(&mut self, location: &dyn Located) -> Result<(), MetaError> {
         for v in self.visitors.iter_mut() {
             v.visit_mod(location)?;
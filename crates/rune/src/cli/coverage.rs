@@ -0,0 +1,275 @@
+//! Coverage collection used by `rune test --coverage`.
+//!
+//! Only lib tests, the `#[test]` functions of the scripts being tested, are
+//! instrumented. Doc tests are not included in the report.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::cli::{Color, Io, Stream};
+use crate::runtime::{Inline, Inst, Repr, Unit, Vm, VmHook, VmHookControl};
+use crate::Sources;
+
+#[derive(Default)]
+struct State {
+    /// The number of times each instruction was executed.
+    instructions: HashMap<usize, u64>,
+    /// The number of times each conditional jump was taken and not taken.
+    branches: HashMap<usize, [u64; 2]>,
+    /// The number of calls to each function.
+    calls: HashMap<usize, u64>,
+}
+
+/// A [`VmHook`] which records the instructions executed in a single unit.
+pub(crate) struct Coverage {
+    unit: Arc<Unit>,
+    sources: Arc<Sources>,
+    state: Mutex<State>,
+}
+
+impl Coverage {
+    /// Construct coverage collection for the given unit.
+    pub(crate) fn new(unit: Arc<Unit>, sources: Arc<Sources>) -> Self {
+        Self {
+            unit,
+            sources,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl VmHook for Coverage {
+    fn instruction(&self, vm: &Vm, ip: usize, inst: &Inst) -> VmHookControl {
+        let mut state = self.state();
+        *state.instructions.entry(ip).or_default() += 1;
+
+        let taken = match *inst {
+            Inst::JumpIf { cond, .. } => matches!(
+                vm.stack().at(cond).as_ref(),
+                Repr::Inline(Inline::Bool(true))
+            ),
+            Inst::JumpIfNot { cond, .. } => matches!(
+                vm.stack().at(cond).as_ref(),
+                Repr::Inline(Inline::Bool(false))
+            ),
+            _ => return VmHookControl::Continue,
+        };
+
+        state.branches.entry(ip).or_default()[usize::from(!taken)] += 1;
+        VmHookControl::Continue
+    }
+
+    fn enter(&self, _: &Vm, ip: usize) -> VmHookControl {
+        *self.state().calls.entry(ip).or_default() += 1;
+        VmHookControl::Continue
+    }
+}
+
+/// Coverage of a single source file.
+#[derive(Default)]
+struct File {
+    /// Execution counts by line, of lines which have code associated with
+    /// them.
+    lines: BTreeMap<usize, u64>,
+    /// Branches by line and the instruction of the jump.
+    branches: BTreeMap<(usize, usize), [u64; 2]>,
+    /// Functions by name, with the line they start on and the number of calls.
+    functions: BTreeMap<String, (usize, u64)>,
+}
+
+/// Coverage collected from all units, by source file.
+#[derive(Default)]
+pub(crate) struct Report {
+    files: BTreeMap<String, File>,
+}
+
+impl Report {
+    /// Add coverage collected for a unit to the report.
+    pub(crate) fn add(&mut self, coverage: &Coverage) {
+        let Some(debug) = coverage.unit.debug_info() else {
+            return;
+        };
+
+        let state = coverage.state();
+
+        // Line of each instruction, and the file it belongs to.
+        let mut locations = BTreeMap::new();
+
+        for (&ip, inst) in &debug.instructions {
+            let Some(source) = coverage.sources.get(inst.source_id) else {
+                continue;
+            };
+
+            let name = match source.path() {
+                Some(path) => path.display().to_string(),
+                None => source.name().to_string(),
+            };
+
+            let (line, _) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+            let count = state.instructions.get(&ip).copied().unwrap_or_default();
+
+            let file = self.files.entry(name.clone()).or_default();
+            let hits = file.lines.entry(line + 1).or_default();
+            *hits = (*hits).max(count);
+
+            if let Some(&[taken, not_taken]) = state.branches.get(&ip) {
+                let branch = file.branches.entry((line + 1, ip)).or_default();
+                branch[0] += taken;
+                branch[1] += not_taken;
+            } else if let Some((Inst::JumpIf { .. } | Inst::JumpIfNot { .. }, _)) =
+                coverage.unit.instruction_at(ip).ok().flatten()
+            {
+                file.branches.entry((line + 1, ip)).or_default();
+            }
+
+            locations.insert(ip, (name, line + 1));
+        }
+
+        let mut entries = debug.functions_rev.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|&(&entry, _)| entry);
+
+        for (n, &(&entry, hash)) in entries.iter().enumerate() {
+            let Some(signature) = debug.functions.get(hash) else {
+                continue;
+            };
+
+            let end = entries.get(n + 1).map_or(usize::MAX, |&(&end, _)| end);
+
+            // NB: the instructions of a function are not ordered by line.
+            let Some((name, line)) = locations
+                .range(entry..end)
+                .map(|(_, location)| location)
+                .min_by_key(|&(_, line)| line)
+            else {
+                continue;
+            };
+
+            let calls = state.calls.get(&entry).copied().unwrap_or_default();
+
+            let file = self.files.entry(name.clone()).or_default();
+            let function = file
+                .functions
+                .entry(signature.path.to_string())
+                .or_insert((*line, 0));
+            function.1 += calls;
+        }
+    }
+
+    /// Write the report in the lcov tracefile format to the file at `path`.
+    pub(super) fn write_lcov(&self, path: &Path) -> io::Result<()> {
+        let mut o = BufWriter::new(fs::File::create(path)?);
+        self.lcov(&mut o)?;
+        o.flush()
+    }
+
+    /// Write the report in the lcov tracefile format.
+    pub(crate) fn lcov<O>(&self, o: &mut O) -> io::Result<()>
+    where
+        O: ?Sized + Write,
+    {
+        for (name, file) in &self.files {
+            writeln!(o, "TN:")?;
+            writeln!(o, "SF:{name}")?;
+
+            for (function, (line, _)) in &file.functions {
+                writeln!(o, "FN:{line},{function}")?;
+            }
+
+            for (function, (_, calls)) in &file.functions {
+                writeln!(o, "FNDA:{calls},{function}")?;
+            }
+
+            let hit = file.functions.values().filter(|(_, calls)| *calls > 0);
+            writeln!(o, "FNF:{}", file.functions.len())?;
+            writeln!(o, "FNH:{}", hit.count())?;
+
+            for (&(line, ip), branch) in &file.branches {
+                let executed = file.lines.get(&line).is_some_and(|&hits| hits > 0);
+
+                for (n, taken) in branch.iter().enumerate() {
+                    if executed {
+                        writeln!(o, "BRDA:{line},{ip},{n},{taken}")?;
+                    } else {
+                        writeln!(o, "BRDA:{line},{ip},{n},-")?;
+                    }
+                }
+            }
+
+            let (found, hit) = file.branch_counts();
+            writeln!(o, "BRF:{found}")?;
+            writeln!(o, "BRH:{hit}")?;
+
+            for (line, hits) in &file.lines {
+                writeln!(o, "DA:{line},{hits}")?;
+            }
+
+            let (found, hit) = file.line_counts();
+            writeln!(o, "LF:{found}")?;
+            writeln!(o, "LH:{hit}")?;
+            writeln!(o, "end_of_record")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a summary of the report to the terminal.
+    pub(super) fn summary(&self, io: &mut Io<'_>) -> io::Result<()> {
+        let mut lines = (0, 0);
+        let mut branches = (0, 0);
+
+        for (name, file) in &self.files {
+            let (found, hit) = file.line_counts();
+            lines.0 += found;
+            lines.1 += hit;
+
+            let (b_found, b_hit) = file.branch_counts();
+            branches.0 += b_found;
+            branches.1 += b_hit;
+
+            writeln!(
+                io.stdout,
+                "{:>12} {name}: lines {}, branches {}",
+                "",
+                ratio(hit, found),
+                ratio(b_hit, b_found)
+            )?;
+        }
+
+        let mut section = io.section("Coverage", Stream::Stdout, Color::Highlight)?;
+        section.append(format_args!(" lines {}", ratio(lines.1, lines.0)))?;
+        section.append(format_args!(", branches {}", ratio(branches.1, branches.0)))?;
+        section.close()
+    }
+}
+
+impl File {
+    /// The number of lines found and hit.
+    fn line_counts(&self) -> (usize, usize) {
+        let hit = self.lines.values().filter(|&&hits| hits > 0).count();
+        (self.lines.len(), hit)
+    }
+
+    /// The number of branches found and hit.
+    fn branch_counts(&self) -> (usize, usize) {
+        let hit = self.branches.values().flatten().filter(|&&n| n > 0).count();
+        (self.branches.len() * 2, hit)
+    }
+}
+
+fn ratio(hit: usize, found: usize) -> String {
+    if found == 0 {
+        return String::from("0/0");
+    }
+
+    format!("{hit}/{found} ({:.1}%)", hit as f64 / found as f64 * 100.0)
+}
@@ -0,0 +1,38 @@
+use anyhow::Result;
+
+use crate::cli::CommandBase;
+use crate::modules::capture_io::CaptureIo;
+use crate::{Context, Options};
+
+mod cli {
+    use std::path::PathBuf;
+
+    use clap::Parser;
+
+    #[derive(Parser, Debug)]
+    #[command(rename_all = "kebab-case")]
+    pub(crate) struct Flags {
+        /// The script to debug if the client doesn't specify a `program` to
+        /// launch.
+        pub(super) program: Option<PathBuf>,
+    }
+}
+
+pub(super) use cli::Flags;
+
+impl CommandBase for Flags {
+    #[inline]
+    fn is_debug(&self) -> bool {
+        true
+    }
+}
+
+pub(super) async fn run(
+    context: Context,
+    capture: &CaptureIo,
+    options: Options,
+    flags: &Flags,
+) -> Result<()> {
+    crate::debugger::run(context, capture, options, flags.program.clone()).await?;
+    Ok(())
+}
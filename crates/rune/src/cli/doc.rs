@@ -94,11 +94,7 @@ where
     let mut naming = Naming::default();
 
     for e in entries {
-        let mut options = options.clone();
-
-        if e.is_argument() {
-            options.function_body = true;
-        }
+        let options = e.options(options);
 
         let item = naming.item(&e)?;
 
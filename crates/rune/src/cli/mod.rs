@@ -9,12 +9,15 @@
 mod ace;
 mod benches;
 mod check;
+pub(crate) mod coverage;
+mod debug;
 mod doc;
 mod format;
 mod languageserver;
 mod loader;
 mod naming;
 mod out;
+pub(crate) mod profile;
 mod run;
 mod tests;
 mod visitor;
@@ -53,6 +56,9 @@ pub struct ContextOptions<'a> {
     pub capture: Option<&'a CaptureIo>,
     /// If we're running in a test context.
     pub test: bool,
+    /// Arguments which should be forwarded to the script, such as the ones
+    /// specified after `--` in `rune run <path> -- <args>`.
+    pub args: &'a [String],
 }
 
 /// Type used to build a context.
@@ -259,6 +265,29 @@ impl EntryPoint<'_> {
             EntryPoint::Package(..) => false,
         }
     }
+
+    /// Compile options to use for the entrypoint.
+    ///
+    /// Arguments are built as function bodies, and packages apply the lint
+    /// levels configured in their manifest.
+    pub(crate) fn options(&self, options: &Options) -> Options {
+        let mut options = options.clone();
+
+        match self {
+            EntryPoint::Path(_, explicit) => {
+                if *explicit {
+                    options.function_body = true;
+                }
+            }
+            EntryPoint::Package(p) => {
+                for &(lint, level) in &p.package.lints {
+                    options.lint(lint, level);
+                }
+            }
+        }
+
+        options
+    }
 }
 
 impl fmt::Display for EntryPoint<'_> {
@@ -430,12 +459,14 @@ enum Command {
     Fmt(CommandShared<format::Flags>),
     /// Run a language server.
     LanguageServer(SharedFlags),
+    /// Run a debug adapter over stdin and stdout.
+    Debug(CommandShared<debug::Flags>),
     /// Helper command to generate type hashes.
     Hash(HashFlags),
 }
 
 impl Command {
-    const ALL: [&'static str; 9] = [
+    const ALL: [&'static str; 10] = [
         "check",
         "doc",
         "ace",
@@ -444,6 +475,7 @@ impl Command {
         "run",
         "fmt",
         "languageserver",
+        "debug",
         "hash",
     ];
 
@@ -457,6 +489,7 @@ impl Command {
             Command::Run(shared) => (&mut shared.shared, &mut shared.command),
             Command::Fmt(shared) => (&mut shared.shared, &mut shared.command),
             Command::LanguageServer(..) => return None,
+            Command::Debug(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -473,6 +506,7 @@ impl Command {
             Command::Run(shared) => (&shared.shared, &shared.command),
             Command::Fmt(shared) => (&shared.shared, &shared.command),
             Command::LanguageServer(..) => return None,
+            Command::Debug(..) => return None,
             Command::Hash(..) => return None,
         };
 
@@ -499,6 +533,8 @@ struct Config {
     all_targets: bool,
     /// Manifest root directory.
     manifest_root: Option<PathBuf>,
+    /// Arguments to forward to the script.
+    args: Vec<String>,
 }
 
 #[derive(Default)]
@@ -573,6 +609,7 @@ impl SharedFlags {
         let opts = ContextOptions {
             capture,
             test: c.test,
+            args: &c.args,
         };
 
         let mut context =
@@ -918,11 +955,7 @@ where
             let options = f.options()?;
 
             for e in entries {
-                let mut options = options.clone();
-
-                if e.is_argument() {
-                    options.function_body = true;
-                }
+                let options = e.options(&options);
 
                 match check::run(io, entry, c, &f.command, &f.shared, &options, e.path())? {
                     ExitCode::Success => (),
@@ -954,11 +987,7 @@ where
             let options = f.options()?;
 
             for e in entries {
-                let mut options = options.clone();
-
-                if e.is_argument() {
-                    options.function_body = true;
-                }
+                let options = e.options(&options);
 
                 let capture_io = crate::modules::capture_io::CaptureIo::new();
                 let context = f.shared.context(entry, c, Some(&capture_io))?;
@@ -993,11 +1022,7 @@ where
             let context = f.shared.context(entry, c, None)?;
 
             for e in entries {
-                let mut options = options.clone();
-
-                if e.is_argument() {
-                    options.function_body = true;
-                }
+                let options = e.options(&options);
 
                 let load = loader::load(
                     io,
@@ -1025,6 +1050,12 @@ where
             let context = shared.context(entry, c, None)?;
             languageserver::run(context).await?;
         }
+        Command::Debug(f) => {
+            let options = f.options()?;
+            let capture = CaptureIo::new();
+            let context = f.shared.context(entry, c, Some(&capture))?;
+            debug::run(context, &capture, options, &f.command).await?;
+        }
         Command::Hash(args) => {
             use rand::prelude::*;
 
@@ -0,0 +1,336 @@
+//! Instrumenting profiler used by `rune run --profile`.
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::runtime::{Inst, Unit, Vm, VmHook, VmHookControl};
+use crate::{SourceId, Sources};
+
+/// Statistics collected for a single instruction in a single call stack.
+#[derive(Default, Clone, Copy)]
+struct Stat {
+    /// Time spent executing the instruction, including time spent in native
+    /// functions and futures it called.
+    time: Duration,
+    /// The number of times the instruction was executed.
+    count: u64,
+}
+
+impl Stat {
+    fn add(&mut self, other: Stat) {
+        self.time += other.time;
+        self.count += other.count;
+    }
+}
+
+#[derive(Default)]
+struct State {
+    /// Interned call stacks, as the entry of each function from the outermost
+    /// to the innermost.
+    stacks: Vec<Vec<usize>>,
+    /// Reverse lookup of interned call stacks.
+    stack_ids: HashMap<Vec<usize>, usize>,
+    /// The virtual machine which last executed an instruction and its call
+    /// stack, cleared when a function is entered or exited.
+    current: Option<(usize, usize)>,
+    /// The instruction which is currently being timed.
+    timing: Option<(usize, usize, Instant)>,
+    /// Statistics by call stack and instruction.
+    stats: HashMap<(usize, usize), Stat>,
+    /// The number of calls to each function.
+    calls: HashMap<usize, u64>,
+}
+
+impl State {
+    /// Stop timing the current instruction.
+    fn stop(&mut self, now: Instant) {
+        if let Some((stack, ip, start)) = self.timing.take() {
+            let stat = self.stats.entry((stack, ip)).or_default();
+            stat.time += now.saturating_duration_since(start);
+        }
+    }
+
+    /// Intern the call stack of the given virtual machine.
+    fn intern(&mut self, entries: &[usize], vm: &Vm, ip: usize) -> usize {
+        let function = |ip: usize| match entries.partition_point(|&entry| entry <= ip) {
+            0 => usize::MAX,
+            n => entries[n - 1],
+        };
+
+        let mut stack = Vec::with_capacity(vm.call_frames().len() + 1);
+
+        for frame in vm.call_frames() {
+            // NB: the frame stores the instruction after the call.
+            stack.push(function(frame.ip.saturating_sub(1)));
+        }
+
+        stack.push(function(ip));
+
+        if let Some(&id) = self.stack_ids.get(&stack) {
+            return id;
+        }
+
+        let id = self.stacks.len();
+        self.stacks.push(stack.clone());
+        self.stack_ids.insert(stack, id);
+        id
+    }
+}
+
+/// A [`VmHook`] which records the time spent and the number of instructions
+/// executed by each call stack.
+pub(crate) struct Profiler {
+    /// Sorted entries of all functions in the unit being profiled.
+    entries: Vec<usize>,
+    state: Mutex<State>,
+}
+
+impl Profiler {
+    /// Construct a new profiler for the given unit.
+    pub(crate) fn new(unit: &Unit) -> Self {
+        let mut entries = Vec::new();
+
+        if let Some(debug) = unit.debug_info() {
+            entries.extend(debug.functions_rev.keys().copied());
+        }
+
+        entries.sort();
+
+        Self {
+            entries,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Write the recorded call stacks in the collapsed format used by
+    /// flamegraph tools to the file at `path`.
+    pub(super) fn write_collapsed(&self, unit: &Unit, path: &Path) -> io::Result<()> {
+        let mut o = BufWriter::new(fs::File::create(path)?);
+        self.collapsed(&mut o, unit)?;
+        o.flush()
+    }
+
+    /// Write the recorded call stacks in the collapsed format used by
+    /// flamegraph tools, weighted by the number of nanoseconds spent in them.
+    pub(crate) fn collapsed<O>(&self, o: &mut O, unit: &Unit) -> io::Result<()>
+    where
+        O: ?Sized + Write,
+    {
+        let mut state = self.state();
+        state.stop(Instant::now());
+
+        let mut weights = HashMap::<usize, Duration>::new();
+
+        for (&(stack, _), stat) in &state.stats {
+            *weights.entry(stack).or_default() += stat.time;
+        }
+
+        let mut lines = Vec::new();
+
+        for (stack, time) in weights {
+            let mut line = String::new();
+
+            for (n, &entry) in state.stacks[stack].iter().enumerate() {
+                if n > 0 {
+                    line.push(';');
+                }
+
+                line.push_str(&function_name(unit, entry));
+            }
+
+            lines.push((line, time.as_nanos()));
+        }
+
+        lines.sort();
+
+        for (line, weight) in lines {
+            writeln!(o, "{line} {weight}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a report of the `top` functions and source lines which most time
+    /// was spent in.
+    pub(super) fn report<O>(
+        &self,
+        o: &mut O,
+        unit: &Unit,
+        sources: &Sources,
+        top: usize,
+    ) -> io::Result<()>
+    where
+        O: ?Sized + Write,
+    {
+        let mut state = self.state();
+        state.stop(Instant::now());
+
+        let mut total = Stat::default();
+        let mut functions = HashMap::<usize, (Stat, Stat)>::new();
+        let mut lines = HashMap::<Result<(SourceId, usize), usize>, Stat>::new();
+
+        for (&(stack, ip), &stat) in &state.stats {
+            total.add(stat);
+
+            let stack = &state.stacks[stack];
+
+            let function = stack.last().copied().unwrap_or(usize::MAX);
+            functions.entry(function).or_default().0.add(stat);
+
+            for (n, &entry) in stack.iter().enumerate() {
+                // NB: recursive functions only count once towards the total.
+                if !stack[..n].contains(&entry) {
+                    functions.entry(entry).or_default().1.add(stat);
+                }
+            }
+
+            let location = unit
+                .debug_info()
+                .and_then(|debug| debug.instruction_at(ip))
+                .and_then(|inst| {
+                    let source = sources.get(inst.source_id)?;
+                    let (line, _) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+                    Some((inst.source_id, line))
+                })
+                .ok_or(function);
+
+            lines.entry(location).or_default().add(stat);
+        }
+
+        let mut functions = functions.into_iter().collect::<Vec<_>>();
+        functions.sort_by_key(|(_, (this, _))| Reverse(this.time));
+
+        let mut lines = lines.into_iter().collect::<Vec<_>>();
+        lines.sort_by_key(|(_, stat)| Reverse(stat.time));
+
+        writeln!(
+            o,
+            "# profile ({} instructions in {:?})",
+            total.count, total.time
+        )?;
+
+        writeln!(o)?;
+        writeln!(
+            o,
+            "{:>12} {:>6} {:>12} {:>12} {:>8}  function",
+            "self", "%", "total", "instructions", "calls"
+        )?;
+
+        for (entry, (this, all)) in functions.into_iter().take(top) {
+            let calls = state.calls.get(&entry).copied().unwrap_or_default();
+
+            writeln!(
+                o,
+                "{:>12} {:>6} {:>12} {:>12} {calls:>8}  {}",
+                format!("{:.2?}", this.time),
+                percent(this.time, total.time),
+                format!("{:.2?}", all.time),
+                this.count,
+                function_name(unit, entry)
+            )?;
+        }
+
+        writeln!(o)?;
+        writeln!(o, "{:>12} {:>6} {:>12}  line", "time", "%", "instructions")?;
+
+        for (location, stat) in lines.into_iter().take(top) {
+            write!(
+                o,
+                "{:>12} {:>6} {:>12}  ",
+                format!("{:.2?}", stat.time),
+                percent(stat.time, total.time),
+                stat.count
+            )?;
+
+            let (source_id, line) = match location {
+                Ok(location) => location,
+                Err(function) => {
+                    writeln!(o, "{} (no source)", function_name(unit, function))?;
+                    continue;
+                }
+            };
+
+            let Some(source) = sources.get(source_id) else {
+                writeln!(o, "<unknown>")?;
+                continue;
+            };
+
+            let text = source.as_str().lines().nth(line).unwrap_or_default();
+            writeln!(o, "{}:{}: {}", source.name(), line + 1, text.trim())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl VmHook for Profiler {
+    fn instruction(&self, vm: &Vm, ip: usize, _: &Inst) -> VmHookControl {
+        let now = Instant::now();
+        let mut state = self.state();
+        state.stop(now);
+
+        let id = vm as *const Vm as usize;
+
+        let stack = match state.current {
+            Some((current, stack)) if current == id => stack,
+            _ => {
+                let stack = state.intern(&self.entries, vm, ip);
+                state.current = Some((id, stack));
+                stack
+            }
+        };
+
+        state.stats.entry((stack, ip)).or_default().count += 1;
+        state.timing = Some((stack, ip, now));
+        VmHookControl::Continue
+    }
+
+    fn enter(&self, _: &Vm, ip: usize) -> VmHookControl {
+        let mut state = self.state();
+        state.current = None;
+        *state.calls.entry(ip).or_default() += 1;
+        VmHookControl::Continue
+    }
+
+    fn exit(&self, _: &Vm, _: usize) -> VmHookControl {
+        self.state().current = None;
+        VmHookControl::Continue
+    }
+}
+
+/// Get the name of the function with the given entry.
+fn function_name(unit: &Unit, entry: usize) -> String {
+    let Some(debug) = unit.debug_info() else {
+        return String::from("<unknown>");
+    };
+
+    let Some(signature) = debug
+        .functions_rev
+        .get(&entry)
+        .and_then(|hash| debug.functions.get(hash))
+    else {
+        return String::from("<unknown>");
+    };
+
+    signature.path.to_string()
+}
+
+fn percent(part: Duration, total: Duration) -> String {
+    if total.is_zero() {
+        return String::from("-");
+    }
+
+    format!("{:.1}%", part.as_secs_f64() / total.as_secs_f64() * 100.0)
+}
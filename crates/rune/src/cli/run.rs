@@ -5,12 +5,14 @@ use std::time::Instant;
 
 use anyhow::{anyhow, Result};
 
+use crate::cli::profile::Profiler;
 use crate::cli::{AssetKind, CommandBase, Config, ExitCode, Io, SharedFlags};
 use crate::runtime::{UnitStorage, VmError, VmExecution, VmResult};
 use crate::{Context, Hash, Sources, Unit, Value, Vm};
 
 mod cli {
     use std::path::PathBuf;
+    use std::string::String;
     use std::vec::Vec;
 
     use clap::Parser;
@@ -28,6 +30,17 @@ mod cli {
         /// Time how long the script took to execute.
         #[arg(long)]
         pub(super) time: bool,
+        /// Profile the script, recording the time spent and the number of
+        /// instructions executed in each function and source line.
+        #[arg(long)]
+        pub(super) profile: bool,
+        /// Where to write the call stacks recorded when profiling, in the
+        /// collapsed format used by flamegraph tools.
+        #[arg(long, default_value = "rune.folded")]
+        pub(super) profile_output: PathBuf,
+        /// The number of functions and lines to include in the profiling report.
+        #[arg(long, default_value = "10")]
+        pub(super) profile_top: usize,
         /// Perform a default dump.
         #[arg(short, long)]
         pub(super) dump: bool,
@@ -69,6 +82,9 @@ mod cli {
         pub(super) trace_limit: Option<usize>,
         /// Explicit paths to run.
         pub(super) run_path: Vec<PathBuf>,
+        /// Arguments to forward to the script, specified after `--`.
+        #[arg(last = true)]
+        pub(super) args: Vec<String>,
     }
 }
 
@@ -81,7 +97,9 @@ impl CommandBase for Flags {
     }
 
     #[inline]
-    fn propagate(&mut self, _: &mut Config, _: &mut SharedFlags) {
+    fn propagate(&mut self, c: &mut Config, _: &mut SharedFlags) {
+        c.args.clone_from(&self.args);
+
         if self.dump || self.dump_all {
             self.dump_unit = true;
             self.dump_stack = true;
@@ -243,7 +261,14 @@ pub(super) async fn run(
 
     let last = Instant::now();
 
-    let mut vm = Vm::new(runtime, unit);
+    let profiler = args.profile.then(|| Arc::new(Profiler::new(&unit)));
+
+    let mut vm = Vm::new(runtime, unit.clone());
+
+    if let Some(profiler) = &profiler {
+        vm.set_hook(Some(profiler.clone()));
+    }
+
     let mut execution: VmExecution<_> = vm.execute(entry, ())?;
 
     let result = if args.trace {
@@ -291,6 +316,17 @@ pub(super) async fn run(
         }
     };
 
+    if let Some(profiler) = &profiler {
+        profiler.write_collapsed(&unit, &args.profile_output)?;
+        profiler.report(io.stderr, &unit, sources, args.profile_top)?;
+
+        writeln!(
+            io.stderr,
+            "Wrote collapsed call stacks to {}",
+            args.profile_output.display()
+        )?;
+    }
+
     let exit = if let Some(error) = errored {
         error.emit(io.stdout, sources)?;
         ExitCode::VmError
@@ -9,6 +9,7 @@ use anyhow::{Context, Result};
 
 use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
+use crate::cli::coverage::{self, Coverage};
 use crate::cli::naming::Naming;
 use crate::cli::visitor;
 use crate::cli::{
@@ -22,6 +23,7 @@ use crate::runtime::{Repr, Value, Vm, VmError, VmResult};
 use crate::{Diagnostics, Hash, Item, ItemBuf, Source, Sources, TypeHash, Unit};
 
 mod cli {
+    use std::path::PathBuf;
     use std::string::String;
     use std::vec::Vec;
 
@@ -43,6 +45,17 @@ mod cli {
         /// tests found in runtime contexts will be run.
         #[arg(long)]
         pub skip_lib_tests: bool,
+        /// Collect line and branch coverage for lib tests.
+        ///
+        /// Coverage is only collected for the `#[test]` functions of the
+        /// scripts being tested. Doc tests and tests from the runtime context
+        /// are run as usual but don't contribute to the report, and nothing is
+        /// collected if lib tests are skipped with `--skip-lib-tests`.
+        #[arg(long)]
+        pub coverage: bool,
+        /// Where to write the collected coverage, in the lcov format.
+        #[arg(long, default_value = "lcov.info")]
+        pub coverage_output: PathBuf,
         /// Filter tests by name.
         pub filters: Vec<String>,
     }
@@ -92,6 +105,7 @@ struct Batch<'a> {
     kind: BatchKind,
     entry: Option<EntryPoint<'a>>,
     cases: Vec<TestCase>,
+    coverage: Option<Arc<Coverage>>,
 }
 
 /// Run all tests that can be found.
@@ -143,11 +157,7 @@ where
             continue;
         }
 
-        let mut options = options.clone();
-
-        if e.is_argument() {
-            options.function_body = true;
-        }
+        let options = e.options(options);
 
         let item = naming.item(&e)?;
 
@@ -206,10 +216,15 @@ where
             ))?;
         }
 
+        let coverage = flags
+            .coverage
+            .then(|| Arc::new(Coverage::new(unit.clone(), sources.clone())));
+
         batches.try_push(Batch {
             kind: BatchKind::LibTests,
             entry: Some(e.try_clone()?),
             cases,
+            coverage,
         })?;
 
         let mut artifacts = crate::doc::Artifacts::without_assets();
@@ -233,6 +248,7 @@ where
                 kind: BatchKind::DocTests,
                 entry: Some(e),
                 cases,
+                coverage: None,
             })?;
         }
     }
@@ -258,11 +274,13 @@ where
             kind: BatchKind::ContextDocTests,
             entry: None,
             cases,
+            coverage: None,
         })?;
     }
 
     let runtime = Arc::new(context.runtime()?);
     let mut failed = Vec::new();
+    let mut report = coverage::Report::default();
 
     for batch in batches {
         if batch.cases.is_empty() {
@@ -309,6 +327,11 @@ where
             }
 
             let mut vm = Vm::new(runtime.clone(), case.unit.clone());
+
+            if let Some(coverage) = &batch.coverage {
+                vm.set_hook(Some(coverage.clone()));
+            }
+
             case.execute(&mut vm, &capture).await?;
             executed = executed.wrapping_add(1);
 
@@ -332,6 +355,10 @@ where
                 break;
             }
         }
+
+        if let Some(coverage) = &batch.coverage {
+            report.add(coverage);
+        }
     }
 
     if flags.quiet {
@@ -392,6 +419,18 @@ where
 
     writeln!(io.stdout, " in {:.3} seconds", elapsed.as_secs_f64())?;
 
+    if flags.coverage {
+        report.write_lcov(&flags.coverage_output)?;
+        report.summary(io)?;
+
+        writeln!(
+            io.stdout,
+            "{:>12} coverage to {}",
+            "Wrote",
+            flags.coverage_output.display()
+        )?;
+    }
+
     if build_errors == 0 && failures == 0 {
         Ok(ExitCode::Success)
     } else {
@@ -45,6 +45,8 @@ pub(crate) struct Assembly {
     pub(crate) instructions: Vec<(AssemblyInst, Span)>,
     /// Comments associated with instructions.
     pub(crate) comments: HashMap<usize, String>,
+    /// Variables which are defined before the instruction at the given offset.
+    pub(crate) variables: HashMap<usize, Vec<(String, InstAddress)>>,
     /// The number of labels.
     pub(crate) label_count: usize,
     /// The collection of functions required by this assembly.
@@ -59,6 +61,7 @@ impl Assembly {
             labels: Default::default(),
             instructions: Default::default(),
             comments: Default::default(),
+            variables: Default::default(),
             label_count,
             required_functions: Default::default(),
         }
@@ -183,6 +186,18 @@ impl Assembly {
         Ok(())
     }
 
+    /// Record that a named variable has been defined at the given address.
+    pub(crate) fn variable(&mut self, name: &str, addr: InstAddress) -> compile::Result<()> {
+        let index = self.instructions.len();
+
+        self.variables
+            .entry(index)
+            .or_try_default()?
+            .try_push((name.try_to_owned()?, addr))?;
+
+        Ok(())
+    }
+
     fn inner_push(&mut self, inst: AssemblyInst, span: &dyn Spanned) -> compile::Result<()> {
         if let AssemblyInst::Raw {
             raw: Inst::Call { hash, .. },
@@ -6,6 +6,7 @@ use crate::alloc::{Vec, VecDeque};
 use crate::ast;
 use crate::ast::{LitStr, Spanned};
 use crate::compile::{self, ErrorKind};
+use crate::diagnostics::{Lint, LintLevel};
 use crate::parse::{self, Parse, Resolve, ResolveContext};
 
 /// Helper for parsing internal attributes.
@@ -229,3 +230,103 @@ impl Attribute for Doc {
     /// Must match the specified name.
     const PATH: &'static str = "doc";
 }
+
+/// An attribute which sets the level of lints, like `#[allow(unused)]`.
+pub(crate) trait LintAttribute: Attribute + Parse {
+    /// The level the attribute sets.
+    const LEVEL: LintLevel;
+
+    /// The names of the lints the level applies to.
+    fn names(&self) -> &ast::Parenthesized<ast::Ident, T![,]>;
+}
+
+macro_rules! lint_attribute {
+    ($(#[doc = $doc:literal])* $ty:ident, $path:literal, $level:ident) => {
+        $(#[doc = $doc])*
+        #[derive(Parse)]
+        pub(crate) struct $ty {
+            /// The names of the lints.
+            pub names: ast::Parenthesized<ast::Ident, T![,]>,
+        }
+
+        impl Attribute for $ty {
+            /// Must match the specified name.
+            const PATH: &'static str = $path;
+        }
+
+        impl LintAttribute for $ty {
+            const LEVEL: LintLevel = LintLevel::$level;
+
+            #[inline]
+            fn names(&self) -> &ast::Parenthesized<ast::Ident, T![,]> {
+                &self.names
+            }
+        }
+    };
+}
+
+lint_attribute!(
+    /// The `#[allow(..)]` attribute.
+    Allow,
+    "allow",
+    Allow
+);
+
+lint_attribute!(
+    /// The `#[warn(..)]` attribute.
+    Warn,
+    "warn",
+    Warn
+);
+
+lint_attribute!(
+    /// The `#[deny(..)]` attribute.
+    Deny,
+    "deny",
+    Deny
+);
+
+/// Parse all `#[allow(..)]`, `#[warn(..)]` and `#[deny(..)]` attributes,
+/// returning the level of each lint they configure.
+pub(crate) fn lint_levels(
+    p: &mut Parser,
+    cx: ResolveContext<'_>,
+    attributes: &[ast::Attribute],
+) -> compile::Result<Vec<(Lint, LintLevel)>> {
+    let mut levels = Vec::new();
+    lint_levels_of::<Allow>(p, cx, attributes, &mut levels)?;
+    lint_levels_of::<Warn>(p, cx, attributes, &mut levels)?;
+    lint_levels_of::<Deny>(p, cx, attributes, &mut levels)?;
+    Ok(levels)
+}
+
+fn lint_levels_of<T>(
+    p: &mut Parser,
+    cx: ResolveContext<'_>,
+    attributes: &[ast::Attribute],
+    levels: &mut Vec<(Lint, LintLevel)>,
+) -> compile::Result<()>
+where
+    T: LintAttribute,
+{
+    for result in p.parse_all::<T>(cx, attributes)? {
+        let (_, attr) = result?;
+
+        for (ident, _) in attr.names() {
+            let name = ident.resolve(cx)?;
+
+            let Some(lint) = Lint::from_name(name) else {
+                return Err(compile::Error::new(
+                    ident,
+                    ErrorKind::UnknownLint {
+                        name: name.try_into()?,
+                    },
+                ));
+            };
+
+            levels.try_push((lint, T::LEVEL))?;
+        }
+    }
+
+    Ok(())
+}
@@ -56,6 +56,10 @@ pub(crate) fn compile(
     // The worker queue.
     let mut worker = Worker::new(q);
 
+    // Warnings are collected until the end of compilation, where their lint
+    // levels are resolved.
+    let lints = worker.q.diagnostics.begin_lints();
+
     // Queue up the initial sources to be loaded.
     for source_id in worker.q.sources.source_ids() {
         let (root_item_id, mod_item) = match worker.q.insert_root_mod(source_id, Span::empty()) {
@@ -84,6 +88,7 @@ pub(crate) fn compile(
     worker.index()?;
 
     if worker.q.diagnostics.has_error() {
+        worker.q.resolve_lints(lints)?;
         return Ok(());
     }
 
@@ -113,6 +118,8 @@ pub(crate) fn compile(
         }
     }
 
+    worker.q.unused_fields()?;
+    worker.q.resolve_lints(lints)?;
     Ok(())
 }
 
@@ -378,7 +385,7 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                 if !self.q.is_used(&item_meta) {
                     self.q
                         .diagnostics
-                        .not_used(location.source_id, &location.span, None)?;
+                        .unused_import(location.source_id, &location.span)?;
                 }
 
                 let missing = match result {
@@ -386,6 +393,8 @@ impl<'arena> CompileBuildEntry<'_, 'arena> {
                         let item = self.q.pool.item(item_id);
 
                         if self.q.context.contains_prefix(item)? || self.q.contains_prefix(item)? {
+                            self.q
+                                .visit_resolved(&import.entry.location, item_id, |_| true)?;
                             None
                         } else {
                             Some(item_id)
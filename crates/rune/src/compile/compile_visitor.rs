@@ -24,6 +24,55 @@ pub trait CompileVisitor {
         Ok(())
     }
 
+    /// Visit a function or closure argument.
+    ///
+    /// Every variable declared inside of the argument at the given location is
+    /// a parameter.
+    fn visit_fn_arg(&mut self, _location: &dyn Located) -> Result<(), MetaError> {
+        Ok(())
+    }
+
+    /// Visit a variable captured by the closure at the given location.
+    ///
+    /// This is called once for every captured variable, in the order in which
+    /// they are captured.
+    fn visit_closure_capture(
+        &mut self,
+        _location: &dyn Located,
+        _name: &str,
+    ) -> Result<(), MetaError> {
+        Ok(())
+    }
+
+    /// Visit the declaration of a named field in a struct or an enum variant.
+    fn visit_field(
+        &mut self,
+        _location: &dyn Located,
+        _item: &Item,
+        _hash: Hash,
+        _field: &str,
+    ) -> Result<(), MetaError> {
+        Ok(())
+    }
+
+    /// Visit a use of a named field, like a field access or a key in a struct
+    /// literal or pattern.
+    ///
+    /// The `hash` is the type hash of the struct or variant the field belongs
+    /// to. It's `None` for field accesses like `value.field`, since values are
+    /// dynamically typed and the access can't be resolved to a type. A
+    /// `shorthand` use like the `x` in `Point { x }` also refers to a variable
+    /// of the same name.
+    fn visit_field_use(
+        &mut self,
+        _location: &dyn Located,
+        _hash: Option<Hash>,
+        _field: &str,
+        _shorthand: bool,
+    ) -> Result<(), MetaError> {
+        Ok(())
+    }
+
     /// Visit something that is a module.
     fn visit_mod(&mut self, _location: &dyn Located) -> Result<(), MetaError> {
         Ok(())
@@ -185,13 +185,10 @@ impl TraitContext<'_> {
             handler: handler.clone(),
             trait_hash: Some(self.trait_hash),
             doc: DocFunction {
-                #[cfg(feature = "doc")]
                 is_async: false,
                 #[cfg(feature = "doc")]
                 args: None,
-                #[cfg(feature = "doc")]
                 argument_types: Box::default(),
-                #[cfg(feature = "doc")]
                 return_type: meta::DocType::empty(),
             },
         };
@@ -203,6 +200,7 @@ impl TraitContext<'_> {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
             kind: ModuleAssociatedKind::Function(function),
         };
@@ -287,6 +285,8 @@ pub struct Context {
     functions: hash::Map<Arc<FunctionHandler>>,
     /// Registered deprecation mesages for native functions.
     deprecations: hash::Map<String>,
+    /// Registered replacements for deprecated native functions.
+    replacements: hash::Map<String>,
     /// Information on associated types.
     #[cfg(feature = "doc")]
     associated: hash::Map<Vec<Hash>>,
@@ -350,6 +350,12 @@ impl Context {
 
         this.install(crate::modules::collections::module()?)?;
         #[cfg(feature = "alloc")]
+        this.install(crate::modules::collections::binary_heap::module()?)?;
+        #[cfg(feature = "alloc")]
+        this.install(crate::modules::collections::btree_map::module()?)?;
+        #[cfg(feature = "alloc")]
+        this.install(crate::modules::collections::btree_set::module()?)?;
+        #[cfg(feature = "alloc")]
         this.install(crate::modules::collections::hash_map::module()?)?;
         #[cfg(feature = "alloc")]
         this.install(crate::modules::collections::hash_set::module()?)?;
@@ -373,6 +379,14 @@ impl Context {
         this.install(crate::modules::option::module()?)?;
         this.install(crate::modules::result::module()?)?;
         this.install(crate::modules::stream::module()?)?;
+        #[cfg(feature = "tokio")]
+        {
+            this.install(crate::modules::sync::module()?)?;
+            this.install(crate::modules::sync::mpsc::module()?)?;
+            this.install(crate::modules::sync::oneshot::module()?)?;
+            this.install(crate::modules::sync::broadcast::module()?)?;
+            this.install(crate::modules::task::module()?)?;
+        }
         this.install(crate::modules::test::module()?)?;
         this.install(crate::modules::vec::module()?)?;
         this.install(crate::modules::slice::module()?)?;
@@ -474,6 +488,11 @@ impl Context {
         Ok(())
     }
 
+    /// Iterate over all available metadata in the [Context].
+    pub(crate) fn iter_meta(&self) -> impl Iterator<Item = &ContextMeta> {
+        self.meta.iter()
+    }
+
     /// Iterate over all available functions in the [Context].
     #[cfg(any(feature = "cli", feature = "languageserver"))]
     pub(crate) fn iter_functions(&self) -> impl Iterator<Item = (&ContextMeta, &meta::Signature)> {
@@ -529,7 +548,6 @@ impl Context {
     }
 
     /// Lookup meta by its hash.
-    #[cfg(any(feature = "cli", feature = "languageserver", feature = "emit"))]
     pub(crate) fn lookup_meta_by_hash(
         &self,
         hash: Hash,
@@ -548,6 +566,11 @@ impl Context {
         self.deprecations.get(&hash).map(|s| s.as_str())
     }
 
+    /// Lookup the replacement of a deprecated function by function hash.
+    pub(crate) fn lookup_replacement(&self, hash: Hash) -> Option<&str> {
+        self.replacements.get(&hash).map(|s| s.as_str())
+    }
+
     /// Check if unit contains the given name by prefix.
     pub(crate) fn contains_prefix(&self, item: &Item) -> alloc::Result<bool> {
         self.names.contains_prefix(item)
@@ -725,15 +748,14 @@ impl Context {
                     let constructor = match &ty.constructor {
                         Some(c) => {
                             let signature = meta::Signature {
-                                #[cfg(feature = "doc")]
                                 is_async: false,
                                 #[cfg(feature = "doc")]
                                 arguments: Some(fields_to_arguments(fields)?),
-                                #[cfg(feature = "doc")]
+                                argument_types: Box::default(),
                                 return_type: meta::DocType::new(ty.hash),
                             };
 
-                            self.insert_native_fn(&ty.type_info, ty.hash, c, None)?;
+                            self.insert_native_fn(&ty.type_info, ty.hash, c, None, None)?;
                             Some(signature)
                         }
                         None => None,
@@ -792,15 +814,20 @@ impl Context {
 
                         let constructor = if let Some(c) = &variant.constructor {
                             let signature = meta::Signature {
-                                #[cfg(feature = "doc")]
                                 is_async: false,
                                 #[cfg(feature = "doc")]
                                 arguments: Some(fields_to_arguments(fields)?),
-                                #[cfg(feature = "doc")]
+                                argument_types: Box::default(),
                                 return_type: meta::DocType::new(ty.hash),
                             };
 
-                            self.insert_native_fn(&item, hash, c, variant.deprecated.as_deref())?;
+                            self.insert_native_fn(
+                                &item,
+                                hash,
+                                c,
+                                variant.deprecated.as_deref(),
+                                None,
+                            )?;
                             Some(signature)
                         } else {
                             None
@@ -1039,7 +1066,13 @@ impl Context {
 
                 let signature = meta::Signature::from_context(&f.doc, &m.common)?;
 
-                self.insert_native_fn(&m.item, m.hash, &f.handler, m.common.deprecated.as_deref())?;
+                self.insert_native_fn(
+                    &m.item,
+                    m.hash,
+                    &f.handler,
+                    m.common.deprecated.as_deref(),
+                    m.common.replacement.as_deref(),
+                )?;
 
                 meta::Kind::Function {
                     associated: None,
@@ -1132,6 +1165,7 @@ impl Context {
                         *hash,
                         &f.handler,
                         assoc.common.deprecated.as_deref(),
+                        assoc.common.replacement.as_deref(),
                     )?;
                 }
 
@@ -1140,6 +1174,7 @@ impl Context {
                     hash,
                     &f.handler,
                     assoc.common.deprecated.as_deref(),
+                    assoc.common.replacement.as_deref(),
                 )?;
 
                 meta::Kind::Function {
@@ -1178,6 +1213,7 @@ impl Context {
         hash: Hash,
         handler: &Arc<FunctionHandler>,
         deprecation: Option<&str>,
+        replacement: Option<&str>,
     ) -> Result<(), ContextError> {
         if self.functions.contains_key(&hash) {
             return Err(ContextError::ConflictingFunction {
@@ -1192,6 +1228,11 @@ impl Context {
             self.deprecations.try_insert(hash, msg.try_to_owned()?)?;
         }
 
+        if let Some(replacement) = replacement {
+            self.replacements
+                .try_insert(hash, replacement.try_to_owned()?)?;
+        }
+
         Ok(())
     }
 
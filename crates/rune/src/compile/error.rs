@@ -14,6 +14,7 @@ use crate::ast::unescape;
 use crate::ast::{Span, Spanned};
 use crate::compile::ir;
 use crate::compile::{HasSpan, Location, MetaInfo, Visibility};
+use crate::diagnostics::Suggestion;
 use crate::hash::TooManyParameters;
 use crate::indexing::items::{GuardMismatch, MissingLastId};
 use crate::macros::{SyntheticId, SyntheticKind};
@@ -33,6 +34,9 @@ pub struct Error {
     // Errors are exempt from fallible allocations since they're not commonly
     // constructed.
     kind: rust_alloc::boxed::Box<ErrorKind>,
+    // Suggestions on how to fix the error, a boxed slice to keep the error
+    // small since most errors don't have any.
+    suggestions: rust_alloc::boxed::Box<[Suggestion]>,
 }
 
 impl Error {
@@ -45,6 +49,7 @@ impl Error {
         Self {
             span: span.span(),
             kind: rust_alloc::boxed::Box::new(ErrorKind::from(kind)),
+            suggestions: rust_alloc::boxed::Box::default(),
         }
     }
 
@@ -57,9 +62,23 @@ impl Error {
         Self {
             span: span.span(),
             kind: rust_alloc::boxed::Box::new(ErrorKind::msg(message)),
+            suggestions: rust_alloc::boxed::Box::default(),
         }
     }
 
+    /// Attach a suggestion on how to fix the error.
+    pub(crate) fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        let mut suggestions = rust_alloc::vec::Vec::from(self.suggestions);
+        suggestions.push(suggestion);
+        self.suggestions = suggestions.into_boxed_slice();
+        self
+    }
+
+    /// Suggestions on how to fix the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
     /// Get the kind of the error.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &ErrorKind {
@@ -330,6 +349,14 @@ pub(crate) enum ErrorKind {
         field: Box<str>,
         item: ItemBuf,
     },
+    MissingTraitFunction {
+        name: Box<str>,
+        item: ItemBuf,
+    },
+    NotTraitFunction {
+        name: Box<str>,
+        item: ItemBuf,
+    },
     UnsupportedAssignExpr,
     UnsupportedBinaryExpr,
     UnsupportedRef,
@@ -337,6 +364,10 @@ pub(crate) enum ErrorKind {
         expected: usize,
         actual: usize,
     },
+    TypeMismatch {
+        expected: Box<str>,
+        actual: Box<str>,
+    },
     UnsupportedPatternExpr,
     UnsupportedBinding,
     DuplicateObjectKey {
@@ -455,6 +486,15 @@ pub(crate) enum ErrorKind {
         depth: usize,
         max: usize,
     },
+    NoMatchingMacroRule {
+        item: ItemBuf,
+    },
+    UnsupportedMacroFragment {
+        name: Box<str>,
+    },
+    MissingMacroVariable {
+        name: Box<str>,
+    },
     YieldInConst,
     AwaitInConst,
     AwaitOutsideAsync,
@@ -492,6 +532,9 @@ pub(crate) enum ErrorKind {
     MultipleMatchingAttributes {
         name: &'static str,
     },
+    UnknownLint {
+        name: Box<str>,
+    },
     MissingSourceId {
         source_id: SourceId,
     },
@@ -773,6 +816,15 @@ impl fmt::Display for ErrorKind {
             ErrorKind::LitObjectNotField { field, item } => {
                 write!(f, "Field `{field}` is not a field in `{item}`")?;
             }
+            ErrorKind::MissingTraitFunction { name, item } => {
+                write!(
+                    f,
+                    "Missing function `{name}` in implementation of trait `{item}`"
+                )?;
+            }
+            ErrorKind::NotTraitFunction { name, item } => {
+                write!(f, "Function `{name}` is not a member of trait `{item}`")?;
+            }
             ErrorKind::UnsupportedAssignExpr => {
                 write!(f, "Cannot assign to expression")?;
             }
@@ -785,6 +837,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::BadArgumentCount { expected, actual } => {
                 write!(f, "Wrong number of arguments {actual}, expected {expected}",)?;
             }
+            ErrorKind::TypeMismatch { expected, actual } => {
+                write!(f, "Expected type `{expected}`, but found `{actual}`")?;
+            }
             ErrorKind::UnsupportedPatternExpr => {
                 write!(f, "This kind of expression is not supported as a pattern")?;
             }
@@ -979,6 +1034,18 @@ impl fmt::Display for ErrorKind {
                     "Reached macro recursion limit at {depth}, limit is {max}",
                 )?;
             }
+            ErrorKind::NoMatchingMacroRule { item } => {
+                write!(f, "No rules of macro `{item}` matched this invocation")?;
+            }
+            ErrorKind::UnsupportedMacroFragment { name } => {
+                write!(
+                    f,
+                    "Unsupported fragment specifier `{name}`, expected one of `block`, `expr`, `ident`, `lit`, `pat`, `path`, `tt` or `ty`",
+                )?;
+            }
+            ErrorKind::MissingMacroVariable { name } => {
+                write!(f, "No macro variable named `${name}` in this rule")?;
+            }
             ErrorKind::YieldInConst => {
                 write!(f, "Expression `yield` inside of constant function")?;
             }
@@ -1052,6 +1119,9 @@ impl fmt::Display for ErrorKind {
             ErrorKind::MultipleMatchingAttributes { name } => {
                 write!(f, "Can only specify one attribute named `{name}`")?;
             }
+            ErrorKind::UnknownLint { name } => {
+                write!(f, "Unknown lint `{name}`")?;
+            }
             ErrorKind::MissingSourceId { source_id } => {
                 write!(f, "Missing source id `{source_id}`")?;
             }
@@ -348,13 +348,16 @@ impl ItemMeta {
 #[derive(Debug, TryClone)]
 pub struct Signature {
     /// An asynchronous function.
-    #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
     /// Arguments to the function.
     #[cfg(feature = "doc")]
     pub(crate) arguments: Option<Box<[DocArgument]>>,
+    /// The base type of each argument, or [`Hash::EMPTY`] if it's not known.
+    ///
+    /// Unlike `arguments` this is available without the `doc` feature, since
+    /// it's used to check type annotations.
+    pub(crate) argument_types: Box<[Hash]>,
     /// Return type of the function.
-    #[cfg(feature = "doc")]
     pub(crate) return_type: DocType,
 }
 
@@ -366,7 +369,6 @@ impl Signature {
         common: &ModuleItemCommon,
     ) -> alloc::Result<Self> {
         Ok(Self {
-            #[cfg(feature = "doc")]
             is_async: doc.is_async,
             #[cfg(feature = "doc")]
             arguments: context_to_arguments(
@@ -374,7 +376,7 @@ impl Signature {
                 doc.argument_types.as_ref(),
                 common.docs.args(),
             )?,
-            #[cfg(feature = "doc")]
+            argument_types: doc.argument_types.iter().map(|ty| ty.base).try_collect()?,
             return_type: doc.return_type.try_clone()?,
         })
     }
@@ -436,7 +438,7 @@ pub(crate) enum DocName {
     Index(#[try_clone(copy)] usize),
 }
 
-#[cfg(feature = "cli")]
+#[cfg(any(feature = "cli", feature = "languageserver"))]
 impl DocName {
     pub(crate) fn is_self(&self) -> bool {
         match self {
@@ -473,7 +475,6 @@ pub(crate) struct DocArgument {
 #[derive(Default, Debug, TryClone)]
 pub struct DocType {
     /// The base type.
-    #[cfg(feature = "doc")]
     pub(crate) base: Hash,
     /// Generic parameters.
     #[cfg(feature = "doc")]
@@ -493,7 +494,6 @@ impl DocType {
         generics: [DocType; N],
     ) -> alloc::Result<Self> {
         Ok(Self {
-            #[cfg(feature = "doc")]
             base,
             #[cfg(feature = "doc")]
             generics: Box::try_from(generics)?,
@@ -501,10 +501,8 @@ impl DocType {
     }
 
     /// Construct type with the specified base type.
-    #[cfg_attr(not(feature = "doc"), allow(unused_variables))]
     pub(crate) fn new(base: Hash) -> Self {
         Self {
-            #[cfg(feature = "doc")]
             base,
             #[cfg(feature = "doc")]
             generics: Box::default(),
@@ -2,6 +2,8 @@ use core::fmt;
 
 use ::rust_alloc::boxed::Box;
 
+use crate::diagnostics::{Lint, LintLevel, LintLevels};
+
 /// Error raised when trying to parse an invalid option.
 #[derive(Debug, Clone)]
 pub struct ParseOptionError {
@@ -124,6 +126,8 @@ pub struct Options {
     pub(crate) max_macro_depth: usize,
     /// Rune format options.
     pub(crate) fmt: FmtOptions,
+    /// Configured lint levels.
+    pub(crate) lints: LintLevels,
 }
 
 impl Options {
@@ -141,6 +145,7 @@ impl Options {
         v2: false,
         max_macro_depth: 64,
         fmt: FmtOptions::DEFAULT,
+        lints: LintLevels::DEFAULT,
     };
 
     /// Construct lossy rune options from the `RUNEFLAGS` environment variable.
@@ -303,6 +308,19 @@ impl Options {
                 default: "true",
                 options: BOOL,
             },
+            OptionMeta {
+                key: "lint.<name>",
+                unstable: false,
+                doc: &docstring! {
+                    /// Set the level of the named lint, like
+                    /// `lint.unused_imports=deny`.
+                    ///
+                    /// Attributes like `#[allow(unused_imports)]` take
+                    /// precedence over this option.
+                },
+                default: "<lint default>",
+                options: "allow, warn, deny",
+            },
         ];
 
         VALUES
@@ -383,6 +401,8 @@ impl Options {
                     self.max_macro_depth = number;
                 }
                 other => {
+                    let value = tail;
+
                     let Some((head, tail)) = other.split_once('.') else {
                         return Err(ParseOptionError {
                             env,
@@ -397,6 +417,18 @@ impl Options {
                         "fmt" => {
                             self.fmt.parse_option_with(tail, env)?;
                         }
+                        "lint" => {
+                            let (Some(lint), Some(level)) =
+                                (Lint::from_name(tail), value.and_then(LintLevel::from_name))
+                            else {
+                                return Err(ParseOptionError {
+                                    env,
+                                    option: option.into(),
+                                });
+                            };
+
+                            self.lints.set(lint, level);
+                        }
                         _ => {
                             return Err(ParseOptionError {
                                 env,
@@ -448,6 +480,14 @@ impl Options {
     pub fn script(&mut self, enabled: bool) {
         self.function_body = enabled;
     }
+
+    /// Set the level of the given lint, overriding its default level.
+    ///
+    /// Lint attributes like `#[allow(..)]` in the compiled sources take
+    /// precedence over the levels configured here.
+    pub fn lint(&mut self, lint: Lint, level: LintLevel) {
+        self.lints.set(lint, level);
+    }
 }
 
 impl Default for Options {
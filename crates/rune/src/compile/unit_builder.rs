@@ -18,8 +18,8 @@ use crate::query::QueryInner;
 use crate::runtime::debug::{DebugArgs, DebugSignature};
 use crate::runtime::unit::UnitEncoder;
 use crate::runtime::{
-    Call, ConstValue, DebugInfo, DebugInst, Inst, InstAddress, Label, Protocol, Rtti, RttiKind,
-    StaticString, Unit, UnitFn,
+    Call, ConstValue, DebugInfo, DebugInst, DebugVariable, Inst, InstAddress, Label, Protocol,
+    Rtti, RttiKind, StaticString, Unit, UnitFn,
 };
 use crate::{Context, Diagnostics, Hash, Item, SourceId};
 
@@ -922,6 +922,16 @@ impl UnitBuilder {
                 at,
                 DebugInst::new(location.source_id, span, comment, labels),
             )?;
+
+            if let Some(variables) = assembly.variables.get(&pos) {
+                let mut debug = Vec::try_with_capacity(variables.len())?;
+
+                for (name, addr) in variables {
+                    debug.try_push(DebugVariable::new(name.as_str().try_into()?, addr.offset()))?;
+                }
+
+                self.debug_mut()?.variables.try_insert(at, debug)?;
+            }
         }
 
         Ok(())
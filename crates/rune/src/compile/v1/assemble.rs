@@ -89,6 +89,30 @@ impl<'hir> Ctxt<'_, 'hir, '_> {
         Ok(())
     }
 
+    /// Define a variable in the current scope.
+    ///
+    /// When emitting debug information, variables which are declared through
+    /// a plain identifier also have their name recorded so that they can be
+    /// inspected at runtime.
+    fn define(
+        &mut self,
+        span: &'hir dyn Spanned,
+        name: hir::Variable,
+        addr: &Address<'_, 'hir>,
+    ) -> compile::Result<()> {
+        self.scopes.define(span, name, addr)?;
+
+        if self.options.debug_info {
+            if let Some(ident) = self.q.sources.source(self.source_id, span.span()) {
+                if is_ident(ident) {
+                    self.asm.variable(ident, addr.addr())?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get the latest relevant warning context.
     pub(crate) fn context(&self) -> Option<&'hir dyn Spanned> {
         self.contexts.last().copied()
@@ -227,7 +251,7 @@ pub(crate) fn fn_from_item_fn<'hir>(
                     return Err(compile::Error::new(span, ErrorKind::UnsupportedSelf));
                 }
 
-                cx.scopes.define(span, *name, needs)?;
+                cx.define(span, *name, needs)?;
             }
             hir::FnArg::Pat(pat) => {
                 let asm = pattern_panic(cx, pat, move |cx, false_label| {
@@ -265,7 +289,7 @@ pub(crate) fn async_block_secondary<'hir>(
     let linear = cx.scopes.linear(&hir.block, hir.captures.len())?;
 
     for (name, needs) in hir.captures.iter().copied().zip(&linear) {
-        cx.scopes.define(&hir.block, name, needs)?;
+        cx.define(&hir.block, name, needs)?;
     }
 
     return_(cx, &hir.block, hir.block, block_without_scope)?.ignore();
@@ -295,7 +319,7 @@ pub(crate) fn expr_closure_secondary<'hir>(
         )?;
 
         for (capture, needs) in hir.captures.iter().copied().zip(&environment) {
-            cx.scopes.define(hir, capture, needs)?;
+            cx.define(hir, capture, needs)?;
         }
     }
 
@@ -451,7 +475,7 @@ fn pat_binding_with<'a, 'hir>(
     }
 
     for (name, needs) in names.iter().copied().zip(linear.iter()) {
-        cx.scopes.define(needs.span(), name, needs)?;
+        cx.define(needs.span(), name, needs)?;
     }
 
     Ok(asm)
@@ -488,7 +512,7 @@ fn pat_binding_with_single<'a, 'hir>(
         ));
     };
 
-    cx.scopes.define(needs.span(), name, addr)?;
+    cx.define(span, name, addr)?;
     Ok(asm)
 }
 
@@ -3324,3 +3348,14 @@ fn local<'a, 'hir>(
 
     Ok(Asm::new(hir, ()))
 }
+
+/// Test if the given source text is a plain identifier.
+fn is_ident(text: &str) -> bool {
+    let mut chars = text.chars();
+
+    let Some(c) = chars.next() else {
+        return false;
+    };
+
+    (c.is_alphabetic() || c == '_') && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
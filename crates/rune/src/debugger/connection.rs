@@ -0,0 +1,225 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::sync::mpsc;
+use std::thread;
+
+use ::rust_alloc::boxed::Box;
+use ::rust_alloc::string::String;
+use ::rust_alloc::vec::Vec;
+
+use anyhow::{anyhow, bail, Result};
+use serde::Serialize;
+
+use super::protocol::{Event, Request, Response};
+
+/// Input connection.
+///
+/// Messages are read on a background thread, so that the adapter can check
+/// for requests like `pause` without blocking while a script is running.
+pub(super) struct Input {
+    rx: mpsc::Receiver<Result<Request>>,
+}
+
+impl Input {
+    /// Wait for the next request, returns `None` if the input was closed.
+    pub(super) fn next(&self) -> Result<Option<Request>> {
+        match self.rx.recv() {
+            Ok(request) => Ok(Some(request?)),
+            Err(mpsc::RecvError) => Ok(None),
+        }
+    }
+
+    /// Get the next request if one is immediately available.
+    pub(super) fn try_next(&self) -> Result<Option<Request>> {
+        match self.rx.try_recv() {
+            Ok(request) => Ok(Some(request?)),
+            Err(mpsc::TryRecvError::Empty) => Ok(None),
+            Err(mpsc::TryRecvError::Disconnected) => bail!("input closed"),
+        }
+    }
+}
+
+/// Output connection.
+pub(super) struct Output {
+    seq: i64,
+    writer: Box<dyn Write + Send>,
+}
+
+impl Output {
+    /// Respond successfully to the given request.
+    pub(super) fn response<B>(&mut self, request: &Request, body: B) -> Result<()>
+    where
+        B: Serialize,
+    {
+        self.respond(request, Some(body))
+    }
+
+    /// Respond successfully to the given request without a body.
+    pub(super) fn ack(&mut self, request: &Request) -> Result<()> {
+        self.respond(request, None::<()>)
+    }
+
+    fn respond<B>(&mut self, request: &Request, body: Option<B>) -> Result<()>
+    where
+        B: Serialize,
+    {
+        self.seq += 1;
+
+        let response = Response {
+            seq: self.seq,
+            ty: "response",
+            request_seq: request.seq,
+            success: true,
+            command: &request.command,
+            message: None,
+            body,
+        };
+
+        self.write(&response)
+    }
+
+    /// Respond to the given request with an error.
+    pub(super) fn error(&mut self, request: &Request, message: &str) -> Result<()> {
+        self.seq += 1;
+
+        let response = Response {
+            seq: self.seq,
+            ty: "response",
+            request_seq: request.seq,
+            success: false,
+            command: &request.command,
+            message: Some(message),
+            body: None::<()>,
+        };
+
+        self.write(&response)
+    }
+
+    /// Send the given event.
+    pub(super) fn event<B>(&mut self, event: &str, body: B) -> Result<()>
+    where
+        B: Serialize,
+    {
+        self.send_event(event, Some(body))
+    }
+
+    /// Send the given event without a body.
+    pub(super) fn signal(&mut self, event: &str) -> Result<()> {
+        self.send_event(event, None::<()>)
+    }
+
+    fn send_event<B>(&mut self, event: &str, body: Option<B>) -> Result<()>
+    where
+        B: Serialize,
+    {
+        self.seq += 1;
+
+        let event = Event {
+            seq: self.seq,
+            ty: "event",
+            event,
+            body,
+        };
+
+        self.write(&event)
+    }
+
+    /// Write a single message.
+    fn write<T>(&mut self, message: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let bytes = serde_json::to_vec(message)?;
+
+        write!(self.writer, "Content-Length: {}\r\n\r\n", bytes.len())?;
+        self.writer.write_all(&bytes)?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Setup a stdin/stdout connection.
+pub(super) fn stdio() -> Result<(Input, Output)> {
+    stream(io::stdin(), io::stdout())
+}
+
+/// Setup a connection over the given reader and writer.
+pub(super) fn stream<R, W>(reader: R, writer: W) -> Result<(Input, Output)>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+
+    thread::Builder::new()
+        .name(String::from("rune-debugger-input"))
+        .spawn(move || {
+            let mut reader = BufReader::new(reader);
+
+            loop {
+                let request = match read_frame(&mut reader) {
+                    Ok(Some(frame)) => {
+                        serde_json::from_slice::<Request>(&frame).map_err(Into::into)
+                    }
+                    Ok(None) => break,
+                    Err(error) => Err(error),
+                };
+
+                let failed = request.is_err();
+
+                if tx.send(request).is_err() || failed {
+                    break;
+                }
+            }
+        })?;
+
+    let output = Output {
+        seq: 0,
+        writer: Box::new(writer),
+    };
+
+    Ok((Input { rx }, output))
+}
+
+/// Read a single frame from the given reader.
+pub(super) fn read_frame<R>(reader: &mut R) -> Result<Option<Vec<u8>>>
+where
+    R: BufRead,
+{
+    let mut content_length = None;
+    let mut line = String::new();
+
+    loop {
+        line.clear();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        let header = line.trim();
+
+        if header.is_empty() {
+            break;
+        }
+
+        let Some((key, value)) = header.split_once(':') else {
+            bail!("bad header: {header:?}");
+        };
+
+        if key.trim().eq_ignore_ascii_case("content-length") {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|error| anyhow!("bad content-length: {error}"))?;
+
+            content_length = Some(length);
+        }
+    }
+
+    let Some(length) = content_length else {
+        bail!("missing content-length");
+    };
+
+    let mut buf = ::rust_alloc::vec![0u8; length];
+    reader.read_exact(&mut buf)?;
+    Ok(Some(buf))
+}
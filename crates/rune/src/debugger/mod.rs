@@ -0,0 +1,453 @@
+//! Utility for building a debug adapter.
+//!
+//! This implements the [Debug Adapter Protocol] over stdin and stdout, which
+//! allows editors to set line breakpoints in scripts, step through them and
+//! inspect their variables while they are paused.
+//!
+//! [Debug Adapter Protocol]: https://microsoft.github.io/debug-adapter-protocol/
+
+#[cfg(test)]
+mod tests;
+
+mod connection;
+mod protocol;
+mod state;
+
+use std::path::PathBuf;
+
+use ::rust_alloc::string::ToString;
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+
+use crate::alloc::Vec;
+use crate::modules::capture_io::CaptureIo;
+use crate::runtime::VmResult;
+use crate::{Context, Hash, Options, Vm};
+
+use self::connection::{Input, Output};
+use self::protocol::Request;
+use self::state::State;
+
+/// The identifier of the single thread that scripts run on.
+const THREAD_ID: i64 = 1;
+
+/// The number of instructions to execute between checking for requests.
+const POLL_INTERVAL: usize = 1024;
+
+/// How execution should proceed when resumed.
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Run until a breakpoint is hit.
+    Continue,
+    /// Stop at the next line, for the given reason.
+    Pause(&'static str),
+    /// Stop at the next line, including lines in called functions.
+    In,
+    /// Stop at the next line in a call frame at or below the given depth.
+    Over(usize),
+    /// Stop at the next line in a call frame below the given depth.
+    Out(usize),
+}
+
+impl Mode {
+    /// Test if a step is complete when entering a new line at the given call
+    /// frame depth.
+    fn is_step_done(self, depth: usize) -> bool {
+        match self {
+            Mode::In => true,
+            Mode::Over(start) => depth <= start,
+            Mode::Out(start) => depth < start,
+            Mode::Continue | Mode::Pause(..) => false,
+        }
+    }
+}
+
+/// What to do after the virtual machine has been paused.
+enum Action {
+    Resume(Mode),
+    Disconnect,
+}
+
+/// Run a debug adapter over stdin and stdout.
+///
+/// Since stdout is used to communicate with the client, the context should be
+/// configured so that output from scripts is written to `capture`. Captured
+/// output is forwarded to the client.
+///
+/// If the client doesn't specify a `program` to launch, `program` is used.
+pub async fn run(
+    context: Context,
+    capture: &CaptureIo,
+    options: Options,
+    program: Option<PathBuf>,
+) -> Result<()> {
+    let (input, output) = connection::stdio()?;
+    serve(input, output, context, capture, options, program).await
+}
+
+/// Serve requests from the given connection until it's closed or the client
+/// disconnects.
+async fn serve(
+    input: Input,
+    mut output: Output,
+    context: Context,
+    capture: &CaptureIo,
+    options: Options,
+    program: Option<PathBuf>,
+) -> Result<()> {
+    let mut state = State::new(&context, capture, options);
+
+    let mut launched = None;
+    let mut configured = false;
+
+    let stop_on_entry = loop {
+        if let (Some(stop_on_entry), true) = (launched, configured) {
+            break stop_on_entry;
+        }
+
+        let Some(request) = input.next()? else {
+            return Ok(());
+        };
+
+        match request.command.as_str() {
+            "initialize" => {
+                let Some(args) = arguments(&request, &mut output)? else {
+                    continue;
+                };
+
+                state.initialize(&args);
+
+                output.response(
+                    &request,
+                    protocol::Capabilities {
+                        supports_configuration_done_request: true,
+                        supports_evaluate_for_hovers: true,
+                        supports_terminate_request: true,
+                    },
+                )?;
+            }
+            "launch" => {
+                let Some(args) = arguments::<protocol::LaunchArguments>(&request, &mut output)?
+                else {
+                    continue;
+                };
+
+                let Some(path) = args.program.or_else(|| program.clone()) else {
+                    output.error(&request, "Missing `program` to launch")?;
+                    continue;
+                };
+
+                if state.launch(&path, &mut output)? {
+                    output.ack(&request)?;
+                    output.signal("initialized")?;
+                    launched = Some(args.stop_on_entry);
+                } else {
+                    output.error(&request, &format!("Failed to build `{}`", path.display()))?;
+                    output.signal("terminated")?;
+                }
+            }
+            "configurationDone" => {
+                output.ack(&request)?;
+                configured = true;
+            }
+            _ => {
+                if !handle(&mut state, &request, &mut output)? {
+                    return Ok(());
+                }
+            }
+        }
+    };
+
+    let Some(exit_code) = execute(&mut state, &input, &mut output, stop_on_entry).await? else {
+        return Ok(());
+    };
+
+    output.event("exited", protocol::ExitedEvent { exit_code })?;
+    output.signal("terminated")?;
+
+    while let Some(request) = input.next()? {
+        if !handle(&mut state, &request, &mut output)? {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute the launched program, returns the exit code of the program or
+/// `None` if the client disconnected.
+async fn execute(
+    state: &mut State<'_>,
+    input: &Input,
+    output: &mut Output,
+    stop_on_entry: bool,
+) -> Result<Option<i64>> {
+    let mut vm = state.vm()?;
+
+    let mut execution = match vm.execute(Hash::type_hash(["main"]), ()) {
+        Ok(execution) => execution,
+        Err(error) => {
+            state.report(&error, output)?;
+            return Ok(Some(1));
+        }
+    };
+
+    let mut mode = if stop_on_entry {
+        Mode::Pause("entry")
+    } else {
+        Mode::Continue
+    };
+
+    // The last line seen by each call frame.
+    let mut lines = Vec::new();
+    let mut count = 0usize;
+
+    let result = loop {
+        count = count.wrapping_add(1);
+
+        if count % POLL_INTERVAL == 0 {
+            state.flush(output)?;
+
+            while let Some(request) = input.try_next()? {
+                if request.command == "pause" {
+                    output.ack(&request)?;
+                    mode = Mode::Pause("pause");
+                    continue;
+                }
+
+                if !handle(state, &request, output)? {
+                    return Ok(None);
+                }
+            }
+        }
+
+        let vm = execution.vm();
+
+        if let Some(location) = state.location(vm.ip()) {
+            let depth = vm.call_frames().len();
+            lines.try_resize(depth + 1, None)?;
+
+            let entered = lines[depth] != Some(location);
+            lines[depth] = Some(location);
+
+            let reason = match mode {
+                Mode::Pause(reason) => Some(reason),
+                _ if entered && state.is_breakpoint(location) => Some("breakpoint"),
+                _ if entered && mode.is_step_done(depth) => Some("step"),
+                _ => None,
+            };
+
+            if let Some(reason) = reason {
+                match paused(state, vm, vm.ip(), input, output, reason, None)? {
+                    Action::Resume(resumed) => mode = resumed,
+                    Action::Disconnect => return Ok(None),
+                }
+            }
+        }
+
+        match execution.async_step().await {
+            VmResult::Ok(Some(..)) => break None,
+            VmResult::Ok(None) => {}
+            VmResult::Err(error) => break Some(error),
+        }
+    };
+
+    state.flush(output)?;
+
+    let Some(error) = result else {
+        return Ok(Some(0));
+    };
+
+    state.report(&error, output)?;
+
+    // Allow the state of the failed program to be inspected before exiting,
+    // at the instruction which caused the error.
+    let message = error.to_string();
+    let vm = execution.vm();
+
+    match paused(
+        state,
+        vm,
+        vm.last_ip(),
+        input,
+        output,
+        "exception",
+        Some(&message),
+    )? {
+        Action::Resume(..) => Ok(Some(2)),
+        Action::Disconnect => Ok(None),
+    }
+}
+
+/// Handle requests while the virtual machine is paused.
+fn paused(
+    state: &mut State<'_>,
+    vm: &Vm,
+    ip: usize,
+    input: &Input,
+    output: &mut Output,
+    reason: &'static str,
+    text: Option<&str>,
+) -> Result<Action> {
+    state.flush(output)?;
+
+    output.event(
+        "stopped",
+        protocol::StoppedEvent {
+            reason,
+            description: text,
+            text,
+            thread_id: THREAD_ID,
+            all_threads_stopped: true,
+        },
+    )?;
+
+    let frames = state.frames(vm, ip)?;
+    let depth = vm.call_frames().len();
+
+    while let Some(request) = input.next()? {
+        let mode = match request.command.as_str() {
+            "continue" => {
+                output.response(
+                    &request,
+                    protocol::ContinueResponse {
+                        all_threads_continued: true,
+                    },
+                )?;
+
+                Mode::Continue
+            }
+            "next" => {
+                output.ack(&request)?;
+                Mode::Over(depth)
+            }
+            "stepIn" => {
+                output.ack(&request)?;
+                Mode::In
+            }
+            "stepOut" => {
+                output.ack(&request)?;
+                Mode::Out(depth)
+            }
+            "pause" => {
+                output.ack(&request)?;
+                continue;
+            }
+            "stackTrace" => {
+                let Some(args) = arguments(&request, output)? else {
+                    continue;
+                };
+
+                let body = state.stack_trace(&frames, &args)?;
+                output.response(&request, body)?;
+                continue;
+            }
+            "scopes" => {
+                let Some(args) = arguments::<protocol::ScopesArguments>(&request, output)? else {
+                    continue;
+                };
+
+                output.response(
+                    &request,
+                    protocol::ScopesResponse {
+                        scopes: state.scopes(args.frame_id),
+                    },
+                )?;
+
+                continue;
+            }
+            "variables" => {
+                let Some(args) = arguments::<protocol::VariablesArguments>(&request, output)?
+                else {
+                    continue;
+                };
+
+                let variables = state.variables(vm, &frames, args.variables_reference)?;
+                output.response(&request, protocol::VariablesResponse { variables })?;
+                continue;
+            }
+            "evaluate" => {
+                let Some(args) = arguments(&request, output)? else {
+                    continue;
+                };
+
+                match state.evaluate(vm, &frames, &args)? {
+                    Ok(body) => output.response(&request, body)?,
+                    Err(message) => output.error(&request, &message)?,
+                }
+
+                continue;
+            }
+            _ => {
+                if !handle(state, &request, output)? {
+                    return Ok(Action::Disconnect);
+                }
+
+                continue;
+            }
+        };
+
+        return Ok(Action::Resume(mode));
+    }
+
+    Ok(Action::Disconnect)
+}
+
+/// Handle requests which are supported regardless of whether the program is
+/// running or not, returns `false` if the client disconnected.
+fn handle(state: &mut State<'_>, request: &Request, output: &mut Output) -> Result<bool> {
+    match request.command.as_str() {
+        "threads" => {
+            output.response(
+                request,
+                protocol::ThreadsResponse {
+                    threads: ::rust_alloc::vec![protocol::Thread {
+                        id: THREAD_ID,
+                        name: "main",
+                    }],
+                },
+            )?;
+        }
+        "setBreakpoints" => {
+            let Some(args) = arguments(request, output)? else {
+                return Ok(true);
+            };
+
+            let breakpoints = state.set_breakpoints(&args)?;
+            output.response(request, protocol::SetBreakpointsResponse { breakpoints })?;
+        }
+        "disconnect" | "terminate" => {
+            output.ack(request)?;
+            return Ok(false);
+        }
+        command => {
+            output.error(request, &format!("Unsupported request `{command}`"))?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// Deserialize the arguments of a request.
+///
+/// If the arguments are malformed the request is answered with an error and
+/// `None` is returned.
+fn arguments<T>(request: &Request, output: &mut Output) -> Result<Option<T>>
+where
+    T: DeserializeOwned,
+{
+    let arguments = match &request.arguments {
+        serde_json::Value::Null => serde_json::Value::Object(Default::default()),
+        arguments => arguments.clone(),
+    };
+
+    match serde_json::from_value(arguments) {
+        Ok(arguments) => Ok(Some(arguments)),
+        Err(error) => {
+            let message = format!("Bad arguments to `{}`: {error}", request.command);
+            output.error(request, &message)?;
+            Ok(None)
+        }
+    }
+}
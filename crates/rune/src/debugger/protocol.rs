@@ -0,0 +1,228 @@
+//! Types for the subset of the debug adapter protocol which is supported.
+//!
+//! See <https://microsoft.github.io/debug-adapter-protocol/specification>.
+
+use std::path::PathBuf;
+
+use ::rust_alloc::string::String;
+use ::rust_alloc::vec::Vec;
+
+use serde::{Deserialize, Serialize};
+
+/// An incoming request.
+#[derive(Debug, Deserialize)]
+pub(super) struct Request {
+    pub(super) seq: i64,
+    pub(super) command: String,
+    #[serde(default)]
+    pub(super) arguments: serde_json::Value,
+}
+
+/// An outgoing response.
+#[derive(Serialize)]
+pub(super) struct Response<'a, B> {
+    pub(super) seq: i64,
+    #[serde(rename = "type")]
+    pub(super) ty: &'static str,
+    pub(super) request_seq: i64,
+    pub(super) success: bool,
+    pub(super) command: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) message: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) body: Option<B>,
+}
+
+/// An outgoing event.
+#[derive(Serialize)]
+pub(super) struct Event<'a, B> {
+    pub(super) seq: i64,
+    #[serde(rename = "type")]
+    pub(super) ty: &'static str,
+    pub(super) event: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) body: Option<B>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(super) struct InitializeArguments {
+    pub(super) lines_start_at1: Option<bool>,
+    pub(super) columns_start_at1: Option<bool>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Capabilities {
+    pub(super) supports_configuration_done_request: bool,
+    pub(super) supports_evaluate_for_hovers: bool,
+    pub(super) supports_terminate_request: bool,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(super) struct LaunchArguments {
+    pub(super) program: Option<PathBuf>,
+    pub(super) stop_on_entry: bool,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(super) struct Source {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) path: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SourceBreakpoint {
+    pub(super) line: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SetBreakpointsArguments {
+    pub(super) source: Source,
+    #[serde(default)]
+    pub(super) breakpoints: Vec<SourceBreakpoint>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Breakpoint {
+    pub(super) verified: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) message: Option<String>,
+}
+
+#[derive(Serialize)]
+pub(super) struct SetBreakpointsResponse {
+    pub(super) breakpoints: Vec<Breakpoint>,
+}
+
+#[derive(Serialize)]
+pub(super) struct ThreadsResponse {
+    pub(super) threads: Vec<Thread>,
+}
+
+#[derive(Serialize)]
+pub(super) struct Thread {
+    pub(super) id: i64,
+    pub(super) name: &'static str,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(super) struct StackTraceArguments {
+    pub(super) start_frame: Option<usize>,
+    pub(super) levels: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct StackFrame {
+    pub(super) id: usize,
+    pub(super) name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) source: Option<Source>,
+    pub(super) line: usize,
+    pub(super) column: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct StackTraceResponse {
+    pub(super) stack_frames: Vec<StackFrame>,
+    pub(super) total_frames: usize,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ScopesArguments {
+    pub(super) frame_id: usize,
+}
+
+#[derive(Serialize)]
+pub(super) struct ScopesResponse {
+    pub(super) scopes: Vec<Scope>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Scope {
+    pub(super) name: &'static str,
+    pub(super) variables_reference: usize,
+    pub(super) expensive: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct VariablesArguments {
+    pub(super) variables_reference: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct Variable {
+    pub(super) name: String,
+    pub(super) value: String,
+    #[serde(rename = "type")]
+    pub(super) ty: String,
+    pub(super) variables_reference: usize,
+}
+
+#[derive(Serialize)]
+pub(super) struct VariablesResponse {
+    pub(super) variables: Vec<Variable>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct EvaluateArguments {
+    pub(super) expression: String,
+    #[serde(default)]
+    pub(super) frame_id: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct EvaluateResponse {
+    pub(super) result: String,
+    #[serde(rename = "type")]
+    pub(super) ty: String,
+    pub(super) variables_reference: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ContinueResponse {
+    pub(super) all_threads_continued: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct StoppedEvent<'a> {
+    pub(super) reason: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) description: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) text: Option<&'a str>,
+    pub(super) thread_id: i64,
+    pub(super) all_threads_stopped: bool,
+}
+
+#[derive(Serialize)]
+pub(super) struct OutputEvent<'a> {
+    pub(super) category: &'static str,
+    pub(super) output: &'a str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ExitedEvent {
+    pub(super) exit_code: i64,
+}
@@ -0,0 +1,634 @@
+use std::path::{Path, PathBuf};
+
+use ::rust_alloc::string::ToString;
+use ::rust_alloc::sync::Arc;
+
+use anyhow::{anyhow, Result};
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{BTreeSet, HashMap, HashSet, String, Vec};
+use crate::compile::FileSourceLoader;
+use crate::diagnostics::Diagnostic;
+use crate::modules::capture_io::CaptureIo;
+use crate::runtime::{budget, DebugInfo, RuntimeContext, VmError, VmErrorKind, VmHaltInfo};
+use crate::termcolor::Buffer;
+use crate::{Context, Diagnostics, Options, Source, SourceId, Sources, Unit, Value, Vm};
+
+use super::connection::Output;
+use super::protocol::{self, Breakpoint, OutputEvent};
+
+/// The number of instructions an evaluated expression may execute before it's
+/// aborted.
+const EVALUATE_BUDGET: usize = 1_000_000;
+
+/// A program which has been launched.
+struct Program {
+    unit: Arc<Unit>,
+    sources: Sources,
+    runtime: Arc<RuntimeContext>,
+    /// The source line of each instruction.
+    locations: HashMap<usize, (SourceId, usize)>,
+    /// Lines which have code associated with them, by source.
+    lines: HashMap<SourceId, BTreeSet<usize>>,
+}
+
+/// A frame in a paused call stack.
+///
+/// The first frame is the one currently executing, followed by its callers.
+pub(super) struct Frame {
+    /// The instruction being executed in the frame, which for callers is
+    /// the call that is in progress.
+    ip: usize,
+    /// The top of the stack for the frame.
+    base: usize,
+    /// The end of the stack for the frame.
+    end: usize,
+}
+
+/// The state of the debug adapter.
+pub(super) struct State<'a> {
+    context: &'a Context,
+    capture: &'a CaptureIo,
+    options: Options,
+    /// Whether lines reported by the client start at 1.
+    lines_start_at1: bool,
+    /// Whether columns reported by the client start at 1.
+    columns_start_at1: bool,
+    /// The launched program.
+    program: Option<Program>,
+    /// Breakpoints as requested by the client, by path.
+    requested: HashMap<PathBuf, Vec<usize>>,
+    /// Breakpoints resolved against the launched program.
+    breakpoints: HashSet<(SourceId, usize)>,
+}
+
+impl<'a> State<'a> {
+    pub(super) fn new(context: &'a Context, capture: &'a CaptureIo, mut options: Options) -> Self {
+        options.debug_info(true);
+
+        Self {
+            context,
+            capture,
+            options,
+            lines_start_at1: true,
+            columns_start_at1: true,
+            program: None,
+            requested: HashMap::new(),
+            breakpoints: HashSet::new(),
+        }
+    }
+
+    /// Handle client configuration.
+    pub(super) fn initialize(&mut self, args: &protocol::InitializeArguments) {
+        self.lines_start_at1 = args.lines_start_at1.unwrap_or(true);
+        self.columns_start_at1 = args.columns_start_at1.unwrap_or(true);
+    }
+
+    /// Build the program at the given path, returns `false` if it failed to
+    /// build.
+    pub(super) fn launch(&mut self, path: &Path, output: &mut Output) -> Result<bool> {
+        let source = Source::from_path(path)
+            .map_err(|error| anyhow!("cannot read file: {}: {error}", path.display()))?;
+
+        let mut sources = Sources::new();
+        sources.insert(source)?;
+
+        let mut diagnostics = Diagnostics::new();
+        let mut source_loader = FileSourceLoader::new();
+
+        let result = crate::prepare(&mut sources)
+            .with_context(self.context)
+            .with_diagnostics(&mut diagnostics)
+            .with_options(&self.options)
+            .with_source_loader(&mut source_loader)
+            .build();
+
+        if !diagnostics.is_empty() {
+            let mut buffer = Buffer::no_color();
+            diagnostics.emit(&mut buffer, &sources)?;
+            let text = ::rust_alloc::string::String::from_utf8_lossy(buffer.as_slice());
+
+            output.event(
+                "output",
+                OutputEvent {
+                    category: "stderr",
+                    output: &text,
+                },
+            )?;
+        }
+
+        let Ok(unit) = result else {
+            return Ok(false);
+        };
+
+        let mut locations = HashMap::new();
+        let mut lines = HashMap::<_, BTreeSet<_>>::new();
+
+        if let Some(debug) = unit.debug_info() {
+            for (&ip, inst) in &debug.instructions {
+                let Some(source) = sources.get(inst.source_id) else {
+                    continue;
+                };
+
+                let (line, _) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+
+                locations.try_insert(ip, (inst.source_id, line))?;
+
+                lines
+                    .entry(inst.source_id)
+                    .or_try_default()?
+                    .try_insert(line)?;
+            }
+        }
+
+        self.program = Some(Program {
+            unit: Arc::new(unit),
+            sources,
+            runtime: Arc::new(self.context.runtime()?),
+            locations,
+            lines,
+        });
+
+        self.resolve_breakpoints()?;
+        Ok(true)
+    }
+
+    /// Construct a virtual machine for the launched program.
+    pub(super) fn vm(&self) -> Result<Vm> {
+        let program = self.program()?;
+        Ok(Vm::new(program.runtime.clone(), program.unit.clone()))
+    }
+
+    /// Set breakpoints for a single source.
+    pub(super) fn set_breakpoints(
+        &mut self,
+        args: &protocol::SetBreakpointsArguments,
+    ) -> Result<::rust_alloc::vec::Vec<Breakpoint>> {
+        let Some(path) = &args.source.path else {
+            return Ok(::rust_alloc::vec::Vec::new());
+        };
+
+        let path = canonicalize(path);
+
+        let mut lines = Vec::new();
+
+        for breakpoint in &args.breakpoints {
+            lines.try_push(self.line_from_client(breakpoint.line))?;
+        }
+
+        let source_id = self.source_id(&path);
+        let mut output = ::rust_alloc::vec::Vec::new();
+
+        for &line in &lines {
+            let actual = match (&self.program, source_id) {
+                (Some(program), Some(source_id)) => code_line(program, source_id, line),
+                _ => None,
+            };
+
+            output.push(Breakpoint {
+                verified: actual.is_some(),
+                line: Some(self.line_to_client(actual.unwrap_or(line))),
+                message: match (&self.program, actual) {
+                    (None, _) => Some("Program has not been launched".into()),
+                    (Some(..), None) => Some("No code at this line".into()),
+                    _ => None,
+                },
+            });
+        }
+
+        self.requested.try_insert(path, lines)?;
+        self.resolve_breakpoints()?;
+        Ok(output)
+    }
+
+    /// Get the source location of the given instruction.
+    pub(super) fn location(&self, ip: usize) -> Option<(SourceId, usize)> {
+        self.program.as_ref()?.locations.get(&ip).copied()
+    }
+
+    /// Test if there is a breakpoint at the given location.
+    pub(super) fn is_breakpoint(&self, location: (SourceId, usize)) -> bool {
+        self.breakpoints.contains(&location)
+    }
+
+    /// Forward any captured output to the client.
+    pub(super) fn flush(&self, output: &mut Output) -> Result<()> {
+        if self.capture.is_empty() {
+            return Ok(());
+        }
+
+        let bytes = self.capture.drain();
+        let text = ::rust_alloc::string::String::from_utf8_lossy(&bytes);
+
+        output.event(
+            "output",
+            OutputEvent {
+                category: "stdout",
+                output: &text,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Report a virtual machine error to the client.
+    pub(super) fn report(&self, error: &VmError, output: &mut Output) -> Result<()> {
+        let program = self.program()?;
+
+        let mut buffer = Buffer::no_color();
+        error.emit(&mut buffer, &program.sources)?;
+        let text = ::rust_alloc::string::String::from_utf8_lossy(buffer.as_slice());
+
+        output.event(
+            "output",
+            OutputEvent {
+                category: "stderr",
+                output: &text,
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Collect the call stack of a paused virtual machine, where `ip` is the
+    /// instruction the current frame is paused at.
+    pub(super) fn frames(&self, vm: &Vm, ip: usize) -> Result<Vec<Frame>> {
+        let debug = self.program()?.unit.debug_info();
+        let stack = vm.stack();
+
+        let mut frames = Vec::new();
+
+        frames.try_push(Frame {
+            ip,
+            base: stack.top(),
+            end: stack.len(),
+        })?;
+
+        let mut end = stack.top();
+
+        for frame in vm.call_frames().iter().rev() {
+            let ip = debug
+                .and_then(|debug| call_site(debug, frame.ip))
+                .unwrap_or(frame.ip);
+
+            frames.try_push(Frame {
+                ip,
+                base: frame.top,
+                end,
+            })?;
+
+            end = frame.top;
+        }
+
+        Ok(frames)
+    }
+
+    /// Build a stack trace.
+    pub(super) fn stack_trace(
+        &self,
+        frames: &[Frame],
+        args: &protocol::StackTraceArguments,
+    ) -> Result<protocol::StackTraceResponse> {
+        let program = self.program()?;
+        let debug = program.unit.debug_info();
+
+        let start = args.start_frame.unwrap_or_default();
+        let levels = args.levels.filter(|&n| n > 0).unwrap_or(usize::MAX);
+
+        let mut stack_frames = ::rust_alloc::vec::Vec::new();
+
+        for (id, frame) in frames.iter().enumerate().skip(start).take(levels) {
+            let name = debug
+                .and_then(|debug| {
+                    let (_, signature) = debug.function_at(function_start(debug, frame.ip)?)?;
+                    Some(signature.path.to_string())
+                })
+                .unwrap_or_else(|| "<unknown>".into());
+
+            let inst = debug.and_then(|debug| debug.instruction_at(frame.ip));
+
+            let (source, line, column) = match inst.and_then(|inst| {
+                let source = program.sources.get(inst.source_id)?;
+                Some((source, inst))
+            }) {
+                Some((source, inst)) => {
+                    let (line, column) = source.pos_to_utf8_linecol(inst.span.start.into_usize());
+
+                    let source = protocol::Source {
+                        name: Some(source.name().into()),
+                        path: source.path().map(Path::to_path_buf),
+                    };
+
+                    (Some(source), line, column)
+                }
+                None => (None, 0, 0),
+            };
+
+            stack_frames.push(protocol::StackFrame {
+                id,
+                name,
+                source,
+                line: self.line_to_client(line),
+                column: column + usize::from(self.columns_start_at1),
+            });
+        }
+
+        Ok(protocol::StackTraceResponse {
+            stack_frames,
+            total_frames: frames.len(),
+        })
+    }
+
+    /// Get the scopes of the given frame.
+    ///
+    /// Each frame has two scopes, one for named variables and one for all of
+    /// the stack slots that belong to the frame.
+    pub(super) fn scopes(&self, frame_id: usize) -> ::rust_alloc::vec::Vec<protocol::Scope> {
+        ::rust_alloc::vec![
+            protocol::Scope {
+                name: "Locals",
+                variables_reference: frame_id * 2 + 1,
+                expensive: false,
+            },
+            protocol::Scope {
+                name: "Stack",
+                variables_reference: frame_id * 2 + 2,
+                expensive: false,
+            },
+        ]
+    }
+
+    /// Get the variables for the given reference.
+    pub(super) fn variables(
+        &self,
+        vm: &Vm,
+        frames: &[Frame],
+        reference: usize,
+    ) -> Result<::rust_alloc::vec::Vec<protocol::Variable>> {
+        let mut output = ::rust_alloc::vec::Vec::new();
+
+        let Some(index) = reference.checked_sub(1) else {
+            return Ok(output);
+        };
+
+        let Some(frame) = frames.get(index / 2) else {
+            return Ok(output);
+        };
+
+        if index % 2 == 0 {
+            for (name, value) in self.locals(vm, frame)? {
+                output.push(variable(vm, name.into(), value));
+            }
+        } else {
+            let values = vm.stack().get(frame.base..frame.end).unwrap_or_default();
+
+            for (n, value) in values.iter().enumerate() {
+                output.push(variable(vm, format!("+{n}"), value));
+            }
+        }
+
+        Ok(output)
+    }
+
+    /// Evaluate an expression in the given frame.
+    ///
+    /// Expressions which are not simply the name of a variable are compiled
+    /// into a function which receives the variables of the frame as
+    /// arguments.
+    pub(super) fn evaluate(
+        &self,
+        vm: &Vm,
+        frames: &[Frame],
+        args: &protocol::EvaluateArguments,
+    ) -> Result<Result<protocol::EvaluateResponse, ::rust_alloc::string::String>> {
+        let Some(frame) = frames.get(args.frame_id.unwrap_or_default()) else {
+            return Ok(Err("No such frame".into()));
+        };
+
+        let expression = args.expression.trim();
+        let locals = self.locals(vm, frame)?;
+
+        let value = match locals.iter().find(|(name, _)| *name == expression) {
+            Some((_, value)) => (*value).clone(),
+            None => {
+                let mut names = Vec::new();
+                let mut arguments = Vec::new();
+
+                for (name, value) in &locals {
+                    if *name != "self" {
+                        names.try_push(*name)?;
+                        arguments.try_push((*value).clone())?;
+                    }
+                }
+
+                let names: String = names.iter().try_join(", ")?;
+
+                let mut source = String::new();
+                write!(source, "pub fn main({names}) {{\n{expression}\n}}\n")?;
+
+                let mut sources = Sources::new();
+                sources.insert(Source::new("<evaluate>", source)?)?;
+
+                let mut diagnostics = Diagnostics::without_warnings();
+
+                let result = crate::prepare(&mut sources)
+                    .with_context(self.context)
+                    .with_diagnostics(&mut diagnostics)
+                    .build();
+
+                let unit = match result {
+                    Ok(unit) => unit,
+                    Err(..) => {
+                        let message = diagnostics
+                            .diagnostics()
+                            .iter()
+                            .find_map(|d| match d {
+                                Diagnostic::Fatal(fatal) => Some(fatal.to_string()),
+                                _ => None,
+                            })
+                            .unwrap_or_else(|| "Failed to compile expression".into());
+
+                        return Ok(Err(message));
+                    }
+                };
+
+                let mut eval = Vm::new(self.program()?.runtime.clone(), Arc::new(unit));
+
+                match budget::with(EVALUATE_BUDGET, || eval.call(["main"], arguments)).call() {
+                    Ok(value) => value,
+                    Err(error) if is_limited(&error) => {
+                        return Ok(Err(format!(
+                            "Evaluation exceeded the budget of {EVALUATE_BUDGET} instructions"
+                        )));
+                    }
+                    Err(error) => return Ok(Err(error.to_string())),
+                }
+            }
+        };
+
+        let variable = variable(vm, ::rust_alloc::string::String::new(), &value);
+
+        Ok(Ok(protocol::EvaluateResponse {
+            result: variable.value,
+            ty: variable.ty,
+            variables_reference: 0,
+        }))
+    }
+
+    /// Get the named variables which are visible in the given frame.
+    fn locals<'vm>(&self, vm: &'vm Vm, frame: &Frame) -> Result<Vec<(&str, &'vm Value)>> {
+        let Some(debug) = self.program()?.unit.debug_info() else {
+            return Ok(Vec::new());
+        };
+
+        let start = function_start(debug, frame.ip).unwrap_or_default();
+
+        let mut definitions = Vec::new();
+
+        for (&ip, variables) in &debug.variables {
+            if (start..=frame.ip).contains(&ip) {
+                definitions.try_push((ip, variables))?;
+            }
+        }
+
+        definitions.sort_by_key(|&(ip, _)| ip);
+
+        // Later definitions shadow earlier ones, both by name and by the slot
+        // they occupy.
+        let mut names = HashMap::<&str, usize>::new();
+        let mut slots = HashMap::<usize, &str>::new();
+
+        for (_, variables) in definitions {
+            for variable in variables {
+                names.try_insert(&variable.name, variable.slot)?;
+                slots.try_insert(variable.slot, &variable.name)?;
+            }
+        }
+
+        let mut output = Vec::new();
+
+        for (name, slot) in names {
+            if slots.get(&slot) != Some(&name) {
+                continue;
+            }
+
+            let Some(value) = frame
+                .base
+                .checked_add(slot)
+                .filter(|&n| n < frame.end)
+                .and_then(|n| vm.stack().get(n))
+            else {
+                continue;
+            };
+
+            output.try_push((slot, name, value))?;
+        }
+
+        output.sort_by_key(|&(slot, ..)| slot);
+
+        let mut locals = Vec::try_with_capacity(output.len())?;
+
+        for (_, name, value) in output {
+            locals.try_push((name, value))?;
+        }
+
+        Ok(locals)
+    }
+
+    /// Resolve requested breakpoints against the launched program.
+    fn resolve_breakpoints(&mut self) -> Result<()> {
+        self.breakpoints.clear();
+
+        let Some(program) = &self.program else {
+            return Ok(());
+        };
+
+        for (path, lines) in &self.requested {
+            let Some(source_id) = self.source_id(path) else {
+                continue;
+            };
+
+            for &line in lines {
+                if let Some(line) = code_line(program, source_id, line) {
+                    self.breakpoints.try_insert((source_id, line))?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the source corresponding to the given path.
+    fn source_id(&self, path: &Path) -> Option<SourceId> {
+        let program = self.program.as_ref()?;
+
+        program
+            .sources
+            .source_ids()
+            .find(|&id| program.sources.path(id).map(canonicalize).as_deref() == Some(path))
+    }
+
+    fn program(&self) -> Result<&Program> {
+        self.program
+            .as_ref()
+            .ok_or_else(|| anyhow!("Program has not been launched"))
+    }
+
+    /// Convert a line from the client into a zero-based line.
+    fn line_from_client(&self, line: usize) -> usize {
+        line.saturating_sub(usize::from(self.lines_start_at1))
+    }
+
+    /// Convert a zero-based line into a line for the client.
+    fn line_to_client(&self, line: usize) -> usize {
+        line + usize::from(self.lines_start_at1)
+    }
+}
+
+/// Describe a value as a variable.
+fn variable(vm: &Vm, name: ::rust_alloc::string::String, value: &Value) -> protocol::Variable {
+    let (value, ty) = vm.with(|| (format!("{value:?}"), value.type_info().to_string()));
+
+    protocol::Variable {
+        name,
+        value,
+        ty,
+        variables_reference: 0,
+    }
+}
+
+/// Test if the given error is caused by running out of budget.
+fn is_limited(error: &VmError) -> bool {
+    matches!(
+        error.at().kind(),
+        VmErrorKind::Halted {
+            halt: VmHaltInfo::Limited
+        }
+    )
+}
+
+/// Find the first line at or after the given line which has code.
+fn code_line(program: &Program, source_id: SourceId, line: usize) -> Option<usize> {
+    program.lines.get(&source_id)?.range(line..).next().copied()
+}
+
+/// Find the instruction pointer of the function which contains the given
+/// instruction.
+fn function_start(debug: &DebugInfo, ip: usize) -> Option<usize> {
+    debug
+        .functions_rev
+        .keys()
+        .copied()
+        .filter(|&start| start <= ip)
+        .max()
+}
+
+/// Find the call instruction preceding the given return address.
+fn call_site(debug: &DebugInfo, ip: usize) -> Option<usize> {
+    debug.instructions.keys().copied().filter(|&n| n < ip).max()
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,375 @@
+use std::collections::VecDeque;
+use std::io::{self, BufReader, Read, Write};
+use std::path::Path;
+use std::sync::mpsc;
+use std::{env, fs, process, thread};
+
+use ::rust_alloc::string::String;
+use ::rust_alloc::vec::Vec;
+
+use anyhow::{bail, Result};
+use serde_json::{json, Value};
+
+use crate::modules::capture_io::CaptureIo;
+use crate::{Context, Options};
+
+use super::connection;
+
+const SOURCE: &str = r#"fn add(a, b) {
+    let sum = a + b;
+    sum * 2
+}
+
+pub fn main() {
+    let x = 1;
+    let y = add(x, 2);
+    y
+}
+"#;
+
+#[test]
+fn round_trip() -> Result<()> {
+    with_script("round-trip", |path| {
+        let (mut client, server) = client();
+        client.launch(path)?;
+
+        let trace = client.request("stackTrace", json!({ "threadId": 1 }))?;
+
+        let frames = trace["stackFrames"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|frame| (frame["name"].as_str(), frame["line"].as_u64()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(frames, [(Some("add"), Some(3)), (Some("main"), Some(8))]);
+
+        assert_eq!(
+            client.locals(0)?,
+            [("a", "1"), ("b", "2"), ("sum", "3")].map(|(name, value)| (name.into(), value.into()))
+        );
+
+        assert_eq!(client.locals(1)?, [(String::from("x"), String::from("1"))]);
+
+        client.finish(server)
+    })
+}
+
+#[test]
+fn bad_arguments() -> Result<()> {
+    with_script("bad-arguments", |path| {
+        let (mut client, server) = client();
+
+        let error = client.request_error("setBreakpoints", json!({ "source": 1 }))?;
+        assert!(
+            error.starts_with("Bad arguments to `setBreakpoints`"),
+            "{error}"
+        );
+
+        client.launch(path)?;
+
+        let error = client.request_error("stackTrace", json!({ "startFrame": "first" }))?;
+        assert!(
+            error.starts_with("Bad arguments to `stackTrace`"),
+            "{error}"
+        );
+
+        let error = client.request_error("evaluate", json!({}))?;
+        assert!(error.starts_with("Bad arguments to `evaluate`"), "{error}");
+
+        let trace = client.request("stackTrace", json!({ "threadId": 1 }))?;
+        assert_eq!(trace["totalFrames"], 2);
+
+        let sum = client.request("evaluate", json!({ "expression": "sum" }))?;
+        assert_eq!(sum["result"], "3");
+
+        client.finish(server)
+    })
+}
+
+#[test]
+fn evaluate_budget() -> Result<()> {
+    with_script("evaluate-budget", |path| {
+        let (mut client, server) = client();
+        client.launch(path)?;
+
+        let error =
+            client.request_error("evaluate", json!({ "expression": "loop { sum += 1; }" }))?;
+        assert!(
+            error.starts_with("Evaluation exceeded the budget"),
+            "{error}"
+        );
+
+        let doubled = client.request("evaluate", json!({ "expression": "sum * 2" }))?;
+        assert_eq!(doubled["result"], "6");
+
+        client.finish(server)
+    })
+}
+
+/// Write [`SOURCE`] to a temporary file with the given name and call `f` with
+/// its path.
+fn with_script(name: &str, f: impl FnOnce(&Path) -> Result<()>) -> Result<()> {
+    let path = env::temp_dir().join(format!("rune-debugger-{name}-{}.rn", process::id()));
+    fs::write(&path, SOURCE)?;
+    let result = f(&path);
+    fs::remove_file(&path)?;
+    result
+}
+
+/// Set up a client connected to a debug adapter running on a background
+/// thread.
+fn client() -> (Client, thread::JoinHandle<Result<()>>) {
+    let (requests, reader) = pipe();
+    let (writer, responses) = pipe();
+
+    let server = thread::spawn(move || {
+        let (input, output) = connection::stream(reader, writer)?;
+        let context = Context::with_default_modules()?;
+        let capture = CaptureIo::new();
+
+        futures_executor::block_on(super::serve(
+            input,
+            output,
+            context,
+            &capture,
+            Options::default(),
+            None,
+        ))
+    });
+
+    let client = Client {
+        seq: 0,
+        writer: requests,
+        reader: BufReader::new(responses),
+        events: VecDeque::new(),
+    };
+
+    (client, server)
+}
+
+/// Construct an in-memory pipe.
+fn pipe() -> (PipeWriter, PipeReader) {
+    let (tx, rx) = mpsc::channel();
+
+    let reader = PipeReader {
+        rx,
+        buf: Vec::new(),
+        pos: 0,
+    };
+
+    (PipeWriter { tx }, reader)
+}
+
+struct PipeWriter {
+    tx: mpsc::Sender<Vec<u8>>,
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if !buf.is_empty() {
+            self.tx
+                .send(buf.to_vec())
+                .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        }
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+struct PipeReader {
+    rx: mpsc::Receiver<Vec<u8>>,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        if self.pos == self.buf.len() {
+            // A closed pipe reads as the end of input.
+            let Ok(buf) = self.rx.recv() else {
+                return Ok(0);
+            };
+
+            self.buf = buf;
+            self.pos = 0;
+        }
+
+        let n = out.len().min(self.buf.len() - self.pos);
+        out[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+struct Client {
+    seq: i64,
+    writer: PipeWriter,
+    reader: BufReader<PipeReader>,
+    /// Events received while waiting for something else.
+    events: VecDeque<Value>,
+}
+
+impl Client {
+    /// Launch the script at the given path with a breakpoint at line 3, and
+    /// wait for it to stop there.
+    fn launch(&mut self, path: &Path) -> Result<()> {
+        let capabilities = self.request("initialize", json!({ "adapterID": "rune" }))?;
+        assert_eq!(capabilities["supportsConfigurationDoneRequest"], true);
+
+        self.request("launch", json!({ "program": path }))?;
+        self.event("initialized")?;
+
+        let breakpoints = self.request(
+            "setBreakpoints",
+            json!({ "source": { "path": path }, "breakpoints": [{ "line": 3 }] }),
+        )?;
+
+        assert_eq!(
+            breakpoints,
+            json!({ "breakpoints": [{ "verified": true, "line": 3 }] })
+        );
+
+        self.request("configurationDone", json!({}))?;
+
+        let stopped = self.event("stopped")?;
+        assert_eq!(stopped["reason"], "breakpoint");
+        assert_eq!(stopped["threadId"], 1);
+        Ok(())
+    }
+
+    /// Run the paused script to completion and disconnect.
+    fn finish(mut self, server: thread::JoinHandle<Result<()>>) -> Result<()> {
+        self.request("continue", json!({ "threadId": 1 }))?;
+
+        let exited = self.event("exited")?;
+        assert_eq!(exited["exitCode"], 0);
+        self.event("terminated")?;
+
+        self.request("disconnect", json!({}))?;
+
+        match server.join() {
+            Ok(result) => result,
+            Err(..) => bail!("debug adapter panicked"),
+        }
+    }
+
+    /// Send a request and wait for its response, returns the body of the
+    /// response.
+    fn request(&mut self, command: &str, arguments: Value) -> Result<Value> {
+        let mut response = self.send(command, arguments)?;
+
+        if response["success"] != true {
+            bail!("`{command}` failed: {}", response["message"]);
+        }
+
+        Ok(response["body"].take())
+    }
+
+    /// Send a request which is expected to fail, returns the error message.
+    fn request_error(&mut self, command: &str, arguments: Value) -> Result<String> {
+        let response = self.send(command, arguments)?;
+
+        if response["success"] != false {
+            bail!("`{command}` unexpectedly succeeded: {response}");
+        }
+
+        match response["message"].as_str() {
+            Some(message) => Ok(message.into()),
+            None => bail!("missing error message: {response}"),
+        }
+    }
+
+    /// Send a request and wait for its response.
+    fn send(&mut self, command: &str, arguments: Value) -> Result<Value> {
+        self.seq += 1;
+
+        let request = json!({
+            "seq": self.seq,
+            "type": "request",
+            "command": command,
+            "arguments": arguments,
+        });
+
+        let bytes = serde_json::to_vec(&request)?;
+        write!(self.writer, "Content-Length: {}\r\n\r\n", bytes.len())?;
+        self.writer.write_all(&bytes)?;
+
+        loop {
+            let message = self.receive()?;
+
+            if message["type"] != "response" {
+                self.events.push_back(message);
+                continue;
+            }
+
+            if message["request_seq"] != self.seq {
+                bail!("unexpected response to `{command}`: {message}");
+            }
+
+            return Ok(message);
+        }
+    }
+
+    /// Wait for the given event, returns its body.
+    fn event(&mut self, event: &str) -> Result<Value> {
+        if let Some(index) = self.events.iter().position(|e| e["event"] == event) {
+            if let Some(mut message) = self.events.remove(index) {
+                return Ok(message["body"].take());
+            }
+        }
+
+        loop {
+            let mut message = self.receive()?;
+
+            if message["type"] == "event" && message["event"] == event {
+                return Ok(message["body"].take());
+            }
+
+            self.events.push_back(message);
+        }
+    }
+
+    /// Get the names and values of the local variables in the given frame.
+    fn locals(&mut self, frame_id: usize) -> Result<Vec<(String, String)>> {
+        let scopes = self.request("scopes", json!({ "frameId": frame_id }))?;
+
+        let Some(locals) = scopes["scopes"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .find(|scope| scope["name"] == "Locals")
+        else {
+            bail!("missing locals scope in {scopes}");
+        };
+
+        let reference = locals["variablesReference"].clone();
+        let variables = self.request("variables", json!({ "variablesReference": reference }))?;
+
+        let mut output = Vec::new();
+
+        for variable in variables["variables"].as_array().into_iter().flatten() {
+            let (Some(name), Some(value)) = (variable["name"].as_str(), variable["value"].as_str())
+            else {
+                bail!("bad variable: {variable}");
+            };
+
+            output.push((name.into(), value.into()));
+        }
+
+        Ok(output)
+    }
+
+    /// Receive the next message from the debug adapter.
+    fn receive(&mut self) -> Result<Value> {
+        let Some(frame) = connection::read_frame(&mut self.reader)? else {
+            bail!("debug adapter closed the connection");
+        };
+
+        Ok(serde_json::from_slice(&frame)?)
+    }
+}
@@ -16,7 +16,7 @@ use crate::ast::{Span, Spanned};
 use crate::compile::{ErrorKind, LinkerError, Location};
 use crate::diagnostics::{
     Diagnostic, FatalDiagnostic, FatalDiagnosticKind, RuntimeWarningDiagnostic,
-    RuntimeWarningDiagnosticKind, WarningDiagnostic, WarningDiagnosticKind,
+    RuntimeWarningDiagnosticKind, Suggestion, WarningDiagnostic, WarningDiagnosticKind,
 };
 use crate::hash::Hash;
 use crate::runtime::DebugInfo;
@@ -101,7 +101,7 @@ impl Diagnostics {
                     fatal_diagnostics_emit(e, out, sources, &config)?;
                 }
                 Diagnostic::Warning(w) => {
-                    warning_diagnostics_emit(w, out, sources, &config)?;
+                    warning_diagnostics_emit(w, out, sources, &config, d::Severity::Warning)?;
                 }
                 Diagnostic::RuntimeWarning(w) => {
                     runtime_warning_diagnostics_emit(w, out, sources, &config, None, None)?;
@@ -140,7 +140,7 @@ impl Diagnostics {
                     fatal_diagnostics_emit(e, out, sources, &config)?;
                 }
                 Diagnostic::Warning(w) => {
-                    warning_diagnostics_emit(w, out, sources, &config)?;
+                    warning_diagnostics_emit(w, out, sources, &config, d::Severity::Warning)?;
                 }
                 Diagnostic::RuntimeWarning(w) => {
                     runtime_warning_diagnostics_emit(
@@ -342,7 +342,7 @@ impl WarningDiagnostic {
         O: WriteColor,
     {
         let config = term::Config::default();
-        warning_diagnostics_emit(self, out, sources, &config)
+        warning_diagnostics_emit(self, out, sources, &config, d::Severity::Warning)
     }
 }
 
@@ -522,11 +522,14 @@ pub fn line_for(source: &Source, span: Span) -> Option<(usize, usize, &str, Span
 }
 
 /// Helper to emit diagnostics for a warning.
+///
+/// Warnings whose lint has been denied are emitted with an error severity.
 fn warning_diagnostics_emit<O>(
     this: &WarningDiagnostic,
     out: &mut O,
     sources: &Sources,
     config: &term::Config,
+    severity: d::Severity,
 ) -> Result<(), EmitError>
 where
     O: WriteColor,
@@ -566,14 +569,28 @@ where
         _ => {}
     };
 
+    // Kinds with hints of their own already describe how to fix them.
+    if notes.is_empty() {
+        push_suggestions(&mut notes, this.suggestions())?;
+    }
+
     if let Some(context) = this.context() {
         labels.push(
             d::Label::secondary(this.source_id(), context.range()).with_message("In this context"),
         );
     }
 
-    let diagnostic = d::Diagnostic::warning()
-        .with_message("Warning")
+    let lint = this.lint().name();
+    let mut note = String::new();
+    write!(
+        note,
+        "Reported by the `{lint}` lint, which can be silenced with `#[allow({lint})]`"
+    )?;
+    notes.push(note.into_std());
+
+    let diagnostic = d::Diagnostic::new(severity)
+        .with_message(this.try_to_string()?)
+        .with_code(lint)
         .with_labels(labels)
         .with_notes(notes);
 
@@ -581,6 +598,20 @@ where
     Ok(())
 }
 
+/// Add a hint for every suggestion.
+fn push_suggestions(
+    notes: &mut ::rust_alloc::vec::Vec<rust_alloc::string::String>,
+    suggestions: &[Suggestion],
+) -> Result<(), EmitError> {
+    for suggestion in suggestions {
+        let mut note = String::new();
+        writeln!(note, "Hint: {}", suggestion.message())?;
+        notes.push(note.into_std());
+    }
+
+    Ok(())
+}
+
 /// Helper to emit diagnostics for a runtime warning.
 fn runtime_warning_diagnostics_emit<O>(
     this: &RuntimeWarningDiagnostic,
@@ -659,6 +690,9 @@ where
     }
 
     match this.kind() {
+        FatalDiagnosticKind::Lint(warning) => {
+            return warning_diagnostics_emit(warning, out, sources, config, d::Severity::Error);
+        }
         FatalDiagnosticKind::Internal(message) => {
             writeln!(out, "internal error: {}", message)?;
             return Ok(());
@@ -700,6 +734,8 @@ where
         }
     };
 
+    push_suggestions(&mut notes, this.suggestions())?;
+
     let diagnostic = d::Diagnostic::error()
         .with_message(this.kind().try_to_string()?)
         .with_labels(labels)
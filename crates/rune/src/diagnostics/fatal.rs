@@ -7,6 +7,8 @@ use crate::ast::{Span, Spanned};
 use crate::compile::{self, LinkerError};
 use crate::SourceId;
 
+use super::{Suggestion, WarningDiagnostic};
+
 /// Fatal diagnostic emitted during compilation. Fatal diagnostics indicates an
 /// unrecoverable issue.
 #[derive(Debug)]
@@ -28,6 +30,15 @@ impl FatalDiagnostic {
         &self.kind
     }
 
+    /// Suggestions on how to fix the error.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        match &*self.kind {
+            FatalDiagnosticKind::CompileError(error) => error.suggestions(),
+            FatalDiagnosticKind::Lint(warning) => warning.suggestions(),
+            _ => &[],
+        }
+    }
+
     /// The kind of the load error.
     #[cfg(test)]
     pub(crate) fn into_kind(self) -> FatalDiagnosticKind {
@@ -40,6 +51,7 @@ impl FatalDiagnostic {
             FatalDiagnosticKind::CompileError(error) => Some(error.span()),
             FatalDiagnosticKind::LinkError(..) => None,
             FatalDiagnosticKind::Internal(..) => None,
+            FatalDiagnosticKind::Lint(warning) => Some(warning.span()),
         }
     }
 }
@@ -56,6 +68,7 @@ impl core::error::Error for FatalDiagnostic {
         match &*self.kind {
             FatalDiagnosticKind::CompileError(error) => Some(error),
             FatalDiagnosticKind::LinkError(error) => Some(error),
+            FatalDiagnosticKind::Lint(warning) => Some(warning),
             _ => None,
         }
     }
@@ -70,6 +83,8 @@ pub enum FatalDiagnosticKind {
     LinkError(LinkerError),
     /// An internal error.
     Internal(&'static str),
+    /// A warning whose lint has been denied.
+    Lint(WarningDiagnostic),
 }
 
 impl fmt::Display for FatalDiagnosticKind {
@@ -79,6 +94,7 @@ impl fmt::Display for FatalDiagnosticKind {
             FatalDiagnosticKind::CompileError(error) => error.fmt(f),
             FatalDiagnosticKind::LinkError(error) => error.fmt(f),
             FatalDiagnosticKind::Internal(message) => message.fmt(f),
+            FatalDiagnosticKind::Lint(warning) => warning.fmt(f),
         }
     }
 }
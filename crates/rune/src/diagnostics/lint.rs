@@ -0,0 +1,237 @@
+use core::fmt;
+
+use crate::alloc::{self, Vec};
+use crate::ast::{Span, Spanned};
+use crate::SourceId;
+
+use super::WarningDiagnostic;
+
+/// The level at which a [Lint] is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LintLevel {
+    /// The lint is not reported.
+    Allow,
+    /// The lint is reported as a warning.
+    Warn,
+    /// The lint is reported as an error, causing compilation to fail.
+    Deny,
+}
+
+impl LintLevel {
+    /// Look up a lint level by its name, like `deny`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rune::diagnostics::LintLevel;
+    ///
+    /// assert_eq!(LintLevel::from_name("deny"), Some(LintLevel::Deny));
+    /// assert_eq!(LintLevel::from_name("forbid"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "allow" => Some(Self::Allow),
+            "warn" => Some(Self::Warn),
+            "deny" => Some(Self::Deny),
+            _ => None,
+        }
+    }
+
+    /// The name of the lint level.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+macro_rules! lints {
+    ($($(#[doc = $doc:literal])* $variant:ident = $name:literal, $level:ident;)*) => {
+        /// A lint which can be configured through `#[allow(..)]`,
+        /// `#[warn(..)]` and `#[deny(..)]` attributes, compile options or
+        /// the `[lints]` table in `Rune.toml`.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        pub enum Lint {
+            $($(#[doc = $doc])* $variant,)*
+        }
+
+        impl Lint {
+            /// All available lints.
+            pub const ALL: &'static [Lint] = &[$(Lint::$variant,)*];
+
+            /// Look up a lint by its name, like `unused_imports`.
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use rune::diagnostics::Lint;
+            ///
+            /// assert_eq!(Lint::from_name("unused_imports"), Some(Lint::UnusedImports));
+            /// assert_eq!(Lint::from_name("not_a_lint"), None);
+            /// ```
+            pub fn from_name(name: &str) -> Option<Self> {
+                match name {
+                    $($name => Some(Lint::$variant),)*
+                    _ => None,
+                }
+            }
+
+            /// The name of the lint, as used in attributes.
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(Lint::$variant => $name,)*
+                }
+            }
+
+            /// The level the lint is reported at unless configured otherwise.
+            pub fn default_level(self) -> LintLevel {
+                match self {
+                    $(Lint::$variant => LintLevel::$level,)*
+                }
+            }
+
+            /// A short description of the lint.
+            pub fn doc(self) -> &'static str {
+                match self {
+                    $(Lint::$variant => concat!($($doc),*).trim_ascii(),)*
+                }
+            }
+        }
+    };
+}
+
+lints! {
+    /// A value or item which is never used.
+    Unused = "unused", Warn;
+    /// Code which can never be reached.
+    UnreachableCode = "unreachable_code", Warn;
+    /// A `let` pattern which panics if it doesn't match.
+    LetPatternMightPanic = "let_pattern_might_panic", Warn;
+    /// A template string without any expansions.
+    TemplateWithoutExpansions = "template_without_expansions", Warn;
+    /// Parentheses when constructing a variant without fields.
+    UnnecessaryCallParens = "unnecessary_call_parens", Warn;
+    /// A semicolon which is not needed.
+    UnnecessarySemicolon = "unnecessary_semicolon", Warn;
+    /// Use of a deprecated function.
+    Deprecated = "deprecated", Warn;
+    /// A `use` import which is never used.
+    UnusedImports = "unused_imports", Warn;
+    /// A named struct field which is never read. This is not reported if
+    /// fields might be read through protocols or by native functions.
+    UnusedFields = "unused_fields", Warn;
+    /// A variable which is never used. Prefix the name with an underscore to
+    /// silence it.
+    UnusedVariables = "unused_variables", Warn;
+    /// A binding which shadows a variable in the same function.
+    ShadowedBindings = "shadowed_bindings", Allow;
+    /// A call to `clone` on a value which was just created.
+    NeedlessClone = "needless_clone", Warn;
+    /// A comparison with `true` or `false`.
+    BoolComparison = "bool_comparison", Warn;
+}
+
+impl fmt::Display for Lint {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(f)
+    }
+}
+
+/// Lint levels which override the default level of each lint.
+#[derive(Debug, Clone)]
+pub(crate) struct LintLevels {
+    levels: [Option<LintLevel>; Lint::ALL.len()],
+}
+
+impl LintLevels {
+    /// Lint levels where every lint uses its default level.
+    pub(crate) const DEFAULT: Self = Self {
+        levels: [None; Lint::ALL.len()],
+    };
+
+    /// Set the level of the given lint.
+    pub(crate) fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels[lint as usize] = Some(level);
+    }
+
+    /// Get the configured level of the given lint, if any.
+    pub(crate) fn get(&self, lint: Lint) -> Option<LintLevel> {
+        self.levels[lint as usize]
+    }
+}
+
+/// Lint levels set by attributes, each of which apply to a span of source.
+#[derive(Default)]
+pub(crate) struct LintScopes {
+    scopes: Vec<LintScope>,
+}
+
+struct LintScope {
+    source_id: SourceId,
+    span: Span,
+    lint: Lint,
+    level: LintLevel,
+}
+
+impl LintScopes {
+    /// Set the level of a lint inside of the given span.
+    pub(crate) fn insert(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        lint: Lint,
+        level: LintLevel,
+    ) -> alloc::Result<()> {
+        self.scopes.try_push(LintScope {
+            source_id,
+            span,
+            lint,
+            level,
+        })
+    }
+
+    /// Resolve the level of the given warning.
+    ///
+    /// The innermost attribute wins, followed by the configured lint levels
+    /// and finally the default level of the lint.
+    pub(crate) fn level(&self, levels: &LintLevels, warning: &WarningDiagnostic) -> LintLevel {
+        let lint = warning.lint();
+        let span = warning.span();
+
+        let mut found = None::<&LintScope>;
+
+        for scope in &self.scopes {
+            if scope.source_id != warning.source_id()
+                || scope.lint != lint
+                || !contains(scope.span, span)
+            {
+                continue;
+            }
+
+            if found.map_or(true, |found| contains(found.span, scope.span)) {
+                found = Some(scope);
+            }
+        }
+
+        found
+            .map(|scope| scope.level)
+            .or_else(|| levels.get(lint))
+            .unwrap_or_else(|| lint.default_level())
+    }
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
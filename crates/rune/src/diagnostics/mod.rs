@@ -33,6 +33,10 @@
 pub use self::fatal::{FatalDiagnostic, FatalDiagnosticKind};
 mod fatal;
 
+pub use self::lint::{Lint, LintLevel};
+pub(crate) use self::lint::{LintLevels, LintScopes};
+mod lint;
+
 pub use self::warning::WarningDiagnostic;
 pub(crate) use self::warning::WarningDiagnosticKind;
 mod warning;
@@ -41,9 +45,13 @@ pub use self::runtime_warning::RuntimeWarningDiagnostic;
 pub(crate) use self::runtime_warning::RuntimeWarningDiagnosticKind;
 mod runtime_warning;
 
+pub use self::suggestion::Suggestion;
+mod suggestion;
+
 use ::rust_alloc::boxed::Box;
 use rune_alloc::String;
 
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::{self, Vec};
 use crate::ast::Spanned;
 use crate::{Hash, SourceId};
@@ -115,6 +123,9 @@ pub struct Diagnostics {
     has_error: bool,
     /// Indicates if diagnostics contains warnings.
     has_warning: bool,
+    /// Warnings are collected regardless of mode, since their lint levels
+    /// have yet to be resolved.
+    linting: bool,
 }
 
 impl Diagnostics {
@@ -124,6 +135,7 @@ impl Diagnostics {
             mode,
             has_error: false,
             has_warning: false,
+            linting: false,
         }
     }
 
@@ -282,13 +294,17 @@ impl Diagnostics {
         variant: &dyn Spanned,
         context: Option<&dyn Spanned>,
     ) -> alloc::Result<()> {
-        self.warning(
+        let mut suggestions = Vec::new();
+        suggestions.try_push(Suggestion::new("Remove the parentheses", span.span(), "")?)?;
+
+        self.warning_with_suggestions(
             source_id,
             WarningDiagnosticKind::RemoveTupleCallParams {
                 span: span.span(),
                 variant: variant.span(),
                 context: context.map(Spanned::span),
             },
+            suggestions,
         )
     }
 
@@ -298,9 +314,13 @@ impl Diagnostics {
         source_id: SourceId,
         span: &dyn Spanned,
     ) -> alloc::Result<()> {
-        self.warning(
+        let mut suggestions = Vec::new();
+        suggestions.try_push(Suggestion::new("Remove the semicolon", span.span(), "")?)?;
+
+        self.warning_with_suggestions(
             source_id,
             WarningDiagnosticKind::UnnecessarySemiColon { span: span.span() },
+            suggestions,
         )
     }
 
@@ -311,14 +331,129 @@ impl Diagnostics {
         span: &dyn Spanned,
         context: Option<&dyn Spanned>,
         message: String,
+        replacement: Option<&str>,
     ) -> alloc::Result<()> {
-        self.warning(
+        let mut suggestions = Vec::new();
+
+        if let Some(path) = replacement {
+            let mut title = String::new();
+            write!(title, "Replace with `{path}`")?;
+            suggestions.try_push(Suggestion::new(&title, span.span(), path)?)?;
+        }
+
+        self.warning_with_suggestions(
             source_id,
             WarningDiagnosticKind::UsedDeprecated {
                 span: span.span(),
                 context: context.map(Spanned::span),
                 message,
             },
+            suggestions,
+        )
+    }
+
+    /// Indicate that a `use` import is never used.
+    pub(crate) fn unused_import(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedImport { span: span.span() },
+        )
+    }
+
+    /// Indicate that a named struct field is never read.
+    pub(crate) fn unused_field(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::UnusedField { span: span.span() },
+        )
+    }
+
+    /// Indicate that a variable is never used.
+    pub(crate) fn unused_variable(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        let span = span.span();
+        let mut suggestions = Vec::new();
+
+        suggestions.try_push(Suggestion::new(
+            "Prefix with an underscore",
+            span.head(),
+            "_",
+        )?)?;
+
+        self.warning_with_suggestions(
+            source_id,
+            WarningDiagnosticKind::UnusedVariable { span },
+            suggestions,
+        )
+    }
+
+    /// Indicate that a binding shadows an existing variable.
+    pub(crate) fn shadowed_binding(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        self.warning(
+            source_id,
+            WarningDiagnosticKind::ShadowedBinding { span: span.span() },
+        )
+    }
+
+    /// Indicate that a value which was just created is cloned.
+    ///
+    /// Like `[1, 2].clone()`.
+    pub(crate) fn needless_clone(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        clone: &dyn Spanned,
+    ) -> alloc::Result<()> {
+        let mut suggestions = Vec::new();
+        suggestions.try_push(Suggestion::new(
+            "Remove the call to `clone`",
+            clone.span(),
+            "",
+        )?)?;
+
+        self.warning_with_suggestions(
+            source_id,
+            WarningDiagnosticKind::NeedlessClone { span: span.span() },
+            suggestions,
+        )
+    }
+
+    /// Indicate that a value is compared with a boolean literal.
+    ///
+    /// Like `value == true`.
+    pub(crate) fn bool_comparison(
+        &mut self,
+        source_id: SourceId,
+        span: &dyn Spanned,
+        replacement: Option<&str>,
+    ) -> alloc::Result<()> {
+        let mut suggestions = Vec::new();
+
+        if let Some(replacement) = replacement {
+            let mut title = String::new();
+            write!(title, "Replace with `{replacement}`")?;
+            suggestions.try_push(Suggestion::new(&title, span.span(), replacement)?)?;
+        }
+
+        self.warning_with_suggestions(
+            source_id,
+            WarningDiagnosticKind::BoolComparison { span: span.span() },
+            suggestions,
         )
     }
 
@@ -332,7 +467,21 @@ impl Diagnostics {
     where
         WarningDiagnosticKind: From<T>,
     {
-        if !self.mode.warnings() {
+        self.warning_with_suggestions(source_id, kind, Vec::new())
+    }
+
+    /// Push a warning with suggestions on how to fix it to the collection of
+    /// diagnostics.
+    pub(crate) fn warning_with_suggestions<T>(
+        &mut self,
+        source_id: SourceId,
+        kind: T,
+        suggestions: Vec<Suggestion>,
+    ) -> alloc::Result<()>
+    where
+        WarningDiagnosticKind: From<T>,
+    {
+        if !self.mode.warnings() && !self.linting {
             return Ok(());
         }
 
@@ -340,6 +489,7 @@ impl Diagnostics {
             .try_push(Diagnostic::Warning(WarningDiagnostic {
                 source_id,
                 kind: kind.into(),
+                suggestions,
             }))?;
 
         self.has_warning = true;
@@ -381,6 +531,59 @@ impl Diagnostics {
     }
 }
 
+impl Diagnostics {
+    /// Start collecting warnings whose lint levels are resolved by
+    /// [Diagnostics::resolve_lints], returning the position to resolve from.
+    pub(crate) fn begin_lints(&mut self) -> usize {
+        self.linting = true;
+        self.diagnostics.len()
+    }
+
+    /// Resolve the lint level of every warning collected since `start`.
+    ///
+    /// Allowed warnings are dropped and denied warnings are turned into
+    /// errors.
+    pub(crate) fn resolve_lints(
+        &mut self,
+        start: usize,
+        mut level: impl FnMut(&WarningDiagnostic) -> LintLevel,
+    ) -> alloc::Result<()> {
+        self.linting = false;
+
+        let mut diagnostics = Vec::new();
+
+        for diagnostic in self.diagnostics.drain(start..) {
+            diagnostics.try_push(diagnostic)?;
+        }
+
+        for diagnostic in diagnostics {
+            let Diagnostic::Warning(warning) = diagnostic else {
+                self.diagnostics.try_push(diagnostic)?;
+                continue;
+            };
+
+            match level(&warning) {
+                LintLevel::Allow => {}
+                LintLevel::Warn => {
+                    if self.mode.warnings() {
+                        self.diagnostics.try_push(Diagnostic::Warning(warning))?;
+                    }
+                }
+                LintLevel::Deny => {
+                    self.error(warning.source_id, FatalDiagnosticKind::Lint(warning))?;
+                }
+            }
+        }
+
+        self.has_warning = self
+            .diagnostics
+            .iter()
+            .any(|d| matches!(d, Diagnostic::Warning(..) | Diagnostic::RuntimeWarning(..)));
+
+        Ok(())
+    }
+}
+
 impl Default for Diagnostics {
     fn default() -> Self {
         Self::with_mode(Mode::All)
@@ -0,0 +1,48 @@
+use crate::alloc::prelude::*;
+use crate::alloc::{self, String};
+use crate::ast::Span;
+
+/// A machine-applicable suggestion attached to a diagnostic.
+///
+/// Applying the suggestion means replacing the text covered by [span] with
+/// [replacement] in the source that the diagnostic belongs to. An empty span
+/// inserts text and an empty replacement removes it.
+///
+/// [span]: Suggestion::span
+/// [replacement]: Suggestion::replacement
+#[derive(Debug)]
+pub struct Suggestion {
+    /// Human-readable description of the suggestion.
+    message: String,
+    /// The span to replace.
+    span: Span,
+    /// The text to replace the span with.
+    replacement: String,
+}
+
+impl Suggestion {
+    /// Construct a new suggestion.
+    pub(crate) fn new(message: &str, span: Span, replacement: &str) -> alloc::Result<Self> {
+        Ok(Self {
+            message: message.try_to_owned()?,
+            span,
+            replacement: replacement.try_to_owned()?,
+        })
+    }
+
+    /// Human-readable description of the suggestion, like ``Import
+    /// `std::collections::HashMap` ``.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The span in the source which is replaced.
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    /// The text to replace the span with.
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+}
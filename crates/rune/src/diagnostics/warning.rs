@@ -1,10 +1,12 @@
 use core::fmt;
 
-use crate::alloc::String;
+use crate::alloc::{String, Vec};
 use crate::ast::Span;
 use crate::ast::Spanned;
 use crate::SourceId;
 
+use super::{Lint, Suggestion};
+
 /// Warning diagnostic emitted during compilation. Warning diagnostics indicates
 /// an recoverable issues.
 #[derive(Debug)]
@@ -13,6 +15,8 @@ pub struct WarningDiagnostic {
     pub(crate) source_id: SourceId,
     /// The kind of the warning.
     pub(crate) kind: WarningDiagnosticKind,
+    /// Suggestions on how to fix the warning.
+    pub(crate) suggestions: Vec<Suggestion>,
 }
 
 impl WarningDiagnostic {
@@ -21,6 +25,16 @@ impl WarningDiagnostic {
         self.source_id
     }
 
+    /// Suggestions on how to fix the warning.
+    pub fn suggestions(&self) -> &[Suggestion] {
+        &self.suggestions
+    }
+
+    /// The lint which this warning is reported for.
+    pub fn lint(&self) -> Lint {
+        self.kind.lint()
+    }
+
     /// The kind of the warning.
     #[cfg(feature = "emit")]
     pub(crate) fn kind(&self) -> &WarningDiagnosticKind {
@@ -57,6 +71,12 @@ impl Spanned for WarningDiagnostic {
             WarningDiagnosticKind::RemoveTupleCallParams { span, .. } => *span,
             WarningDiagnosticKind::UnnecessarySemiColon { span, .. } => *span,
             WarningDiagnosticKind::UsedDeprecated { span, .. } => *span,
+            WarningDiagnosticKind::UnusedImport { span, .. } => *span,
+            WarningDiagnosticKind::UnusedField { span, .. } => *span,
+            WarningDiagnosticKind::UnusedVariable { span, .. } => *span,
+            WarningDiagnosticKind::ShadowedBinding { span, .. } => *span,
+            WarningDiagnosticKind::NeedlessClone { span, .. } => *span,
+            WarningDiagnosticKind::BoolComparison { span, .. } => *span,
         }
     }
 }
@@ -138,6 +158,59 @@ pub(crate) enum WarningDiagnosticKind {
         /// Deprecated message.
         message: String,
     },
+    /// A `use` import which is never used.
+    UnusedImport {
+        /// The span of the import.
+        span: Span,
+    },
+    /// A named struct field which is never read.
+    UnusedField {
+        /// The span of the field.
+        span: Span,
+    },
+    /// A variable which is never used.
+    UnusedVariable {
+        /// The span of the binding.
+        span: Span,
+    },
+    /// A binding which shadows a variable in the same function.
+    ShadowedBinding {
+        /// The span of the binding.
+        span: Span,
+    },
+    /// A call to `clone` on a value which was just created.
+    NeedlessClone {
+        /// The span of the call.
+        span: Span,
+    },
+    /// A comparison with a boolean literal.
+    BoolComparison {
+        /// The span of the comparison.
+        span: Span,
+    },
+}
+
+impl WarningDiagnosticKind {
+    /// The lint which this kind of warning is reported for.
+    pub(crate) fn lint(&self) -> Lint {
+        match self {
+            WarningDiagnosticKind::NotUsed { .. } => Lint::Unused,
+            WarningDiagnosticKind::Unreachable { .. } => Lint::UnreachableCode,
+            WarningDiagnosticKind::LetPatternMightPanic { .. } => Lint::LetPatternMightPanic,
+            WarningDiagnosticKind::TemplateWithoutExpansions { .. } => {
+                Lint::TemplateWithoutExpansions
+            }
+            WarningDiagnosticKind::RemoveTupleCallParams { .. } => Lint::UnnecessaryCallParens,
+            WarningDiagnosticKind::UnnecessarySemiColon { .. } => Lint::UnnecessarySemicolon,
+            WarningDiagnosticKind::UsedDeprecated { .. } => Lint::Deprecated,
+            WarningDiagnosticKind::UnusedImport { .. } => Lint::UnusedImports,
+            WarningDiagnosticKind::UnusedField { .. } => Lint::UnusedFields,
+            WarningDiagnosticKind::UnusedVariable { .. } => Lint::UnusedVariables,
+            WarningDiagnosticKind::ShadowedBinding { .. } => Lint::ShadowedBindings,
+            WarningDiagnosticKind::NeedlessClone { .. } => Lint::NeedlessClone,
+            WarningDiagnosticKind::BoolComparison { .. } => Lint::BoolComparison,
+        }
+    }
 }
 
 impl fmt::Display for WarningDiagnosticKind {
@@ -161,6 +234,18 @@ impl fmt::Display for WarningDiagnosticKind {
             WarningDiagnosticKind::UsedDeprecated { message, .. } => {
                 write!(f, "Used deprecated function: {message}")
             }
+            WarningDiagnosticKind::UnusedImport { .. } => write!(f, "Unused import"),
+            WarningDiagnosticKind::UnusedField { .. } => write!(f, "Field is never read"),
+            WarningDiagnosticKind::UnusedVariable { .. } => write!(f, "Unused variable"),
+            WarningDiagnosticKind::ShadowedBinding { .. } => {
+                write!(f, "Binding shadows an existing variable")
+            }
+            WarningDiagnosticKind::NeedlessClone { .. } => {
+                write!(f, "Needless clone of a newly created value")
+            }
+            WarningDiagnosticKind::BoolComparison { .. } => {
+                write!(f, "Comparison with a boolean literal")
+            }
         }
     }
 }
@@ -515,6 +515,64 @@ where
     Ok(())
 }
 
+/// Process markdown for display in an editor.
+///
+/// This leaves everything except code blocks untouched. Code blocks are
+/// treated the same way as when building html documentation, so they default
+/// to being rune code, have their test parameters stripped, and don't include
+/// any hidden lines.
+#[cfg(feature = "languageserver")]
+pub(crate) fn push_markdown(string: &mut String, input: &str) -> Result<()> {
+    let mut iter = pulldown_cmark::Parser::new(input).into_offset_iter();
+    let mut last = 0;
+
+    while let Some((event, range)) = iter.next() {
+        let Start(Tag::CodeBlock(kind)) = event else {
+            continue;
+        };
+
+        string.try_push_str(&input[last..range.start])?;
+        last = range.end;
+
+        let mut lang = RUNE_TOKEN;
+
+        if let CodeBlockKind::Fenced(fences) = &kind {
+            for token in fences.split(',') {
+                match token.trim() {
+                    "no_run" | "should_panic" | "ignore" | "" => {}
+                    token => {
+                        lang = token;
+                        break;
+                    }
+                }
+            }
+        }
+
+        let mut lines = String::new();
+
+        for (event, _) in iter.by_ref() {
+            match event {
+                Text(text) => {
+                    lines.try_push_str(&text)?;
+                }
+                End(TagEnd::CodeBlock) => break,
+                _ => {}
+            }
+        }
+
+        let code = render_code_without_syntax(lines.lines(), None)?;
+
+        string.try_push_str("```")?;
+        string.try_push_str(lang)?;
+        string.try_push('\n')?;
+        string.try_push_str(&code)?;
+        string.try_push_str("```\n")?;
+    }
+
+    string.try_push_str(&input[last..])?;
+    Ok(())
+}
+
 /// Render documentation.
 pub(super) fn render_code_by_syntax<I>(
     syntax_set: &SyntaxSet,
@@ -5,10 +5,13 @@ mod context;
 #[cfg(feature = "cli")]
 pub(crate) use self::context::{Context, Function, Kind, Meta, Signature};
 
-#[cfg(feature = "cli")]
+#[cfg(any(feature = "cli", feature = "languageserver"))]
+#[cfg_attr(not(feature = "cli"), allow(dead_code))]
 mod artifacts;
+#[cfg(any(feature = "cli", feature = "languageserver"))]
+pub(crate) use self::artifacts::TestParams;
 #[cfg(feature = "cli")]
-pub(crate) use self::artifacts::{Artifacts, TestKind, TestParams};
+pub(crate) use self::artifacts::{Artifacts, TestKind};
 
 #[cfg(feature = "cli")]
 mod templating;
@@ -23,5 +26,6 @@ mod visitor;
 #[cfg(feature = "languageserver")]
 pub(crate) use self::visitor::{Visitor, VisitorData};
 
-#[cfg(feature = "cli")]
+#[cfg(any(feature = "cli", feature = "languageserver"))]
+#[cfg_attr(not(feature = "cli"), allow(dead_code))]
 pub(crate) mod markdown;
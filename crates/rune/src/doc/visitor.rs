@@ -8,7 +8,6 @@ use crate::item::IntoComponent;
 use crate::{Hash, Item, ItemBuf};
 
 pub(crate) struct VisitorData {
-    #[cfg_attr(not(feature = "cli"), allow(dead_code))]
     pub(crate) item: ItemBuf,
     #[cfg_attr(not(feature = "cli"), allow(dead_code))]
     pub(crate) hash: Hash,
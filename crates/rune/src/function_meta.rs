@@ -52,13 +52,10 @@ pub type MacroMeta = fn() -> alloc::Result<MacroMetaData>;
 pub struct FunctionData {
     pub(crate) item: ItemBuf,
     pub(crate) handler: Arc<FunctionHandler>,
-    #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
-    #[cfg(feature = "doc")]
     pub(crate) argument_types: Box<[meta::DocType]>,
-    #[cfg(feature = "doc")]
     pub(crate) return_type: meta::DocType,
 }
 
@@ -67,13 +64,10 @@ impl FunctionData {
         Self {
             item,
             handler,
-            #[cfg(feature = "doc")]
             is_async: false,
             #[cfg(feature = "doc")]
             args: None,
-            #[cfg(feature = "doc")]
             argument_types: Box::default(),
-            #[cfg(feature = "doc")]
             return_type: meta::DocType::empty(),
         }
     }
@@ -92,13 +86,10 @@ impl FunctionData {
             handler: Arc::new(move |stack, addr, args, output| {
                 f.fn_call(stack, addr, args, output)
             }),
-            #[cfg(feature = "doc")]
             is_async: K::IS_ASYNC,
             #[cfg(feature = "doc")]
             args: Some(F::ARGS),
-            #[cfg(feature = "doc")]
             argument_types: A::into_box()?,
-            #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of()?,
         })
     }
@@ -263,13 +254,10 @@ impl Associated {
 pub struct AssociatedFunctionData {
     pub(crate) associated: Associated,
     pub(crate) handler: Arc<FunctionHandler>,
-    #[cfg(feature = "doc")]
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     pub(crate) args: Option<usize>,
-    #[cfg(feature = "doc")]
     pub(crate) argument_types: Box<[meta::DocType]>,
-    #[cfg(feature = "doc")]
     pub(crate) return_type: meta::DocType,
 }
 
@@ -278,13 +266,10 @@ impl AssociatedFunctionData {
         Self {
             associated,
             handler,
-            #[cfg(feature = "doc")]
             is_async: false,
             #[cfg(feature = "doc")]
             args: None,
-            #[cfg(feature = "doc")]
             argument_types: Box::default(),
-            #[cfg(feature = "doc")]
             return_type: meta::DocType::empty(),
         }
     }
@@ -302,13 +287,10 @@ impl AssociatedFunctionData {
             handler: Arc::new(move |stack, addr, args, output| {
                 f.fn_call(stack, addr, args, output)
             }),
-            #[cfg(feature = "doc")]
             is_async: K::IS_ASYNC,
             #[cfg(feature = "doc")]
             args: Some(F::ARGS),
-            #[cfg(feature = "doc")]
             argument_types: A::into_box()?,
-            #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of()?,
         })
     }
@@ -326,13 +308,10 @@ impl AssociatedFunctionData {
             handler: Arc::new(move |stack, addr, args, output| {
                 f.fn_call(stack, addr, args, output)
             }),
-            #[cfg(feature = "doc")]
             is_async: K::IS_ASYNC,
             #[cfg(feature = "doc")]
             args: Some(F::ARGS),
-            #[cfg(feature = "doc")]
             argument_types: A::into_box()?,
-            #[cfg(feature = "doc")]
             return_type: F::Return::maybe_type_of()?,
         })
     }
@@ -30,7 +30,10 @@ pub(crate) struct Ctxt<'hir, 'a, 'arena> {
     pub(super) statement_buffer: Vec<hir::Stmt<'hir>>,
     pub(super) statements: Vec<hir::Stmt<'hir>>,
     pub(super) pattern_bindings: Vec<hir::Variable>,
+    /// Named bindings which should be reported if they are never used.
+    pub(super) bindings: Vec<(hir::Variable, ast::Span)>,
     pub(super) label: Option<ast::Label>,
+    pub(super) types: hir::types::Types,
 }
 
 impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
@@ -77,7 +80,9 @@ impl<'hir, 'a, 'arena> Ctxt<'hir, 'a, 'arena> {
             statement_buffer: Vec::new(),
             statements: Vec::new(),
             pattern_bindings: Vec::new(),
+            bindings: Vec::new(),
             label: None,
+            types: hir::types::Types::default(),
         })
     }
 
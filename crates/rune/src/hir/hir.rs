@@ -609,6 +609,19 @@ pub(crate) enum ExprObjectKind {
     Anonymous,
 }
 
+impl ExprObjectKind {
+    /// The hash of the type being created, if it's not anonymous.
+    pub(crate) fn hash(&self) -> Option<Hash> {
+        match *self {
+            Self::EmptyStruct { hash }
+            | Self::Struct { hash }
+            | Self::StructVariant { hash }
+            | Self::ExternalType { hash, .. } => Some(hash),
+            Self::Anonymous => None,
+        }
+    }
+}
+
 /// An object expression.
 #[derive(Debug, TryClone, Clone, Copy)]
 #[try_clone(copy)]
@@ -4,12 +4,13 @@ use core::ops::Neg;
 use num::ToPrimitive;
 use tracing::instrument_ast;
 
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
 use crate::alloc::try_format;
-use crate::alloc::{self, Box, HashMap, HashSet};
-use crate::ast::{self, NumberSize, Spanned};
+use crate::alloc::{self, Box, HashMap, HashSet, String};
+use crate::ast::{self, NumberSize, Span, Spanned};
 use crate::compile::meta;
-use crate::compile::{self, ErrorKind, WithSpan};
+use crate::compile::{self, DynLocation, ErrorKind, WithSpan};
 use crate::hash::ParametersBuilder;
 use crate::hir;
 use crate::parse::Resolve;
@@ -17,10 +18,10 @@ use crate::query::AsyncBlock;
 use crate::query::Closure;
 use crate::query::SecondaryBuildEntry;
 use crate::query::{self, GenericsParameters, Named, SecondaryBuild};
-use crate::runtime::{ConstValue, ConstValueKind, Inline, Type, TypeCheck};
+use crate::runtime::{format, ConstValue, ConstValueKind, Inline, Type, TypeCheck};
 use crate::{Hash, Item};
 
-use super::{Ctxt, Needs};
+use super::{types, Ctxt, Needs};
 
 /// Lower an empty function.
 #[instrument_ast(span = span)]
@@ -29,10 +30,13 @@ pub(crate) fn empty_fn<'hir>(
     ast: &ast::EmptyBlock,
     span: &dyn Spanned,
 ) -> compile::Result<hir::ItemFn<'hir>> {
+    let body = statements(cx, None, &ast.statements, span)?;
+    unused_variables(cx)?;
+
     Ok(hir::ItemFn {
         span: span.span(),
         args: &[],
-        body: statements(cx, None, &ast.statements, span)?,
+        body,
     })
 }
 
@@ -44,10 +48,23 @@ pub(crate) fn item_fn<'hir>(
 ) -> compile::Result<hir::ItemFn<'hir>> {
     alloc_with!(cx, ast);
 
+    if let Some(ty) = &ast.fn_type {
+        cx.types.return_type = Some(cx.q.resolve_type(cx.source_id, ty)?);
+    }
+
+    let args = iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?);
+    let body = block(cx, None, &ast.body)?;
+
+    if let (Some(expected), Some(value)) = (cx.types.return_type, body.value) {
+        types::check(cx, expected, value)?;
+    }
+
+    unused_variables(cx)?;
+
     Ok(hir::ItemFn {
         span: ast.span(),
-        args: iter!(&ast.args, |(ast, _)| fn_arg(cx, ast)?),
-        body: block(cx, None, &ast.body)?,
+        args,
+        body,
     })
 }
 
@@ -84,15 +101,34 @@ fn expr_call_closure<'hir>(
 
     cx.scopes.push_captures()?;
 
+    // NB: Returns inside of the closure return from the closure.
+    let return_type = match &ast.output {
+        Some((_, ty)) => Some(cx.q.resolve_type(cx.source_id, ty)?),
+        None => None,
+    };
+
+    let return_type = replace(&mut cx.types.return_type, return_type);
     let args = iter!(ast.args.as_slice(), |(arg, _)| fn_arg(cx, arg)?);
     let body = alloc!(expr(cx, &ast.body)?);
 
+    if let Some(expected) = cx.types.return_type {
+        types::check(cx, expected, body)?;
+    }
+
+    cx.types.return_type = return_type;
+
     let layer = cx.scopes.pop().with_span(&ast.body)?;
 
     cx.q.set_used(&meta.item_meta)?;
 
     let captures = &*iter!(layer.captures().map(|(_, id)| id));
 
+    for (name, _) in layer.captures() {
+        cx.q.visitor
+            .visit_closure_capture(&DynLocation::new(cx.source_id, ast), &name.try_to_string()?)
+            .with_span(ast)?;
+    }
+
     let Some(queue) = cx.secondary_builds.as_mut() else {
         return Err(compile::Error::new(ast, ErrorKind::ClosureInConst));
     };
@@ -152,11 +188,12 @@ fn statements<'hir>(
     for ast in statements {
         let last = match ast {
             ast::Stmt::Local(ast) => {
-                let depacked = if ast.attributes.is_empty() && cx.q.options.lowering > 0 {
-                    unpack_locals(cx, &ast.pat, &ast.expr)?
-                } else {
-                    false
-                };
+                let depacked =
+                    if ast.attributes.is_empty() && ast.ty.is_none() && cx.q.options.lowering > 0 {
+                        unpack_locals(cx, &ast.pat, &ast.expr)?
+                    } else {
+                        false
+                    };
 
                 if !depacked {
                     let stmt = hir::Stmt::Local(alloc!(local(cx, ast)?));
@@ -377,6 +414,21 @@ fn expr_object<'hir>(
         ast::ObjectIdent::Anonymous(..) => hir::ExprObjectKind::Anonymous,
     };
 
+    if let Some(hash) = kind.hash() {
+        for ((ast, _), assign) in ast.assignments.iter().zip(assignments.iter()) {
+            let (span, key) = assign.key;
+
+            cx.q.visitor
+                .visit_field_use(
+                    &DynLocation::new(cx.source_id, &span),
+                    Some(hash),
+                    key,
+                    ast.assign.is_none(),
+                )
+                .with_span(span)?;
+        }
+    }
+
     Ok(hir::ExprKind::Object(alloc!(hir::ExprObject {
         kind,
         assignments,
@@ -395,10 +447,15 @@ pub(crate) fn expr<'hir>(
 
     let kind = match ast {
         ast::Expr::Path(ast) => expr_path(cx, ast, in_path)?,
-        ast::Expr::Assign(ast) => hir::ExprKind::Assign(alloc!(hir::ExprAssign {
-            lhs: expr(cx, &ast.lhs)?,
-            rhs: expr(cx, &ast.rhs)?,
-        })),
+        ast::Expr::Assign(ast) => {
+            let assign = hir::ExprAssign {
+                lhs: expr(cx, &ast.lhs)?,
+                rhs: expr(cx, &ast.rhs)?,
+            };
+
+            types::assign(cx, &assign)?;
+            hir::ExprKind::Assign(alloc!(assign))
+        }
         // TODO: lower all of these loop constructs to the same loop-like
         // representation. We only do different ones here right now since it's
         // easier when refactoring.
@@ -507,6 +564,21 @@ pub(crate) fn expr<'hir>(
             let rhs = expr(cx, &ast.rhs)?;
             cx.needs = needs;
 
+            bool_comparison(cx, ast)?;
+
+            // NB: Comparisons read every field of a struct through protocols.
+            if matches!(
+                ast.op,
+                ast::BinOp::Eq(..)
+                    | ast::BinOp::Neq(..)
+                    | ast::BinOp::Gt(..)
+                    | ast::BinOp::Lt(..)
+                    | ast::BinOp::Gte(..)
+                    | ast::BinOp::Lte(..)
+            ) {
+                cx.q.fields_read_dynamically();
+            }
+
             hir::ExprKind::Binary(alloc!(hir::ExprBinary {
                 lhs,
                 op: ast.op,
@@ -522,7 +594,15 @@ pub(crate) fn expr<'hir>(
         ast::Expr::Break(ast) => hir::ExprKind::Break(alloc!(expr_break(cx, ast)?)),
         ast::Expr::Continue(ast) => hir::ExprKind::Continue(alloc!(expr_continue(cx, ast)?)),
         ast::Expr::Yield(ast) => hir::ExprKind::Yield(option!(&ast.expr, |ast| expr(cx, ast)?)),
-        ast::Expr::Return(ast) => hir::ExprKind::Return(option!(&ast.expr, |ast| expr(cx, ast)?)),
+        ast::Expr::Return(ast) => {
+            let value = option!(&ast.expr, |ast| expr(cx, ast)?);
+
+            if let (Some(expected), Some(value)) = (cx.types.return_type, value) {
+                types::check(cx, expected, value)?;
+            }
+
+            hir::ExprKind::Return(value)
+        }
         ast::Expr::Await(ast) => hir::ExprKind::Await(alloc!(expr(cx, &ast.expr)?)),
         ast::Expr::Try(ast) => hir::ExprKind::Try(alloc!(expr(cx, &ast.expr)?)),
         ast::Expr::Select(ast) => {
@@ -597,6 +677,10 @@ pub(crate) fn expr<'hir>(
                     result
                 }
                 query::BuiltInMacro::Format(ast) => {
+                    if matches!(ast.format_type, Some(format::Type::Debug)) {
+                        cx.q.fields_read_dynamically();
+                    }
+
                     let spec = hir::BuiltInFormatSpec {
                         fill: ast.fill,
                         align: ast.align,
@@ -1001,7 +1085,9 @@ fn expr_block<'hir>(
             };
 
             cx.scopes.push_captures()?;
+            let return_type = cx.types.return_type.take();
             let block = alloc!(block(cx, None, &ast.block)?);
+            cx.types.return_type = return_type;
             let layer = cx.scopes.pop().with_span(&ast.block)?;
 
             cx.q.set_used(&meta.item_meta)?;
@@ -1116,12 +1202,22 @@ fn fn_arg<'hir>(
 ) -> compile::Result<hir::FnArg<'hir>> {
     alloc_with!(cx, ast);
 
+    cx.q.visitor
+        .visit_fn_arg(&DynLocation::new(cx.source_id, ast))
+        .with_span(ast)?;
+
     Ok(match ast {
         ast::FnArg::SelfValue(ast) => {
             let id = cx.scopes.define(hir::Name::SelfValue, ast)?;
             hir::FnArg::SelfValue(ast.span(), id)
         }
         ast::FnArg::Pat(ast) => hir::FnArg::Pat(alloc!(pat_binding(cx, ast)?)),
+        ast::FnArg::Typed(ast) => {
+            let ty = cx.q.resolve_type(cx.source_id, &ast.ty)?;
+            let pat = pat_binding(cx, &ast.pat)?;
+            types::declare(cx, &pat, ty)?;
+            hir::FnArg::Pat(alloc!(pat))
+        }
     })
 }
 
@@ -1132,6 +1228,12 @@ fn local<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Local) -> compile::Result
     let expr = expr(cx, &ast.expr)?;
     let pat = pat_binding(cx, &ast.pat)?;
 
+    if let Some((_, ty)) = &ast.ty {
+        let ty = cx.q.resolve_type(cx.source_id, ty)?;
+        types::check(cx, ty, &expr)?;
+        types::declare(cx, &pat, ty)?;
+    }
+
     Ok(hir::Local {
         span: ast.span(),
         pat,
@@ -1139,6 +1241,84 @@ fn local<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Local) -> compile::Result
     })
 }
 
+/// Define a named binding introduced by a pattern.
+fn binding<'hir>(
+    cx: &mut Ctxt<'hir, '_, '_>,
+    name: &'hir str,
+    span: &dyn Spanned,
+) -> compile::Result<hir::Variable> {
+    if !name.starts_with('_') && cx.scopes.is_defined(hir::Name::Str(name)) {
+        cx.q.diagnostics.shadowed_binding(cx.source_id, span)?;
+    }
+
+    let id = cx.scopes.define(hir::Name::Str(name), span)?;
+    cx.pattern_bindings.try_push(id)?;
+
+    if !name.starts_with('_') {
+        cx.bindings.try_push((id, span.span()))?;
+    }
+
+    Ok(id)
+}
+
+/// Warn about named bindings which are never used.
+fn unused_variables(cx: &mut Ctxt<'_, '_, '_>) -> compile::Result<()> {
+    for (id, span) in cx.bindings.drain(..) {
+        if !cx.scopes.is_used(id) {
+            cx.q.diagnostics.unused_variable(cx.source_id, &span)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Warn about comparing a value with `true` or `false`, like `value == true`.
+fn bool_comparison(cx: &mut Ctxt<'_, '_, '_>, ast: &ast::ExprBinary) -> compile::Result<()> {
+    let negate = match &ast.op {
+        ast::BinOp::Eq(..) => false,
+        ast::BinOp::Neq(..) => true,
+        _ => return Ok(()),
+    };
+
+    let (value, other) = match (&*ast.lhs, &*ast.rhs) {
+        (
+            ast::Expr::Lit(ast::ExprLit {
+                lit: ast::Lit::Bool(lit),
+                ..
+            }),
+            other,
+        )
+        | (
+            other,
+            ast::Expr::Lit(ast::ExprLit {
+                lit: ast::Lit::Bool(lit),
+                ..
+            }),
+        ) => (lit.value, other),
+        _ => return Ok(()),
+    };
+
+    let mut replacement = String::new();
+
+    if let Some(source) = cx.q.sources.source(cx.source_id, other.span()) {
+        if value == negate {
+            if matches!(other, ast::Expr::Binary(..)) {
+                write!(replacement, "!({source})")?;
+            } else {
+                write!(replacement, "!{source}")?;
+            }
+        } else {
+            write!(replacement, "{source}")?;
+        }
+    }
+
+    let replacement = (!replacement.is_empty()).then_some(replacement.as_str());
+
+    cx.q.diagnostics
+        .bool_comparison(cx.source_id, ast, replacement)?;
+    Ok(())
+}
+
 /// The is a simple locals optimization which unpacks locals from a tuple and
 /// assigns them directly to local.
 fn unpack_locals(cx: &mut Ctxt<'_, '_, '_>, p: &ast::Pat, e: &ast::Expr) -> compile::Result<bool> {
@@ -1252,8 +1432,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
 
                     if let Some(ident) = ast.path.try_as_ident() {
                         let name = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                        let name = cx.scopes.define(hir::Name::Str(name), ast)?;
-                        cx.pattern_bindings.try_push(name)?;
+                        let name = binding(cx, name, ast)?;
                         break 'path hir::PatPathKind::Ident(name);
                     }
 
@@ -1351,8 +1530,7 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                             };
 
                             let key = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
-                            let id = cx.scopes.define(hir::Name::Str(key), ident)?;
-                            cx.pattern_bindings.try_push(id)?;
+                            let id = binding(cx, key, ident)?;
                             (key, hir::Binding::Ident(path.span(), key, id))
                         }
                         _ => {
@@ -1363,6 +1541,8 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                         }
                     };
 
+                    cx.q.field_read(key)?;
+
                     if let Some(_existing) = keys_dup.try_insert(key, pat)? {
                         return Err(compile::Error::new(
                             pat,
@@ -1395,6 +1575,15 @@ fn pat<'hir>(cx: &mut Ctxt<'hir, '_, '_>, ast: &ast::Pat) -> compile::Result<hir
                         };
 
                         for binding in bindings.iter() {
+                            cx.q.visitor
+                                .visit_field_use(
+                                    &DynLocation::new(cx.source_id, binding),
+                                    Some(meta.hash),
+                                    binding.key(),
+                                    matches!(binding, hir::Binding::Ident(..)),
+                                )
+                                .with_span(binding)?;
+
                             if !fields.remove(binding.key()) {
                                 return Err(compile::Error::new(
                                     ast,
@@ -1532,7 +1721,9 @@ fn expr_path<'hir>(
         }
     };
 
-    Err(compile::Error::new(ast, kind))
+    let error = compile::Error::new(ast, kind);
+    let location = DynLocation::new(cx.source_id, ast);
+    Err(cx.q.with_import_suggestions(&location, error)?)
 }
 
 /// Compile an item.
@@ -1788,6 +1979,9 @@ fn expr_call<'hir>(
     let expr = expr(cx, &ast.expr)?;
     cx.in_path = in_path;
 
+    let mut callee = None;
+    let mut receiver = false;
+
     let call = 'ok: {
         match expr.kind {
             hir::ExprKind::Variable(name) => {
@@ -1850,6 +2044,31 @@ fn expr_call<'hir>(
                             )?;
                         }
                     }
+                    meta::Kind::Function {
+                        associated: Some(meta::AssociatedKind::Instance(name)),
+                        ..
+                    } if cx.q.is_trait_function(meta.item_meta.item)? => {
+                        // Calling a trait function through its path, as in
+                        // `Trait::function(value)`, dispatches on the type of
+                        // the first argument.
+                        let Some((first, _)) = ast.args.first() else {
+                            return Err(compile::Error::msg(
+                                &ast.args,
+                                try_format!(
+                                    "Trait function `{}` must be called with a receiver",
+                                    cx.q.pool.item(meta.item_meta.item)
+                                ),
+                            ));
+                        };
+
+                        let target = self::expr(cx, first)?;
+                        receiver = true;
+
+                        break 'ok hir::Call::Associated {
+                            target: alloc!(target),
+                            hash: Hash::ident(name),
+                        };
+                    }
                     meta::Kind::Function { .. } => {
                         if let Some(message) = cx.q.lookup_deprecation(meta.hash) {
                             cx.q.diagnostics.used_deprecated(
@@ -1857,6 +2076,7 @@ fn expr_call<'hir>(
                                 &expr.span,
                                 None,
                                 message.try_into()?,
+                                cx.q.context.lookup_replacement(meta.hash),
                             )?;
                         };
                     }
@@ -1880,7 +2100,9 @@ fn expr_call<'hir>(
                     }
                 };
 
-                break 'ok hir::Call::Meta { hash: meta.hash };
+                let hash = meta.hash;
+                callee = Some(meta);
+                break 'ok hir::Call::Meta { hash };
             }
             hir::ExprKind::FieldAccess(&hir::ExprFieldAccess {
                 expr_field,
@@ -1890,6 +2112,11 @@ fn expr_call<'hir>(
                     hir::ExprField::Index(index) => Hash::index(index),
                     hir::ExprField::Ident(ident) => {
                         cx.q.unit.insert_debug_ident(ident)?;
+
+                        if ident == "clone" && ast.args.is_empty() {
+                            needless_clone(cx, ast, &target)?;
+                        }
+
                         Hash::ident(ident)
                     }
                     hir::ExprField::IdentGenerics(ident, hash) => {
@@ -1909,10 +2136,58 @@ fn expr_call<'hir>(
         break 'ok hir::Call::Expr { expr: alloc!(expr) };
     };
 
-    Ok(hir::ExprCall {
-        call,
-        args: iter!(&ast.args, |(ast, _)| self::expr(cx, ast)?),
-    })
+    // NB: Native functions, and functions which are only known at runtime,
+    // might read the fields of a struct they are passed.
+    let reads_fields = match &callee {
+        Some(meta) => meta.context && matches!(meta.kind, meta::Kind::Function { .. }),
+        None => true,
+    };
+
+    if reads_fields && !ast.args.is_empty() {
+        cx.q.fields_read_dynamically();
+    }
+
+    let args = iter!(&ast.args.as_slice()[usize::from(receiver)..], |(ast, _)| {
+        self::expr(cx, ast)?
+    });
+
+    match (call, callee) {
+        (_, Some(meta)) => types::call(cx, &meta, args)?,
+        (hir::Call::Associated { target, hash }, None) => {
+            types::associated(cx, target, hash, args)?
+        }
+        _ => {}
+    }
+
+    Ok(hir::ExprCall { call, args })
+}
+
+/// Warn about calling `clone` on a value which was just created, like
+/// `[1, 2].clone()`.
+fn needless_clone(
+    cx: &mut Ctxt<'_, '_, '_>,
+    ast: &ast::ExprCall,
+    target: &hir::Expr<'_>,
+) -> compile::Result<()> {
+    if !matches!(
+        target.kind,
+        hir::ExprKind::Lit(..)
+            | hir::ExprKind::Vec(..)
+            | hir::ExprKind::Tuple(..)
+            | hir::ExprKind::Object(..)
+            | hir::ExprKind::Template(..)
+    ) {
+        return Ok(());
+    }
+
+    let ast::Expr::FieldAccess(field_access) = &*ast.expr else {
+        return Ok(());
+    };
+
+    let clone = Span::new(field_access.expr.span().end, ast.span().end);
+
+    cx.q.diagnostics.needless_clone(cx.source_id, ast, &clone)?;
+    Ok(())
 }
 
 #[instrument_ast(span = ast)]
@@ -1941,6 +2216,11 @@ fn expr_field_access<'hir>(
             };
 
             let ident = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
+            cx.q.field_read(ident)?;
+
+            cx.q.visitor
+                .visit_field_use(&DynLocation::new(cx.source_id, ast), None, ident, false)
+                .with_span(ast)?;
 
             match generics {
                 Some(generics) => {
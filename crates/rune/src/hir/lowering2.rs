@@ -10,7 +10,7 @@ use tracing::instrument_ast;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, HashMap, HashSet};
 use crate::ast::{self, Delimiter, Kind, NumberSize, Span, Spanned};
-use crate::compile::{meta, Error, ErrorKind, ItemId, Result, WithSpan};
+use crate::compile::{meta, DynLocation, Error, ErrorKind, ItemId, Result, WithSpan};
 use crate::grammar::{
     classify, object_key, Ignore, MaybeNode, NodeClass, Remaining, Stream, StreamBuf, Tree,
 };
@@ -63,6 +63,11 @@ pub(crate) fn item_fn<'hir>(
 
         while let MaybeNode::Some(pat) = p.eat(Pat) {
             comma.exactly_one(cx)?;
+
+            cx.q.visitor
+                .visit_fn_arg(&DynLocation::new(cx.source_id, &pat))
+                .with_span(&pat)?;
+
             let pat = pat.parse(|p| self::pat_binding_with(cx, p, is_instance))?;
             args.try_push(hir::FnArg::Pat(alloc!(pat)))?;
             comma = p.one(K![,]);
@@ -298,7 +303,12 @@ enum ExprInnerKind<'hir, 'a> {
 }
 
 impl<'hir> ExprInner<'hir, '_> {
-    fn into_call(self, cx: &mut Ctxt<'hir, '_, '_>, args: usize) -> Result<hir::Call<'hir>> {
+    fn into_call(
+        self,
+        cx: &mut Ctxt<'hir, '_, '_>,
+        args: usize,
+        args_span: Span,
+    ) -> Result<hir::Call<'hir>> {
         match self.kind {
             ExprInnerKind::Path(p) => {
                 let named = p.parse(|p| cx.q.convert_path2(p))?;
@@ -347,7 +357,7 @@ impl<'hir> ExprInner<'hir, '_> {
                         if *expected == 0 {
                             cx.q.diagnostics.remove_tuple_call_parens(
                                 cx.source_id,
-                                &self.span,
+                                &args_span,
                                 &self.span,
                                 None,
                             )?;
@@ -360,6 +370,7 @@ impl<'hir> ExprInner<'hir, '_> {
                                 &self.span,
                                 None,
                                 message.try_into()?,
+                                cx.q.context.lookup_replacement(meta.hash),
                             )?;
                         };
                     }
@@ -459,7 +470,9 @@ impl<'hir> ExprInner<'hir, '_> {
                     }
                 };
 
-                Err(Error::new(self.span, kind))
+                let error = Error::new(self.span, kind);
+                let location = DynLocation::new(cx.source_id, self.span);
+                Err(cx.q.with_import_suggestions(&location, error)?)
             }
         }
     }
@@ -1119,6 +1132,7 @@ fn expr_object<'hir>(
     let key = p.pump()?;
 
     let mut assignments = Vec::new();
+    let mut shorthands = Vec::new();
     let mut comma = Remaining::default();
     let mut keys_dup = HashMap::new();
 
@@ -1143,7 +1157,9 @@ fn expr_object<'hir>(
             }
         };
 
-        let assign = if p.eat(K![:]).is_some() {
+        let shorthand = p.eat(K![:]).is_none();
+
+        let assign = if !shorthand {
             p.expect(Expr)?.parse(|p| expr(cx, p))?
         } else {
             let Some((name, _)) = cx.scopes.get(hir::Name::Str(key))? else {
@@ -1179,6 +1195,7 @@ fn expr_object<'hir>(
             position: None,
         })?;
 
+        shorthands.try_push(shorthand)?;
         comma = p.one(K![,]);
     }
 
@@ -1272,6 +1289,21 @@ fn expr_object<'hir>(
         }
     };
 
+    if let Some(hash) = kind.hash() {
+        for (assign, shorthand) in assignments.iter().zip(shorthands) {
+            let (span, key) = assign.key;
+
+            cx.q.visitor
+                .visit_field_use(
+                    &DynLocation::new(cx.source_id, &span),
+                    Some(hash),
+                    key,
+                    shorthand,
+                )
+                .with_span(span)?;
+        }
+    }
+
     let object = alloc!(hir::ExprObject {
         kind,
         assignments: iter!(assignments),
@@ -1880,6 +1912,11 @@ fn expr_closure<'hir>(
 
         while let MaybeNode::Some(pat) = p.eat(Pat) {
             comma.exactly_one(cx)?;
+
+            cx.q.visitor
+                .visit_fn_arg(&DynLocation::new(cx.source_id, &pat))
+                .with_span(&pat)?;
+
             let binding = pat.parse(|p| self::pat_binding(cx, p))?;
             comma = p.remaining(cx, K![,])?;
             args.try_push(hir::FnArg::Pat(alloc!(binding)))
@@ -1900,6 +1937,12 @@ fn expr_closure<'hir>(
 
     let captures = &*iter!(layer.captures().map(|(_, id)| id));
 
+    for (name, _) in layer.captures() {
+        cx.q.visitor
+            .visit_closure_capture(&DynLocation::new(cx.source_id, &*p), &name.try_to_string()?)
+            .with_span(&*p)?;
+    }
+
     let Some(queue) = cx.secondary_builds.as_mut() else {
         return Err(Error::new(&*p, ErrorKind::ClosureInConst));
     };
@@ -1935,7 +1978,7 @@ fn expr_call<'hir>(
 ) -> Result<hir::ExprKind<'hir>> {
     alloc_with!(cx, p);
 
-    p.expect(K!['('])?;
+    let open = p.expect(K!['('])?;
 
     let mut comma = Remaining::default();
     let mut args = Vec::new();
@@ -1948,9 +1991,10 @@ fn expr_call<'hir>(
     }
 
     comma.at_most_one(cx)?;
-    p.expect(K![')'])?;
+    let close = p.expect(K![')'])?;
 
-    let call = inner.into_call(cx, args.len())?;
+    let args_span = open.span().join(close.span());
+    let call = inner.into_call(cx, args.len(), args_span)?;
 
     let kind = hir::ExprKind::Call(alloc!(hir::ExprCall {
         call,
@@ -1986,15 +2030,22 @@ fn expr_field<'hir>(
         }
         IndexedPath(..) => p.pump()?.parse(|p| match p.kinds() {
             Some([K![ident]]) => {
-                let base = p.ast::<ast::Ident>()?;
-                let base = base.resolve(resolve_context!(cx.q))?;
-                let base = alloc_str!(base);
+                let ident = p.ast::<ast::Ident>()?;
+                let base = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
+
+                cx.q.visitor
+                    .visit_field_use(&DynLocation::new(cx.source_id, &ident), None, base, false)
+                    .with_span(ident)?;
+
                 Ok(hir::ExprField::Ident(base))
             }
             None => {
-                let base = p.ast::<ast::Ident>()?;
-                let base = base.resolve(resolve_context!(cx.q))?;
-                let base = alloc_str!(base);
+                let ident = p.ast::<ast::Ident>()?;
+                let base = alloc_str!(ident.resolve(resolve_context!(cx.q))?);
+
+                cx.q.visitor
+                    .visit_field_use(&DynLocation::new(cx.source_id, &ident), None, base, false)
+                    .with_span(ident)?;
 
                 if p.eat(K![::]).is_some() {
                     let hash = p
@@ -2349,6 +2400,7 @@ fn pat_object<'hir>(cx: &mut Ctxt<'hir, '_, '_>, p: &mut Stream<'_>) -> Result<h
     let mut bindings = Vec::new();
     let mut comma = Remaining::default();
     let mut keys_dup = HashMap::new();
+    let mut keys = Vec::new();
 
     while matches!(p.peek(), object_key!()) {
         comma.exactly_one(cx)?;
@@ -2381,7 +2433,11 @@ fn pat_object<'hir>(cx: &mut Ctxt<'hir, '_, '_>, p: &mut Stream<'_>) -> Result<h
             ));
         }
 
-        if p.eat(K![:]).is_some() {
+        let shorthand = p.eat(K![:]).is_none();
+
+        keys.try_push((span, key, shorthand))?;
+
+        if !shorthand {
             let pat = p.expect(Pat)?.parse(|p| pat(cx, p))?;
             bindings.try_push(hir::Binding::Binding(p.span(), key, alloc!(pat)))?;
         } else {
@@ -2419,6 +2475,17 @@ fn pat_object<'hir>(cx: &mut Ctxt<'hir, '_, '_>, p: &mut Stream<'_>) -> Result<h
                 ));
             };
 
+            for &(span, key, shorthand) in &keys {
+                cx.q.visitor
+                    .visit_field_use(
+                        &DynLocation::new(cx.source_id, &span),
+                        Some(meta.hash),
+                        key,
+                        shorthand,
+                    )
+                    .with_span(span)?;
+            }
+
             for binding in bindings.iter() {
                 if !fields.remove(binding.key()) {
                     return Err(Error::new(
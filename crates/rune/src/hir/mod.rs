@@ -15,6 +15,8 @@ pub(crate) use self::scopes::Scopes;
 
 pub(crate) mod interpreter;
 
+mod types;
+
 mod ctxt;
 pub(crate) use self::ctxt::Ctxt;
 use self::ctxt::Needs;
@@ -5,7 +5,7 @@ use core::fmt;
 use core::num::NonZeroUsize;
 
 use crate::alloc::prelude::*;
-use crate::alloc::{self, BTreeSet, HashMap, Vec};
+use crate::alloc::{self, BTreeSet, HashMap, HashSet, Vec};
 use crate::ast::Spanned;
 use crate::compile::error::{MissingScope, PopError};
 use crate::compile::{self, HasSpan};
@@ -66,6 +66,8 @@ impl<'hir> Layer<'hir> {
 pub(crate) struct Scopes<'hir, 'a> {
     scope: Scope,
     scopes: Vec<Layer<'hir>>,
+    /// Variables which have been looked up.
+    used: HashSet<hir::Variable>,
     gen: &'a Gen,
 }
 
@@ -81,6 +83,7 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         Ok(Self {
             scope: Scopes::ROOT,
             scopes,
+            used: HashSet::new(),
             gen,
         })
     }
@@ -177,6 +180,33 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
         Ok(id)
     }
 
+    /// Test if the given variable is defined in the current scope or any of
+    /// its parents.
+    ///
+    /// Unlike [Scopes::get] this does not register any captures.
+    pub(crate) fn is_defined(&self, name: hir::Name<'hir>) -> bool {
+        let mut scope = self.scopes.get(self.scope.0);
+
+        while let Some(layer) = scope.take() {
+            if layer.variables.contains_key(&name) {
+                return true;
+            }
+
+            let Some(parent) = layer.parent() else {
+                return false;
+            };
+
+            scope = self.scopes.get(parent);
+        }
+
+        false
+    }
+
+    /// Test if the given variable has been looked up.
+    pub(crate) fn is_used(&self, id: hir::Variable) -> bool {
+        self.used.contains(&id)
+    }
+
     /// Try to lookup the given variable.
     #[tracing::instrument(skip_all, fields(?self.scope, ?name))]
     pub(crate) fn get(
@@ -212,6 +242,8 @@ impl<'hir, 'a> Scopes<'hir, 'a> {
             }
         };
 
+        self.used.try_insert(id)?;
+
         for s in blocks {
             let Some(layer) = self.scopes.get_mut(s.0) else {
                 continue;
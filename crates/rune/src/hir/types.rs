@@ -0,0 +1,231 @@
+//! Checking of optional type annotations.
+//!
+//! This is deliberately conservative. The type of an expression is only known
+//! if it's a literal, an annotated binding or a call to a function with a
+//! known signature, and any expression with an unknown type is accepted.
+//! Calling an async function produces a future, and awaiting it produces its
+//! annotated return type.
+//! Annotations never change how a function is compiled.
+
+use crate::alloc::{self, HashMap, String};
+use crate::compile::{self, meta, ErrorKind};
+use crate::diagnostics::Suggestion;
+use crate::hir;
+use crate::runtime::{Bytes, Future, Object, OwnedTuple, Vec};
+use crate::{Hash, TypeHash};
+
+use super::Ctxt;
+
+/// Integer types which are compatible with each other.
+///
+/// NB: Narrower integer types like `u8` share the type of `u64` or `i64`, so
+/// they don't need to be listed separately.
+const INTEGERS: [Hash; 2] = [u64::HASH, i64::HASH];
+
+/// Types known while lowering a single function.
+#[derive(Default)]
+pub(super) struct Types {
+    /// Types of annotated variables.
+    variables: HashMap<hir::Variable, Hash>,
+    /// The known return types of called functions, by function hash.
+    returns: HashMap<Hash, Hash>,
+    /// The known types produced by awaiting called async functions, by
+    /// function hash.
+    awaited: HashMap<Hash, Hash>,
+    /// The annotated return type of the function or closure being lowered.
+    pub(super) return_type: Option<Hash>,
+}
+
+/// Declare the type of a pattern binding if it binds a single variable.
+pub(super) fn declare(
+    cx: &mut Ctxt<'_, '_, '_>,
+    pat: &hir::PatBinding<'_>,
+    ty: Hash,
+) -> alloc::Result<()> {
+    if let (hir::PatKind::Path(&hir::PatPathKind::Ident(variable)), [_]) = (pat.pat.kind, pat.names)
+    {
+        cx.types.variables.try_insert(variable, ty)?;
+    }
+
+    Ok(())
+}
+
+/// Check that the type of an expression is compatible with the expected
+/// type.
+pub(super) fn check(
+    cx: &Ctxt<'_, '_, '_>,
+    expected: Hash,
+    expr: &hir::Expr<'_>,
+) -> compile::Result<()> {
+    let Some(actual) = type_of(cx, expr) else {
+        return Ok(());
+    };
+
+    if is_compatible(expected, actual) {
+        return Ok(());
+    }
+
+    let error = compile::Error::new(
+        expr,
+        ErrorKind::TypeMismatch {
+            expected: cx.q.type_name(expected)?,
+            actual: cx.q.type_name(actual)?,
+        },
+    );
+
+    if let Some(awaited) = awaited_type_of(cx, expr) {
+        if is_compatible(expected, awaited) {
+            let span = expr.span.tail();
+            return Err(error.with_suggestion(Suggestion::new("Add `.await`", span, ".await")?));
+        }
+    }
+
+    Err(error)
+}
+
+/// Check an assignment to an annotated variable.
+pub(super) fn assign(cx: &Ctxt<'_, '_, '_>, assign: &hir::ExprAssign<'_>) -> compile::Result<()> {
+    if let hir::ExprKind::Variable(variable) = assign.lhs.kind {
+        if let Some(&expected) = cx.types.variables.get(&variable) {
+            check(cx, expected, &assign.rhs)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the arguments of a call to the item with the given metadata, and
+/// record the type it returns.
+pub(super) fn call(
+    cx: &mut Ctxt<'_, '_, '_>,
+    meta: &meta::Meta,
+    args: &[hir::Expr<'_>],
+) -> compile::Result<()> {
+    match &meta.kind {
+        meta::Kind::Function { signature, .. } => {
+            check_signature(cx, meta.hash, signature, args.iter())?;
+        }
+        meta::Kind::Struct { .. } => {
+            cx.types.returns.try_insert(meta.hash, meta.hash)?;
+        }
+        meta::Kind::Variant { enum_hash, .. } => {
+            cx.types.returns.try_insert(meta.hash, *enum_hash)?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Check the arguments of an instance function call on a value of a known
+/// type.
+pub(super) fn associated(
+    cx: &mut Ctxt<'_, '_, '_>,
+    target: &hir::Expr<'_>,
+    hash: Hash,
+    args: &[hir::Expr<'_>],
+) -> compile::Result<()> {
+    let Some(ty) = type_of(cx, target) else {
+        return Ok(());
+    };
+
+    let hash = Hash::associated_function(ty, hash);
+
+    let Some(meta::Kind::Function { signature, .. }) =
+        cx.q.context
+            .lookup_meta_by_hash(hash)
+            .next()
+            .map(|meta| &meta.kind)
+    else {
+        return Ok(());
+    };
+
+    check_signature(cx, hash, signature, [target].into_iter().chain(args))
+}
+
+fn check_signature<'a, 'hir: 'a>(
+    cx: &mut Ctxt<'_, '_, '_>,
+    hash: Hash,
+    signature: &meta::Signature,
+    args: impl IntoIterator<Item = &'a hir::Expr<'hir>>,
+) -> compile::Result<()> {
+    for (&expected, expr) in signature.argument_types.iter().zip(args) {
+        if expected != Hash::EMPTY {
+            check(cx, expected, expr)?;
+        }
+    }
+
+    let return_type = signature.return_type.base;
+
+    if signature.is_async {
+        cx.types.returns.try_insert(hash, Future::HASH)?;
+
+        if return_type != Hash::EMPTY {
+            cx.types.awaited.try_insert(hash, return_type)?;
+        }
+    } else if return_type != Hash::EMPTY {
+        cx.types.returns.try_insert(hash, return_type)?;
+    }
+
+    Ok(())
+}
+
+/// Get the type of an expression, if it's known.
+fn type_of(cx: &Ctxt<'_, '_, '_>, expr: &hir::Expr<'_>) -> Option<Hash> {
+    match expr.kind {
+        hir::ExprKind::Lit(lit) => Some(match lit {
+            hir::Lit::Bool(..) => bool::HASH,
+            hir::Lit::Unsigned(..) => u64::HASH,
+            hir::Lit::Signed(..) => i64::HASH,
+            hir::Lit::Float(..) => f64::HASH,
+            hir::Lit::Char(..) => char::HASH,
+            hir::Lit::Str(..) => String::HASH,
+            hir::Lit::ByteStr(..) => Bytes::HASH,
+        }),
+        hir::ExprKind::Variable(variable) => cx.types.variables.get(&variable).copied(),
+        hir::ExprKind::Template(..) => Some(String::HASH),
+        hir::ExprKind::Vec(..) => Some(Vec::HASH),
+        hir::ExprKind::Tuple(..) => Some(OwnedTuple::HASH),
+        hir::ExprKind::Object(object) => match object.kind {
+            hir::ExprObjectKind::EmptyStruct { hash } => Some(hash),
+            hir::ExprObjectKind::Struct { hash } => Some(hash),
+            hir::ExprObjectKind::ExternalType { hash, .. } => Some(hash),
+            hir::ExprObjectKind::Anonymous => Some(Object::HASH),
+            hir::ExprObjectKind::StructVariant { .. } => None,
+        },
+        hir::ExprKind::Call(..) => cx.types.returns.get(&call_hash(cx, expr)?).copied(),
+        hir::ExprKind::Await(expr) => awaited_type_of(cx, expr),
+        hir::ExprKind::Group(expr) => type_of(cx, expr),
+        hir::ExprKind::Block(block) if block.label.is_none() => type_of(cx, block.value?),
+        _ => None,
+    }
+}
+
+/// Get the type produced by awaiting an expression, if it's a call to an async
+/// function with a known return type.
+fn awaited_type_of(cx: &Ctxt<'_, '_, '_>, expr: &hir::Expr<'_>) -> Option<Hash> {
+    match expr.kind {
+        hir::ExprKind::Call(..) => cx.types.awaited.get(&call_hash(cx, expr)?).copied(),
+        hir::ExprKind::Group(expr) => awaited_type_of(cx, expr),
+        _ => None,
+    }
+}
+
+/// Get the hash of the function called by an expression, if it's known.
+fn call_hash(cx: &Ctxt<'_, '_, '_>, expr: &hir::Expr<'_>) -> Option<Hash> {
+    let hir::ExprKind::Call(call) = expr.kind else {
+        return None;
+    };
+
+    match call.call {
+        hir::Call::Meta { hash } => Some(hash),
+        hir::Call::Associated { target, hash } => {
+            Some(Hash::associated_function(type_of(cx, target)?, hash))
+        }
+        _ => None,
+    }
+}
+
+fn is_compatible(expected: Hash, actual: Hash) -> bool {
+    expected == actual || INTEGERS.contains(&expected) && INTEGERS.contains(&actual)
+}
@@ -3,22 +3,21 @@ use core::mem::take;
 use tracing::instrument_ast;
 
 use crate::alloc::prelude::*;
-use crate::alloc::VecDeque;
-use crate::ast::{self, OptionSpanned, Spanned};
+use crate::alloc::{HashSet, VecDeque};
+use crate::ast::{self, OptionSpanned, Span, Spanned};
 use crate::compile::{
     self, attrs, meta, Doc, DynLocation, ErrorKind, ItemMeta, Location, Visibility, WithSpan,
 };
 use crate::indexing::{self, Indexed};
+use crate::macros::MacroRules;
 use crate::parse::{Resolve, ResolveContext};
 use crate::query::{DeferEntry, ImplItem, ImplItemKind};
 use crate::runtime::Call;
 use crate::worker::{Import, ImportKind, ImportState};
+use crate::ItemBuf;
 
 use super::{ast_to_visibility, validate_call, Indexer};
 
-/// Macros are only allowed to expand recursively into other macros 64 times.
-const MAX_MACRO_RECURSION: usize = 64;
-
 /// Index the contents of a module known by its AST as a "file".
 pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes)?;
@@ -40,6 +39,9 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
             .with_span(span)?;
     }
 
+    let span = ast.option_span().unwrap_or(Span::empty());
+    lint_levels(idx, &mut p, &ast.attributes, &span)?;
+
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
@@ -47,6 +49,12 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
         ));
     }
 
+    last_import(idx, ast.items.iter().map(|(item, semi)| (item, semi)))?;
+
+    // Macro definitions are indexed first, so that they can be used anywhere
+    // in the module.
+    let mut definitions = Vec::new();
+
     // Items take priority.
     let mut head = VecDeque::new();
 
@@ -56,6 +64,9 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
 
     for (item, semi) in ast.items.drain(..) {
         match item {
+            ast::Item::MacroRules(i) => {
+                definitions.try_push(i)?;
+            }
             i @ ast::Item::MacroCall(_) => {
                 queue.try_push_back((0, i, Vec::new(), semi))?;
             }
@@ -68,6 +79,10 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
         }
     }
 
+    for definition in definitions {
+        item_macro_rules(idx, definition)?;
+    }
+
     'uses: while !head.is_empty() || !queue.is_empty() {
         while let Some((i, semi)) = head.pop_front() {
             if let Some(semi) = semi {
@@ -82,12 +97,12 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
         }
 
         while let Some((depth, mut item, mut skipped_attributes, semi)) = queue.pop_front() {
-            if depth >= MAX_MACRO_RECURSION {
+            if depth >= idx.q.options.max_macro_depth {
                 return Err(compile::Error::new(
                     &item,
                     ErrorKind::MaxMacroRecursion {
                         depth,
-                        max: MAX_MACRO_RECURSION,
+                        max: idx.q.options.max_macro_depth,
                     },
                 ));
             }
@@ -187,15 +202,71 @@ pub(crate) fn file(idx: &mut Indexer<'_, '_>, ast: &mut ast::File) -> compile::R
     Ok(())
 }
 
+/// Register the lint levels set by `#[allow(..)]`, `#[warn(..)]` and
+/// `#[deny(..)]` attributes, which apply to everything inside of `span`.
+fn lint_levels(
+    idx: &mut Indexer<'_, '_>,
+    p: &mut attrs::Parser,
+    attributes: &[ast::Attribute],
+    span: &dyn Spanned,
+) -> compile::Result<()> {
+    let levels = attrs::lint_levels(p, resolve_context!(idx.q), attributes)?;
+
+    for (lint, level) in levels {
+        idx.q.lint_level(idx.source_id, span.span(), lint, level)?;
+    }
+
+    Ok(())
+}
+
 #[instrument_ast(span = span)]
+/// Record the span of the last top-level `use` item, after which new imports
+/// are suggested.
+fn last_import<'a>(
+    idx: &mut Indexer<'_, '_>,
+    items: impl DoubleEndedIterator<Item = (&'a ast::Item, &'a Option<ast::SemiColon>)>,
+) -> compile::Result<()> {
+    let last = items.rev().find_map(|(item, semi)| match item {
+        ast::Item::Use(item) => Some(match semi.option_span() {
+            Some(semi) => item.span().join(semi),
+            None => item.span(),
+        }),
+        _ => None,
+    });
+
+    if let Some(span) = last {
+        idx.q.insert_last_import(idx.source_id, span)?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn empty_block_fn(
     idx: &mut Indexer<'_, '_>,
     mut ast: ast::EmptyBlock,
     span: &dyn Spanned,
 ) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    lint_levels(idx, &mut p, &ast.attributes, span)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "File attributes are not supported",
+        ));
+    }
+
     let item_meta = idx.insert_new_item(span, Visibility::Public, &[])?;
     let idx_item = idx.item.replace(item_meta.item);
 
+    let items = ast.statements.iter().filter_map(|stmt| match stmt {
+        ast::Stmt::Item(item, semi) => Some((item, semi)),
+        _ => None,
+    });
+
+    last_import(idx, items)?;
+
     idx.scopes.push()?;
 
     statements(idx, &mut ast.statements)?;
@@ -238,15 +309,27 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
 
     let guard = idx.items.push_name(name.as_ref())?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
     let item_meta = idx.insert_new_item(&ast, visibility, &docs)?;
     let idx_item = idx.item.replace(item_meta.item);
 
     for (arg, _) in &mut ast.args {
-        if let ast::FnArg::Pat(p) = arg {
-            pat(idx, p)?;
+        match arg {
+            ast::FnArg::SelfValue(..) => {}
+            ast::FnArg::Pat(p) => {
+                pat(idx, p)?;
+            }
+            ast::FnArg::Typed(arg) => {
+                pat(idx, &mut arg.pat)?;
+                ty(idx, &mut arg.ty)?;
+            }
         }
     }
 
+    if let Some(fn_type) = &mut ast.fn_type {
+        ty(idx, fn_type)?;
+    }
+
     idx.scopes.push()?;
 
     // Take and restore item nesting.
@@ -343,7 +426,15 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
     }
 
     let name = ast.name;
-    let args = ast.args.iter().map(|(a, _)| a.span()).try_collect()?;
+    // NB: Arguments are named by their pattern, excluding any type annotation.
+    let args = ast
+        .args
+        .iter()
+        .map(|(a, _)| match a {
+            ast::FnArg::Typed(a) => a.pat.span(),
+            a => a.span(),
+        })
+        .try_collect()?;
 
     let entry = indexing::Entry {
         item_meta,
@@ -375,9 +466,33 @@ pub(crate) fn item_fn(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemFn) -> compil
     Ok(())
 }
 
+/// Index the default implementation of a trait function for a type
+/// implementing the trait.
+///
+/// The function is indexed under an anonymous item inside of the trait, so
+/// that names in its body are resolved in the scope of the trait. Returns the
+/// item of the indexed function.
+pub(crate) fn trait_default_fn(
+    idx: &mut Indexer<'_, '_>,
+    ast: ast::ItemFn,
+) -> compile::Result<ItemBuf> {
+    let span = ast.span();
+
+    let guard = idx.push_id()?;
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let item = idx.items.item().extended(name)?;
+    item_fn(idx, ast)?;
+    idx.items.pop(guard).with_span(span)?;
+    Ok(item)
+}
+
 #[instrument_ast(span = ast)]
 fn expr_block(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprBlock) -> compile::Result<()> {
-    if let Some(span) = ast.attributes.option_span() {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    lint_levels(idx, &mut p, &ast.attributes, &ast.block)?;
+
+    if let Some(span) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             span,
             "Attributes on blocks are not supported",
@@ -438,7 +553,49 @@ fn expr_block(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprBlock) -> compile::R
 fn statements(idx: &mut Indexer<'_, '_>, ast: &mut Vec<ast::Stmt>) -> compile::Result<()> {
     let mut statements = Vec::new();
 
+    // Statements paired with the depth of macro expansion they were produced
+    // at.
+    let mut queue = VecDeque::new();
+
     for stmt in ast.drain(..) {
+        queue.try_push_back((0, stmt))?;
+    }
+
+    while let Some((depth, stmt)) = queue.pop_front() {
+        // Calls to declarative macros in statement position expand into
+        // statements, which are spliced into the enclosing block.
+        let stmt = match stmt {
+            ast::Stmt::Expr(ast::Expr::MacroCall(mut macro_call)) => {
+                match expand_statements(idx, depth, &mut macro_call, None)? {
+                    Some(expanded) => {
+                        for stmt in expanded.into_iter().rev() {
+                            queue.try_push_front((depth + 1, stmt))?;
+                        }
+
+                        continue;
+                    }
+                    None => ast::Stmt::Expr(ast::Expr::MacroCall(macro_call)),
+                }
+            }
+            ast::Stmt::Semi(ast::StmtSemi {
+                expr: ast::Expr::MacroCall(mut macro_call),
+                semi_token,
+            }) => match expand_statements(idx, depth, &mut macro_call, Some(semi_token))? {
+                Some(expanded) => {
+                    for stmt in expanded.into_iter().rev() {
+                        queue.try_push_front((depth + 1, stmt))?;
+                    }
+
+                    continue;
+                }
+                None => ast::Stmt::Semi(ast::StmtSemi::new(
+                    ast::Expr::MacroCall(macro_call),
+                    semi_token,
+                )),
+            },
+            stmt => stmt,
+        };
+
         match stmt {
             ast::Stmt::Item(i, semi) => {
                 if let Some(semi) = semi {
@@ -500,6 +657,51 @@ fn statements(idx: &mut Indexer<'_, '_>, ast: &mut Vec<ast::Stmt>) -> compile::R
     Ok(())
 }
 
+/// Expand a call to a declarative macro in statement position into the
+/// statements it produces.
+///
+/// If the call is terminated by `semi_token`, it is used to terminate a
+/// trailing expression in the expansion. Returns `None` if the call is not to a
+/// declarative macro, in which case it is expanded as an expression.
+fn expand_statements(
+    idx: &mut Indexer<'_, '_>,
+    depth: usize,
+    macro_call: &mut ast::MacroCall,
+    semi_token: Option<T![;]>,
+) -> compile::Result<Option<Vec<ast::Stmt>>> {
+    if !macro_call.attributes.is_empty() || macro_call.id.is_some() {
+        return Ok(None);
+    }
+
+    if depth >= idx.q.options.max_macro_depth {
+        return Err(compile::Error::new(
+            &*macro_call,
+            ErrorKind::MaxMacroRecursion {
+                depth,
+                max: idx.q.options.max_macro_depth,
+            },
+        ));
+    }
+
+    let Some(mut statements) = idx.expand_macro_rules_statements(macro_call)? else {
+        return Ok(None);
+    };
+
+    if let Some(semi_token) = semi_token {
+        match statements.pop() {
+            Some(ast::Stmt::Expr(expr)) => {
+                statements.try_push(ast::Stmt::Semi(ast::StmtSemi::new(expr, semi_token)))?;
+            }
+            Some(stmt) => {
+                statements.try_push(stmt)?;
+            }
+            None => {}
+        }
+    }
+
+    Ok(Some(statements))
+}
+
 #[instrument_ast(span = ast)]
 fn block(idx: &mut Indexer<'_, '_>, ast: &mut ast::Block) -> compile::Result<ItemMeta> {
     let guard = idx.push_id()?;
@@ -530,6 +732,27 @@ fn local(idx: &mut Indexer<'_, '_>, ast: &mut ast::Local) -> compile::Result<()>
     // declaration and use that instead of capturing from the outside.
     expr(idx, &mut ast.expr)?;
     pat(idx, &mut ast.pat)?;
+
+    if let Some((_, ast)) = &mut ast.ty {
+        ty(idx, ast)?;
+    }
+
+    Ok(())
+}
+
+#[instrument_ast(span = ast)]
+fn ty(idx: &mut Indexer<'_, '_>, ast: &mut ast::Type) -> compile::Result<()> {
+    match ast {
+        ast::Type::Path(p) => {
+            path(idx, p)?;
+        }
+        ast::Type::Tuple(tuple) => {
+            for (ast, _) in tuple {
+                ty(idx, ast)?;
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -624,7 +847,9 @@ pub(crate) fn expr(idx: &mut Indexer<'_, '_>, ast: &mut ast::Expr) -> compile::R
             expr_let(idx, ast)?;
         }
         ast::Expr::Block(ast) => {
+            // Blocks support lint attributes, which are checked here.
             expr_block(idx, ast)?;
+            return Ok(());
         }
         ast::Expr::Group(ast) => {
             expr(idx, &mut ast.expr)?;
@@ -833,6 +1058,7 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -886,6 +1112,16 @@ fn item_enum(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemEnum) -> compile::Resu
 
             let name = field.name.resolve(cx)?;
 
+            idx.q
+                .visitor
+                .visit_field(
+                    &DynLocation::new(idx.source_id, &field.name),
+                    idx.q.pool.item(item_meta.item),
+                    idx.q.pool.item_type_hash(item_meta.item),
+                    name,
+                )
+                .with_span(&field.name)?;
+
             for doc in docs {
                 idx.q
                     .visitor
@@ -923,6 +1159,7 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -954,6 +1191,16 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
         let name = field.name.resolve(cx)?;
 
+        idx.q
+            .visitor
+            .visit_field(
+                &DynLocation::new(idx.source_id, &field.name),
+                idx.q.pool.item(item_meta.item),
+                idx.q.pool.item_type_hash(item_meta.item),
+                name,
+            )
+            .with_span(&field.name)?;
+
         for doc in docs {
             idx.q
                 .visitor
@@ -975,6 +1222,18 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
         }
     }
 
+    // Fields of private structs can only be read by the current script.
+    if matches!(visibility, Visibility::Inherited) {
+        for (field, _) in ast.body.fields() {
+            let name = field
+                .name
+                .resolve(resolve_context!(idx.q))?
+                .try_to_owned()?;
+            idx.q
+                .declare_field(Location::new(idx.source_id, field.name.span()), &name)?;
+        }
+    }
+
     idx.item = idx_item;
     idx.items.pop(guard).with_span(&ast)?;
 
@@ -985,7 +1244,11 @@ fn item_struct(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemStruct) -> compile::
 
 #[instrument_ast(span = ast)]
 fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Result<()> {
-    if let Some(first) = ast.attributes.first() {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
             first,
             "Attributes on impl blocks are not supported",
@@ -994,6 +1257,14 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
 
     path(idx, &mut ast.path)?;
 
+    let trait_path = match ast.trait_ {
+        Some((mut trait_path, _)) => {
+            path(idx, &mut trait_path)?;
+            Some(Box::try_new(trait_path)?)
+        }
+        None => None,
+    };
+
     let location = Location::new(idx.source_id, ast.path.span());
 
     idx.q
@@ -1002,6 +1273,7 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
         .try_push_back(DeferEntry::ImplItem(ImplItem {
             kind: ImplItemKind::Ast {
                 path: Box::try_new(ast.path)?,
+                trait_path,
                 functions: take(&mut ast.functions),
             },
             location,
@@ -1013,11 +1285,73 @@ fn item_impl(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemImpl) -> compile::Resu
     Ok(())
 }
 
+#[instrument_ast(span = ast)]
+fn item_trait(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemTrait) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on traits are not supported",
+        ));
+    }
+
+    let mut names = HashSet::new();
+
+    for f in &ast.functions {
+        let name = f.name.resolve(resolve_context!(idx.q))?;
+
+        if !names.try_insert(name.try_to_owned()?)? {
+            return Err(compile::Error::msg(
+                f.name,
+                try_format!("Function `{name}` is declared more than once in trait"),
+            ));
+        }
+    }
+
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let guard = idx.items.push_name(name.as_ref())?;
+
+    let visibility = ast_to_visibility(&ast.visibility)?;
+    let item_meta = idx.insert_new_item(&ast, visibility, &docs)?;
+
+    let mut functions = Vec::new();
+
+    // Trait functions are declared as items inside of the trait, so that they
+    // can be called through it, as in `Trait::function(value)`.
+    for f in take(&mut ast.functions) {
+        let mut p = attrs::Parser::new(&f.attributes)?;
+        let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &f.attributes)?;
+
+        let name = f.name.resolve(resolve_context!(idx.q))?;
+        let guard = idx.items.push_name(name.as_ref())?;
+        let item_meta = idx.insert_new_item(&f, visibility, &docs)?;
+        idx.items.pop(guard).with_span(&f)?;
+
+        functions.try_push((item_meta, f))?;
+    }
+
+    idx.items.pop(guard).with_span(&ast)?;
+
+    let tr = indexing::Trait {
+        source_id: idx.source_id,
+        functions,
+    };
+
+    idx.q.index_trait(&ast, item_meta, tr)?;
+
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn item_mod(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemMod) -> compile::Result<()> {
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -1064,6 +1398,7 @@ fn item_const(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemConst) -> compile::Re
     let mut p = attrs::Parser::new(&ast.attributes)?;
 
     let docs = Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+    lint_levels(idx, &mut p, &ast.attributes, &ast)?;
 
     if let Some(first) = p.remaining(&ast.attributes).next() {
         return Err(compile::Error::msg(
@@ -1094,6 +1429,47 @@ fn item_const(idx: &mut Indexer<'_, '_>, mut ast: ast::ItemConst) -> compile::Re
     Ok(())
 }
 
+#[instrument_ast(span = ast)]
+fn item_macro_rules(idx: &mut Indexer<'_, '_>, ast: ast::ItemMacroRules) -> compile::Result<()> {
+    let mut p = attrs::Parser::new(&ast.attributes)?;
+
+    Doc::collect_from(resolve_context!(idx.q), &mut p, &ast.attributes)?;
+
+    if let Some(first) = p.remaining(&ast.attributes).next() {
+        return Err(compile::Error::msg(
+            first,
+            "Attributes on macro definitions are not supported",
+        ));
+    }
+
+    let is_macro_rules = match ast.path.try_as_ident() {
+        Some(ident) => ident.resolve(resolve_context!(idx.q))? == "macro_rules",
+        None => false,
+    };
+
+    if !is_macro_rules {
+        return Err(compile::Error::msg(
+            &ast.path,
+            "Expected `macro_rules` to define a macro",
+        ));
+    }
+
+    // NB: Macros defined in scripts are named after the module they are
+    // defined in, regardless of how deeply they are nested inside of it.
+    let name = ast.name.resolve(resolve_context!(idx.q))?;
+    let item = idx.q.pool.module_item(idx.item.module).extended(name)?;
+    let item = idx.q.pool.alloc_item(item)?;
+
+    let location = Location::new(idx.source_id, ast.descriptive_span());
+    let visibility = ast_to_visibility(&ast.visibility)?;
+
+    let macro_rules = MacroRules::compile(resolve_context!(idx.q), &ast)?;
+
+    idx.q
+        .insert_macro_rules(item, idx.item.module, location, visibility, macro_rules)?;
+    Ok(())
+}
+
 #[instrument_ast(span = ast)]
 fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
     match ast {
@@ -1109,12 +1485,18 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         ast::Item::Impl(item) => {
             item_impl(idx, item)?;
         }
+        ast::Item::Trait(item) => {
+            item_trait(idx, item)?;
+        }
         ast::Item::Mod(item) => {
             item_mod(idx, item)?;
         }
         ast::Item::Const(item) => {
             item_const(idx, item)?;
         }
+        ast::Item::MacroRules(item) => {
+            item_macro_rules(idx, item)?;
+        }
         ast::Item::MacroCall(macro_call) => {
             // Note: There is a preprocessing step involved with items for
             // which the macro must have been expanded to a built-in macro
@@ -1141,7 +1523,11 @@ fn item(idx: &mut Indexer<'_, '_>, ast: ast::Item) -> compile::Result<()> {
         }
         // NB: imports are ignored during indexing.
         ast::Item::Use(item_use) => {
-            if let Some(span) = item_use.attributes.first() {
+            let mut p = attrs::Parser::new(&item_use.attributes)?;
+
+            lint_levels(idx, &mut p, &item_use.attributes, &item_use)?;
+
+            if let Some(span) = p.remaining(&item_use.attributes).next() {
                 return Err(compile::Error::msg(
                     span,
                     "Attributes on uses are not supported",
@@ -1240,9 +1626,17 @@ fn expr_closure(idx: &mut Indexer<'_, '_>, ast: &mut ast::ExprClosure) -> compil
             ast::FnArg::Pat(p) => {
                 pat(idx, p)?;
             }
+            ast::FnArg::Typed(arg) => {
+                pat(idx, &mut arg.pat)?;
+                ty(idx, &mut arg.ty)?;
+            }
         }
     }
 
+    if let Some((_, ast)) = &mut ast.output {
+        ty(idx, ast)?;
+    }
+
     expr(idx, &mut ast.body)?;
 
     let layer = idx.scopes.pop().with_span(&*ast)?;
@@ -22,9 +22,6 @@ use crate::SourceId;
 
 use super::{Guard, Items, Layer, Scopes};
 
-/// Macros are only allowed to expand recursively into other macros 64 times.
-const MAX_MACRO_RECURSION: usize = 64;
-
 pub(crate) struct Indexer<'a, 'arena> {
     /// Query engine.
     pub(crate) q: Query<'a, 'arena>,
@@ -97,7 +94,7 @@ impl Indexer<'_, '_> {
     }
 
     /// Indicate that we've entered an expanded macro context, and ensure that
-    /// we don't blow past [`MAX_MACRO_RECURSION`].
+    /// we don't blow past the configured maximum macro depth.
     ///
     /// This is used when entering expressions which have been expanded from a
     /// macro - cause those expression might in turn be macros themselves.
@@ -107,12 +104,12 @@ impl Indexer<'_, '_> {
     {
         self.macro_depth = self.macro_depth.wrapping_add(1);
 
-        if self.macro_depth >= MAX_MACRO_RECURSION {
+        if self.macro_depth >= self.q.options.max_macro_depth {
             return Err(compile::Error::new(
                 span,
                 ErrorKind::MaxMacroRecursion {
                     depth: self.macro_depth,
-                    max: MAX_MACRO_RECURSION,
+                    max: self.q.options.max_macro_depth,
                 },
             ));
         }
@@ -416,6 +413,26 @@ impl Indexer<'_, '_> {
         compiler.eval_macro::<T>(ast)
     }
 
+    /// Expand a call to a declarative macro defined in a script into a
+    /// sequence of statements.
+    ///
+    /// Returns `None` if the call is not to a declarative macro.
+    pub(super) fn expand_macro_rules_statements(
+        &mut self,
+        ast: &mut ast::MacroCall,
+    ) -> compile::Result<Option<Vec<ast::Stmt>>> {
+        ast.path.id = self.item.id;
+
+        let item = self.q.item_for("macro", self.item.id).with_span(&*ast)?;
+
+        let mut compiler = MacroCompiler {
+            item_meta: item,
+            idx: self,
+        };
+
+        compiler.eval_macro_rules_statements(ast)
+    }
+
     /// Perform an attribute macro expansion.
     pub(super) fn expand_attribute_macro<T>(
         &mut self,
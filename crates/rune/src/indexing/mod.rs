@@ -11,6 +11,7 @@ use crate::compile::meta;
 use crate::compile::{ItemId, ItemMeta};
 use crate::grammar::NodeAt;
 use crate::runtime::Call;
+use crate::SourceId;
 
 use self::indexer::{ast_to_visibility, validate_call};
 pub(crate) use self::indexer::{IndexItem, Indexer};
@@ -108,6 +109,16 @@ pub(crate) struct Struct {
     pub(crate) fields: meta::Fields,
 }
 
+/// A trait declared in a script.
+#[derive(Debug, TryClone)]
+pub(crate) struct Trait {
+    /// The source the trait is declared in.
+    pub(crate) source_id: SourceId,
+    /// The functions of the trait, including their default implementations,
+    /// along with the items they are declared as.
+    pub(crate) functions: Vec<(ItemMeta, ast::TraitFn)>,
+}
+
 #[derive(Debug, TryClone)]
 pub(crate) struct Variant {
     /// Id of of the enum type.
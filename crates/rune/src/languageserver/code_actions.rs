@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use lsp::Url;
+use serde::{Deserialize, Serialize};
+
+use crate::alloc::Vec;
+use crate::diagnostics::Suggestion;
+use crate::Source;
+
+use super::state::StateEncoding;
+
+/// A fix stored in the data of a published diagnostic.
+#[derive(Serialize, Deserialize)]
+struct Fix {
+    title: ::rust_alloc::string::String,
+    edit: lsp::TextEdit,
+}
+
+/// Encode suggestions into data which is attached to a diagnostic, so that
+/// they can be turned into code actions once the client asks for them.
+pub(super) fn data(
+    source: &Source,
+    encoding: &StateEncoding,
+    suggestions: &[Suggestion],
+) -> Result<Option<serde_json::Value>> {
+    if suggestions.is_empty() {
+        return Ok(None);
+    }
+
+    let mut fixes = Vec::new();
+
+    for suggestion in suggestions {
+        fixes.try_push(Fix {
+            title: suggestion.message().into(),
+            edit: lsp::TextEdit {
+                range: encoding.source_range(source, suggestion.span())?,
+                new_text: suggestion.replacement().into(),
+            },
+        })?;
+    }
+
+    Ok(Some(serde_json::to_value(fixes.as_slice())?))
+}
+
+/// Build quick fixes for the diagnostics in a code action request.
+pub(super) fn code_actions(
+    uri: &Url,
+    context: &lsp::CodeActionContext,
+) -> Result<Vec<lsp::CodeActionOrCommand>> {
+    let mut output = Vec::new();
+
+    if let Some(only) = &context.only {
+        if !only.contains(&lsp::CodeActionKind::QUICKFIX) {
+            return Ok(output);
+        }
+    }
+
+    for diagnostic in &context.diagnostics {
+        let Some(data) = &diagnostic.data else {
+            continue;
+        };
+
+        let Ok(fixes) = ::rust_alloc::vec::Vec::<Fix>::deserialize(data) else {
+            continue;
+        };
+
+        let is_preferred = fixes.len() == 1;
+
+        for fix in fixes {
+            let mut changes = HashMap::new();
+            changes.insert(uri.clone(), vec![fix.edit]);
+
+            output.try_push(lsp::CodeActionOrCommand::CodeAction(lsp::CodeAction {
+                title: fix.title,
+                kind: Some(lsp::CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic.clone()]),
+                edit: Some(lsp::WorkspaceEdit {
+                    changes: Some(changes),
+                    ..Default::default()
+                }),
+                is_preferred: Some(is_preferred),
+                ..Default::default()
+            }))?;
+        }
+    }
+
+    Ok(output)
+}
@@ -1,11 +1,14 @@
 use core::fmt;
+use core::pin::Pin;
 
+use ::rust_alloc::boxed::Box;
 use ::rust_alloc::sync::Arc;
 
 use anyhow::{anyhow, bail, Result};
 use tokio::io;
 use tokio::io::{
-    AsyncBufRead, AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufReader,
+    AsyncBufRead, AsyncBufReadExt as _, AsyncRead, AsyncReadExt as _, AsyncWrite,
+    AsyncWriteExt as _, BufReader,
 };
 use tokio::sync::Mutex;
 
@@ -21,13 +24,13 @@ pub(super) struct Frame<'a> {
 /// Input connection.
 pub(super) struct Input {
     buf: ::rust_alloc::vec::Vec<u8>,
-    stdin: BufReader<io::Stdin>,
+    reader: BufReader<Pin<Box<dyn AsyncRead + Send>>>,
 }
 
 impl Input {
     /// Get the next input frame.
     pub(super) async fn next(&mut self) -> Result<Option<Frame<'_>>> {
-        let headers = match Headers::read(&mut self.buf, &mut self.stdin).await? {
+        let headers = match Headers::read(&mut self.buf, &mut self.reader).await? {
             Some(headers) => headers,
             None => return Ok(None),
         };
@@ -40,7 +43,7 @@ impl Input {
         };
 
         self.buf.resize(length, 0u8);
-        self.stdin.read_exact(&mut self.buf[..]).await?;
+        self.reader.read_exact(&mut self.buf[..]).await?;
         Ok(Some(Frame { content: &self.buf }))
     }
 }
@@ -48,7 +51,7 @@ impl Input {
 /// Output connection.
 #[derive(Clone)]
 pub(super) struct Output {
-    stdout: Arc<Mutex<io::Stdout>>,
+    writer: Arc<Mutex<Pin<Box<dyn AsyncWrite + Send>>>>,
 }
 
 impl Output {
@@ -148,28 +151,34 @@ impl Output {
         write!(m, "\r\n")?;
         m.append(bytes);
 
-        let mut stdout = self.stdout.lock().await;
-        stdout.write_all(&m).await?;
-        stdout.flush().await?;
+        let mut writer = self.writer.lock().await;
+        writer.write_all(&m).await?;
+        writer.flush().await?;
         Ok(())
     }
 }
 
 /// Setup a stdin/stdout connection.
 pub(super) fn stdio() -> Result<(Input, Output)> {
-    let stdin = io::stdin();
-    let stdout = io::stdout();
+    Ok(stream(io::stdin(), io::stdout()))
+}
 
+/// Setup a connection over the given reader and writer.
+pub(super) fn stream<R, W>(reader: R, writer: W) -> (Input, Output)
+where
+    R: 'static + Send + AsyncRead,
+    W: 'static + Send + AsyncWrite,
+{
     let input = Input {
         buf: ::rust_alloc::vec::Vec::new(),
-        stdin: BufReader::new(stdin),
+        reader: BufReader::new(Box::pin(reader)),
     };
 
     let output = Output {
-        stdout: Arc::new(Mutex::new(stdout)),
+        writer: Arc::new(Mutex::new(Box::pin(writer))),
     };
 
-    Ok((input, output))
+    (input, output)
 }
 
 #[derive(Debug)]
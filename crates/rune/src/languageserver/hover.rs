@@ -0,0 +1,211 @@
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::String;
+use crate::compile::meta;
+use crate::doc::markdown;
+use crate::{Context, Hash};
+
+use super::state::{Definition, DefinitionKind, ServerSource, Symbol};
+
+/// Build hover contents for a resolved symbol.
+pub(super) fn hover_for_symbol(
+    context: &Context,
+    source: &ServerSource,
+    symbol: &Symbol,
+) -> Result<Option<String>> {
+    let mut out = String::new();
+
+    out.try_push_str("```rune\n")?;
+
+    if !write_signature(&mut out, context, source, symbol)? {
+        return Ok(None);
+    }
+
+    out.try_push_str("\n```\n")?;
+
+    let mut docs = String::new();
+    let mut deprecated = None;
+
+    if symbol.context {
+        if let Some(meta) = context.lookup_meta_by_hash(symbol.hash).next() {
+            for line in meta.docs.lines() {
+                docs.try_push_str(line)?;
+                docs.try_push('\n')?;
+            }
+
+            deprecated = meta.deprecated.as_deref();
+        }
+    } else if let Some(data) = source.get_docs_by_hash(symbol.hash) {
+        for line in &data.docs {
+            docs.try_push_str(line)?;
+            docs.try_push('\n')?;
+        }
+    }
+
+    if let Some(deprecated) = deprecated {
+        write!(out, "\n**Deprecated:** {deprecated}\n")?;
+    }
+
+    if !docs.trim().is_empty() {
+        out.try_push_str("\n---\n\n")?;
+        markdown::push_markdown(&mut out, &docs)?;
+    }
+
+    Ok(Some(out))
+}
+
+/// Build hover contents for a definition which doesn't have an associated
+/// symbol, such as a local variable.
+pub(super) fn hover_for_definition(
+    source: &ServerSource,
+    definition: &Definition,
+) -> Result<Option<String>> {
+    let DefinitionKind::Local = definition.kind else {
+        return Ok(None);
+    };
+
+    let Some(name) = source.definition_source_text(definition) else {
+        return Ok(None);
+    };
+
+    let mut out = String::new();
+    write!(out, "```rune\nlet {name}\n```\n")?;
+    Ok(Some(out))
+}
+
+/// Write the signature of a symbol, returns `false` if the symbol has no
+/// signature that can be shown.
+fn write_signature(
+    out: &mut String,
+    context: &Context,
+    source: &ServerSource,
+    symbol: &Symbol,
+) -> Result<bool> {
+    let item = symbol.item.try_to_string()?;
+    let item = item.trim_start_matches("::");
+
+    match &symbol.kind {
+        meta::Kind::Type { .. } => {
+            write!(out, "type {item}")?;
+        }
+        meta::Kind::Struct { fields, .. } => {
+            write!(out, "struct {item}")?;
+            write_fields(out, fields)?;
+        }
+        meta::Kind::Variant { fields, .. } => {
+            write!(out, "{item}")?;
+            write_fields(out, fields)?;
+        }
+        meta::Kind::Enum { .. } => {
+            write!(out, "enum {item}")?;
+        }
+        meta::Kind::Macro | meta::Kind::AttributeMacro => {
+            write!(out, "macro {item}")?;
+        }
+        meta::Kind::Function { signature, .. } => {
+            if signature.is_async {
+                out.try_push_str("async ")?;
+            }
+
+            write!(out, "fn {item}(")?;
+
+            if let Some(arguments) = &signature.arguments {
+                let mut it = arguments.iter().peekable();
+
+                while let Some(argument) = it.next() {
+                    write!(out, "{}", argument.name)?;
+
+                    if !argument.name.is_self() {
+                        if let Some(ty) = type_name(context, source, argument.base) {
+                            write!(out, ": {ty}")?;
+                        }
+                    }
+
+                    if it.peek().is_some() {
+                        out.try_push_str(", ")?;
+                    }
+                }
+            }
+
+            out.try_push(')')?;
+
+            if let Some(ty) = type_name(context, source, signature.return_type.base) {
+                write!(out, " -> {ty}")?;
+            }
+        }
+        meta::Kind::Const => {
+            write!(out, "const {item}")?;
+        }
+        meta::Kind::ConstFn => {
+            write!(out, "const fn {item}")?;
+        }
+        meta::Kind::Import(..) | meta::Kind::Alias(..) => {
+            write!(out, "use {item}")?;
+        }
+        meta::Kind::Module => {
+            write!(out, "mod {item}")?;
+        }
+        meta::Kind::Trait => {
+            write!(out, "trait {item}")?;
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+fn write_fields(out: &mut String, fields: &meta::Fields) -> Result<()> {
+    match fields {
+        meta::Fields::Named(named) => {
+            out.try_push_str(" { ")?;
+
+            let mut it = named.fields.iter().peekable();
+
+            while let Some(field) = it.next() {
+                out.try_push_str(&field.name)?;
+
+                if it.peek().is_some() {
+                    out.try_push_str(", ")?;
+                }
+            }
+
+            out.try_push_str(" }")?;
+        }
+        meta::Fields::Unnamed(n) => {
+            out.try_push('(')?;
+
+            for n in 0..*n {
+                if n > 0 {
+                    out.try_push_str(", ")?;
+                }
+
+                write!(out, "_{n}")?;
+            }
+
+            out.try_push(')')?;
+        }
+        meta::Fields::Empty => {}
+    }
+
+    Ok(())
+}
+
+/// Look up the name of a type by its hash, either from the context or from
+/// the items declared in the build.
+pub(super) fn type_name(context: &Context, source: &ServerSource, hash: Hash) -> Option<String> {
+    let hash = hash.as_non_empty()?;
+
+    let item = match context
+        .lookup_meta_by_hash(hash)
+        .next()
+        .and_then(|meta| meta.item.as_deref())
+    {
+        Some(item) => item,
+        None => &source.get_docs_by_hash(hash)?.item,
+    };
+
+    let item = item.try_to_string().ok()?;
+    item.trim_start_matches("::").try_to_owned().ok()
+}
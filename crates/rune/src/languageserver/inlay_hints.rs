@@ -0,0 +1,168 @@
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::{String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::meta;
+use crate::parse::Lexer;
+use crate::{Source, SourceId};
+
+use super::state::{StateEncoding, Workspace};
+
+/// Build inlay hints for the given url inside of the given range.
+///
+/// This shows parameter names at the call sites of statically resolved
+/// functions, and the variables captured by closures.
+pub(super) fn inlay_hints(
+    workspace: &Workspace,
+    encoding: &StateEncoding,
+    url: &Url,
+    range: lsp::Range,
+) -> Result<Option<Vec<lsp::InlayHint>>> {
+    let Some(indexed) = workspace.index(url) else {
+        return Ok(None);
+    };
+
+    let Some(source) = indexed.source() else {
+        return Ok(None);
+    };
+
+    let tokens = lex(source)?;
+    let mut hints = Vec::new();
+
+    for (span, symbol) in &indexed.index.symbols {
+        let meta::Kind::Function { signature, .. } = &symbol.kind else {
+            continue;
+        };
+
+        let Some(parameters) = &signature.arguments else {
+            continue;
+        };
+
+        let n = tokens.partition_point(|(_, token)| token.start < span.end);
+
+        for (parameter, argument) in parameters.iter().zip(call_arguments(&tokens[n..])?) {
+            let meta::DocName::Name(name) = &parameter.name else {
+                continue;
+            };
+
+            // Hints are noise if the argument already says what it is.
+            if parameter.name.is_self()
+                || name.starts_with('_')
+                || source.get(argument.range()) == Some(name.as_ref())
+            {
+                continue;
+            }
+
+            let mut label = String::new();
+            write!(label, "{name}:")?;
+
+            hints.try_push((argument.start, Some(lsp::InlayHintKind::PARAMETER), label))?;
+        }
+    }
+
+    for (span, captures) in &indexed.index.captures {
+        let mut label = String::new();
+        label.try_push_str("captures ")?;
+
+        let mut it = captures.iter().peekable();
+
+        while let Some(name) = it.next() {
+            label.try_push_str(name)?;
+
+            if it.peek().is_some() {
+                label.try_push_str(", ")?;
+            }
+        }
+
+        hints.try_push((span.start, None, label))?;
+    }
+
+    hints.sort_by_key(|&(position, ..)| position);
+
+    let mut output = Vec::new();
+
+    for (position, kind, label) in hints {
+        let position = encoding.source_position(source, position.into_usize())?;
+
+        if position < range.start || position > range.end {
+            continue;
+        }
+
+        output.try_push(lsp::InlayHint {
+            position,
+            label: lsp::InlayHintLabel::String(label.into_std()),
+            kind,
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(true),
+            data: None,
+        })?;
+    }
+
+    Ok(Some(output))
+}
+
+/// Find the spans of the arguments of a call, where the given tokens start
+/// right after the called path.
+fn call_arguments(tokens: &[(ast::Kind, Span)]) -> Result<Vec<Span>> {
+    let mut output = Vec::new();
+
+    let mut it = tokens.iter();
+
+    let Some((ast::Kind::Open(ast::Delimiter::Parenthesis), _)) = it.next() else {
+        return Ok(output);
+    };
+
+    let mut depth = 0usize;
+    let mut current = None::<Span>;
+
+    for &(kind, span) in it {
+        match kind {
+            ast::Kind::Close(..) if depth == 0 => break,
+            ast::Kind::SemiColon if depth == 0 => return Ok(Vec::new()),
+            ast::Kind::Comma if depth == 0 => {
+                if let Some(argument) = current.take() {
+                    output.try_push(argument)?;
+                }
+
+                continue;
+            }
+            ast::Kind::Open(..) => depth += 1,
+            ast::Kind::Close(..) => depth -= 1,
+            _ => {}
+        }
+
+        current = Some(match current {
+            Some(current) => current.join(span),
+            None => span,
+        });
+    }
+
+    if let Some(argument) = current {
+        output.try_push(argument)?;
+    }
+
+    Ok(output)
+}
+
+/// Lex the given source, skipping whitespace and comments.
+fn lex(source: &Source) -> Result<Vec<(ast::Kind, Span)>> {
+    let mut output = Vec::new();
+    let mut lexer = Lexer::new(source.as_str(), SourceId::EMPTY, false);
+
+    while let Ok(Some(token)) = lexer.next() {
+        if matches!(
+            token.kind,
+            ast::Kind::Whitespace | ast::Kind::Comment | ast::Kind::MultilineComment(..)
+        ) {
+            continue;
+        }
+
+        output.try_push((token.kind, token.span))?;
+    }
+
+    Ok(output)
+}
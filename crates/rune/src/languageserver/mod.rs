@@ -5,11 +5,18 @@
 #[cfg(test)]
 mod tests;
 
+mod code_actions;
 mod completion;
 mod connection;
 pub mod envelope;
 mod fs;
+mod hover;
+mod inlay_hints;
+mod references;
+mod semantic_tokens;
+mod signature_help;
 mod state;
+mod symbols;
 mod url;
 
 use lsp::notification::Notification;
@@ -18,7 +25,7 @@ use serde::Deserialize;
 use tokio::sync::Notify;
 
 use crate::alloc::String;
-use crate::languageserver::connection::stdio;
+use crate::languageserver::connection::{stdio, Input, Output};
 use crate::languageserver::envelope::Code;
 use crate::languageserver::state::State;
 use crate::support::Result;
@@ -34,8 +41,13 @@ enum Language {
 
 /// Run a language server with the given options.
 pub async fn run(context: Context, options: Options) -> Result<()> {
-    let (mut input, output) = stdio()?;
+    let (input, output) = stdio()?;
+    serve(input, output, context, options).await
+}
 
+/// Serve requests from the given connection until it's closed or the server
+/// is shut down.
+async fn serve(mut input: Input, output: Output, context: Context, options: Options) -> Result<()> {
     let rebuild_notify = Notify::new();
 
     let rebuild = rebuild_notify.notified();
@@ -105,6 +117,17 @@ pub async fn run(context: Context, options: Options) -> Result<()> {
                     req(lsp::request::Shutdown, shutdown),
                     req(lsp::request::GotoDefinition, goto_definition),
                     req(lsp::request::Completion, completion),
+                    req(lsp::request::HoverRequest, hover),
+                    req(lsp::request::References, references),
+                    req(lsp::request::PrepareRenameRequest, prepare_rename),
+                    req(lsp::request::Rename, rename),
+                    req(lsp::request::SignatureHelpRequest, signature_help),
+                    req(lsp::request::DocumentSymbolRequest, document_symbol),
+                    req(lsp::request::WorkspaceSymbolRequest, workspace_symbol),
+                    req(lsp::request::SemanticTokensFullRequest, semantic_tokens_full),
+                    req(lsp::request::CodeActionRequest, code_action),
+                    req(lsp::request::InlayHintRequest, inlay_hint),
+                    req(lsp::request::SemanticTokensRangeRequest, semantic_tokens_range),
                     req(lsp::request::Formatting, formatting),
                     req(lsp::request::RangeFormatting, range_formatting),
                     notif(lsp::notification::DidOpenTextDocument, did_open_text_document),
@@ -171,6 +194,14 @@ async fn initialize(
             lsp::TextDocumentSyncKind::INCREMENTAL,
         )),
         definition_provider: Some(lsp::OneOf::Left(true)),
+        hover_provider: Some(lsp::HoverProviderCapability::Simple(true)),
+        references_provider: Some(lsp::OneOf::Left(true)),
+        rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
         completion_provider: Some(lsp::CompletionOptions {
             all_commit_characters: None,
             resolve_provider: Some(false),
@@ -182,6 +213,37 @@ async fn initialize(
                 label_details_support: Some(true),
             }),
         }),
+        document_symbol_provider: Some(lsp::OneOf::Left(true)),
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
+        signature_help_provider: Some(lsp::SignatureHelpOptions {
+            trigger_characters: Some(vec!["(".into(), ",".into()]),
+            retrigger_characters: None,
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        }),
+        semantic_tokens_provider: Some(
+            lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                lsp::SemanticTokensOptions {
+                    work_done_progress_options: lsp::WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                    legend: semantic_tokens::legend(),
+                    range: Some(true),
+                    full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                },
+            ),
+        ),
+        code_action_provider: Some(lsp::CodeActionProviderCapability::Options(
+            lsp::CodeActionOptions {
+                code_action_kinds: Some(vec![lsp::CodeActionKind::QUICKFIX]),
+                work_done_progress_options: lsp::WorkDoneProgressOptions {
+                    work_done_progress: None,
+                },
+                resolve_provider: Some(false),
+            },
+        )),
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
         document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
         ..Default::default()
@@ -247,6 +309,146 @@ async fn goto_definition(
     Ok(position.map(lsp::GotoDefinitionResponse::Scalar))
 }
 
+/// Handle hover request.
+async fn hover(s: &mut State<'_>, params: lsp::HoverParams) -> Result<Option<lsp::Hover>> {
+    s.ensure_fresh().await?;
+    s.hover(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle references request.
+async fn references(
+    s: &mut State<'_>,
+    params: lsp::ReferenceParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::Location>>> {
+    s.ensure_fresh().await?;
+
+    let locations = s.references(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        params.context.include_declaration,
+    )?;
+
+    Ok(locations.map(|locations| locations.into_std()))
+}
+
+/// Handle prepare rename request.
+async fn prepare_rename(
+    s: &mut State<'_>,
+    params: lsp::TextDocumentPositionParams,
+) -> Result<Option<lsp::PrepareRenameResponse>> {
+    s.ensure_fresh().await?;
+    s.prepare_rename(&params.text_document.uri, params.position)
+}
+
+/// Handle rename request.
+async fn rename(
+    s: &mut State<'_>,
+    params: lsp::RenameParams,
+) -> Result<Option<lsp::WorkspaceEdit>> {
+    s.ensure_fresh().await?;
+    s.rename(
+        &params.text_document_position.text_document.uri,
+        params.text_document_position.position,
+        &params.new_name,
+    )
+}
+
+/// Handle signature help request.
+async fn signature_help(
+    s: &mut State<'_>,
+    params: lsp::SignatureHelpParams,
+) -> Result<Option<lsp::SignatureHelp>> {
+    s.signature_help(
+        &params.text_document_position_params.text_document.uri,
+        params.text_document_position_params.position,
+    )
+}
+
+/// Handle document symbol request.
+async fn document_symbol(
+    s: &mut State<'_>,
+    params: lsp::DocumentSymbolParams,
+) -> Result<Option<lsp::DocumentSymbolResponse>> {
+    s.ensure_fresh().await?;
+
+    let symbols = s.document_symbols(&params.text_document.uri)?;
+    Ok(symbols.map(|symbols| lsp::DocumentSymbolResponse::Nested(symbols.into_std())))
+}
+
+/// Handle workspace symbol request.
+async fn workspace_symbol(
+    s: &mut State<'_>,
+    params: lsp::WorkspaceSymbolParams,
+) -> Result<Option<lsp::WorkspaceSymbolResponse>> {
+    s.ensure_fresh().await?;
+
+    let symbols = s.workspace_symbols(&params.query)?;
+    Ok(Some(lsp::WorkspaceSymbolResponse::Nested(
+        symbols.into_std(),
+    )))
+}
+
+/// Handle full semantic tokens request.
+async fn semantic_tokens_full(
+    s: &mut State<'_>,
+    params: lsp::SemanticTokensParams,
+) -> Result<Option<lsp::SemanticTokensResult>> {
+    s.ensure_fresh().await?;
+
+    let Some(data) = s.semantic_tokens(&params.text_document.uri, None)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(lsp::SemanticTokensResult::Tokens(
+        lsp::SemanticTokens {
+            result_id: None,
+            data: data.into_std(),
+        },
+    )))
+}
+
+/// Handle range semantic tokens request.
+async fn semantic_tokens_range(
+    s: &mut State<'_>,
+    params: lsp::SemanticTokensRangeParams,
+) -> Result<Option<lsp::SemanticTokensRangeResult>> {
+    s.ensure_fresh().await?;
+
+    let Some(data) = s.semantic_tokens(&params.text_document.uri, Some(params.range))? else {
+        return Ok(None);
+    };
+
+    Ok(Some(lsp::SemanticTokensRangeResult::Tokens(
+        lsp::SemanticTokens {
+            result_id: None,
+            data: data.into_std(),
+        },
+    )))
+}
+
+/// Handle code action request.
+async fn code_action(
+    s: &mut State<'_>,
+    params: lsp::CodeActionParams,
+) -> Result<Option<lsp::CodeActionResponse>> {
+    let actions = s.code_actions(&params.text_document.uri, &params.context)?;
+    Ok(Some(actions.into_std()))
+}
+
+/// Handle inlay hint request.
+async fn inlay_hint(
+    s: &mut State<'_>,
+    params: lsp::InlayHintParams,
+) -> Result<Option<::rust_alloc::vec::Vec<lsp::InlayHint>>> {
+    s.ensure_fresh().await?;
+
+    let hints = s.inlay_hints(&params.text_document.uri, params.range)?;
+    Ok(hints.map(|hints| hints.into_std()))
+}
+
 /// Handle initialized notification.
 async fn completion(
     state: &mut State<'_>,
@@ -0,0 +1,447 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::{Box, Vec};
+use crate::ast::{self, Span};
+use crate::item::ComponentRef;
+use crate::parse::Lexer;
+use crate::{Hash, Item, Source, SourceId};
+
+use super::state::{
+    find_at, DefinitionKind, DefinitionSource, FieldKind, StateEncoding, Symbol, Workspace,
+};
+
+/// Something which can be referenced.
+pub(super) enum Target {
+    /// An item with the given hash.
+    Item {
+        /// If the item comes from the context.
+        context: bool,
+        /// The hash of the item.
+        hash: Hash,
+        /// The name of the item, as it's written in sources.
+        name: Box<str>,
+    },
+    /// A local variable declared in the given source.
+    Local {
+        /// The url of the source the local is declared in.
+        url: Url,
+        /// The span of the declared variable.
+        span: Span,
+    },
+    /// A named field of the struct or variant with the given hash.
+    ///
+    /// Field accesses like `value.field` can't be resolved to the type they
+    /// belong to, so they only refer to the field if no other type declares a
+    /// field with the same name.
+    Field {
+        /// The type hash of the struct or variant the field belongs to.
+        hash: Hash,
+        /// The name of the field.
+        name: Box<str>,
+    },
+}
+
+/// A reference to a target.
+pub(super) struct Reference {
+    /// The location of the reference.
+    pub(super) location: lsp::Location,
+    /// The name of the field, if the reference is a shorthand field like the
+    /// `x` in `Point { x }` which refers to both a field and a variable.
+    pub(super) shorthand: Option<Box<str>>,
+}
+
+/// Find the target at the given offset, together with the span of the
+/// identifier that refers to it.
+pub(super) fn find_target(
+    workspace: &Workspace,
+    url: &Url,
+    offset: usize,
+) -> Result<Option<(Target, Span)>> {
+    let Some(indexed) = workspace.index(url) else {
+        return Ok(None);
+    };
+
+    let Some(source) = indexed.source() else {
+        return Ok(None);
+    };
+
+    let span = Span::point(offset);
+
+    // Shorthand fields are also variables, which are looked up below.
+    if let Some((found, field)) = find_at(&indexed.index.fields, span) {
+        if field.kind != FieldKind::Shorthand {
+            let hash = match field.hash {
+                Some(hash) => Some(hash),
+                None => field_owner(workspace, &field.name),
+            };
+
+            // An access to a field which is declared by several types is
+            // ambiguous.
+            let Some(hash) = hash else {
+                return Ok(None);
+            };
+
+            let target = Target::Field {
+                hash,
+                name: field.name.as_str().try_into()?,
+            };
+
+            return Ok(Some((target, found)));
+        }
+    }
+
+    if let Some((found, symbol)) = find_at(&indexed.index.symbols, span) {
+        let Some(target) = item_target(symbol)? else {
+            return Ok(None);
+        };
+
+        let Some(name) = target_name(&target) else {
+            return Ok(None);
+        };
+
+        let Some(ident) = idents(source, found, name)?.last().copied() else {
+            return Ok(None);
+        };
+
+        if !contains(ident, offset) {
+            return Ok(None);
+        }
+
+        return Ok(Some((target, ident)));
+    }
+
+    if let Some((found, definition)) = find_at(&indexed.index.definitions, span) {
+        if let DefinitionKind::Local = definition.kind {
+            let target = Target::Local {
+                url: url.clone(),
+                span: definition.source.span(),
+            };
+
+            return Ok(Some((target, found)));
+        }
+    }
+
+    // Look for the declaration of an item.
+    for (_, other) in workspace.indexes() {
+        for (key, definition) in &other.index.definitions {
+            let DefinitionSource::SourceMeta(meta) = &definition.source else {
+                continue;
+            };
+
+            if !contains(meta.location.span, offset) || !is_url(meta.path.as_deref(), url) {
+                continue;
+            }
+
+            let Some(symbol) = other.index.symbols.get(key) else {
+                continue;
+            };
+
+            let Some(source) = other.sources.get(meta.location.source_id) else {
+                continue;
+            };
+
+            let Some(target) = item_target(symbol)? else {
+                continue;
+            };
+
+            let Some(name) = target_name(&target) else {
+                continue;
+            };
+
+            let Some(ident) = idents(source, meta.location.span, name)?.first().copied() else {
+                continue;
+            };
+
+            if contains(ident, offset) {
+                return Ok(Some((target, ident)));
+            }
+        }
+    }
+
+    // Look for the declaration of a local variable.
+    for definition in indexed.index.definitions.values() {
+        let DefinitionKind::Local = definition.kind else {
+            continue;
+        };
+
+        let span = definition.source.span();
+
+        if contains(span, offset) {
+            let target = Target::Local {
+                url: url.clone(),
+                span,
+            };
+
+            return Ok(Some((target, span)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Find all references to the given target across the workspace.
+pub(super) fn find_references(
+    workspace: &Workspace,
+    encoding: &StateEncoding,
+    target: &Target,
+    include_declaration: bool,
+) -> Result<Vec<Reference>> {
+    let mut output = Output {
+        encoding,
+        seen: HashSet::new(),
+        references: Vec::new(),
+    };
+
+    match target {
+        Target::Item { hash, name, .. } => {
+            for (url, indexed) in workspace.indexes() {
+                let Some(source) = indexed.source() else {
+                    continue;
+                };
+
+                for (span, symbol) in &indexed.index.symbols {
+                    if symbol.hash != *hash {
+                        continue;
+                    }
+
+                    if let Some(ident) = idents(source, *span, name)?.last() {
+                        output.push(url, source, *ident, None)?;
+                    }
+
+                    if !include_declaration {
+                        continue;
+                    }
+
+                    let Some(definition) = indexed.index.definitions.get(span) else {
+                        continue;
+                    };
+
+                    let DefinitionSource::SourceMeta(meta) = &definition.source else {
+                        continue;
+                    };
+
+                    let Some(path) = meta.path.as_deref() else {
+                        continue;
+                    };
+
+                    let Ok(url) = crate::languageserver::url::from_file_path(path) else {
+                        continue;
+                    };
+
+                    let Some(source) = indexed.sources.get(meta.location.source_id) else {
+                        continue;
+                    };
+
+                    if let Some(ident) = idents(source, meta.location.span, name)?.first() {
+                        output.push(&url, source, *ident, None)?;
+                    }
+                }
+            }
+        }
+        Target::Local { url, span } => {
+            let Some(indexed) = workspace.index(url) else {
+                return Ok(output.references);
+            };
+
+            let Some(source) = indexed.source() else {
+                return Ok(output.references);
+            };
+
+            let shorthand = |span: &Span| {
+                let field = indexed.index.fields.get(span)?;
+
+                if field.kind != FieldKind::Shorthand {
+                    return None;
+                }
+
+                Some(field.name.as_str())
+            };
+
+            if include_declaration {
+                output.push(url, source, *span, shorthand(span))?;
+            }
+
+            for (use_span, definition) in &indexed.index.definitions {
+                if let DefinitionKind::Local = definition.kind {
+                    if definition.source.span() == *span {
+                        output.push(url, source, *use_span, shorthand(use_span))?;
+                    }
+                }
+            }
+        }
+        Target::Field { hash, name } => {
+            let accesses = field_owner(workspace, name) == Some(*hash);
+
+            for (url, indexed) in workspace.indexes() {
+                let Some(source) = indexed.source() else {
+                    continue;
+                };
+
+                for (span, field) in &indexed.index.fields {
+                    if field.name.as_str() != &**name {
+                        continue;
+                    }
+
+                    match field.hash {
+                        Some(other) if other == *hash => {}
+                        None if accesses => {}
+                        _ => continue,
+                    }
+
+                    let shorthand = match field.kind {
+                        FieldKind::Declaration if !include_declaration => continue,
+                        FieldKind::Shorthand => Some(field.name.as_str()),
+                        _ => None,
+                    };
+
+                    output.push(url, source, *span, shorthand)?;
+                }
+            }
+        }
+    }
+
+    Ok(output.references)
+}
+
+/// Find the type hash of the only struct or variant which declares a field
+/// with the given name.
+fn field_owner(workspace: &Workspace, name: &str) -> Option<Hash> {
+    let mut owner = None;
+
+    for (_, indexed) in workspace.indexes() {
+        for field in indexed.index.fields.values() {
+            if field.kind != FieldKind::Declaration || field.name.as_str() != name {
+                continue;
+            }
+
+            match (owner, field.hash) {
+                (_, None) => {}
+                (None, Some(hash)) => owner = Some(hash),
+                (Some(owner), Some(hash)) if owner == hash => {}
+                _ => return None,
+            }
+        }
+    }
+
+    owner
+}
+
+/// Get the name of the given target.
+fn target_name(target: &Target) -> Option<&str> {
+    match target {
+        Target::Item { name, .. } => Some(name),
+        Target::Local { .. } | Target::Field { .. } => None,
+    }
+}
+
+/// Test if the given string is a valid identifier.
+pub(super) fn is_ident(name: &str) -> bool {
+    let mut lexer = Lexer::new(name, SourceId::EMPTY, false);
+
+    let Ok(Some(token)) = lexer.next() else {
+        return false;
+    };
+
+    if !matches!(token.kind, ast::Kind::Ident(..)) || token.span.range() != (0..name.len()) {
+        return false;
+    }
+
+    matches!(lexer.next(), Ok(None))
+}
+
+struct Output<'a> {
+    encoding: &'a StateEncoding,
+    seen: HashSet<(Url, Span)>,
+    references: Vec<Reference>,
+}
+
+impl Output<'_> {
+    fn push(
+        &mut self,
+        url: &Url,
+        source: &Source,
+        span: Span,
+        shorthand: Option<&str>,
+    ) -> Result<()> {
+        if !self.seen.insert((url.clone(), span)) {
+            return Ok(());
+        }
+
+        let range = self.encoding.source_range(source, span)?;
+
+        let shorthand = match shorthand {
+            Some(name) => Some(name.try_into()?),
+            None => None,
+        };
+
+        self.references.try_push(Reference {
+            location: lsp::Location {
+                uri: url.clone(),
+                range,
+            },
+            shorthand,
+        })?;
+
+        Ok(())
+    }
+}
+
+fn item_target(symbol: &Symbol) -> Result<Option<Target>> {
+    let Some(name) = item_name(&symbol.item) else {
+        return Ok(None);
+    };
+
+    Ok(Some(Target::Item {
+        context: symbol.context,
+        hash: symbol.hash,
+        name: name.try_into()?,
+    }))
+}
+
+fn item_name(item: &Item) -> Option<&str> {
+    match item.last()? {
+        ComponentRef::Str(name) => Some(name),
+        _ => None,
+    }
+}
+
+fn is_url(path: Option<&std::path::Path>, url: &Url) -> bool {
+    let Some(path) = path else {
+        return false;
+    };
+
+    matches!(crate::languageserver::url::from_file_path(path), Ok(other) if other == *url)
+}
+
+fn contains(span: Span, offset: usize) -> bool {
+    span.start.into_usize() <= offset && offset <= span.end.into_usize()
+}
+
+/// Find the spans of all identifiers matching `name` inside of the given span
+/// of a source.
+fn idents(source: &Source, span: Span, name: &str) -> Result<Vec<Span>> {
+    let mut output = Vec::new();
+
+    let Some(text) = source.get(span.range()) else {
+        return Ok(output);
+    };
+
+    let mut lexer = Lexer::new(text, SourceId::EMPTY, false);
+
+    while let Ok(Some(token)) = lexer.next() {
+        if !matches!(token.kind, ast::Kind::Ident(..)) {
+            continue;
+        }
+
+        if text.get(token.span.range()) == Some(name) {
+            let start = span.start.into_usize();
+            let range = token.span.range();
+            output.try_push(Span::new(start + range.start, start + range.end))?;
+        }
+    }
+
+    Ok(output)
+}
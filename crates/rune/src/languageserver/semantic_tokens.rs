@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::Vec;
+use crate::ast::{self, Span};
+use crate::compile::meta;
+use crate::item::ComponentRef;
+use crate::parse::Lexer;
+use crate::{Source, SourceId};
+
+use super::state::{DefinitionKind, Index, StateEncoding, Symbol, Workspace};
+
+/// The kind of a semantic token.
+///
+/// The discriminant is the index of the token type in the legend.
+#[derive(Clone, Copy)]
+enum TokenType {
+    Namespace,
+    Type,
+    EnumMember,
+    Function,
+    Macro,
+    Parameter,
+    Variable,
+}
+
+/// The token is the declaration of a symbol.
+const DECLARATION: u32 = 1 << 0;
+/// The token refers to something provided by the context.
+const DEFAULT_LIBRARY: u32 = 1 << 1;
+
+/// Construct the legend of semantic tokens produced by the language server.
+pub(super) fn legend() -> lsp::SemanticTokensLegend {
+    lsp::SemanticTokensLegend {
+        token_types: vec![
+            lsp::SemanticTokenType::NAMESPACE,
+            lsp::SemanticTokenType::TYPE,
+            lsp::SemanticTokenType::ENUM_MEMBER,
+            lsp::SemanticTokenType::FUNCTION,
+            lsp::SemanticTokenType::MACRO,
+            lsp::SemanticTokenType::PARAMETER,
+            lsp::SemanticTokenType::VARIABLE,
+        ],
+        token_modifiers: vec![
+            lsp::SemanticTokenModifier::DECLARATION,
+            lsp::SemanticTokenModifier::DEFAULT_LIBRARY,
+        ],
+    }
+}
+
+/// Build semantic tokens for the given url, optionally limited to the given
+/// range.
+pub(super) fn semantic_tokens(
+    workspace: &Workspace,
+    encoding: &StateEncoding,
+    url: &Url,
+    range: Option<lsp::Range>,
+) -> Result<Option<Vec<lsp::SemanticToken>>> {
+    let Some(indexed) = workspace.index(url) else {
+        return Ok(None);
+    };
+
+    let Some(source) = indexed.source() else {
+        return Ok(None);
+    };
+
+    let tokens = collect(source, &indexed.index)?;
+
+    let mut output = Vec::new();
+    let mut line = 0;
+    let mut character = 0;
+
+    for (span, (ty, modifiers)) in tokens {
+        let lsp::Range { start, end } = encoding.source_range(source, span)?;
+
+        if start.line != end.line {
+            continue;
+        }
+
+        if let Some(range) = &range {
+            if end < range.start || start > range.end {
+                continue;
+            }
+        }
+
+        let delta_line = start.line - line;
+
+        let delta_start = if delta_line == 0 {
+            start.character - character
+        } else {
+            start.character
+        };
+
+        output.try_push(lsp::SemanticToken {
+            delta_line,
+            delta_start,
+            length: end.character - start.character,
+            token_type: ty as u32,
+            token_modifiers_bitset: modifiers,
+        })?;
+
+        line = start.line;
+        character = start.character;
+    }
+
+    Ok(Some(output))
+}
+
+/// Collect all tokens of a source ordered by their span.
+fn collect(source: &Source, index: &Index) -> Result<BTreeMap<Span, (TokenType, u32)>> {
+    let mut tokens = BTreeMap::new();
+
+    for (span, symbol) in &index.declarations {
+        let Some(ty) = token_type(&symbol.kind) else {
+            continue;
+        };
+
+        let Some(ComponentRef::Str(name)) = symbol.item.last() else {
+            continue;
+        };
+
+        let found = lex(source, *span)?
+            .into_iter()
+            .find(|&(kind, span)| is_ident(source, kind, span, name));
+
+        if let Some((_, span)) = found {
+            tokens.insert(span, (ty, DECLARATION));
+        }
+    }
+
+    for (span, symbol) in &index.symbols {
+        let Some(ty) = token_type(&symbol.kind) else {
+            continue;
+        };
+
+        let modifiers = if symbol.context { DEFAULT_LIBRARY } else { 0 };
+
+        for (n, span) in path(source, *span, symbol)?.into_iter().enumerate() {
+            let ty = match n {
+                0 => ty,
+                1 if has_type_parent(&symbol.kind) => TokenType::Type,
+                _ => TokenType::Namespace,
+            };
+
+            tokens.entry(span).or_insert((ty, modifiers));
+        }
+    }
+
+    for (span, definition) in &index.definitions {
+        match definition.kind {
+            DefinitionKind::Local => {
+                let declaration = definition.source.span();
+
+                let ty = if index.arguments.iter().any(|a| contains(*a, declaration)) {
+                    TokenType::Parameter
+                } else {
+                    TokenType::Variable
+                };
+
+                if let [(kind, span)] = lex(source, declaration)?[..] {
+                    if matches!(kind, ast::Kind::Ident(..)) {
+                        tokens.insert(span, (ty, DECLARATION));
+                    }
+                }
+
+                if let [(kind, span)] = lex(source, *span)?[..] {
+                    if matches!(kind, ast::Kind::Ident(..)) {
+                        tokens.entry(span).or_insert((ty, 0));
+                    }
+                }
+            }
+            DefinitionKind::Module => {
+                let found = lex(source, *span)?
+                    .into_iter()
+                    .find(|(kind, _)| matches!(kind, ast::Kind::Ident(..)));
+
+                if let Some((_, span)) = found {
+                    tokens.insert(span, (TokenType::Namespace, DECLARATION));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Get the token type of a symbol.
+fn token_type(kind: &meta::Kind) -> Option<TokenType> {
+    let ty = match kind {
+        meta::Kind::Module => TokenType::Namespace,
+        meta::Kind::Type { .. }
+        | meta::Kind::Struct { .. }
+        | meta::Kind::Enum { .. }
+        | meta::Kind::Trait => TokenType::Type,
+        meta::Kind::Variant { .. } => TokenType::EnumMember,
+        meta::Kind::Function { .. } | meta::Kind::ConstFn => TokenType::Function,
+        meta::Kind::Macro | meta::Kind::AttributeMacro => TokenType::Macro,
+        _ => return None,
+    };
+
+    Some(ty)
+}
+
+/// Test if the parent of the given symbol is a type.
+fn has_type_parent(kind: &meta::Kind) -> bool {
+    matches!(
+        kind,
+        meta::Kind::Variant { .. }
+            | meta::Kind::Function {
+                associated: Some(..),
+                ..
+            }
+    )
+}
+
+/// Find the identifiers of the path which refers to the given symbol inside
+/// of a span, starting with the last component.
+fn path(source: &Source, span: Span, symbol: &Symbol) -> Result<Vec<Span>> {
+    let mut output = Vec::new();
+
+    let tokens = lex(source, span)?;
+    let mut names = symbol.item.iter().rev();
+
+    let Some(ComponentRef::Str(name)) = names.next() else {
+        return Ok(output);
+    };
+
+    let Some(mut n) = tokens
+        .iter()
+        .rposition(|&(kind, span)| is_ident(source, kind, span, name))
+    else {
+        return Ok(output);
+    };
+
+    output.try_push(tokens[n].1)?;
+
+    while let (Some(ComponentRef::Str(name)), 2..) = (names.next(), n) {
+        let [(kind, span), (ast::Kind::ColonColon, _), ..] = tokens[n - 2..] else {
+            break;
+        };
+
+        if !is_ident(source, kind, span, name) {
+            break;
+        }
+
+        output.try_push(span)?;
+        n -= 2;
+    }
+
+    Ok(output)
+}
+
+/// Lex the given span of a source.
+fn lex(source: &Source, span: Span) -> Result<Vec<(ast::Kind, Span)>> {
+    let mut output = Vec::new();
+
+    let Some(text) = source.get(span.range()) else {
+        return Ok(output);
+    };
+
+    let mut lexer = Lexer::new(text, SourceId::EMPTY, false);
+    let start = span.start.into_usize();
+
+    while let Ok(Some(token)) = lexer.next() {
+        let range = token.span.range();
+        let span = Span::new(start + range.start, start + range.end);
+        output.try_push((token.kind, span))?;
+    }
+
+    Ok(output)
+}
+
+fn is_ident(source: &Source, kind: ast::Kind, span: Span, name: &str) -> bool {
+    matches!(kind, ast::Kind::Ident(..)) && source.get(span.range()) == Some(name)
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
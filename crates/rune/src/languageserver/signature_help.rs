@@ -0,0 +1,375 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::ast;
+use crate::compile::meta;
+use crate::doc::markdown;
+use crate::item::ComponentRef;
+use crate::parse::Lexer;
+use crate::runtime::debug::DebugArgs;
+use crate::{Context, Item, SourceId};
+
+use super::hover::type_name;
+use super::state::{ServerSource, StateEncoding, Workspace};
+
+/// A function call which is being typed.
+struct Call<'a> {
+    /// The path of the called function, or the name of the called method.
+    path: Vec<&'a str>,
+    /// If the call is an instance function call.
+    instance: bool,
+    /// The number of arguments preceding the cursor.
+    argument: u32,
+}
+
+/// Build signature help for the call surrounding the end of the given text.
+pub(super) fn signature_help(
+    context: &Context,
+    workspace: &Workspace,
+    source: &ServerSource,
+    encoding: &StateEncoding,
+    text: &str,
+) -> Result<Option<lsp::SignatureHelp>> {
+    let Some(call) = find_call(text)? else {
+        return Ok(None);
+    };
+
+    let mut signatures = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (_, indexed) in workspace.indexes() {
+        for symbol in indexed.index.declarations.values() {
+            if !call.matches(&symbol.item, &symbol.kind) || !seen.insert(symbol.hash) {
+                continue;
+            }
+
+            let meta::Kind::Function { signature, .. } = &symbol.kind else {
+                continue;
+            };
+
+            let mut docs = String::new();
+
+            if let Some(data) = source.get_docs_by_hash(symbol.hash) {
+                for line in &data.docs {
+                    docs.try_push_str(line)?;
+                    docs.try_push('\n')?;
+                }
+            }
+
+            let signature = from_signature(
+                context,
+                source,
+                encoding,
+                &call,
+                &symbol.item,
+                signature,
+                &docs,
+            )?;
+
+            signatures.try_push(signature)?;
+        }
+    }
+
+    if let Some(debug_info) = source.unit.as_ref().and_then(|unit| unit.debug_info()) {
+        for (hash, function) in debug_info.functions.iter() {
+            if call.instance || !call.matches_path(&function.path) || !seen.insert(*hash) {
+                continue;
+            }
+
+            let mut label = Label::new(encoding, &function.path, false)?;
+
+            match &function.args {
+                DebugArgs::EmptyArgs => {}
+                DebugArgs::TupleArgs(n) => {
+                    for n in 0..*n {
+                        let mut name = String::new();
+                        write!(name, "_{n}")?;
+                        label.parameter(&name, None)?;
+                    }
+                }
+                DebugArgs::Named(names) => {
+                    for name in names.iter() {
+                        label.parameter(name, None)?;
+                    }
+                }
+            }
+
+            let mut information = label.build(None, "")?;
+            information.active_parameter = Some(call.argument);
+            signatures.try_push(information)?;
+        }
+    }
+
+    for (meta, signature) in context.iter_functions() {
+        let Some(item) = &meta.item else {
+            continue;
+        };
+
+        if !call.matches(item, &meta.kind) || !seen.insert(meta.hash) {
+            continue;
+        }
+
+        let docs = meta.docs.lines().join("\n");
+
+        let signature = from_signature(context, source, encoding, &call, item, signature, &docs)?;
+
+        signatures.try_push(signature)?;
+    }
+
+    if signatures.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(lsp::SignatureHelp {
+        signatures: signatures.into_std(),
+        active_signature: Some(0),
+        active_parameter: None,
+    }))
+}
+
+/// Build signature information out of function metadata.
+fn from_signature(
+    context: &Context,
+    source: &ServerSource,
+    encoding: &StateEncoding,
+    call: &Call<'_>,
+    item: &Item,
+    signature: &meta::Signature,
+    docs: &str,
+) -> Result<lsp::SignatureInformation> {
+    let mut label = Label::new(encoding, item, signature.is_async)?;
+
+    let mut receiver = false;
+
+    if let Some(arguments) = &signature.arguments {
+        for (n, argument) in arguments.iter().enumerate() {
+            let name = argument.name.try_to_string()?;
+
+            if argument.name.is_self() {
+                receiver |= n == 0;
+                label.parameter(&name, None)?;
+            } else {
+                let ty = type_name(context, source, argument.base);
+                label.parameter(&name, ty.as_deref())?;
+            }
+        }
+    }
+
+    let return_type = type_name(context, source, signature.return_type.base);
+
+    // The receiver of an instance function call is the first parameter.
+    let active = if call.instance && receiver {
+        call.argument + 1
+    } else {
+        call.argument
+    };
+
+    let mut information = label.build(return_type.as_deref(), docs)?;
+    information.active_parameter = Some(active);
+    Ok(information)
+}
+
+impl Call<'_> {
+    /// Test if the call refers to the given item.
+    fn matches(&self, item: &Item, kind: &meta::Kind) -> bool {
+        let meta::Kind::Function { associated, .. } = kind else {
+            return false;
+        };
+
+        match associated {
+            Some(meta::AssociatedKind::Instance(name)) if self.instance => {
+                matches!(self.path[..], [method] if method == name.as_ref())
+            }
+            _ if self.instance => false,
+            _ => self.matches_path(item),
+        }
+    }
+
+    /// Test if the path of the call is a suffix of the given item.
+    fn matches_path(&self, item: &Item) -> bool {
+        let mut components = item.iter().rev();
+
+        for name in self.path.iter().rev() {
+            match components.next() {
+                Some(ComponentRef::Str(component) | ComponentRef::Crate(component))
+                    if component == *name => {}
+                _ => return false,
+            }
+        }
+
+        true
+    }
+}
+
+/// A signature label which keeps track of the offsets of its parameters.
+struct Label<'a> {
+    encoding: &'a StateEncoding,
+    label: String,
+    parameters: Vec<lsp::ParameterInformation>,
+}
+
+impl<'a> Label<'a> {
+    fn new(encoding: &'a StateEncoding, item: &Item, is_async: bool) -> Result<Self> {
+        let item = item.try_to_string()?;
+
+        let mut label = String::new();
+
+        if is_async {
+            label.try_push_str("async ")?;
+        }
+
+        write!(label, "fn {}(", item.trim_start_matches("::"))?;
+
+        Ok(Self {
+            encoding,
+            label,
+            parameters: Vec::new(),
+        })
+    }
+
+    /// Add a parameter to the label.
+    fn parameter(&mut self, name: &str, ty: Option<&str>) -> Result<()> {
+        if !self.parameters.is_empty() {
+            self.label.try_push_str(", ")?;
+        }
+
+        let start = self.offset()?;
+        self.label.try_push_str(name)?;
+
+        if let Some(ty) = ty {
+            write!(self.label, ": {ty}")?;
+        }
+
+        let end = self.offset()?;
+
+        self.parameters.try_push(lsp::ParameterInformation {
+            label: lsp::ParameterLabel::LabelOffsets([start, end]),
+            documentation: None,
+        })?;
+
+        Ok(())
+    }
+
+    /// The current offset in the label, in the units of the encoding.
+    fn offset(&self) -> Result<u32> {
+        let offset = match self.encoding {
+            StateEncoding::Utf8 => self.label.len(),
+            StateEncoding::Utf16 => self.label.encode_utf16().count(),
+        };
+
+        Ok(u32::try_from(offset)?)
+    }
+
+    fn build(mut self, return_type: Option<&str>, docs: &str) -> Result<lsp::SignatureInformation> {
+        self.label.try_push(')')?;
+
+        if let Some(ty) = return_type {
+            write!(self.label, " -> {ty}")?;
+        }
+
+        let documentation = if docs.trim().is_empty() {
+            None
+        } else {
+            let mut value = String::new();
+            markdown::push_markdown(&mut value, docs)?;
+
+            Some(lsp::Documentation::MarkupContent(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: value.into_std(),
+            }))
+        };
+
+        Ok(lsp::SignatureInformation {
+            label: self.label.into_std(),
+            documentation,
+            parameters: Some(self.parameters.into_std()),
+            active_parameter: None,
+        })
+    }
+}
+
+/// Find the call whose argument list surrounds the end of the given text.
+fn find_call(text: &str) -> Result<Option<Call<'_>>> {
+    let mut tokens = Vec::new();
+    let mut lexer = Lexer::new(text, SourceId::EMPTY, false);
+
+    while let Ok(Some(token)) = lexer.next() {
+        tokens.try_push(token)?;
+    }
+
+    let mut depth = 0usize;
+    let mut argument = 0;
+
+    let mut it = tokens.iter().rev();
+
+    loop {
+        let Some(token) = it.next() else {
+            return Ok(None);
+        };
+
+        match token.kind {
+            ast::Kind::Close(..) => {
+                depth += 1;
+            }
+            ast::Kind::Open(delimiter) => {
+                if depth > 0 {
+                    depth -= 1;
+                    continue;
+                }
+
+                if delimiter != ast::Delimiter::Parenthesis {
+                    return Ok(None);
+                }
+
+                break;
+            }
+            ast::Kind::Comma if depth == 0 => {
+                argument += 1;
+            }
+            ast::Kind::SemiColon if depth == 0 => {
+                return Ok(None);
+            }
+            _ => {}
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut instance = false;
+
+    while let Some(token) = it.next() {
+        let ast::Kind::Ident(..) = token.kind else {
+            return Ok(None);
+        };
+
+        let Some(name) = text.get(token.span.range()) else {
+            return Ok(None);
+        };
+
+        path.try_push(name)?;
+
+        match it.next().map(|token| token.kind) {
+            Some(ast::Kind::ColonColon) if !instance => {}
+            Some(ast::Kind::Dot) if path.len() == 1 => {
+                instance = true;
+                break;
+            }
+            _ => break,
+        }
+    }
+
+    if path.is_empty() {
+        return Ok(None);
+    }
+
+    path.reverse();
+
+    Ok(Some(Call {
+        path,
+        instance,
+        argument,
+    }))
+}
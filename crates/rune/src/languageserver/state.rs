@@ -1,4 +1,4 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
@@ -15,14 +15,15 @@ use crate::compile::meta;
 use crate::compile::{
     self, CompileVisitor, LinkerError, Located, Location, MetaError, MetaRef, SourceMeta, WithSpan,
 };
-use crate::diagnostics::{Diagnostic, FatalDiagnosticKind};
+use crate::diagnostics::{Diagnostic, FatalDiagnosticKind, Suggestion};
 use crate::doc::VisitorData;
 use crate::item::ComponentRef;
 use crate::languageserver::connection::Output;
+use crate::languageserver::references::Target;
 use crate::languageserver::Language;
 use crate::workspace::{self, WorkspaceError};
 use crate::{self as rune, Diagnostics};
-use crate::{BuildError, Context, Item, Options, Source, SourceId, Sources, Unit};
+use crate::{BuildError, Context, Hash, Item, ItemBuf, Options, Source, SourceId, Sources, Unit};
 
 #[derive(Default)]
 struct Reporter {
@@ -183,6 +184,8 @@ pub(super) struct State<'a> {
     initialized: bool,
     /// Indicate that the server is stopped.
     stopped: bool,
+    /// Indicate that sources have been modified since the last build.
+    stale: bool,
     /// Sources used in the project.
     pub(super) workspace: Workspace,
 }
@@ -203,6 +206,7 @@ impl<'a> State<'a> {
             options,
             initialized: bool::default(),
             stopped: bool::default(),
+            stale: bool::default(),
             workspace: Workspace::default(),
         }
     }
@@ -230,10 +234,23 @@ impl<'a> State<'a> {
     /// Indicate interest in having the project rebuild.
     ///
     /// Sources that have been modified will be marked as dirty.
-    pub(super) fn rebuild_interest(&self) {
+    pub(super) fn rebuild_interest(&mut self) {
+        self.stale = true;
         self.rebuild_notify.notify_one();
     }
 
+    /// Rebuild the project if sources have been modified since the last build.
+    ///
+    /// Requests which map spans in the index back onto sources must call this
+    /// first, since the index otherwise refers to an older version of them.
+    pub(super) async fn ensure_fresh(&mut self) -> Result<()> {
+        if self.stale {
+            self.rebuild().await?;
+        }
+
+        Ok(())
+    }
+
     /// Find definition at the given uri and LSP position.
     pub(super) async fn goto_definition(
         &self,
@@ -246,7 +263,7 @@ impl<'a> State<'a> {
 
         let offset = self.encoding.rope_position(&source.content, position)?;
 
-        let Some(def) = source.find_definition_at(Span::point(offset)) else {
+        let Some((_, def)) = source.find_definition_at(Span::point(offset)) else {
             return Ok(None);
         };
 
@@ -271,6 +288,214 @@ impl<'a> State<'a> {
         Ok(Some(location))
     }
 
+    /// Get hover information at the given uri and LSP position.
+    pub(super) fn hover(&self, uri: &Url, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+        let span = Span::point(offset);
+
+        let (span, value) = if let Some((span, symbol)) = source.find_symbol_at(span) {
+            let Some(value) = super::hover::hover_for_symbol(&self.context, source, symbol)? else {
+                return Ok(None);
+            };
+
+            (span, value)
+        } else if let Some((span, def)) = source.find_definition_at(span) {
+            let Some(value) = super::hover::hover_for_definition(source, def)? else {
+                return Ok(None);
+            };
+
+            (span, value)
+        } else {
+            return Ok(None);
+        };
+
+        let range = match source.build_source() {
+            Some(build_source) => Some(self.encoding.source_range(build_source, span)?),
+            None => None,
+        };
+
+        Ok(Some(lsp::Hover {
+            contents: lsp::HoverContents::Markup(lsp::MarkupContent {
+                kind: lsp::MarkupKind::Markdown,
+                value: value.into_std(),
+            }),
+            range,
+        }))
+    }
+
+    /// Find all references to the symbol at the given uri and LSP position.
+    pub(super) fn references(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Result<Option<Vec<lsp::Location>>> {
+        let Some((target, _)) = self.find_target(uri, position)? else {
+            return Ok(None);
+        };
+
+        let references = super::references::find_references(
+            &self.workspace,
+            &self.encoding,
+            &target,
+            include_declaration,
+        )?;
+
+        let mut locations = Vec::new();
+
+        for reference in references {
+            locations.try_push(reference.location)?;
+        }
+
+        Ok(Some(locations))
+    }
+
+    /// Test if the symbol at the given uri and LSP position can be renamed.
+    pub(super) fn prepare_rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::PrepareRenameResponse>> {
+        let Some((target, span)) = self.find_target(uri, position)? else {
+            return Ok(None);
+        };
+
+        if let Target::Item { context: true, .. } = target {
+            return Ok(None);
+        }
+
+        let Some(source) = self.workspace.index(uri).and_then(|i| i.source()) else {
+            return Ok(None);
+        };
+
+        let range = self.encoding.source_range(source, span)?;
+        Ok(Some(lsp::PrepareRenameResponse::Range(range)))
+    }
+
+    /// Rename the symbol at the given uri and LSP position.
+    pub(super) fn rename(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        if !super::references::is_ident(new_name) {
+            return Ok(None);
+        }
+
+        let Some((target, _)) = self.find_target(uri, position)? else {
+            return Ok(None);
+        };
+
+        if let Target::Item { context: true, .. } = target {
+            return Ok(None);
+        }
+
+        let references =
+            super::references::find_references(&self.workspace, &self.encoding, &target, true)?;
+
+        let mut changes = ::std::collections::HashMap::<_, ::rust_alloc::vec::Vec<_>>::new();
+
+        for reference in references {
+            // Shorthand fields have to be expanded to keep referring to both
+            // the field and the variable.
+            let text = match (&target, reference.shorthand) {
+                (Target::Field { .. }, Some(variable)) => format!("{new_name}: {variable}"),
+                (Target::Local { .. }, Some(field)) => format!("{field}: {new_name}"),
+                _ => new_name.into(),
+            };
+
+            changes
+                .entry(reference.location.uri)
+                .or_default()
+                .push(lsp::TextEdit::new(reference.location.range, text));
+        }
+
+        Ok(Some(lsp::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }))
+    }
+
+    /// Get quick fixes for the diagnostics of a code action request.
+    pub(super) fn code_actions(
+        &self,
+        uri: &Url,
+        context: &lsp::CodeActionContext,
+    ) -> Result<Vec<lsp::CodeActionOrCommand>> {
+        super::code_actions::code_actions(uri, context)
+    }
+
+    /// Get inlay hints for the given uri inside of the given range.
+    pub(super) fn inlay_hints(
+        &self,
+        uri: &Url,
+        range: lsp::Range,
+    ) -> Result<Option<Vec<lsp::InlayHint>>> {
+        super::inlay_hints::inlay_hints(&self.workspace, &self.encoding, uri, range)
+    }
+
+    /// Get semantic tokens for the given uri, optionally limited to the given
+    /// range.
+    pub(super) fn semantic_tokens(
+        &self,
+        uri: &Url,
+        range: Option<lsp::Range>,
+    ) -> Result<Option<Vec<lsp::SemanticToken>>> {
+        super::semantic_tokens::semantic_tokens(&self.workspace, &self.encoding, uri, range)
+    }
+
+    /// Get signature help for the call surrounding the given uri and LSP
+    /// position.
+    pub(super) fn signature_help(
+        &self,
+        uri: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::SignatureHelp>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+
+        let Some(text) = source.content.get_slice(..offset) else {
+            return Ok(None);
+        };
+
+        let text = text.try_to_string()?;
+
+        super::signature_help::signature_help(
+            &self.context,
+            &self.workspace,
+            source,
+            &self.encoding,
+            &text,
+        )
+    }
+
+    /// Get the symbols declared in the given uri.
+    pub(super) fn document_symbols(&self, uri: &Url) -> Result<Option<Vec<lsp::DocumentSymbol>>> {
+        super::symbols::document_symbols(&self.workspace, &self.encoding, uri)
+    }
+
+    /// Search for symbols matching the given query in the workspace.
+    pub(super) fn workspace_symbols(&self, query: &str) -> Result<Vec<lsp::WorkspaceSymbol>> {
+        super::symbols::workspace_symbols(&self.workspace, &self.encoding, query)
+    }
+
+    fn find_target(&self, uri: &Url, position: lsp::Position) -> Result<Option<(Target, Span)>> {
+        let Some(source) = self.workspace.get(uri) else {
+            return Ok(None);
+        };
+
+        let offset = self.encoding.rope_position(&source.content, position)?;
+        super::references::find_target(&self.workspace, uri, offset)
+    }
+
     /// Find definition at the given uri and LSP position.
     #[tracing::instrument(skip_all)]
     pub(super) fn complete(
@@ -424,6 +649,9 @@ impl<'a> State<'a> {
         // Emitted diagnostics, grouped by URL.
         let mut reporter = Reporter::default();
 
+        self.stale = false;
+        self.workspace.indexes.clear();
+
         if let Some((workspace_url, workspace_path)) = &self.workspace.manifest_path {
             let mut diagnostics = workspace::Diagnostics::default();
             let mut build = Build::from_workspace();
@@ -501,11 +729,23 @@ impl<'a> State<'a> {
                     continue;
                 };
 
+                let index = Arc::new(value);
+
+                self.workspace.indexes.try_insert(
+                    url.clone(),
+                    IndexedSource {
+                        source_id,
+                        sources: sources.clone(),
+                        index: index.clone(),
+                    },
+                )?;
+
                 let Some(source) = self.workspace.sources.get_mut(url) else {
                     continue;
                 };
 
-                source.index = value;
+                source.index = index;
+                source.source_id = Some(source_id);
                 source.build_sources = Some(sources.clone());
 
                 if let Ok(unit) = &unit {
@@ -658,7 +898,7 @@ impl<'a> State<'a> {
             tracing::trace!(?diagnostic, "workspace diagnostic");
 
             let workspace::Diagnostic::Fatal(f) = diagnostic;
-            self.report(build, reporter, f.source_id(), f.error(), to_error)?;
+            self.report(build, reporter, f.source_id(), f.error(), &[], to_error)?;
         }
 
         Ok(())
@@ -687,7 +927,8 @@ impl<'a> State<'a> {
             match diagnostic {
                 Diagnostic::Fatal(f) => match f.kind() {
                     FatalDiagnosticKind::CompileError(e) => {
-                        self.report(build, reporter, f.source_id(), e, to_error)?;
+                        let suggestions = e.suggestions();
+                        self.report(build, reporter, f.source_id(), e, suggestions, to_error)?;
                     }
                     FatalDiagnosticKind::LinkError(e) => match e {
                         LinkerError::MissingFunction { hash, spans } => {
@@ -713,9 +954,14 @@ impl<'a> State<'a> {
                     FatalDiagnosticKind::Internal(e) => {
                         report_without_span(build, reporter, f.source_id(), e, to_error)?;
                     }
+                    FatalDiagnosticKind::Lint(e) => {
+                        let suggestions = e.suggestions();
+                        self.report(build, reporter, f.source_id(), e, suggestions, to_error)?;
+                    }
                 },
                 Diagnostic::Warning(e) => {
-                    self.report(build, reporter, e.source_id(), e, to_warning)?;
+                    let suggestions = e.suggestions();
+                    self.report(build, reporter, e.source_id(), e, suggestions, to_warning)?;
                 }
                 Diagnostic::RuntimeWarning(_) => {}
             }
@@ -731,6 +977,7 @@ impl<'a> State<'a> {
         reporter: &mut Reporter,
         source_id: SourceId,
         error: E,
+        suggestions: &[Suggestion],
         report: R,
     ) -> Result<()>
     where
@@ -749,7 +996,10 @@ impl<'a> State<'a> {
 
         let range = self.encoding.source_range(source, span)?;
 
-        reporter.entry(url).try_push(report(range, error)?)?;
+        let mut diagnostic = report(range, error)?;
+        diagnostic.data = super::code_actions::data(source, &self.encoding, suggestions)?;
+
+        reporter.entry(url).try_push(diagnostic)?;
         Ok(())
     }
 }
@@ -763,6 +1013,9 @@ pub(super) struct Workspace {
     sources: HashMap<Url, ServerSource>,
     /// A source that has been removed.
     removed: Vec<Url>,
+    /// Indexes of every source which was part of the last build, including
+    /// sources which are not open.
+    indexes: HashMap<Url, IndexedSource>,
 }
 
 impl Workspace {
@@ -776,6 +1029,7 @@ impl Workspace {
         let source = ServerSource {
             content: Rope::from_str(text.as_str()),
             index: Default::default(),
+            source_id: None,
             build_sources: None,
             language,
             unit: None,
@@ -790,6 +1044,17 @@ impl Workspace {
         self.sources.get(url)
     }
 
+    /// Get the index of the source at the given url.
+    pub(super) fn index(&self, url: &Url) -> Option<&IndexedSource> {
+        self.indexes.get(url)
+    }
+
+    /// Iterate over the indexes of all sources which were part of the last
+    /// build.
+    pub(super) fn indexes(&self) -> impl Iterator<Item = (&Url, &IndexedSource)> {
+        self.indexes.iter()
+    }
+
     /// Get the mutable source at the given url.
     pub(super) fn get_mut(&mut self, url: &Url) -> Option<&mut ServerSource> {
         self.sources.get_mut(url)
@@ -810,29 +1075,40 @@ pub(super) struct ServerSource {
     /// The content of the current source.
     content: Rope,
     /// Indexes used to answer queries.
-    index: Index,
+    index: Arc<Index>,
+    /// The identifier of this source in the last build.
+    source_id: Option<SourceId>,
     /// Loaded Rune sources for this source file. Will be present after the
     /// source file has been built.
     build_sources: Option<Arc<Sources>>,
     /// The language of the source.
     language: Language,
     /// The compiled unit
-    unit: Option<Unit>,
+    pub(super) unit: Option<Unit>,
     /// Comments captured
     docs: Option<Arc<crate::doc::Visitor>>,
 }
 
 impl ServerSource {
     /// Find the definition at the given span.
-    pub(super) fn find_definition_at(&self, span: Span) -> Option<&Definition> {
-        let (found_span, definition) = self.index.definitions.range(..=span).next_back()?;
+    pub(super) fn find_definition_at(&self, span: Span) -> Option<(Span, &Definition)> {
+        find_at(&self.index.definitions, span)
+    }
 
-        if span.start >= found_span.start && span.end <= found_span.end {
-            tracing::trace!("found {:?}", definition);
-            return Some(definition);
-        }
+    /// Get the built source corresponding to this source file.
+    pub(super) fn build_source(&self) -> Option<&Source> {
+        self.build_sources.as_ref()?.get(self.source_id?)
+    }
 
-        None
+    /// Get the source text that the given definition points to.
+    pub(super) fn definition_source_text(&self, definition: &Definition) -> Option<&str> {
+        let sources = self.build_sources.as_ref()?;
+        sources.source(definition.source.source_id(), definition.source.span())
+    }
+
+    /// Find the resolved symbol at the given span.
+    pub(super) fn find_symbol_at(&self, span: Span) -> Option<(Span, &Symbol)> {
+        find_at(&self.index.symbols, span)
     }
 
     /// Modify the given lsp range in the file.
@@ -894,6 +1170,21 @@ impl fmt::Display for ServerSource {
     }
 }
 
+/// Find the indexed value whose span covers the given span.
+pub(super) fn find_at<T>(map: &BTreeMap<Span, T>, span: Span) -> Option<(Span, &T)>
+where
+    T: fmt::Debug,
+{
+    let (found_span, value) = map.range(..=span).next_back()?;
+
+    if span.start >= found_span.start && span.end <= found_span.end {
+        tracing::trace!("found {:?}", value);
+        return Some((*found_span, value));
+    }
+
+    None
+}
+
 /// Convert the given span and error into an error diagnostic.
 fn report_without_span<E, R>(
     build: &Build,
@@ -957,7 +1248,60 @@ where
 #[derive(Default)]
 pub(super) struct Index {
     /// Spans mapping to their corresponding definitions.
-    definitions: BTreeMap<Span, Definition>,
+    pub(super) definitions: BTreeMap<Span, Definition>,
+    /// Spans mapping to the symbols they resolve to.
+    pub(super) symbols: BTreeMap<Span, Symbol>,
+    /// Spans of items declared in the source mapping to their symbols.
+    pub(super) declarations: BTreeMap<Span, Symbol>,
+    /// Spans of function and closure arguments.
+    pub(super) arguments: BTreeSet<Span>,
+    /// Spans of closures mapping to the names of the variables they capture.
+    pub(super) captures: BTreeMap<Span, Vec<String>>,
+    /// Spans of named fields mapping to the fields they refer to.
+    pub(super) fields: BTreeMap<Span, Field>,
+}
+
+/// A named field which is declared or used in a source.
+#[derive(Debug)]
+pub(super) struct Field {
+    /// The name of the field.
+    pub(super) name: String,
+    /// The type hash of the struct or variant the field belongs to, if it's
+    /// known.
+    pub(super) hash: Option<Hash>,
+    /// How the field is referred to.
+    pub(super) kind: FieldKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FieldKind {
+    /// The declaration of a field in a struct or an enum variant.
+    Declaration,
+    /// A use of a field, like `value.field` or `Struct { field: value }`.
+    ///
+    /// Uses through field accesses like `value.field` don't know which type
+    /// they belong to.
+    Use,
+    /// A shorthand use of a field which also refers to a variable, like
+    /// `Struct { field }`.
+    Shorthand,
+}
+
+/// The index of a source which was part of a build.
+pub(super) struct IndexedSource {
+    /// The identifier of the source in the build.
+    pub(super) source_id: SourceId,
+    /// The sources of the build.
+    pub(super) sources: Arc<Sources>,
+    /// The index of the source.
+    pub(super) index: Arc<Index>,
+}
+
+impl IndexedSource {
+    /// Get the built source being indexed.
+    pub(super) fn source(&self) -> Option<&Source> {
+        self.sources.get(self.source_id)
+    }
 }
 
 /// A definition source.
@@ -972,7 +1316,7 @@ pub(super) enum DefinitionSource {
 }
 
 impl DefinitionSource {
-    fn span(&self) -> Span {
+    pub(super) fn span(&self) -> Span {
         match self {
             Self::Source(..) => Span::empty(),
             Self::Location(location) => location.span,
@@ -980,7 +1324,7 @@ impl DefinitionSource {
         }
     }
 
-    fn source_id(&self) -> SourceId {
+    pub(super) fn source_id(&self) -> SourceId {
         match self {
             Self::Source(source_id) => *source_id,
             Self::Location(location) => location.source_id,
@@ -988,7 +1332,7 @@ impl DefinitionSource {
         }
     }
 
-    fn path(&self) -> Option<&Path> {
+    pub(super) fn path(&self) -> Option<&Path> {
         match self {
             Self::SourceMeta(compile_source) => compile_source.path.as_deref(),
             _ => None,
@@ -1031,6 +1375,19 @@ pub(super) enum DefinitionKind {
     Module,
 }
 
+/// A resolved symbol.
+#[derive(Debug, TryClone)]
+pub(super) struct Symbol {
+    /// If the symbol comes from the context.
+    pub(super) context: bool,
+    /// The hash of the symbol.
+    pub(super) hash: Hash,
+    /// The item of the symbol.
+    pub(super) item: ItemBuf,
+    /// The kind of the symbol.
+    pub(super) kind: meta::Kind,
+}
+
 #[derive(Default)]
 struct Visitor {
     indexes: HashMap<SourceId, Index>,
@@ -1041,10 +1398,59 @@ impl Visitor {
     pub(super) fn into_indexes(self) -> HashMap<SourceId, Index> {
         self.indexes
     }
+
+    fn insert_field(
+        &mut self,
+        location: &dyn Located,
+        hash: Option<Hash>,
+        name: &str,
+        kind: FieldKind,
+    ) -> Result<(), MetaError> {
+        let location = location.location();
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+
+        let field = Field {
+            name: name.try_to_owned()?,
+            hash,
+            kind,
+        };
+
+        index.fields.insert(location.span, field);
+        Ok(())
+    }
 }
 
 impl CompileVisitor for Visitor {
+    fn register_meta(&mut self, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        let Some(source) = meta.source else {
+            return Ok(());
+        };
+
+        let symbol = Symbol {
+            context: meta.context,
+            hash: meta.hash,
+            item: meta.item.try_to_owned()?,
+            kind: meta.kind.try_clone()?,
+        };
+
+        let location = source.location;
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+        index.declarations.insert(location.span, symbol);
+        Ok(())
+    }
+
     fn visit_meta(&mut self, location: &dyn Located, meta: MetaRef<'_>) -> Result<(), MetaError> {
+        let symbol = Symbol {
+            context: meta.context,
+            hash: meta.hash,
+            item: meta.item.try_to_owned()?,
+            kind: meta.kind.try_clone()?,
+        };
+
+        let location = location.location();
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+        index.symbols.insert(location.span, symbol);
+
         let Some(source) = meta.source else {
             return Ok(());
         };
@@ -1090,10 +1496,6 @@ impl CompileVisitor for Visitor {
             source: DefinitionSource::SourceMeta(source.try_clone()?),
         };
 
-        let location = location.location();
-
-        let index = self.indexes.entry(location.source_id).or_try_default()?;
-
         if let Some(_def) = index.definitions.insert(location.span, definition) {
             tracing::warn!("Replaced definition: {:?}", _def.kind);
         }
@@ -1121,6 +1523,51 @@ impl CompileVisitor for Visitor {
         Ok(())
     }
 
+    fn visit_fn_arg(&mut self, location: &dyn Located) -> Result<(), MetaError> {
+        let location = location.location();
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+        index.arguments.insert(location.span);
+        Ok(())
+    }
+
+    fn visit_closure_capture(
+        &mut self,
+        location: &dyn Located,
+        name: &str,
+    ) -> Result<(), MetaError> {
+        let location = location.location();
+        let index = self.indexes.entry(location.source_id).or_try_default()?;
+        let captures = index.captures.entry(location.span).or_default();
+        captures.try_push(name.try_to_owned()?)?;
+        Ok(())
+    }
+
+    fn visit_field(
+        &mut self,
+        location: &dyn Located,
+        _: &Item,
+        hash: Hash,
+        field: &str,
+    ) -> Result<(), MetaError> {
+        self.insert_field(location, Some(hash), field, FieldKind::Declaration)
+    }
+
+    fn visit_field_use(
+        &mut self,
+        location: &dyn Located,
+        hash: Option<Hash>,
+        field: &str,
+        shorthand: bool,
+    ) -> Result<(), MetaError> {
+        let kind = if shorthand {
+            FieldKind::Shorthand
+        } else {
+            FieldKind::Use
+        };
+
+        self.insert_field(location, hash, field, kind)
+    }
+
     fn visit_mod(&mut self, location: &dyn Located) -> Result<(), MetaError> {
         let location = location.location();
 
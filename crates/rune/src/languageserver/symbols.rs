@@ -0,0 +1,345 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use lsp::Url;
+
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::alloc::{String, Vec};
+use crate::ast::{self, Span};
+use crate::compile::meta;
+use crate::item::ComponentRef;
+use crate::parse::Lexer;
+use crate::{ItemBuf, Source, SourceId};
+
+use super::state::{StateEncoding, Symbol, Workspace};
+
+/// A symbol in a document which is being built.
+struct Node {
+    item: ItemBuf,
+    name: String,
+    detail: Option<String>,
+    kind: lsp::SymbolKind,
+    /// The type this symbol is implemented for, if it's an associated
+    /// function.
+    impl_of: Option<ItemBuf>,
+    range: Span,
+    selection: Span,
+    children: Vec<Node>,
+}
+
+/// Build hierarchical symbols for the given url.
+pub(super) fn document_symbols(
+    workspace: &Workspace,
+    encoding: &StateEncoding,
+    url: &Url,
+) -> Result<Option<Vec<lsp::DocumentSymbol>>> {
+    let Some(indexed) = workspace.index(url) else {
+        return Ok(None);
+    };
+
+    let Some(source) = indexed.source() else {
+        return Ok(None);
+    };
+
+    let types = types(workspace)?;
+
+    let mut nodes = Vec::new();
+
+    for (span, symbol) in &indexed.index.declarations {
+        if let Some(node) = node(source, &types, *span, symbol)? {
+            nodes.try_push(node)?;
+        }
+    }
+
+    // Modules only span their declaration, so extend them to cover the items
+    // declared inside of them.
+    for n in 0..nodes.len() {
+        if nodes[n].kind != lsp::SymbolKind::MODULE {
+            continue;
+        }
+
+        let mut range = nodes[n].range;
+
+        for other in &nodes {
+            if other.item != nodes[n].item && other.item.starts_with(&nodes[n].item) {
+                range = range.join(other.range);
+            }
+        }
+
+        nodes[n].range = range;
+    }
+
+    // Containers sort before the symbols they contain.
+    nodes.sort_by(|a, b| {
+        a.range
+            .start
+            .cmp(&b.range.start)
+            .then(b.range.end.cmp(&a.range.end))
+    });
+
+    let mut roots = Vec::new();
+
+    for node in nodes {
+        insert(&mut roots, node)?;
+    }
+
+    let roots = group_impls(roots)?;
+    Ok(Some(convert(source, encoding, roots)?))
+}
+
+/// Search for symbols matching the given query across the workspace.
+pub(super) fn workspace_symbols(
+    workspace: &Workspace,
+    encoding: &StateEncoding,
+    query: &str,
+) -> Result<Vec<lsp::WorkspaceSymbol>> {
+    let types = types(workspace)?;
+
+    let mut output = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (url, indexed) in workspace.indexes() {
+        let Some(source) = indexed.source() else {
+            continue;
+        };
+
+        for (span, symbol) in &indexed.index.declarations {
+            let Some(node) = node(source, &types, *span, symbol)? else {
+                continue;
+            };
+
+            if !is_match(&node.name, query) || !seen.insert((url, *span)) {
+                continue;
+            }
+
+            let container_name = match symbol.item.parent() {
+                Some(parent) if !parent.is_empty() => Some(parent.try_to_string()?.into_std()),
+                _ => None,
+            };
+
+            output.try_push(lsp::WorkspaceSymbol {
+                name: node.name.into_std(),
+                kind: node.kind,
+                tags: None,
+                container_name,
+                location: lsp::OneOf::Left(lsp::Location {
+                    uri: url.clone(),
+                    range: encoding.source_range(source, node.selection)?,
+                }),
+                data: None,
+            })?;
+        }
+    }
+
+    Ok(output)
+}
+
+/// Construct a node out of a declared symbol, if it's the kind of symbol
+/// which is shown.
+fn node(
+    source: &Source,
+    types: &HashSet<ItemBuf>,
+    span: Span,
+    symbol: &Symbol,
+) -> Result<Option<Node>> {
+    let mut impl_of = None;
+
+    let kind = match &symbol.kind {
+        meta::Kind::Function { .. } => match symbol.item.parent() {
+            Some(parent) if types.contains(parent) => {
+                impl_of = Some(parent.try_to_owned()?);
+                lsp::SymbolKind::METHOD
+            }
+            _ => lsp::SymbolKind::FUNCTION,
+        },
+        meta::Kind::ConstFn => lsp::SymbolKind::FUNCTION,
+        meta::Kind::Struct { .. } => lsp::SymbolKind::STRUCT,
+        meta::Kind::Enum { .. } => lsp::SymbolKind::ENUM,
+        meta::Kind::Variant { .. } => lsp::SymbolKind::ENUM_MEMBER,
+        meta::Kind::Const => lsp::SymbolKind::CONSTANT,
+        meta::Kind::Module => lsp::SymbolKind::MODULE,
+        meta::Kind::Trait => lsp::SymbolKind::INTERFACE,
+        _ => return Ok(None),
+    };
+
+    let Some(ComponentRef::Str(name)) = symbol.item.last() else {
+        return Ok(None);
+    };
+
+    let Some(selection) = ident(source, span, name)? else {
+        return Ok(None);
+    };
+
+    let detail = match &symbol.kind {
+        meta::Kind::Function { signature, .. } => {
+            let mut detail = String::new();
+
+            if signature.is_async {
+                detail.try_push_str("async ")?;
+            }
+
+            detail.try_push_str("fn(")?;
+
+            if let Some(arguments) = &signature.arguments {
+                let mut it = arguments.iter().peekable();
+
+                while let Some(argument) = it.next() {
+                    write!(detail, "{}", argument.name)?;
+
+                    if it.peek().is_some() {
+                        detail.try_push_str(", ")?;
+                    }
+                }
+            }
+
+            detail.try_push(')')?;
+            Some(detail)
+        }
+        _ => None,
+    };
+
+    Ok(Some(Node {
+        item: symbol.item.try_clone()?,
+        name: name.try_to_owned()?,
+        detail,
+        kind,
+        impl_of,
+        range: span,
+        selection,
+        children: Vec::new(),
+    }))
+}
+
+/// Collect the items of all types declared in the workspace.
+fn types(workspace: &Workspace) -> Result<HashSet<ItemBuf>> {
+    let mut types = HashSet::new();
+
+    for (_, indexed) in workspace.indexes() {
+        for symbol in indexed.index.declarations.values() {
+            if let meta::Kind::Struct { .. } | meta::Kind::Enum { .. } = symbol.kind {
+                types.insert(symbol.item.try_clone()?);
+            }
+        }
+    }
+
+    Ok(types)
+}
+
+/// Insert a node into the last node which contains it.
+fn insert(nodes: &mut Vec<Node>, node: Node) -> Result<()> {
+    if let Some(last) = nodes.last_mut() {
+        if last.range.start <= node.range.start && node.range.end <= last.range.end {
+            return insert(&mut last.children, node);
+        }
+    }
+
+    nodes.try_push(node)?;
+    Ok(())
+}
+
+/// Group adjacent associated functions of the same type into `impl` nodes.
+fn group_impls(nodes: Vec<Node>) -> Result<Vec<Node>> {
+    let mut output = Vec::<Node>::new();
+
+    for mut node in nodes {
+        node.children = group_impls(node.children)?;
+
+        let Some(impl_of) = node.impl_of.take() else {
+            output.try_push(node)?;
+            continue;
+        };
+
+        if let Some(last) = output.last_mut() {
+            if last.kind == lsp::SymbolKind::OBJECT && last.impl_of.as_ref() == Some(&impl_of) {
+                last.range = last.range.join(node.range);
+                last.children.try_push(node)?;
+                continue;
+            }
+        }
+
+        let mut name = String::new();
+
+        if let Some(ComponentRef::Str(ty)) = impl_of.last() {
+            write!(name, "impl {ty}")?;
+        } else {
+            write!(name, "impl {impl_of}")?;
+        }
+
+        let mut children = Vec::new();
+        let (range, selection) = (node.range, node.selection);
+        children.try_push(node)?;
+
+        output.try_push(Node {
+            item: impl_of.try_clone()?,
+            name,
+            detail: None,
+            kind: lsp::SymbolKind::OBJECT,
+            impl_of: Some(impl_of),
+            range,
+            selection,
+            children,
+        })?;
+    }
+
+    Ok(output)
+}
+
+/// Convert nodes into document symbols.
+fn convert(
+    source: &Source,
+    encoding: &StateEncoding,
+    nodes: Vec<Node>,
+) -> Result<Vec<lsp::DocumentSymbol>> {
+    let mut output = Vec::new();
+
+    for node in nodes {
+        let children = convert(source, encoding, node.children)?;
+
+        #[allow(deprecated)]
+        output.try_push(lsp::DocumentSymbol {
+            name: node.name.into_std(),
+            detail: node.detail.map(String::into_std),
+            kind: node.kind,
+            tags: None,
+            deprecated: None,
+            range: encoding.source_range(source, node.range)?,
+            selection_range: encoding.source_range(source, node.selection)?,
+            children: (!children.is_empty()).then(|| children.into_std()),
+        })?;
+    }
+
+    Ok(output)
+}
+
+/// Test if the query matches the given name.
+///
+/// Every character of the query has to be present in order in the name,
+/// ignoring case.
+fn is_match(name: &str, query: &str) -> bool {
+    let mut chars = name.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .all(|q| chars.any(|c| c == q))
+}
+
+/// Find the first identifier matching `name` inside of the given span.
+fn ident(source: &Source, span: Span, name: &str) -> Result<Option<Span>> {
+    let Some(text) = source.get(span.range()) else {
+        return Ok(None);
+    };
+
+    let mut lexer = Lexer::new(text, SourceId::EMPTY, false);
+
+    while let Ok(Some(token)) = lexer.next() {
+        if matches!(token.kind, ast::Kind::Ident(..)) && text.get(token.span.range()) == Some(name)
+        {
+            let start = span.start.into_usize();
+            let range = token.span.range();
+            return Ok(Some(Span::new(start + range.start, start + range.end)));
+        }
+    }
+
+    Ok(None)
+}
@@ -1,3 +1,18 @@
+use core::future::Future;
+
+use ::rust_alloc::string::String;
+use ::rust_alloc::vec::Vec;
+
+use anyhow::{bail, Context as _, Result};
+use lsp::notification::Notification;
+use lsp::request::Request;
+use lsp::Url;
+use serde_json::{json, Value};
+use tokio::io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader, DuplexStream};
+
+use crate::{Context, Options};
+
+use super::connection::{self, Headers};
 use super::Code;
 
 #[test]
@@ -10,3 +25,919 @@ fn test_code() {
     assert_eq!(code, Code::MethodNotFound);
     assert_eq!(serde_json::to_string(&code).unwrap(), "-32601");
 }
+
+#[tokio::test]
+async fn hover() -> Result<()> {
+    const SOURCE: &str = r#"
+/// Add two numbers.
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let value = add(1, 2);
+    value
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let hover = client
+            .hover(&url, cursor(SOURCE, "a|dd(1")?)
+            .await?
+            .context("missing hover for function")?;
+
+        assert_eq!(
+            markup(&hover)?,
+            "```rune\nfn add(a, b)\n```\n\n---\n\n Add two numbers.\n"
+        );
+        assert_eq!(hover.range, Some(range(SOURCE, "add(1", 3)?));
+
+        let hover = client
+            .hover(&url, cursor(SOURCE, "v|alue\n}")?)
+            .await?
+            .context("missing hover for local")?;
+
+        assert_eq!(markup(&hover)?, "```rune\nlet value\n```\n");
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn hover_after_change() -> Result<()> {
+    const SOURCE: &str = "fn add(a, b) { a + b }\npub fn main() { add(1, 2) }\n";
+    const CHANGED: &str = "fn sum(a, b) { a + b }\npub fn main() { sum(1, 2) }\n";
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+        client.change(&url, SOURCE, CHANGED).await?;
+
+        // The change has not been built yet, so the request has to rebuild.
+        let hover = client
+            .hover(&url, cursor(CHANGED, "s|um(1")?)
+            .await?
+            .context("missing hover")?;
+
+        assert_eq!(markup(&hover)?, "```rune\nfn sum(a, b)\n```\n");
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn rename_field() -> Result<()> {
+    const SOURCE: &str = r#"
+struct Point { x: i64, y: i64 }
+
+pub fn main() {
+    let x = 1;
+    let p = Point { x, y: 2 };
+    p.x + x
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let references = client
+            .references(&url, cursor(SOURCE, "p.x|")?, false)
+            .await?;
+        let mut references = references.iter().map(|l| l.range).collect::<Vec<_>>();
+        references.sort_by_key(|r| r.start);
+
+        assert_eq!(
+            references,
+            [range(SOURCE, "x, y: 2", 1)?, range(SOURCE, "x + x", 1)?]
+        );
+
+        let edit = client.rename(&url, cursor(SOURCE, "p.x|")?, "z").await?;
+
+        assert_eq!(
+            apply(SOURCE, &url, edit)?,
+            r#"
+struct Point { z: i64, y: i64 }
+
+pub fn main() {
+    let x = 1;
+    let p = Point { z: x, y: 2 };
+    p.z + x
+}
+"#
+        );
+
+        let edit = client.rename(&url, cursor(SOURCE, "let x|")?, "w").await?;
+
+        assert_eq!(
+            apply(SOURCE, &url, edit)?,
+            r#"
+struct Point { x: i64, y: i64 }
+
+pub fn main() {
+    let w = 1;
+    let p = Point { x: w, y: 2 };
+    p.x + w
+}
+"#
+        );
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn rename_shared_field() -> Result<()> {
+    const SOURCE: &str = r#"
+struct Point { x: i64, y: i64 }
+struct Size { x: i64 }
+
+pub fn main() {
+    let x = 1;
+    let p = Point { x, y: 2 };
+    let s = Size { x };
+    let Size { x: w } = s;
+    p.x + w
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let references = client
+            .references(&url, cursor(SOURCE, "Size { x|: i64")?, false)
+            .await?;
+        let mut references = references.iter().map(|l| l.range).collect::<Vec<_>>();
+        references.sort_by_key(|r| r.start);
+
+        assert_eq!(
+            references,
+            [range(SOURCE, "x };", 1)?, range(SOURCE, "x: w", 1)?]
+        );
+
+        let edit = client
+            .rename(&url, cursor(SOURCE, "Point { x|: i64")?, "z")
+            .await?;
+
+        assert_eq!(
+            apply(SOURCE, &url, edit)?,
+            r#"
+struct Point { z: i64, y: i64 }
+struct Size { x: i64 }
+
+pub fn main() {
+    let x = 1;
+    let p = Point { z: x, y: 2 };
+    let s = Size { x };
+    let Size { x: w } = s;
+    p.x + w
+}
+"#
+        );
+
+        let edit = client
+            .rename(&url, cursor(SOURCE, "Size { x|: i64")?, "z")
+            .await?;
+
+        assert_eq!(
+            apply(SOURCE, &url, edit)?,
+            r#"
+struct Point { x: i64, y: i64 }
+struct Size { z: i64 }
+
+pub fn main() {
+    let x = 1;
+    let p = Point { x, y: 2 };
+    let s = Size { z: x };
+    let Size { z: w } = s;
+    p.x + w
+}
+"#
+        );
+
+        // Both types declare `x`, so the access can't be attributed to either.
+        let edit = client.rename(&url, cursor(SOURCE, "p.x|")?, "z").await?;
+        assert!(edit.is_none(), "{edit:?}");
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn semantic_tokens() -> Result<()> {
+    const DECLARATION: u32 = 1;
+
+    const SOURCE: &str = r#"
+struct Point { x: i64 }
+
+pub fn main(arg) {
+    let p = Point { x: arg };
+    p
+}
+"#;
+
+    const CHANGED: &str = r#"
+struct Point { x: i64 }
+
+pub fn main(argument) {
+    let point = Point { x: argument };
+    point
+}
+"#;
+
+    let legend = super::semantic_tokens::legend();
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let tokens = client.semantic_tokens(&url).await?;
+
+        assert_eq!(
+            decode(&legend, SOURCE, &tokens),
+            [
+                ("Point", "type", DECLARATION),
+                ("main", "function", DECLARATION),
+                ("arg", "parameter", DECLARATION),
+                ("p", "variable", DECLARATION),
+                ("Point", "type", 0),
+                ("arg", "parameter", 0),
+                ("p", "variable", 0),
+            ]
+        );
+
+        client.change(&url, SOURCE, CHANGED).await?;
+
+        // The change has not been built yet, so the request has to rebuild.
+        let tokens = client.semantic_tokens(&url).await?;
+
+        assert_eq!(
+            decode(&legend, CHANGED, &tokens),
+            [
+                ("Point", "type", DECLARATION),
+                ("main", "function", DECLARATION),
+                ("argument", "parameter", DECLARATION),
+                ("point", "variable", DECLARATION),
+                ("Point", "type", 0),
+                ("argument", "parameter", 0),
+                ("point", "variable", 0),
+            ]
+        );
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn signature_help() -> Result<()> {
+    const SOURCE: &str = r#"
+/// Add two numbers.
+fn add(a, b) {
+    a + b
+}
+
+pub fn main() {
+    let n = add(1, 2);
+    std::cmp::max(n, 3)
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let help = client
+            .signature_help(&url, cursor(SOURCE, "add(1, |2)")?)
+            .await?
+            .context("missing signature help")?;
+
+        let [add] = &help.signatures[..] else {
+            bail!("expected one signature, got {:?}", help.signatures);
+        };
+
+        assert_eq!(
+            signature(add)?,
+            ("fn add(a, b)", ::rust_alloc::vec!["a", "b"], Some(1))
+        );
+        assert_eq!(documentation(add)?, " Add two numbers.\n");
+
+        let help = client
+            .signature_help(&url, cursor(SOURCE, "max(|n")?)
+            .await?
+            .context("missing signature help")?;
+
+        let [max] = &help.signatures[..] else {
+            bail!("expected one signature, got {:?}", help.signatures);
+        };
+
+        assert_eq!(
+            signature(max)?,
+            (
+                "fn std::cmp::max(v1, v2)",
+                ::rust_alloc::vec!["v1", "v2"],
+                Some(0)
+            )
+        );
+        assert!(documentation(max)?.starts_with(" Compares and returns the maximum of two values."));
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn symbols() -> Result<()> {
+    const SOURCE: &str = r#"
+struct Point { x: i64 }
+
+impl Point {
+    fn new() {
+        Point { x: 0 }
+    }
+}
+
+mod shapes {
+    fn area() {
+        0
+    }
+}
+
+pub fn main() {
+    Point::new()
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        let symbols = client.document_symbols(&url).await?;
+
+        assert_eq!(
+            outline(SOURCE, &symbols, 0)?,
+            [
+                "Struct Point",
+                "Object impl Point",
+                "  Method new",
+                "Module shapes",
+                "  Function area",
+                "Function main",
+            ]
+        );
+
+        let symbols = client.workspace_symbols("a").await?;
+
+        assert_eq!(
+            symbols,
+            [
+                (String::from("shapes"), None, range(SOURCE, "shapes", 6)?),
+                (
+                    String::from("area"),
+                    Some(String::from("shapes")),
+                    range(SOURCE, "area", 4)?
+                ),
+                (String::from("main"), None, range(SOURCE, "main", 4)?),
+            ]
+        );
+
+        let changed = SOURCE.replace("area", "volume");
+        client.change(&url, SOURCE, &changed).await?;
+
+        // The change has not been built yet, so the request has to rebuild.
+        let symbols = client.workspace_symbols("volume").await?;
+
+        assert_eq!(
+            symbols,
+            [(
+                String::from("volume"),
+                Some(String::from("shapes")),
+                range(&changed, "volume", 6)?
+            )]
+        );
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn inlay_hints() -> Result<()> {
+    const SOURCE: &str = r#"
+fn area(width, height) {
+    width * height
+}
+
+pub fn main() {
+    let height = 2;
+    let scale = 3;
+    let f = |n| n * scale;
+    f(area(1, height))
+}
+"#;
+
+    let (mut client, server) = client()?;
+
+    let test = async move {
+        let url = client.open(SOURCE).await?;
+
+        assert_eq!(
+            client.inlay_hints(&url, SOURCE).await?,
+            [
+                (cursor(SOURCE, "= ||n|")?, String::from("captures scale")),
+                (cursor(SOURCE, "area(|1")?, String::from("width:")),
+            ]
+        );
+
+        let changed = SOURCE.replace("width", "w");
+        client.change(&url, SOURCE, &changed).await?;
+
+        // The change has not been built yet, so the request has to rebuild.
+        assert_eq!(
+            client.inlay_hints(&url, &changed).await?,
+            [
+                (cursor(&changed, "= ||n|")?, String::from("captures scale")),
+                (cursor(&changed, "area(|1")?, String::from("w:")),
+            ]
+        );
+
+        client.shutdown().await
+    };
+
+    tokio::try_join!(server, test)?;
+    Ok(())
+}
+
+/// Set up a client connected to a language server, along with the future
+/// which runs the server until the client shuts it down.
+fn client() -> Result<(Client, impl Future<Output = Result<()>>)> {
+    let (client_writer, server_reader) = tokio::io::duplex(1 << 16);
+    let (server_writer, client_reader) = tokio::io::duplex(1 << 16);
+
+    let (input, output) = connection::stream(server_reader, server_writer);
+    let context = Context::with_default_modules()?;
+    let server = super::serve(input, output, context, Options::default());
+
+    let client = Client {
+        writer: client_writer,
+        reader: BufReader::new(client_reader),
+        buf: Vec::new(),
+        id: 0,
+    };
+
+    Ok((client, server))
+}
+
+/// A test client speaking the language server protocol.
+struct Client {
+    writer: DuplexStream,
+    reader: BufReader<DuplexStream>,
+    buf: Vec<u8>,
+    id: u64,
+}
+
+impl Client {
+    /// Initialize the server and open a document with the given contents,
+    /// waiting until it has been built.
+    async fn open(&mut self, text: &str) -> Result<Url> {
+        self.request::<lsp::request::Initialize>(lsp::InitializeParams::default())
+            .await?;
+        self.notify::<lsp::notification::Initialized>(lsp::InitializedParams {})
+            .await?;
+
+        let url = Url::parse("file:///main.rn")?;
+
+        self.notify::<lsp::notification::DidOpenTextDocument>(lsp::DidOpenTextDocumentParams {
+            text_document: lsp::TextDocumentItem {
+                uri: url.clone(),
+                language_id: String::from("rune"),
+                version: 0,
+                text: String::from(text),
+            },
+        })
+        .await?;
+
+        loop {
+            let message = self.receive().await?;
+
+            if message["method"] == lsp::notification::PublishDiagnostics::METHOD
+                && message["params"]["uri"] == url.as_str()
+            {
+                break;
+            }
+        }
+
+        Ok(url)
+    }
+
+    /// Replace the contents of an open document.
+    async fn change(&mut self, url: &Url, from: &str, to: &str) -> Result<()> {
+        let end = position_at(from, from.len());
+
+        self.notify::<lsp::notification::DidChangeTextDocument>(lsp::DidChangeTextDocumentParams {
+            text_document: lsp::VersionedTextDocumentIdentifier {
+                uri: url.clone(),
+                version: 1,
+            },
+            content_changes: ::rust_alloc::vec![lsp::TextDocumentContentChangeEvent {
+                range: Some(lsp::Range::new(lsp::Position::new(0, 0), end)),
+                range_length: None,
+                text: String::from(to),
+            }],
+        })
+        .await
+    }
+
+    async fn hover(&mut self, url: &Url, position: lsp::Position) -> Result<Option<lsp::Hover>> {
+        self.request::<lsp::request::HoverRequest>(lsp::HoverParams {
+            text_document_position_params: text_document_position(url, position),
+            work_done_progress_params: Default::default(),
+        })
+        .await
+    }
+
+    async fn references(
+        &mut self,
+        url: &Url,
+        position: lsp::Position,
+        include_declaration: bool,
+    ) -> Result<Vec<lsp::Location>> {
+        let locations = self
+            .request::<lsp::request::References>(lsp::ReferenceParams {
+                text_document_position: text_document_position(url, position),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+                context: lsp::ReferenceContext {
+                    include_declaration,
+                },
+            })
+            .await?;
+
+        Ok(locations.unwrap_or_default())
+    }
+
+    async fn rename(
+        &mut self,
+        url: &Url,
+        position: lsp::Position,
+        new_name: &str,
+    ) -> Result<Option<lsp::WorkspaceEdit>> {
+        self.request::<lsp::request::Rename>(lsp::RenameParams {
+            text_document_position: text_document_position(url, position),
+            new_name: String::from(new_name),
+            work_done_progress_params: Default::default(),
+        })
+        .await
+    }
+
+    async fn semantic_tokens(&mut self, url: &Url) -> Result<Vec<lsp::SemanticToken>> {
+        let result = self
+            .request::<lsp::request::SemanticTokensFullRequest>(lsp::SemanticTokensParams {
+                text_document: lsp::TextDocumentIdentifier { uri: url.clone() },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .await?;
+
+        match result {
+            Some(lsp::SemanticTokensResult::Tokens(tokens)) => Ok(tokens.data),
+            result => bail!("expected semantic tokens, got {result:?}"),
+        }
+    }
+
+    async fn signature_help(
+        &mut self,
+        url: &Url,
+        position: lsp::Position,
+    ) -> Result<Option<lsp::SignatureHelp>> {
+        self.request::<lsp::request::SignatureHelpRequest>(lsp::SignatureHelpParams {
+            context: None,
+            text_document_position_params: text_document_position(url, position),
+            work_done_progress_params: Default::default(),
+        })
+        .await
+    }
+
+    async fn document_symbols(&mut self, url: &Url) -> Result<Vec<lsp::DocumentSymbol>> {
+        let response = self
+            .request::<lsp::request::DocumentSymbolRequest>(lsp::DocumentSymbolParams {
+                text_document: lsp::TextDocumentIdentifier { uri: url.clone() },
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .await?;
+
+        match response {
+            Some(lsp::DocumentSymbolResponse::Nested(symbols)) => Ok(symbols),
+            response => bail!("expected nested symbols, got {response:?}"),
+        }
+    }
+
+    /// Search for workspace symbols, returning their names, containers and
+    /// ranges.
+    async fn workspace_symbols(
+        &mut self,
+        query: &str,
+    ) -> Result<Vec<(String, Option<String>, lsp::Range)>> {
+        let response = self
+            .request::<lsp::request::WorkspaceSymbolRequest>(lsp::WorkspaceSymbolParams {
+                query: String::from(query),
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            })
+            .await?;
+
+        // Both kinds of responses have the same shape when locations include
+        // a range, so a nested response might be deserialized as a flat one.
+        match response {
+            Some(lsp::WorkspaceSymbolResponse::Flat(symbols)) => Ok(symbols
+                .into_iter()
+                .map(|s| (s.name, s.container_name, s.location.range))
+                .collect()),
+            response => bail!("expected workspace symbols, got {response:?}"),
+        }
+    }
+
+    /// Get the positions and labels of all inlay hints in a document.
+    async fn inlay_hints(
+        &mut self,
+        url: &Url,
+        source: &str,
+    ) -> Result<Vec<(lsp::Position, String)>> {
+        let hints = self
+            .request::<lsp::request::InlayHintRequest>(lsp::InlayHintParams {
+                text_document: lsp::TextDocumentIdentifier { uri: url.clone() },
+                range: lsp::Range::new(lsp::Position::new(0, 0), position_at(source, source.len())),
+                work_done_progress_params: Default::default(),
+            })
+            .await?;
+
+        let mut output = Vec::new();
+
+        for hint in hints.into_iter().flatten() {
+            let lsp::InlayHintLabel::String(label) = hint.label else {
+                bail!("expected a string label, got {:?}", hint.label);
+            };
+
+            output.push((hint.position, label));
+        }
+
+        Ok(output)
+    }
+
+    /// Shut down the server.
+    async fn shutdown(&mut self) -> Result<()> {
+        self.request::<lsp::request::Shutdown>(()).await
+    }
+
+    /// Send a request and wait for its response, skipping over any
+    /// notifications sent by the server in the meantime.
+    async fn request<R>(&mut self, params: R::Params) -> Result<R::Result>
+    where
+        R: Request,
+    {
+        self.id += 1;
+
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "id": self.id,
+            "method": R::METHOD,
+            "params": params,
+        }))
+        .await?;
+
+        loop {
+            let mut message = self.receive().await?;
+
+            if message.get("id") != Some(&json!(self.id)) {
+                continue;
+            }
+
+            if let Some(error) = message.get("error") {
+                bail!("{} failed: {error}", R::METHOD);
+            }
+
+            let result = message.get_mut("result").map(Value::take);
+            return Ok(serde_json::from_value(result.unwrap_or(Value::Null))?);
+        }
+    }
+
+    /// Send a notification.
+    async fn notify<N>(&mut self, params: N::Params) -> Result<()>
+    where
+        N: Notification,
+    {
+        self.send(json!({
+            "jsonrpc": "2.0",
+            "method": N::METHOD,
+            "params": params,
+        }))
+        .await
+    }
+
+    async fn send(&mut self, message: Value) -> Result<()> {
+        let body = serde_json::to_vec(&message)?;
+        let header = ::rust_alloc::format!("Content-Length: {}\r\n\r\n", body.len());
+        self.writer.write_all(header.as_bytes()).await?;
+        self.writer.write_all(&body).await?;
+        Ok(())
+    }
+
+    async fn receive(&mut self) -> Result<Value> {
+        let Some(headers) = Headers::read(&mut self.buf, &mut self.reader).await? else {
+            bail!("server closed the connection");
+        };
+
+        let length = headers.content_length.context("missing content-length")?;
+        self.buf.resize(length as usize, 0);
+        self.reader.read_exact(&mut self.buf).await?;
+        Ok(serde_json::from_slice(&self.buf)?)
+    }
+}
+
+fn text_document_position(url: &Url, position: lsp::Position) -> lsp::TextDocumentPositionParams {
+    lsp::TextDocumentPositionParams {
+        text_document: lsp::TextDocumentIdentifier { uri: url.clone() },
+        position,
+    }
+}
+
+/// Apply the edits to the given document to its source.
+fn apply(source: &str, url: &Url, edit: Option<lsp::WorkspaceEdit>) -> Result<String> {
+    let mut changes = edit.and_then(|e| e.changes).context("missing changes")?;
+    let mut edits = changes.remove(url).context("missing edits for document")?;
+    assert!(changes.is_empty(), "unexpected edits to other documents");
+
+    edits.sort_by_key(|e| e.range.start);
+
+    let mut output = String::from(source);
+
+    for edit in edits.iter().rev() {
+        let start = offset_at(source, edit.range.start);
+        let end = offset_at(source, edit.range.end);
+        output.replace_range(start..end, &edit.new_text);
+    }
+
+    Ok(output)
+}
+
+/// Decode semantic tokens into the text, type and modifiers of each token.
+fn decode<'a>(
+    legend: &'a lsp::SemanticTokensLegend,
+    source: &'a str,
+    tokens: &[lsp::SemanticToken],
+) -> Vec<(&'a str, &'a str, u32)> {
+    let mut output = Vec::new();
+    let mut line = 0;
+    let mut character = 0;
+
+    for token in tokens {
+        if token.delta_line != 0 {
+            line += token.delta_line;
+            character = 0;
+        }
+
+        character += token.delta_start;
+
+        let start = offset_at(source, lsp::Position::new(line, character));
+        let end = start + token.length as usize;
+        let ty = &legend.token_types[token.token_type as usize];
+        output.push((
+            &source[start..end],
+            ty.as_str(),
+            token.token_modifiers_bitset,
+        ));
+    }
+
+    output
+}
+
+/// Get the label of a signature along with the labels of its parameters and
+/// the active parameter.
+fn signature(signature: &lsp::SignatureInformation) -> Result<(&str, Vec<&str>, Option<u32>)> {
+    let mut parameters = Vec::new();
+
+    for parameter in signature.parameters.iter().flatten() {
+        let lsp::ParameterLabel::LabelOffsets([start, end]) = parameter.label else {
+            bail!("expected label offsets, got {:?}", parameter.label);
+        };
+
+        parameters.push(&signature.label[start as usize..end as usize]);
+    }
+
+    Ok((&signature.label, parameters, signature.active_parameter))
+}
+
+/// Get the markdown documentation of a signature.
+fn documentation(signature: &lsp::SignatureInformation) -> Result<&str> {
+    let Some(lsp::Documentation::MarkupContent(markup)) = &signature.documentation else {
+        bail!(
+            "expected markup documentation, got {:?}",
+            signature.documentation
+        );
+    };
+
+    Ok(&markup.value)
+}
+
+/// Render document symbols as an indented outline of their kinds and names,
+/// checking that the selection range of each symbol covers its name.
+fn outline(source: &str, symbols: &[lsp::DocumentSymbol], depth: usize) -> Result<Vec<String>> {
+    let mut output = Vec::new();
+
+    for symbol in symbols {
+        // Groups of associated functions select their first function.
+        if symbol.kind != lsp::SymbolKind::OBJECT {
+            let start = offset_at(source, symbol.selection_range.start);
+            let end = offset_at(source, symbol.selection_range.end);
+            assert_eq!(&source[start..end], symbol.name);
+        }
+
+        let indent = "  ".repeat(depth);
+        output.push(::rust_alloc::format!(
+            "{indent}{:?} {}",
+            symbol.kind,
+            symbol.name
+        ));
+
+        if let Some(children) = &symbol.children {
+            output.extend(outline(source, children, depth + 1)?);
+        }
+    }
+
+    Ok(output)
+}
+
+/// Get the markdown contents of a hover.
+fn markup(hover: &lsp::Hover) -> Result<&str> {
+    let lsp::HoverContents::Markup(markup) = &hover.contents else {
+        bail!("expected markup contents, got {:?}", hover.contents);
+    };
+
+    Ok(&markup.value)
+}
+
+/// Get the position of the cursor marked with `|` in the first occurrence of
+/// `needle` in `source`.
+fn cursor(source: &str, needle: &str) -> Result<lsp::Position> {
+    let (before, after) = needle.split_once('|').context("needle has no cursor")?;
+    let needle = ::rust_alloc::format!("{before}{after}");
+    let offset = source.find(&needle).context("needle not in source")?;
+    Ok(position_at(source, offset + before.len()))
+}
+
+/// Get the range of the first `len` bytes of the first occurrence of `needle`
+/// in `source`.
+fn range(source: &str, needle: &str, len: usize) -> Result<lsp::Range> {
+    let offset = source.find(needle).context("needle not in source")?;
+    Ok(lsp::Range::new(
+        position_at(source, offset),
+        position_at(source, offset + len),
+    ))
+}
+
+/// Convert a position in an ASCII source into a byte offset.
+fn offset_at(source: &str, position: lsp::Position) -> usize {
+    let line = source
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum::<usize>();
+
+    line + position.character as usize
+}
+
+/// Convert a byte offset in an ASCII source into a position.
+fn position_at(source: &str, offset: usize) -> lsp::Position {
+    let before = &source[..offset];
+    let line = before.matches('\n').count();
+    let character = before.len() - before.rfind('\n').map_or(0, |n| n + 1);
+    lsp::Position::new(line as u32, character as u32)
+}
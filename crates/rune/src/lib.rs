@@ -654,6 +654,9 @@ pub mod cli;
 #[cfg(feature = "languageserver")]
 pub mod languageserver;
 
+#[cfg(feature = "debugger")]
+pub mod debugger;
+
 #[cfg(feature = "doc")]
 #[cfg_attr(rune_docsrs, doc(cfg(feature = "doc")))]
 pub(crate) mod doc;
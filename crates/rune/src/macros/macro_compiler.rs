@@ -2,8 +2,8 @@
 
 use crate::alloc::prelude::*;
 use crate::ast;
-use crate::ast::Spanned;
-use crate::compile::{self, ErrorKind, ItemMeta};
+use crate::ast::{OptionSpanned, Spanned};
+use crate::compile::{self, meta, DynLocation, ErrorKind, ItemId, ItemMeta};
 use crate::indexing::Indexer;
 use crate::macros::{MacroContext, ToTokens};
 use crate::parse::{Parse, Parser};
@@ -31,35 +31,114 @@ impl MacroCompiler<'_, '_, '_> {
         }
 
         let named = self.idx.q.convert_path(&macro_call.path)?;
-        let hash = self.idx.q.pool.item_type_hash(named.item);
 
-        let Some(handler) = self.idx.q.context.lookup_macro(hash) else {
+        let token_stream = match self.eval_macro_rules(macro_call, named.item)? {
+            Some(token_stream) => token_stream,
+            None => self.eval_native_macro(macro_call, named.item)?,
+        };
+
+        let mut parser = Parser::from_token_stream(&token_stream, span);
+        let output = parser.parse::<T>()?;
+        parser.eof()?;
+
+        Ok(output)
+    }
+
+    /// Evaluate a call to a declarative macro defined in a script in statement
+    /// position, producing the statements it expands into.
+    ///
+    /// Returns `None` if the call is not to a declarative macro.
+    pub(crate) fn eval_macro_rules_statements(
+        &mut self,
+        macro_call: &ast::MacroCall,
+    ) -> compile::Result<Option<Vec<ast::Stmt>>> {
+        if !self.idx.q.options.macros {
+            return Ok(None);
+        }
+
+        let named = self.idx.q.convert_path(&macro_call.path)?;
+
+        let Some(token_stream) = self.eval_macro_rules(macro_call, named.item)? else {
+            return Ok(None);
+        };
+
+        let mut parser = Parser::from_token_stream(&token_stream, macro_call.span());
+        let block = parser.parse::<ast::EmptyBlock>()?;
+        parser.eof()?;
+
+        if let Some(span) = block.attributes.option_span() {
+            return Err(compile::Error::msg(
+                span,
+                "Attributes are not supported in macro expansions",
+            ));
+        }
+
+        Ok(Some(block.statements))
+    }
+
+    /// Evaluate a declarative macro defined in a script, returning `None` if
+    /// `item` doesn't refer to one.
+    fn eval_macro_rules(
+        &mut self,
+        macro_call: &ast::MacroCall,
+        item: ItemId,
+    ) -> compile::Result<Option<TokenStream>> {
+        let span = macro_call.span();
+
+        let Some(macro_rules) =
+            self.idx
+                .q
+                .macro_rules_for(&macro_call.path, self.item_meta.module, item)?
+        else {
+            return Ok(None);
+        };
+
+        let Some(token_stream) =
+            macro_rules.expand(resolve_context!(self.idx.q), &macro_call.input, span)?
+        else {
             return Err(compile::Error::new(
                 span,
-                ErrorKind::MissingMacro {
-                    item: self.idx.q.pool.item(named.item).try_to_owned()?,
+                ErrorKind::NoMatchingMacroRule {
+                    item: self.idx.q.pool.item(item).try_to_owned()?,
                 },
             ));
         };
 
-        let input_stream = &macro_call.input;
+        Ok(Some(token_stream))
+    }
 
-        let token_stream = {
-            let mut macro_context = MacroContext {
-                macro_span: span,
-                input_span: macro_call.input_span(),
-                item_meta: self.item_meta,
-                idx: self.idx,
-            };
+    /// Evaluate a macro implemented natively in a context module.
+    fn eval_native_macro(
+        &mut self,
+        macro_call: &ast::MacroCall,
+        item: ItemId,
+    ) -> compile::Result<TokenStream> {
+        let span = macro_call.span();
+        let hash = self.idx.q.pool.item_type_hash(item);
 
-            handler(&mut macro_context, input_stream)?
+        let Some(handler) = self.idx.q.context.lookup_macro(hash) else {
+            return Err(compile::Error::new(
+                span,
+                ErrorKind::MissingMacro {
+                    item: self.idx.q.pool.item(item).try_to_owned()?,
+                },
+            ));
         };
 
-        let mut parser = Parser::from_token_stream(&token_stream, span);
-        let output = parser.parse::<T>()?;
-        parser.eof()?;
+        self.idx.q.visit_resolved(
+            &DynLocation::new(self.idx.source_id, &macro_call.path),
+            item,
+            |kind| matches!(kind, meta::Kind::Macro),
+        )?;
+
+        let mut macro_context = MacroContext {
+            macro_span: span,
+            input_span: macro_call.input_span(),
+            item_meta: self.item_meta,
+            idx: self.idx,
+        };
 
-        Ok(output)
+        handler(&mut macro_context, &macro_call.input)
     }
 
     /// Compile the given macro into the given output type.
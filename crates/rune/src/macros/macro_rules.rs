@@ -0,0 +1,874 @@
+//! Declarative macros defined in scripts through `macro_rules!`.
+
+use core::mem::discriminant;
+
+use crate::alloc::prelude::*;
+use crate::alloc::{Box, HashMap, Vec};
+use crate::ast::{self, Delimiter, Kind, Span};
+use crate::compile::{self, ErrorKind};
+use crate::parse::{Parser, Resolve, ResolveContext};
+use crate::SourceId;
+
+use super::TokenStream;
+
+/// A declarative macro defined in a script.
+///
+/// Tokens produced by an expansion keep the span they have in the macro
+/// definition, so that errors in the expanded code point into the definition
+/// rather than at the invocation.
+pub(crate) struct MacroRules {
+    rules: Vec<Rule>,
+}
+
+impl MacroRules {
+    /// Compile the rules of a `macro_rules!` definition.
+    pub(crate) fn compile(
+        cx: ResolveContext<'_>,
+        ast: &ast::ItemMacroRules,
+    ) -> compile::Result<Self> {
+        let tokens = ast.rules.as_slice();
+        let mut rules = Vec::new();
+        let mut n = 0;
+
+        while n < tokens.len() {
+            let (matcher, end) = group(tokens, n)?;
+            let matcher = parse_matchers(cx, matcher)?;
+            n = end;
+
+            match tokens.get(n) {
+                Some(token) if token.kind == K![=>] => n += 1,
+                Some(token) => return Err(compile::Error::expected(*token, K![=>])),
+                None => return Err(compile::Error::expected(ast.close, K![=>])),
+            }
+
+            if n == tokens.len() {
+                return Err(compile::Error::msg(
+                    ast.close,
+                    "Expected macro transcriber delimited by `(`, `[` or `{`",
+                ));
+            }
+
+            let (transcriber, end) = group(tokens, n)?;
+            let transcriber = parse_transcribers(cx, transcriber)?;
+            n = end;
+
+            {
+                let mut bound = Vec::new();
+                matcher_variables(&matcher, &mut bound)?;
+                check_variables(&transcriber, &bound)?;
+            }
+
+            rules.try_push(Rule {
+                matcher,
+                transcriber,
+            })?;
+
+            match tokens.get(n) {
+                Some(token) if token.kind == K![;] => n += 1,
+                Some(token) => return Err(compile::Error::expected(*token, K![;])),
+                None => {}
+            }
+        }
+
+        if rules.is_empty() {
+            return Err(compile::Error::msg(
+                ast.descriptive_span(),
+                "Macro definitions must have at least one rule",
+            ));
+        }
+
+        Ok(Self { rules })
+    }
+
+    /// Expand the macro with the given input.
+    ///
+    /// Returns `None` if none of the rules matched the input.
+    pub(crate) fn expand(
+        &self,
+        cx: ResolveContext<'_>,
+        input: &TokenStream,
+        span: Span,
+    ) -> compile::Result<Option<TokenStream>> {
+        let input = input.as_slice();
+
+        for rule in &self.rules {
+            let mut bindings = HashMap::new();
+
+            let Some(n) = match_sequence(cx, &rule.matcher, input, span, &mut bindings)? else {
+                continue;
+            };
+
+            if n != input.len() {
+                continue;
+            }
+
+            let mut output = Vec::new();
+            let mut indexes = Vec::new();
+            transcribe(&rule.transcriber, &bindings, &mut indexes, &mut output)?;
+            return Ok(Some(TokenStream::from(output)));
+        }
+
+        Ok(None)
+    }
+}
+
+struct Rule {
+    matcher: Vec<Matcher>,
+    transcriber: Vec<Transcriber>,
+}
+
+/// The kind of syntax a macro variable matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fragment {
+    Block,
+    Expr,
+    Ident,
+    Lit,
+    Pat,
+    Path,
+    Tt,
+    Ty,
+}
+
+impl Fragment {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "block" => Self::Block,
+            "expr" => Self::Expr,
+            "ident" => Self::Ident,
+            "lit" => Self::Lit,
+            "pat" => Self::Pat,
+            "path" => Self::Path,
+            "tt" => Self::Tt,
+            "ty" => Self::Ty,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kleene {
+    ZeroOrMore,
+    OneOrMore,
+    ZeroOrOne,
+}
+
+impl Kleene {
+    fn from_kind(kind: Kind) -> Option<Self> {
+        match kind {
+            K![*] => Some(Self::ZeroOrMore),
+            K![+] => Some(Self::OneOrMore),
+            K![?] => Some(Self::ZeroOrOne),
+            _ => None,
+        }
+    }
+}
+
+/// A repetition, like `$($a:expr),*`.
+struct Repeat<T> {
+    span: Span,
+    body: Vec<T>,
+    separator: Option<ast::Token>,
+    kleene: Kleene,
+}
+
+enum Matcher {
+    /// A token which has to match exactly.
+    Token(ast::Token),
+    /// A delimited group of matchers.
+    Group {
+        delimiter: Delimiter,
+        body: Vec<Matcher>,
+    },
+    /// A variable binding a fragment, like `$a:expr`.
+    Fragment {
+        span: Span,
+        name: Box<str>,
+        fragment: Fragment,
+    },
+    Repeat(Repeat<Matcher>),
+}
+
+enum Transcriber {
+    /// A token which is copied to the output.
+    Token(ast::Token),
+    /// A variable being substituted, like `$a`.
+    Variable {
+        span: Span,
+        name: Box<str>,
+    },
+    Repeat(Repeat<Transcriber>),
+}
+
+/// What a variable has been bound to when matching.
+enum Binding<'a> {
+    Fragment {
+        fragment: Fragment,
+        tokens: &'a [ast::Token],
+    },
+    Repeated(Vec<Binding<'a>>),
+}
+
+type Bindings<'a, 'm> = HashMap<&'m str, Binding<'a>>;
+
+/// Split out the delimited group starting at `n`, returning the tokens inside
+/// of the group and the index just past its closing delimiter.
+fn group(tokens: &[ast::Token], n: usize) -> compile::Result<(&[ast::Token], usize)> {
+    let Some(open) = tokens.get(n) else {
+        return Err(compile::Error::msg(
+            Span::empty(),
+            "Expected delimited group",
+        ));
+    };
+
+    if !matches!(open.kind, Kind::Open(..)) {
+        return Err(compile::Error::msg(
+            open,
+            "Expected a group delimited by `(`, `[` or `{`",
+        ));
+    }
+
+    let mut level = 0usize;
+
+    for (index, token) in tokens.iter().enumerate().skip(n) {
+        match token.kind {
+            Kind::Open(..) => level += 1,
+            Kind::Close(..) => {
+                level -= 1;
+
+                if level == 0 {
+                    return Ok((&tokens[n + 1..index], index + 1));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Err(compile::Error::msg(open, "Unclosed delimiter"))
+}
+
+/// Parse a variable name following a `$`.
+fn variable_name(cx: ResolveContext<'_>, token: &ast::Token) -> compile::Result<Box<str>> {
+    let Kind::Ident(source) = token.kind else {
+        return Err(compile::Error::msg(
+            token,
+            "Expected a variable name or `(` after `$`",
+        ));
+    };
+
+    let ident = ast::Ident {
+        span: token.span,
+        source,
+    };
+
+    Ok(Box::try_from(ident.resolve(cx)?)?)
+}
+
+/// Parse the separator and kleene operator following a repetition.
+fn parse_kleene(
+    tokens: &[ast::Token],
+    n: usize,
+    span: Span,
+) -> compile::Result<(Option<ast::Token>, Kleene, usize)> {
+    let expected = || compile::Error::msg(span, "Expected one of `*`, `+` or `?` after repetition");
+
+    let Some(first) = tokens.get(n) else {
+        return Err(expected());
+    };
+
+    if let Some(kleene) = Kleene::from_kind(first.kind) {
+        return Ok((None, kleene, n + 1));
+    }
+
+    if matches!(first.kind, Kind::Open(..) | Kind::Close(..) | Kind::Dollar) {
+        return Err(expected());
+    }
+
+    match tokens.get(n + 1).and_then(|t| Kleene::from_kind(t.kind)) {
+        Some(Kleene::ZeroOrOne) => Err(compile::Error::msg(
+            first,
+            "The `?` repetition can't have a separator",
+        )),
+        Some(kleene) => Ok((Some(*first), kleene, n + 2)),
+        None => Err(expected()),
+    }
+}
+
+fn parse_matchers(cx: ResolveContext<'_>, tokens: &[ast::Token]) -> compile::Result<Vec<Matcher>> {
+    let mut matchers = Vec::new();
+    let mut n = 0;
+
+    while let Some(token) = tokens.get(n) {
+        match token.kind {
+            Kind::Dollar => {
+                let Some(next) = tokens.get(n + 1) else {
+                    return Err(compile::Error::msg(
+                        token,
+                        "Expected a variable name or `(` after `$`",
+                    ));
+                };
+
+                if let Kind::Open(Delimiter::Parenthesis) = next.kind {
+                    let (body, end) = group(tokens, n + 1)?;
+                    let span = token.span.join(tokens[end - 1].span);
+                    let body = parse_matchers(cx, body)?;
+                    let (separator, kleene, end) = parse_kleene(tokens, end, span)?;
+
+                    matchers.try_push(Matcher::Repeat(Repeat {
+                        span,
+                        body,
+                        separator,
+                        kleene,
+                    }))?;
+
+                    n = end;
+                    continue;
+                }
+
+                let name = variable_name(cx, next)?;
+
+                match tokens.get(n + 2) {
+                    Some(colon) if colon.kind == K![:] => {}
+                    _ => {
+                        return Err(compile::Error::msg(
+                            token.span.join(next.span),
+                            "Missing fragment specifier, like `$a:expr`",
+                        ));
+                    }
+                }
+
+                let Some(spec) = tokens.get(n + 3) else {
+                    return Err(compile::Error::msg(
+                        token.span.join(next.span),
+                        "Missing fragment specifier, like `$a:expr`",
+                    ));
+                };
+
+                let fragment = match spec.kind {
+                    Kind::Ident(source) => {
+                        let ident = ast::Ident {
+                            span: spec.span,
+                            source,
+                        };
+
+                        let spec_name = ident.resolve(cx)?;
+
+                        let Some(fragment) = Fragment::from_name(spec_name) else {
+                            return Err(compile::Error::new(
+                                spec,
+                                ErrorKind::UnsupportedMacroFragment {
+                                    name: Box::try_from(spec_name)?,
+                                },
+                            ));
+                        };
+
+                        fragment
+                    }
+                    _ => {
+                        return Err(compile::Error::msg(spec, "Expected fragment specifier"));
+                    }
+                };
+
+                matchers.try_push(Matcher::Fragment {
+                    span: token.span.join(spec.span),
+                    name,
+                    fragment,
+                })?;
+
+                n += 4;
+            }
+            Kind::Open(delimiter) => {
+                let (body, end) = group(tokens, n)?;
+
+                matchers.try_push(Matcher::Group {
+                    delimiter,
+                    body: parse_matchers(cx, body)?,
+                })?;
+
+                n = end;
+            }
+            _ => {
+                matchers.try_push(Matcher::Token(*token))?;
+                n += 1;
+            }
+        }
+    }
+
+    Ok(matchers)
+}
+
+fn parse_transcribers(
+    cx: ResolveContext<'_>,
+    tokens: &[ast::Token],
+) -> compile::Result<Vec<Transcriber>> {
+    let mut transcribers = Vec::new();
+    let mut n = 0;
+
+    while let Some(token) = tokens.get(n) {
+        if token.kind != Kind::Dollar {
+            transcribers.try_push(Transcriber::Token(*token))?;
+            n += 1;
+            continue;
+        }
+
+        let Some(next) = tokens.get(n + 1) else {
+            return Err(compile::Error::msg(
+                token,
+                "Expected a variable name or `(` after `$`",
+            ));
+        };
+
+        if let Kind::Open(Delimiter::Parenthesis) = next.kind {
+            let (body, end) = group(tokens, n + 1)?;
+            let span = token.span.join(tokens[end - 1].span);
+            let body = parse_transcribers(cx, body)?;
+            let (separator, kleene, end) = parse_kleene(tokens, end, span)?;
+
+            transcribers.try_push(Transcriber::Repeat(Repeat {
+                span,
+                body,
+                separator,
+                kleene,
+            }))?;
+
+            n = end;
+            continue;
+        }
+
+        transcribers.try_push(Transcriber::Variable {
+            span: token.span.join(next.span),
+            name: variable_name(cx, next)?,
+        })?;
+
+        n += 2;
+    }
+
+    Ok(transcribers)
+}
+
+/// Collect the names of all variables bound by the given matchers.
+fn matcher_variables<'m>(matchers: &'m [Matcher], out: &mut Vec<&'m str>) -> compile::Result<()> {
+    for matcher in matchers {
+        match matcher {
+            Matcher::Token(..) => {}
+            Matcher::Group { body, .. } => {
+                matcher_variables(body, out)?;
+            }
+            Matcher::Fragment { span, name, .. } => {
+                if out.contains(&&**name) {
+                    return Err(compile::Error::msg(
+                        span,
+                        try_format!("Duplicate macro variable `${name}`"),
+                    ));
+                }
+
+                out.try_push(name)?;
+            }
+            Matcher::Repeat(repeat) => {
+                matcher_variables(&repeat.body, out)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that every variable used in a transcriber is bound by its matcher.
+fn check_variables(transcribers: &[Transcriber], bound: &[&str]) -> compile::Result<()> {
+    for transcriber in transcribers {
+        match transcriber {
+            Transcriber::Token(..) => {}
+            Transcriber::Variable { span, name } => {
+                if !bound.contains(&&**name) {
+                    return Err(compile::Error::new(
+                        span,
+                        ErrorKind::MissingMacroVariable {
+                            name: name.try_clone()?,
+                        },
+                    ));
+                }
+            }
+            Transcriber::Repeat(repeat) => {
+                check_variables(&repeat.body, bound)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Match a sequence of matchers against the start of `tokens`, returning the
+/// number of tokens consumed if it matched.
+fn match_sequence<'a, 'm>(
+    cx: ResolveContext<'_>,
+    matchers: &'m [Matcher],
+    tokens: &'a [ast::Token],
+    span: Span,
+    bindings: &mut Bindings<'a, 'm>,
+) -> compile::Result<Option<usize>> {
+    let mut n = 0;
+
+    for matcher in matchers {
+        match matcher {
+            Matcher::Token(expected) => {
+                let Some(token) = tokens.get(n) else {
+                    return Ok(None);
+                };
+
+                if !token_eq(cx, expected, token)? {
+                    return Ok(None);
+                }
+
+                n += 1;
+            }
+            Matcher::Group { delimiter, body } => {
+                match tokens.get(n) {
+                    Some(token) if token.kind == Kind::Open(*delimiter) => {}
+                    _ => return Ok(None),
+                }
+
+                let (inner, end) = group(tokens, n)?;
+
+                match match_sequence(cx, body, inner, span, bindings)? {
+                    Some(len) if len == inner.len() => {}
+                    _ => return Ok(None),
+                }
+
+                n = end;
+            }
+            Matcher::Fragment { name, fragment, .. } => {
+                let Some(len) = match_fragment(*fragment, &tokens[n..], span)? else {
+                    return Ok(None);
+                };
+
+                bindings.try_insert(
+                    name,
+                    Binding::Fragment {
+                        fragment: *fragment,
+                        tokens: &tokens[n..n + len],
+                    },
+                )?;
+
+                n += len;
+            }
+            Matcher::Repeat(repeat) => {
+                let mut iterations = Vec::new();
+
+                loop {
+                    if repeat.kleene == Kleene::ZeroOrOne && !iterations.is_empty() {
+                        break;
+                    }
+
+                    let mut start = n;
+
+                    if !iterations.is_empty() {
+                        if let Some(separator) = &repeat.separator {
+                            match tokens.get(start) {
+                                Some(token) if token_eq(cx, separator, token)? => start += 1,
+                                _ => break,
+                            }
+                        }
+                    }
+
+                    let mut inner = HashMap::new();
+
+                    let Some(len) =
+                        match_sequence(cx, &repeat.body, &tokens[start..], span, &mut inner)?
+                    else {
+                        break;
+                    };
+
+                    iterations.try_push(inner)?;
+                    n = start + len;
+
+                    // NB: Avoid looping forever over a body which matches
+                    // nothing.
+                    if len == 0 {
+                        break;
+                    }
+                }
+
+                if repeat.kleene == Kleene::OneOrMore && iterations.is_empty() {
+                    return Ok(None);
+                }
+
+                let mut names = Vec::new();
+                matcher_variables(&repeat.body, &mut names)?;
+
+                for name in names {
+                    let mut repeated = Vec::try_with_capacity(iterations.len())?;
+
+                    for iteration in &mut iterations {
+                        if let Some(binding) = iteration.remove(name) {
+                            repeated.try_push(binding)?;
+                        }
+                    }
+
+                    bindings.try_insert(name, Binding::Repeated(repeated))?;
+                }
+            }
+        }
+    }
+
+    Ok(Some(n))
+}
+
+/// Match a single fragment against the start of `tokens`, returning the
+/// number of tokens it consists of.
+fn match_fragment(
+    fragment: Fragment,
+    tokens: &[ast::Token],
+    span: Span,
+) -> compile::Result<Option<usize>> {
+    let Some(first) = tokens.first() else {
+        return Ok(None);
+    };
+
+    match fragment {
+        Fragment::Tt => {
+            return Ok(match first.kind {
+                Kind::Open(..) => Some(group(tokens, 0)?.1),
+                Kind::Close(..) => None,
+                _ => Some(1),
+            });
+        }
+        Fragment::Ident => {
+            return Ok(matches!(first.kind, Kind::Ident(..)).then_some(1));
+        }
+        Fragment::Lit => {
+            return Ok(matches!(
+                first.kind,
+                Kind::Number(..)
+                    | Kind::Str(..)
+                    | Kind::ByteStr(..)
+                    | Kind::Char(..)
+                    | Kind::Byte(..)
+                    | K![true]
+                    | K![false]
+            )
+            .then_some(1));
+        }
+        _ => {}
+    }
+
+    let stream = TokenStream::from(Vec::try_from(tokens)?);
+    let mut p = Parser::from_token_stream(&stream, span);
+
+    let parsed = match fragment {
+        Fragment::Block => p.parse::<ast::Block>().is_ok(),
+        Fragment::Expr => p.parse::<ast::Expr>().is_ok(),
+        Fragment::Pat => p.parse::<ast::Pat>().is_ok(),
+        Fragment::Path => p.parse::<ast::Path>().is_ok(),
+        Fragment::Ty => p.parse::<ast::Type>().is_ok(),
+        Fragment::Ident | Fragment::Lit | Fragment::Tt => false,
+    };
+
+    if !parsed {
+        return Ok(None);
+    }
+
+    let mut remaining = 0;
+
+    while !p.is_eof()? {
+        p.next()?;
+        remaining += 1;
+    }
+
+    Ok(Some(tokens.len() - remaining))
+}
+
+/// Get the source a token has been copied from, if any.
+fn text_source(kind: Kind) -> Option<SourceId> {
+    match kind {
+        Kind::Ident(ast::LitSource::Text(id)) | Kind::Label(ast::LitSource::Text(id)) => Some(id),
+        Kind::Byte(ast::CopySource::Text(id)) | Kind::Char(ast::CopySource::Text(id)) => Some(id),
+        Kind::Str(ast::StrSource::Text(text)) | Kind::ByteStr(ast::StrSource::Text(text)) => {
+            Some(text.source_id)
+        }
+        Kind::Number(ast::NumberSource::Text(text)) => Some(text.source_id),
+        _ => None,
+    }
+}
+
+/// Test if a token matches a token in a macro rule.
+fn token_eq(cx: ResolveContext<'_>, a: &ast::Token, b: &ast::Token) -> compile::Result<bool> {
+    if discriminant(&a.kind) != discriminant(&b.kind) {
+        return Ok(false);
+    }
+
+    match (a.kind, b.kind) {
+        (Kind::Ident(a_source), Kind::Ident(b_source)) => {
+            let a = ast::Ident {
+                span: a.span,
+                source: a_source,
+            };
+
+            let b = ast::Ident {
+                span: b.span,
+                source: b_source,
+            };
+
+            Ok(a.resolve(cx)? == b.resolve(cx)?)
+        }
+        (Kind::Label(a_source), Kind::Label(b_source)) => {
+            let a = ast::Label {
+                span: a.span,
+                source: a_source,
+            };
+
+            let b = ast::Label {
+                span: b.span,
+                source: b_source,
+            };
+
+            Ok(a.resolve(cx)? == b.resolve(cx)?)
+        }
+        _ => match (text_source(a.kind), text_source(b.kind)) {
+            (Some(a_id), Some(b_id)) => {
+                let a = cx.sources.source(a_id, a.span);
+                let b = cx.sources.source(b_id, b.span);
+                Ok(a.is_some() && a == b)
+            }
+            _ => Ok(a.kind == b.kind),
+        },
+    }
+}
+
+/// Look up the binding of a variable at the current repetition.
+fn lookup<'b, 'a>(binding: &'b Binding<'a>, indexes: &[usize]) -> Option<&'b Binding<'a>> {
+    let mut binding = binding;
+
+    for &index in indexes {
+        match binding {
+            Binding::Fragment { .. } => break,
+            Binding::Repeated(repeated) => {
+                binding = repeated.get(index)?;
+            }
+        }
+    }
+
+    Some(binding)
+}
+
+/// Calculate the number of times a repetition in a transcriber should be
+/// repeated, based on the variables used inside of it.
+fn repeat_count(
+    transcribers: &[Transcriber],
+    bindings: &Bindings<'_, '_>,
+    indexes: &[usize],
+    count: &mut Option<usize>,
+) -> compile::Result<()> {
+    for transcriber in transcribers {
+        match transcriber {
+            Transcriber::Token(..) => {}
+            Transcriber::Variable { span, name } => {
+                let Some(Binding::Repeated(repeated)) = bindings
+                    .get(&**name)
+                    .and_then(|binding| lookup(binding, indexes))
+                else {
+                    continue;
+                };
+
+                match *count {
+                    Some(len) if len != repeated.len() => {
+                        return Err(compile::Error::msg(
+                            span,
+                            try_format!(
+                                "Macro variable `${name}` repeats {} times, but another variable in the same repetition repeats {len} times",
+                                repeated.len()
+                            ),
+                        ));
+                    }
+                    Some(..) => {}
+                    None => {
+                        *count = Some(repeated.len());
+                    }
+                }
+            }
+            Transcriber::Repeat(repeat) => {
+                repeat_count(&repeat.body, bindings, indexes, count)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn transcribe(
+    transcribers: &[Transcriber],
+    bindings: &Bindings<'_, '_>,
+    indexes: &mut Vec<usize>,
+    output: &mut Vec<ast::Token>,
+) -> compile::Result<()> {
+    for transcriber in transcribers {
+        match transcriber {
+            Transcriber::Token(token) => {
+                output.try_push(*token)?;
+            }
+            Transcriber::Variable { span, name } => {
+                let binding = bindings
+                    .get(&**name)
+                    .and_then(|binding| lookup(binding, indexes));
+
+                let Some(Binding::Fragment { fragment, tokens }) = binding else {
+                    return Err(compile::Error::msg(
+                        span,
+                        try_format!("Macro variable `${name}` is still repeating at this depth"),
+                    ));
+                };
+
+                // NB: Expressions are wrapped in parenthesis to preserve their
+                // precedence where they are substituted.
+                if let (Fragment::Expr, [first, .., last]) = (fragment, tokens) {
+                    let span = first.span.join(last.span);
+
+                    output.try_push(ast::Token {
+                        span: span.head(),
+                        kind: Kind::Open(Delimiter::Parenthesis),
+                    })?;
+
+                    output.try_extend(tokens.iter().copied())?;
+
+                    output.try_push(ast::Token {
+                        span: span.tail(),
+                        kind: Kind::Close(Delimiter::Parenthesis),
+                    })?;
+                } else {
+                    output.try_extend(tokens.iter().copied())?;
+                }
+            }
+            Transcriber::Repeat(repeat) => {
+                let mut count = None;
+                repeat_count(&repeat.body, bindings, indexes, &mut count)?;
+
+                let Some(count) = count else {
+                    return Err(compile::Error::msg(
+                        repeat.span,
+                        "Repetition doesn't contain any variables which repeat at this depth",
+                    ));
+                };
+
+                if repeat.kleene == Kleene::ZeroOrOne && count > 1 {
+                    return Err(compile::Error::msg(
+                        repeat.span,
+                        "The `?` repetition can be repeated at most once",
+                    ));
+                }
+
+                for index in 0..count {
+                    if index > 0 {
+                        if let Some(separator) = repeat.separator {
+                            output.try_push(separator)?;
+                        }
+                    }
+
+                    indexes.try_push(index)?;
+                    transcribe(&repeat.body, bindings, indexes, output)?;
+                    indexes.pop();
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
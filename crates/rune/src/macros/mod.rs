@@ -108,6 +108,7 @@ mod format_args;
 mod into_lit;
 mod macro_compiler;
 mod macro_context;
+mod macro_rules;
 mod quote_fn;
 mod storage;
 mod token_stream;
@@ -118,6 +119,7 @@ pub(crate) use self::macro_compiler::MacroCompiler;
 #[cfg(feature = "std")]
 pub use self::macro_context::test;
 pub use self::macro_context::MacroContext;
+pub(crate) use self::macro_rules::MacroRules;
 pub use self::quote_fn::{quote_fn, Quote};
 pub(crate) use self::storage::Storage;
 pub use self::storage::{SyntheticId, SyntheticKind};
@@ -1,8 +1,6 @@
 use core::fmt;
 
-#[cfg(feature = "doc")]
 use crate::alloc::Box;
-#[cfg(feature = "doc")]
 use crate::compile::meta;
 use crate::compile::{ContextError, Docs};
 use crate::function_meta::FunctionArgs;
@@ -31,13 +29,11 @@ pub struct ItemFnMut<'a> {
     pub(super) docs: &'a mut Docs,
     #[cfg(feature = "doc")]
     pub(super) deprecated: &'a mut Option<Box<str>>,
-    #[cfg(feature = "doc")]
+    pub(super) replacement: &'a mut Option<Box<str>>,
     pub(super) is_async: &'a mut bool,
     #[cfg(feature = "doc")]
     pub(super) args: &'a mut Option<usize>,
-    #[cfg(feature = "doc")]
     pub(super) argument_types: &'a mut Box<[meta::DocType]>,
-    #[cfg(feature = "doc")]
     pub(super) return_type: &'a mut meta::DocType,
 }
 
@@ -51,12 +47,8 @@ impl ItemFnMut<'_> {
     }
 
     /// Mark the given item as an async function.
-    pub fn is_async(self, #[cfg_attr(not(feature = "doc"), allow(unused))] is_async: bool) -> Self {
-        #[cfg(feature = "doc")]
-        {
-            *self.is_async = is_async;
-        }
-
+    pub fn is_async(self, is_async: bool) -> Self {
+        *self.is_async = is_async;
         self
     }
 
@@ -73,6 +65,14 @@ impl ItemFnMut<'_> {
         Ok(self)
     }
 
+    /// Set the item which replaces this deprecated item, like `String::from`.
+    ///
+    /// The replacement is suggested wherever the deprecated item is used.
+    pub fn replaced_by(self, replacement: impl AsRef<str>) -> Result<Self, ContextError> {
+        *self.replacement = Some(replacement.as_ref().try_into()?);
+        Ok(self)
+    }
+
     /// Indicate the number of arguments this function accepts.
     pub fn args(self, #[cfg_attr(not(feature = "doc"), allow(unused))] args: usize) -> Self {
         #[cfg(feature = "doc")]
@@ -88,11 +88,7 @@ impl ItemFnMut<'_> {
     where
         T: MaybeTypeOf,
     {
-        #[cfg(feature = "doc")]
-        {
-            *self.return_type = T::maybe_type_of()?;
-        }
-
+        *self.return_type = T::maybe_type_of()?;
         Ok(self)
     }
 
@@ -101,9 +97,10 @@ impl ItemFnMut<'_> {
     where
         A: FunctionArgs,
     {
+        *self.argument_types = A::into_box()?;
+
         #[cfg(feature = "doc")]
         {
-            *self.argument_types = A::into_box()?;
             *self.args = Some(A::len());
         }
 
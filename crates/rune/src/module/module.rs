@@ -138,6 +138,7 @@ impl Module {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
         }
     }
@@ -213,6 +214,7 @@ impl Module {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
             type_parameters: T::PARAMETERS,
             type_info: T::type_info(),
@@ -416,6 +418,7 @@ impl Module {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
             kind: ModuleItemKind::Constant(value),
         })?;
@@ -459,6 +462,7 @@ impl Module {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
             kind: ModuleAssociatedKind::Constant(value),
         })?;
@@ -550,6 +554,7 @@ impl Module {
                     common: ModuleItemCommon {
                         docs,
                         deprecated: None,
+                        replacement: None,
                     },
                     kind: ModuleItemKind::Macro(ModuleMacro {
                         handler: data.handler,
@@ -575,6 +580,7 @@ impl Module {
                     common: ModuleItemCommon {
                         docs,
                         deprecated: None,
+                        replacement: None,
                     },
                     kind: ModuleItemKind::AttributeMacro(ModuleAttributeMacro {
                         handler: data.handler,
@@ -708,6 +714,7 @@ impl Module {
             common: ModuleItemCommon {
                 docs: Docs::EMPTY,
                 deprecated: None,
+                replacement: None,
             },
             kind: ModuleItemKind::AttributeMacro(ModuleAttributeMacro { handler }),
         })?;
@@ -1285,18 +1292,19 @@ impl Module {
         self.items.try_push(ModuleItem {
             item,
             hash,
-            common: ModuleItemCommon { docs, deprecated },
+            common: ModuleItemCommon {
+                docs,
+                deprecated,
+                replacement: None,
+            },
             kind: ModuleItemKind::Function(ModuleFunction {
                 handler: data.handler,
                 trait_hash: None,
                 doc: DocFunction {
-                    #[cfg(feature = "doc")]
                     is_async: data.is_async,
                     #[cfg(feature = "doc")]
                     args: data.args,
-                    #[cfg(feature = "doc")]
                     return_type: data.return_type,
-                    #[cfg(feature = "doc")]
                     argument_types: data.argument_types,
                 },
             }),
@@ -1304,7 +1312,6 @@ impl Module {
 
         let last = self.items.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleItemKind::Function(f) => f,
             _ => unreachable!(),
@@ -1314,13 +1321,11 @@ impl Module {
             docs: &mut last.common.docs,
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
-            #[cfg(feature = "doc")]
+            replacement: &mut last.common.replacement,
             is_async: &mut last_fn.doc.is_async,
             #[cfg(feature = "doc")]
             args: &mut last_fn.doc.args,
-            #[cfg(feature = "doc")]
             return_type: &mut last_fn.doc.return_type,
-            #[cfg(feature = "doc")]
             argument_types: &mut last_fn.doc.argument_types,
         })
     }
@@ -1338,18 +1343,19 @@ impl Module {
             container: data.associated.container,
             container_type_info: data.associated.container_type_info,
             name: data.associated.name,
-            common: ModuleItemCommon { docs, deprecated },
+            common: ModuleItemCommon {
+                docs,
+                deprecated,
+                replacement: None,
+            },
             kind: ModuleAssociatedKind::Function(ModuleFunction {
                 handler: data.handler,
                 trait_hash: None,
                 doc: DocFunction {
-                    #[cfg(feature = "doc")]
                     is_async: data.is_async,
                     #[cfg(feature = "doc")]
                     args: data.args,
-                    #[cfg(feature = "doc")]
                     return_type: data.return_type,
-                    #[cfg(feature = "doc")]
                     argument_types: data.argument_types,
                 },
             }),
@@ -1357,7 +1363,6 @@ impl Module {
 
         let last = self.associated.last_mut().unwrap();
 
-        #[cfg(feature = "doc")]
         let last_fn = match &mut last.kind {
             ModuleAssociatedKind::Function(f) => f,
             _ => unreachable!(),
@@ -1367,13 +1372,11 @@ impl Module {
             docs: &mut last.common.docs,
             #[cfg(feature = "doc")]
             deprecated: &mut last.common.deprecated,
-            #[cfg(feature = "doc")]
+            replacement: &mut last.common.replacement,
             is_async: &mut last_fn.doc.is_async,
             #[cfg(feature = "doc")]
             args: &mut last_fn.doc.args,
-            #[cfg(feature = "doc")]
             return_type: &mut last_fn.doc.return_type,
-            #[cfg(feature = "doc")]
             argument_types: &mut last_fn.doc.argument_types,
         })
     }
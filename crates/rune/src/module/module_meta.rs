@@ -190,15 +190,12 @@ pub(crate) struct ModuleItem {
 
 #[derive(Default, TryClone)]
 pub(crate) struct DocFunction {
-    #[cfg(feature = "doc")]
     #[try_clone(copy)]
     pub(crate) is_async: bool,
     #[cfg(feature = "doc")]
     #[try_clone(copy)]
     pub(crate) args: Option<usize>,
-    #[cfg(feature = "doc")]
     pub(crate) argument_types: Box<[meta::DocType]>,
-    #[cfg(feature = "doc")]
     pub(crate) return_type: meta::DocType,
 }
 
@@ -224,6 +221,8 @@ pub(crate) struct ModuleItemCommon {
     pub(crate) docs: Docs,
     /// Deprecation marker for the item.
     pub(crate) deprecated: Option<Box<str>>,
+    /// The item which replaces a deprecated item, like `String::from`.
+    pub(crate) replacement: Option<Box<str>>,
 }
 
 #[derive(TryClone)]
@@ -86,13 +86,11 @@ impl TraitMut<'_> {
             docs: &mut f.common.docs,
             #[cfg(feature = "doc")]
             deprecated: &mut f.common.deprecated,
-            #[cfg(feature = "doc")]
+            replacement: &mut f.common.replacement,
             is_async: &mut f.doc.is_async,
             #[cfg(feature = "doc")]
             args: &mut f.doc.args,
-            #[cfg(feature = "doc")]
             return_type: &mut f.doc.return_type,
-            #[cfg(feature = "doc")]
             argument_types: &mut f.doc.argument_types,
         })
     }
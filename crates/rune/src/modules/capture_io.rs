@@ -7,6 +7,7 @@
 //! use rune::modules::capture_io::{self, CaptureIo};
 //!
 //! let io = CaptureIo::new();
+//! io.push_stdin(b"input read by std::io::stdin()\n");
 //!
 //! let mut context = rune::Context::with_config(false)?;
 //! context.install(capture_io::module(&io)?)?;
@@ -14,7 +15,12 @@
 //! ```
 
 use core::mem::take;
+#[cfg(all(feature = "std", feature = "tokio"))]
+use core::pin::Pin;
+#[cfg(all(feature = "std", feature = "tokio"))]
+use core::task::{Context, Poll};
 
+use ::rust_alloc::collections::VecDeque;
 use ::rust_alloc::sync::Arc;
 
 use parking_lot::Mutex;
@@ -62,13 +68,26 @@ pub fn module(io: &CaptureIo) -> Result<Module, ContextError> {
         })
         .build()?;
 
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    {
+        let stdin = crate::modules::io::Stdin::new(CaptureStdin {
+            input: io.input.clone(),
+        });
+
+        module.function("stdin", move || stdin.clone()).build()?;
+    }
+
     Ok(module)
 }
 
 /// Type which captures output from rune scripts.
+///
+/// It also provides the input read through `std::io::stdin()`, which can be
+/// injected with [`CaptureIo::push_stdin`].
 #[derive(Default, Clone)]
 pub struct CaptureIo {
     inner: Arc<Mutex<Vec<u8>>>,
+    input: Arc<Mutex<VecDeque<u8>>>,
 }
 
 impl CaptureIo {
@@ -109,6 +128,37 @@ impl CaptureIo {
     pub fn drain_utf8(&self) -> Result<String, FromUtf8Error> {
         String::from_utf8(self.drain())
     }
+
+    /// Push input which will be read through `std::io::stdin()`.
+    ///
+    /// Once all input has been read, standard input reports that it has
+    /// reached its end.
+    pub fn push_stdin(&self, input: &[u8]) {
+        self.input.lock().extend(input);
+    }
+}
+
+/// Standard input which reads from the input of a [`CaptureIo`].
+#[cfg(all(feature = "std", feature = "tokio"))]
+struct CaptureStdin {
+    input: Arc<Mutex<VecDeque<u8>>>,
+}
+
+#[cfg(all(feature = "std", feature = "tokio"))]
+impl tokio::io::AsyncRead for CaptureStdin {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        _: &mut Context<'_>,
+        buf: &mut tokio::io::ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let mut input = self.input.lock();
+        let n = buf.remaining().min(input.len());
+
+        buf.put_slice(&input.make_contiguous()[..n]);
+        input.drain(..n);
+
+        Poll::Ready(Ok(()))
+    }
 }
 
 fn dbg_impl(
@@ -0,0 +1,642 @@
+use core::cmp::Ordering;
+use core::mem;
+
+use crate as rune;
+use crate::alloc;
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::runtime::slice::Iter;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, Function, Iterator, ProtocolCaller, Ref, Value, Vec, VmResult,
+};
+use crate::{Any, ContextError, Module};
+
+/// A priority queue implemented with a binary heap.
+#[rune::module(::std::collections::binary_heap)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.ty::<BinaryHeap>()?;
+    m.function_meta(BinaryHeap::new__meta)?;
+    m.function_meta(BinaryHeap::with_capacity__meta)?;
+    m.function_meta(BinaryHeap::with_comparator__meta)?;
+    m.function_meta(BinaryHeap::len__meta)?;
+    m.function_meta(BinaryHeap::is_empty__meta)?;
+    m.function_meta(BinaryHeap::capacity__meta)?;
+    m.function_meta(BinaryHeap::push__meta)?;
+    m.function_meta(BinaryHeap::pop__meta)?;
+    m.function_meta(BinaryHeap::peek__meta)?;
+    m.function_meta(BinaryHeap::clear__meta)?;
+    m.function_meta(BinaryHeap::extend__meta)?;
+    m.function_meta(BinaryHeap::into_sorted_vec__meta)?;
+    m.function_meta(BinaryHeap::into_vec__meta)?;
+    m.function_meta(BinaryHeap::iter__meta)?;
+    m.function_meta(BinaryHeap::into_iter__meta)?;
+    m.function_meta(BinaryHeap::from_iter__meta)?;
+    m.function_meta(BinaryHeap::debug_fmt__meta)?;
+
+    m.function_meta(BinaryHeap::clone__meta)?;
+    m.implement_trait::<BinaryHeap>(rune::item!(::std::clone::Clone))?;
+
+    Ok(m)
+}
+
+/// A priority queue implemented with a binary heap.
+///
+/// This will be a max-heap, so [`BinaryHeap::pop`] always returns the
+/// greatest element. Elements are ordered through the [`CMP`] protocol, unless
+/// the heap was constructed with a custom comparator through
+/// [`BinaryHeap::with_comparator`].
+///
+/// It is a logic error for an element to be modified in such a way that its
+/// ordering relative to any other element changes while it is in the heap.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BinaryHeap;
+///
+/// let heap = BinaryHeap::new();
+///
+/// heap.push(1);
+/// heap.push(5);
+/// heap.push(2);
+///
+/// assert_eq!(heap.peek(), Some(5));
+/// assert_eq!(heap.len(), 3);
+///
+/// assert_eq!(heap.pop(), Some(5));
+/// assert_eq!(heap.pop(), Some(2));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), None);
+/// ```
+#[derive(Any)]
+#[rune(item = ::std::collections::binary_heap)]
+pub(crate) struct BinaryHeap {
+    data: alloc::Vec<Value>,
+    comparator: Option<Function>,
+}
+
+impl BinaryHeap {
+    /// Creates an empty `BinaryHeap` as a max-heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// heap.push(4);
+    /// assert_eq!(heap.pop(), Some(4));
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub(crate) fn new() -> Self {
+        Self {
+            data: alloc::Vec::new(),
+            comparator: None,
+        }
+    }
+
+    /// Creates an empty `BinaryHeap` with at least the specified capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::with_capacity(10);
+    /// assert!(heap.capacity() >= 10);
+    /// heap.push(4);
+    /// ```
+    #[rune::function(keep, path = Self::with_capacity)]
+    pub(crate) fn with_capacity(capacity: usize) -> VmResult<Self> {
+        VmResult::Ok(Self {
+            data: vm_try!(alloc::Vec::try_with_capacity(capacity)),
+            comparator: None,
+        })
+    }
+
+    /// Creates an empty `BinaryHeap` which orders its elements using the given
+    /// comparator.
+    ///
+    /// The comparator is called with two elements and must return an
+    /// [`Ordering`]. The element which compares as the greatest is the one
+    /// returned first by [`BinaryHeap::pop`], so reversing the comparison
+    /// turns the heap into a min-heap.
+    ///
+    /// [`Ordering`]: crate::cmp::Ordering
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    /// use std::ops::cmp;
+    ///
+    /// let heap = BinaryHeap::with_comparator(|a, b| cmp(b, a));
+    ///
+    /// heap.push(3);
+    /// heap.push(1);
+    /// heap.push(2);
+    ///
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), Some(2));
+    /// assert_eq!(heap.pop(), Some(3));
+    /// ```
+    #[rune::function(keep, path = Self::with_comparator)]
+    fn with_comparator(comparator: Function) -> Self {
+        Self {
+            data: alloc::Vec::new(),
+            comparator: Some(comparator),
+        }
+    }
+
+    /// Returns the length of the binary heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    /// assert_eq!(heap.len(), 2);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Checks if the binary heap is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// assert!(heap.is_empty());
+    ///
+    /// heap.push(3);
+    /// assert!(!heap.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Returns the number of elements the binary heap can hold without
+    /// reallocating.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::with_capacity(100);
+    /// assert!(heap.capacity() >= 100);
+    /// ```
+    #[rune::function(keep)]
+    fn capacity(&self) -> usize {
+        self.data.capacity()
+    }
+
+    /// Pushes an item onto the binary heap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the item is not comparable with the items already in the
+    /// heap, or if the custom comparator errors. The heap is left unchanged if
+    /// this happens.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// heap.push(3);
+    /// heap.push(5);
+    /// heap.push(1);
+    ///
+    /// assert_eq!(heap.len(), 3);
+    /// assert_eq!(heap.peek(), Some(5));
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn push(&mut self, item: Value) -> VmResult<()> {
+        self.push_with(item, &mut EnvProtocolCaller)
+    }
+
+    fn push_with(&mut self, item: Value, caller: &mut dyn ProtocolCaller) -> VmResult<()> {
+        let pos = self.data.len();
+        vm_try!(self.data.try_push(item));
+
+        if let VmResult::Err(error) = self.sift_up(pos, caller) {
+            self.data.pop();
+            return VmResult::Err(error);
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Removes the greatest item from the binary heap and returns it, or
+    /// `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    ///
+    /// assert_eq!(heap.pop(), Some(3));
+    /// assert_eq!(heap.pop(), Some(1));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    #[rune::function(keep)]
+    fn pop(&mut self) -> VmResult<Option<Value>> {
+        let Some(mut item) = self.data.pop() else {
+            return VmResult::Ok(None);
+        };
+
+        if let Some(first) = self.data.first_mut() {
+            mem::swap(&mut item, first);
+            let end = self.data.len();
+
+            if let VmResult::Err(error) = self.sift_down(0, end, &mut EnvProtocolCaller) {
+                let last = mem::replace(&mut self.data[0], item);
+                vm_try!(self.data.try_push(last));
+                return VmResult::Err(error);
+            }
+        }
+
+        VmResult::Ok(Some(item))
+    }
+
+    /// Returns the greatest item in the binary heap, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// assert_eq!(heap.peek(), None);
+    ///
+    /// heap.push(1);
+    /// heap.push(5);
+    /// heap.push(2);
+    /// assert_eq!(heap.peek(), Some(5));
+    /// ```
+    #[rune::function(keep)]
+    fn peek(&self) -> Option<Value> {
+        self.data.first().cloned()
+    }
+
+    /// Drops all items from the binary heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 3]);
+    /// assert!(!heap.is_empty());
+    ///
+    /// heap.clear();
+    /// assert!(heap.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    /// Extend the binary heap from an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::new();
+    /// heap.extend([3, 1, 2]);
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3]);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> VmResult<()> {
+        let caller: &mut dyn ProtocolCaller = &mut EnvProtocolCaller;
+        let mut it = vm_try!(value.into_iter());
+
+        while let Some(value) = vm_try!(it.next()) {
+            vm_try!(self.push_with(value, caller));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Consumes the `BinaryHeap` and returns a vector in sorted (ascending)
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 4, 5, 7]);
+    /// heap.push(6);
+    /// heap.push(3);
+    ///
+    /// assert_eq!(heap.into_sorted_vec(), [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    #[rune::function(keep)]
+    fn into_sorted_vec(mut self) -> VmResult<Vec> {
+        let caller: &mut dyn ProtocolCaller = &mut EnvProtocolCaller;
+        let mut end = self.data.len();
+
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end);
+            vm_try!(self.sift_down(0, end, caller));
+        }
+
+        VmResult::Ok(Vec::from(self.data))
+    }
+
+    /// Consumes the `BinaryHeap` and returns the underlying vector in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 3, 4, 5, 6, 7]);
+    /// let vec = heap.into_vec();
+    ///
+    /// assert_eq!(vec.len(), 7);
+    /// ```
+    #[rune::function(keep)]
+    fn into_vec(self) -> Vec {
+        Vec::from(self.data)
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 3, 4]);
+    ///
+    /// let values = heap.iter().collect::<Vec>();
+    /// values.sort();
+    /// assert_eq!(values, [1, 2, 3, 4]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::iter)]
+    fn iter(this: Ref<Self>) -> Iter {
+        Iter::new(Ref::map(this, |heap| &*heap.data))
+    }
+
+    /// Returns an iterator visiting all values in the underlying vector, in
+    /// arbitrary order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1, 2, 3, 4]);
+    /// let sum = 0;
+    ///
+    /// for value in heap {
+    ///     sum += value;
+    /// }
+    ///
+    /// assert_eq!(sum, 10);
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_ITER, path = Self)]
+    fn into_iter(this: Ref<Self>) -> Iter {
+        Self::iter(this)
+    }
+
+    /// Convert a binary heap from a value convert into an iterator.
+    ///
+    /// The heap can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter(["b", "c", "a"]);
+    /// assert_eq!(heap.peek(), Some("c"));
+    /// ```
+    #[rune::function(keep, path = Self::from_iter)]
+    fn from_iter(it: Iterator) -> VmResult<Self> {
+        Self::from_iter_with(it, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn from_iter_with(
+        mut it: Iterator,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<Self> {
+        let (cap, _) = vm_try!(it.size_hint());
+        let mut data = vm_try!(alloc::Vec::try_with_capacity(cap));
+
+        while let Some(value) = vm_try!(it.next()) {
+            vm_try!(data.try_push(value));
+        }
+
+        let mut heap = Self {
+            data,
+            comparator: None,
+        };
+
+        vm_try!(heap.rebuild(caller));
+        VmResult::Ok(heap)
+    }
+
+    /// Debug format the binary heap.
+    ///
+    /// Elements are printed in the order they are stored in the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let heap = BinaryHeap::from_iter([1]);
+    /// assert_eq!(format!("{:?}", heap), "[1]");
+    /// ```
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> VmResult<()> {
+        self.debug_fmt_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<()> {
+        vm_try!(vm_write!(f, "["));
+
+        let mut it = self.data.iter().peekable();
+
+        while let Some(value) = it.next() {
+            vm_try!(value.debug_fmt_with(f, caller));
+
+            if it.peek().is_some() {
+                vm_try!(vm_write!(f, ", "));
+            }
+        }
+
+        vm_try!(vm_write!(f, "]"));
+        VmResult::Ok(())
+    }
+
+    /// Clone the binary heap.
+    ///
+    /// The clone shares the comparator of the original heap.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BinaryHeap;
+    ///
+    /// let a = BinaryHeap::from_iter([1, 2]);
+    /// let b = a.clone();
+    ///
+    /// b.push(3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &BinaryHeap) -> VmResult<BinaryHeap> {
+        VmResult::Ok(Self {
+            data: vm_try!(this.data.try_clone()),
+            comparator: vm_try!(this.comparator.try_clone()),
+        })
+    }
+
+    /// Compare two elements, either through the custom comparator or the
+    /// [`CMP`] protocol.
+    fn compare(
+        comparator: Option<&Function>,
+        a: &Value,
+        b: &Value,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<Ordering> {
+        match comparator {
+            Some(comparator) => comparator.call::<Ordering>((a, b)),
+            None => Value::cmp_with(a, b, caller),
+        }
+    }
+
+    /// Move the element at `pos` up towards the root until its parent is
+    /// greater than or equal to it.
+    ///
+    /// The final position is found before any element is moved, so that the
+    /// heap is left unchanged if a comparison errors.
+    fn sift_up(&mut self, pos: usize, caller: &mut dyn ProtocolCaller) -> VmResult<()> {
+        let mut target = pos;
+
+        while target > 0 {
+            let parent = (target - 1) / 2;
+
+            let ordering = vm_try!(Self::compare(
+                self.comparator.as_ref(),
+                &self.data[pos],
+                &self.data[parent],
+                caller
+            ));
+
+            if ordering != Ordering::Greater {
+                break;
+            }
+
+            target = parent;
+        }
+
+        let mut hole = pos;
+
+        while hole > target {
+            let parent = (hole - 1) / 2;
+            self.data.swap(hole, parent);
+            hole = parent;
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Move the element at `pos` down until both of its children in the range
+    /// `..end` are less than or equal to it.
+    ///
+    /// The path the element takes is found before any element is moved, so
+    /// that the heap is left unchanged if a comparison errors.
+    fn sift_down(
+        &mut self,
+        pos: usize,
+        end: usize,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<()> {
+        let mut path = alloc::Vec::new();
+        let mut hole = pos;
+
+        loop {
+            let mut child = 2 * hole + 1;
+
+            if child >= end {
+                break;
+            }
+
+            if child + 1 < end {
+                let ordering = vm_try!(Self::compare(
+                    self.comparator.as_ref(),
+                    &self.data[child + 1],
+                    &self.data[child],
+                    caller
+                ));
+
+                if ordering == Ordering::Greater {
+                    child += 1;
+                }
+            }
+
+            let ordering = vm_try!(Self::compare(
+                self.comparator.as_ref(),
+                &self.data[child],
+                &self.data[pos],
+                caller
+            ));
+
+            if ordering != Ordering::Greater {
+                break;
+            }
+
+            vm_try!(path.try_push(child));
+            hole = child;
+        }
+
+        let mut hole = pos;
+
+        for child in path {
+            self.data.swap(hole, child);
+            hole = child;
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Restore the heap property over the whole underlying vector.
+    fn rebuild(&mut self, caller: &mut dyn ProtocolCaller) -> VmResult<()> {
+        let end = self.data.len();
+
+        for pos in (0..end / 2).rev() {
+            vm_try!(self.sift_down(pos, end, caller));
+        }
+
+        VmResult::Ok(())
+    }
+}
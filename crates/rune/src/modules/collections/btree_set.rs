@@ -0,0 +1,658 @@
+use core::iter;
+
+use crate as rune;
+use crate::alloc::btree_map::{self, Entry, IterRaw, RangeRaw};
+use crate::alloc::fmt::TryWrite;
+use crate::alloc::prelude::*;
+use crate::runtime::{
+    EnvProtocolCaller, Formatter, Iterator, ProtocolCaller, RawAnyGuard, Ref, Value, VmResult,
+};
+use crate::{Any, ContextError, Module};
+
+use super::btree_map::{cmp, range_bounds};
+
+/// A dynamic ordered set.
+#[rune::module(::std::collections::btree_set)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.ty::<BTreeSet>()?;
+    m.function_meta(BTreeSet::new__meta)?;
+    m.function_meta(BTreeSet::len__meta)?;
+    m.function_meta(BTreeSet::is_empty__meta)?;
+    m.function_meta(BTreeSet::insert__meta)?;
+    m.function_meta(BTreeSet::remove__meta)?;
+    m.function_meta(BTreeSet::contains__meta)?;
+    m.function_meta(BTreeSet::clear__meta)?;
+    m.function_meta(BTreeSet::first__meta)?;
+    m.function_meta(BTreeSet::last__meta)?;
+    m.function_meta(BTreeSet::pop_first__meta)?;
+    m.function_meta(BTreeSet::pop_last__meta)?;
+    m.function_meta(BTreeSet::range__meta)?;
+    m.function_meta(BTreeSet::extend__meta)?;
+    m.function_meta(BTreeSet::iter__meta)?;
+    m.function_meta(BTreeSet::into_iter__meta)?;
+    m.function_meta(BTreeSet::from_iter__meta)?;
+    m.function_meta(BTreeSet::debug_fmt__meta)?;
+
+    m.function_meta(BTreeSet::clone__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::clone::Clone))?;
+
+    m.function_meta(BTreeSet::partial_eq__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::cmp::PartialEq))?;
+
+    m.function_meta(BTreeSet::eq__meta)?;
+    m.implement_trait::<BTreeSet>(rune::item!(::std::cmp::Eq))?;
+
+    m.ty::<Iter>()?;
+    m.function_meta(Iter::next__meta)?;
+    m.function_meta(Iter::size_hint__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(Iter::next_back__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::DoubleEndedIterator))?;
+    m.function_meta(Iter::len__meta)?;
+    m.implement_trait::<Iter>(rune::item!(::std::iter::ExactSizeIterator))?;
+
+    m.ty::<RangeIter>()?;
+    m.function_meta(RangeIter::next__meta)?;
+    m.implement_trait::<RangeIter>(rune::item!(::std::iter::Iterator))?;
+    m.function_meta(RangeIter::next_back__meta)?;
+    m.implement_trait::<RangeIter>(rune::item!(::std::iter::DoubleEndedIterator))?;
+
+    Ok(m)
+}
+
+/// An ordered set based on a B-Tree.
+///
+/// This is implemented as a [`BTreeMap`] where the value is `()`, so values
+/// are kept sorted and iteration always happens in ascending order.
+///
+/// As with the [`BTreeMap`] type, a `BTreeSet` requires that its values
+/// implement the [`CMP`] protocol, and that the ordering it defines is total.
+///
+/// [`BTreeMap`]: crate::collections::BTreeMap
+///
+/// # Examples
+///
+/// ```rune
+/// use std::collections::BTreeSet;
+///
+/// let set = BTreeSet::new();
+///
+/// set.insert(3);
+/// set.insert(1);
+/// set.insert(2);
+///
+/// assert!(set.contains(1));
+/// assert!(!set.contains(4));
+/// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+/// ```
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set)]
+pub(crate) struct BTreeSet {
+    map: btree_map::BTreeMap<Value, ()>,
+}
+
+impl BTreeSet {
+    /// Makes a new, empty `BTreeSet`.
+    ///
+    /// Does not allocate anything on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// assert!(set.is_empty());
+    /// ```
+    #[rune::function(keep, path = Self::new)]
+    pub(crate) fn new() -> Self {
+        Self {
+            map: btree_map::BTreeMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// assert_eq!(v.len(), 0);
+    /// v.insert(1);
+    /// assert_eq!(v.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// assert!(v.is_empty());
+    /// v.insert(1);
+    /// assert!(!v.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain an equal value, `true` is
+    ///   returned.
+    /// - If the set already contained an equal value, `false` is returned, and
+    ///   the entry is not updated.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// assert_eq!(set.insert(2), true);
+    /// assert_eq!(set.insert(2), false);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[rune::function(keep)]
+    pub(crate) fn insert(&mut self, key: Value) -> VmResult<bool> {
+        self.insert_with(key, &mut EnvProtocolCaller)
+    }
+
+    fn insert_with(&mut self, key: Value, caller: &mut dyn ProtocolCaller) -> VmResult<bool> {
+        match vm_try!(self.map.entry_with(caller, key, cmp)) {
+            Entry::Occupied(..) => VmResult::Ok(false),
+            Entry::Vacant(entry) => {
+                vm_try!(entry.try_insert(()));
+                VmResult::Ok(true)
+            }
+        }
+    }
+
+    /// If the set contains an element equal to the value, removes it from the
+    /// set and drops it. Returns whether such an element was present.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// set.insert(2);
+    /// assert!(set.remove(2));
+    /// assert!(!set.remove(2));
+    /// ```
+    #[rune::function(keep)]
+    fn remove(&mut self, key: Value) -> VmResult<bool> {
+        let caller: &mut dyn ProtocolCaller = &mut EnvProtocolCaller;
+        let entry = vm_try!(self.map.remove_entry_with(caller, &key, cmp));
+        VmResult::Ok(entry.is_some())
+    }
+
+    /// Returns `true` if the set contains an element equal to the value.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([1, 2, 3]);
+    /// assert_eq!(set.contains(1), true);
+    /// assert_eq!(set.contains(4), false);
+    /// ```
+    #[rune::function(keep)]
+    fn contains(&self, key: Value) -> VmResult<bool> {
+        let caller: &mut dyn ProtocolCaller = &mut EnvProtocolCaller;
+        let value = vm_try!(self.map.get_with(caller, &key, cmp));
+        VmResult::Ok(value.is_some())
+    }
+
+    /// Clears the set, removing all values.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let v = BTreeSet::new();
+    /// v.insert(1);
+    /// v.clear();
+    /// assert!(v.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Returns the first element in the set, if any. This element is always
+    /// the minimum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// assert_eq!(set.first(), None);
+    /// set.insert(1);
+    /// assert_eq!(set.first(), Some(1));
+    /// set.insert(2);
+    /// assert_eq!(set.first(), Some(1));
+    /// ```
+    #[rune::function(keep)]
+    fn first(&self) -> Option<Value> {
+        let (key, ()) = self.map.first_key_value()?;
+        Some(key.clone())
+    }
+
+    /// Returns the last element in the set, if any. This element is always the
+    /// maximum of all elements in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// assert_eq!(set.last(), None);
+    /// set.insert(1);
+    /// assert_eq!(set.last(), Some(1));
+    /// set.insert(2);
+    /// assert_eq!(set.last(), Some(2));
+    /// ```
+    #[rune::function(keep)]
+    fn last(&self) -> Option<Value> {
+        let (key, ()) = self.map.last_key_value()?;
+        Some(key.clone())
+    }
+
+    /// Removes the first element from the set and returns it, if any. The
+    /// first element is always the minimum element in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// set.insert(1);
+    ///
+    /// while let Some(n) = set.pop_first() {
+    ///     assert_eq!(n, 1);
+    /// }
+    ///
+    /// assert!(set.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn pop_first(&mut self) -> Option<Value> {
+        let (key, ()) = self.map.pop_first()?;
+        Some(key)
+    }
+
+    /// Removes the last element from the set and returns it, if any. The last
+    /// element is always the maximum element in the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    ///
+    /// set.insert(1);
+    ///
+    /// while let Some(n) = set.pop_last() {
+    ///     assert_eq!(n, 1);
+    /// }
+    ///
+    /// assert!(set.is_empty());
+    /// ```
+    #[rune::function(keep)]
+    fn pop_last(&mut self) -> Option<Value> {
+        let (key, ()) = self.map.pop_last()?;
+        Some(key)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of elements in the
+    /// set.
+    ///
+    /// The range is specified using range syntax, so `range(4..8)` yields
+    /// elements from `4` (inclusive) to `8` (exclusive).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the start of the range is greater than its end.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 5, 8]);
+    ///
+    /// assert_eq!(set.range(4..).collect::<Vec>(), [5, 8]);
+    /// assert_eq!(set.range(..=5).rev().collect::<Vec>(), [5, 3]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::range)]
+    fn range(this: Ref<Self>, range: Value) -> VmResult<RangeIter> {
+        let caller: &mut dyn ProtocolCaller = &mut EnvProtocolCaller;
+        let bounds = vm_try!(range_bounds(&range, caller));
+        // SAFETY: We're holding onto the reference guard.
+        let iter = vm_try!(unsafe { this.map.range_raw_with(caller, bounds, cmp) });
+        let (_, guard) = Ref::into_raw(this);
+        VmResult::Ok(RangeIter { iter, guard })
+    }
+
+    /// Extend this set from an iterator.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::new();
+    /// set.extend([3, 1, 2]);
+    /// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+    /// ```
+    #[rune::function(keep)]
+    fn extend(&mut self, value: Value) -> VmResult<()> {
+        let mut it = vm_try!(value.into_iter());
+
+        while let Some(value) = vm_try!(it.next()) {
+            vm_try!(self.insert(value));
+        }
+
+        VmResult::Ok(())
+    }
+
+    /// Gets an iterator that visits the elements in the set in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 1, 2]);
+    /// assert_eq!(set.iter().collect::<Vec>(), [1, 2, 3]);
+    /// assert_eq!(set.iter().rev().collect::<Vec>(), [3, 2, 1]);
+    /// ```
+    #[rune::function(keep, instance, path = Self::iter)]
+    fn iter(this: Ref<Self>) -> Iter {
+        // SAFETY: We're holding onto the reference guard.
+        let iter = unsafe { this.map.iter_raw() };
+        let (_, guard) = Ref::into_raw(this);
+        Iter { iter, guard }
+    }
+
+    /// Gets an iterator that visits the elements in the set in ascending
+    /// order.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([3, 1, 2]);
+    ///
+    /// let values = [];
+    ///
+    /// for value in set {
+    ///     values.push(value);
+    /// }
+    ///
+    /// assert_eq!(values, [1, 2, 3]);
+    /// ```
+    #[rune::function(keep, instance, protocol = INTO_ITER, path = Self)]
+    fn into_iter(this: Ref<Self>) -> Iter {
+        Self::iter(this)
+    }
+
+    /// Convert a set from a value convert into an iterator.
+    ///
+    /// The set can be converted from anything that implements the
+    /// [`INTO_ITER`] protocol.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter(["b", "a", "b"]);
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(set.first(), Some("a"));
+    /// ```
+    #[rune::function(keep, path = Self::from_iter)]
+    fn from_iter(it: Iterator) -> VmResult<BTreeSet> {
+        Self::from_iter_with(it, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn from_iter_with(
+        mut it: Iterator,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<Self> {
+        let mut set = Self::new();
+
+        while let Some(value) = vm_try!(it.next()) {
+            vm_try!(set.insert_with(value, caller));
+        }
+
+        VmResult::Ok(set)
+    }
+
+    /// Debug format the current set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let set = BTreeSet::from_iter([2, 1]);
+    /// assert_eq!(format!("{:?}", set), "{1, 2}");
+    /// ```
+    #[rune::function(keep, protocol = DEBUG_FMT)]
+    fn debug_fmt(&self, f: &mut Formatter) -> VmResult<()> {
+        self.debug_fmt_with(f, &mut EnvProtocolCaller)
+    }
+
+    pub(crate) fn debug_fmt_with(
+        &self,
+        f: &mut Formatter,
+        caller: &mut dyn ProtocolCaller,
+    ) -> VmResult<()> {
+        vm_try!(vm_write!(f, "{{"));
+
+        let mut it = self.map.keys().peekable();
+
+        while let Some(value) = it.next() {
+            vm_try!(value.debug_fmt_with(f, caller));
+
+            if it.peek().is_some() {
+                vm_try!(vm_write!(f, ", "));
+            }
+        }
+
+        vm_try!(vm_write!(f, "}}"));
+        VmResult::Ok(())
+    }
+
+    /// Clone the set.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = a.clone();
+    ///
+    /// b.insert(3);
+    ///
+    /// assert_eq!(a.len(), 2);
+    /// assert_eq!(b.len(), 3);
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &BTreeSet) -> VmResult<BTreeSet> {
+        VmResult::Ok(Self {
+            map: vm_try!(this.map.try_clone()),
+        })
+    }
+
+    /// Perform a partial equality check over two sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = BTreeSet::from_iter([2, 1]);
+    /// let c = BTreeSet::from_iter([1, 3]);
+    ///
+    /// assert!(a == b);
+    /// assert!(a != c);
+    /// ```
+    #[rune::function(keep, protocol = PARTIAL_EQ)]
+    fn partial_eq(&self, other: &Self) -> VmResult<bool> {
+        self.partial_eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn partial_eq_with(&self, other: &Self, caller: &mut dyn ProtocolCaller) -> VmResult<bool> {
+        if self.map.len() != other.map.len() {
+            return VmResult::Ok(false);
+        }
+
+        for (a, b) in self.map.keys().zip(other.map.keys()) {
+            if !vm_try!(Value::partial_eq_with(a, b, caller)) {
+                return VmResult::Ok(false);
+            }
+        }
+
+        VmResult::Ok(true)
+    }
+
+    /// Perform a total equality check over two sets.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::collections::BTreeSet;
+    /// use std::ops::eq;
+    ///
+    /// let a = BTreeSet::from_iter([1, 2]);
+    /// let b = BTreeSet::from_iter([2, 1]);
+    ///
+    /// assert!(eq(a, b));
+    /// ```
+    #[rune::function(keep, protocol = EQ)]
+    fn eq(&self, other: &Self) -> VmResult<bool> {
+        self.eq_with(other, &mut EnvProtocolCaller)
+    }
+
+    fn eq_with(&self, other: &Self, caller: &mut dyn ProtocolCaller) -> VmResult<bool> {
+        if self.map.len() != other.map.len() {
+            return VmResult::Ok(false);
+        }
+
+        for (a, b) in self.map.keys().zip(other.map.keys()) {
+            if !vm_try!(Value::eq_with(a, b, caller)) {
+                return VmResult::Ok(false);
+            }
+        }
+
+        VmResult::Ok(true)
+    }
+}
+
+/// An iterator over a btree set.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set)]
+pub(crate) struct Iter {
+    iter: IterRaw<Value, ()>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl Iter {
+    #[rune::function(keep, instance, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        let (key, _) = self.iter.next()?;
+        unsafe { Some((*key).clone()) }
+    }
+
+    #[rune::function(keep, instance, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        let (key, _) = self.iter.next_back()?;
+        unsafe { Some((*key).clone()) }
+    }
+
+    #[rune::function(keep, instance, protocol = SIZE_HINT)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    #[rune::function(keep, instance, protocol = LEN)]
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+}
+
+impl iter::Iterator for Iter {
+    type Item = Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        Iter::next(self)
+    }
+}
+
+impl iter::DoubleEndedIterator for Iter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        Iter::next_back(self)
+    }
+}
+
+/// An iterator over a sub-range of values in a btree set.
+#[derive(Any)]
+#[rune(item = ::std::collections::btree_set, name = Range)]
+pub(crate) struct RangeIter {
+    iter: RangeRaw<Value, ()>,
+    // Drop must happen after the raw iterator.
+    #[allow(unused)]
+    guard: RawAnyGuard,
+}
+
+impl RangeIter {
+    #[rune::function(keep, instance, protocol = NEXT)]
+    fn next(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        let (key, _) = self.iter.next()?;
+        unsafe { Some((*key).clone()) }
+    }
+
+    #[rune::function(keep, instance, protocol = NEXT_BACK)]
+    fn next_back(&mut self) -> Option<Value> {
+        // SAFETY: We're holding onto the reference guard.
+        let (key, _) = self.iter.next_back()?;
+        unsafe { Some((*key).clone()) }
+    }
+}
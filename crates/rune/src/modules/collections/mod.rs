@@ -1,5 +1,20 @@
 //! Dynamic collections.
 
+#[cfg(feature = "alloc")]
+pub(crate) mod binary_heap;
+#[cfg(feature = "alloc")]
+pub(crate) use binary_heap::BinaryHeap;
+
+#[cfg(feature = "alloc")]
+pub(crate) mod btree_map;
+#[cfg(feature = "alloc")]
+pub(crate) use btree_map::BTreeMap;
+
+#[cfg(feature = "alloc")]
+pub(crate) mod btree_set;
+#[cfg(feature = "alloc")]
+pub(crate) use btree_set::BTreeSet;
+
 #[cfg(feature = "alloc")]
 pub(crate) mod hash_map;
 #[cfg(feature = "alloc")]
@@ -23,6 +38,24 @@ use crate::{ContextError, Module};
 pub fn module() -> Result<Module, ContextError> {
     let mut m = Module::from_meta(self::module_meta)?;
 
+    #[cfg(feature = "alloc")]
+    m.reexport(
+        ["BinaryHeap"],
+        rune::item!(::std::collections::binary_heap::BinaryHeap),
+    )?;
+
+    #[cfg(feature = "alloc")]
+    m.reexport(
+        ["BTreeMap"],
+        rune::item!(::std::collections::btree_map::BTreeMap),
+    )?;
+
+    #[cfg(feature = "alloc")]
+    m.reexport(
+        ["BTreeSet"],
+        rune::item!(::std::collections::btree_set::BTreeSet),
+    )?;
+
     #[cfg(feature = "alloc")]
     m.reexport(
         ["HashMap"],
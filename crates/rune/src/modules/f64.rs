@@ -1,6 +1,7 @@
 //! Floating point numbers.
 
 use core::cmp::Ordering;
+use core::f64::consts;
 use core::num::ParseFloatError;
 
 use crate as rune;
@@ -38,6 +39,79 @@ pub fn module() -> Result<Module, ContextError> {
     m.function_meta(ceil)?;
     #[cfg(feature = "std")]
     m.function_meta(round)?;
+    #[cfg(feature = "std")]
+    m.function_meta(round_ties_even)?;
+    #[cfg(feature = "std")]
+    m.function_meta(trunc)?;
+    #[cfg(feature = "std")]
+    m.function_meta(fract)?;
+    #[cfg(feature = "std")]
+    m.function_meta(signum)?;
+    #[cfg(feature = "std")]
+    m.function_meta(copysign)?;
+    #[cfg(feature = "std")]
+    m.function_meta(mul_add)?;
+    #[cfg(feature = "std")]
+    m.function_meta(div_euclid)?;
+    #[cfg(feature = "std")]
+    m.function_meta(rem_euclid)?;
+    #[cfg(feature = "std")]
+    m.function_meta(exp)?;
+    #[cfg(feature = "std")]
+    m.function_meta(exp2)?;
+    #[cfg(feature = "std")]
+    m.function_meta(ln)?;
+    #[cfg(feature = "std")]
+    m.function_meta(log)?;
+    #[cfg(feature = "std")]
+    m.function_meta(log2)?;
+    #[cfg(feature = "std")]
+    m.function_meta(log10)?;
+    #[cfg(feature = "std")]
+    m.function_meta(cbrt)?;
+    #[cfg(feature = "std")]
+    m.function_meta(hypot)?;
+    #[cfg(feature = "std")]
+    m.function_meta(sin)?;
+    #[cfg(feature = "std")]
+    m.function_meta(cos)?;
+    #[cfg(feature = "std")]
+    m.function_meta(tan)?;
+    #[cfg(feature = "std")]
+    m.function_meta(asin)?;
+    #[cfg(feature = "std")]
+    m.function_meta(acos)?;
+    #[cfg(feature = "std")]
+    m.function_meta(atan)?;
+    #[cfg(feature = "std")]
+    m.function_meta(atan2)?;
+    #[cfg(feature = "std")]
+    m.function_meta(sin_cos)?;
+    #[cfg(feature = "std")]
+    m.function_meta(exp_m1)?;
+    #[cfg(feature = "std")]
+    m.function_meta(ln_1p)?;
+    #[cfg(feature = "std")]
+    m.function_meta(sinh)?;
+    #[cfg(feature = "std")]
+    m.function_meta(cosh)?;
+    #[cfg(feature = "std")]
+    m.function_meta(tanh)?;
+    #[cfg(feature = "std")]
+    m.function_meta(asinh)?;
+    #[cfg(feature = "std")]
+    m.function_meta(acosh)?;
+    #[cfg(feature = "std")]
+    m.function_meta(atanh)?;
+    m.function_meta(is_sign_positive)?;
+    m.function_meta(is_sign_negative)?;
+    m.function_meta(recip)?;
+    m.function_meta(to_degrees)?;
+    m.function_meta(to_radians)?;
+    m.function_meta(clamp)?;
+    m.function_meta(to_bits)?;
+    m.function_meta(from_bits)?;
+    m.function_meta(total_cmp)?;
     m.function_meta(to_integer)?;
 
     m.function_meta(clone__meta)?;
@@ -66,6 +140,118 @@ pub fn module() -> Result<Module, ContextError> {
     m.constant("NAN", f64::NAN).build()?;
     m.constant("INFINITY", f64::INFINITY).build()?;
     m.constant("NEG_INFINITY", f64::NEG_INFINITY).build()?;
+    m.constant("RADIX", f64::RADIX).build()?;
+    m.constant("MANTISSA_DIGITS", f64::MANTISSA_DIGITS)
+        .build()?;
+    m.constant("DIGITS", f64::DIGITS).build()?;
+
+    m.constant("PI", consts::PI).build()?.docs(docstring! {
+        /// Archimedes' constant (π).
+    })?;
+
+    m.constant("TAU", consts::TAU).build()?.docs(docstring! {
+        /// The full circle constant (τ).
+        ///
+        /// Equal to 2π.
+    })?;
+
+    m.constant("E", consts::E).build()?.docs(docstring! {
+        /// Euler's number (e).
+    })?;
+
+    m.constant("FRAC_PI_2", consts::FRAC_PI_2)
+        .build()?
+        .docs(docstring! {
+            /// π/2
+        })?;
+
+    m.constant("FRAC_PI_3", consts::FRAC_PI_3)
+        .build()?
+        .docs(docstring! {
+            /// π/3
+        })?;
+
+    m.constant("FRAC_PI_4", consts::FRAC_PI_4)
+        .build()?
+        .docs(docstring! {
+            /// π/4
+        })?;
+
+    m.constant("FRAC_PI_6", consts::FRAC_PI_6)
+        .build()?
+        .docs(docstring! {
+            /// π/6
+        })?;
+
+    m.constant("FRAC_PI_8", consts::FRAC_PI_8)
+        .build()?
+        .docs(docstring! {
+            /// π/8
+        })?;
+
+    m.constant("FRAC_1_PI", consts::FRAC_1_PI)
+        .build()?
+        .docs(docstring! {
+            /// 1/π
+        })?;
+
+    m.constant("FRAC_2_PI", consts::FRAC_2_PI)
+        .build()?
+        .docs(docstring! {
+            /// 2/π
+        })?;
+
+    m.constant("FRAC_2_SQRT_PI", consts::FRAC_2_SQRT_PI)
+        .build()?
+        .docs(docstring! {
+            /// 2/sqrt(π)
+        })?;
+
+    m.constant("SQRT_2", consts::SQRT_2)
+        .build()?
+        .docs(docstring! {
+            /// sqrt(2)
+        })?;
+
+    m.constant("FRAC_1_SQRT_2", consts::FRAC_1_SQRT_2)
+        .build()?
+        .docs(docstring! {
+            /// 1/sqrt(2)
+        })?;
+
+    m.constant("LN_2", consts::LN_2).build()?.docs(docstring! {
+        /// ln(2)
+    })?;
+
+    m.constant("LN_10", consts::LN_10)
+        .build()?
+        .docs(docstring! {
+            /// ln(10)
+        })?;
+
+    m.constant("LOG2_E", consts::LOG2_E)
+        .build()?
+        .docs(docstring! {
+            /// log<sub>2</sub>(e)
+        })?;
+
+    m.constant("LOG2_10", consts::LOG2_10)
+        .build()?
+        .docs(docstring! {
+            /// log<sub>2</sub>(10)
+        })?;
+
+    m.constant("LOG10_E", consts::LOG10_E)
+        .build()?
+        .docs(docstring! {
+            /// log<sub>10</sub>(e)
+        })?;
+
+    m.constant("LOG10_2", consts::LOG10_2)
+        .build()?
+        .docs(docstring! {
+            /// log<sub>10</sub>(2)
+        })?;
     Ok(m)
 }
 
@@ -380,6 +566,804 @@ fn round(this: f64) -> f64 {
     this.round()
 }
 
+/// Returns the nearest integer to a number. Rounds half-way cases to the number
+/// with an even least significant digit.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 3.3_f64;
+/// let g = -3.3_f64;
+/// let h = 3.5_f64;
+/// let i = 4.5_f64;
+///
+/// assert_eq!(f.round_ties_even(), 3.0);
+/// assert_eq!(g.round_ties_even(), -3.0);
+/// assert_eq!(h.round_ties_even(), 4.0);
+/// assert_eq!(i.round_ties_even(), 4.0);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn round_ties_even(this: f64) -> f64 {
+    this.round_ties_even()
+}
+
+/// Returns the integer part of `self`. This means that non-integer numbers are
+/// always truncated towards zero.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 3.7_f64;
+/// let g = 3.0_f64;
+/// let h = -3.7_f64;
+///
+/// assert_eq!(f.trunc(), 3.0);
+/// assert_eq!(g.trunc(), 3.0);
+/// assert_eq!(h.trunc(), -3.0);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn trunc(this: f64) -> f64 {
+    this.trunc()
+}
+
+/// Returns the fractional part of `self`.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 3.6_f64;
+/// let y = -3.6_f64;
+///
+/// let abs_difference_x = (x.fract() - 0.6).abs();
+/// let abs_difference_y = (y.fract() - (-0.6)).abs();
+///
+/// assert!(abs_difference_x < 1e-10);
+/// assert!(abs_difference_y < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn fract(this: f64) -> f64 {
+    this.fract()
+}
+
+/// Returns a number that represents the sign of `self`.
+///
+/// - `1.0` if the number is positive, `+0.0` or `INFINITY`
+/// - `-1.0` if the number is negative, `-0.0` or `NEG_INFINITY`
+/// - NaN if the number is NaN
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 3.5_f64;
+///
+/// assert_eq!(f.signum(), 1.0);
+/// assert_eq!(f64::NEG_INFINITY.signum(), -1.0);
+///
+/// assert!(f64::NAN.signum().is_nan());
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn signum(this: f64) -> f64 {
+    this.signum()
+}
+
+/// Returns a number composed of the magnitude of `self` and the sign of
+/// `sign`.
+///
+/// Equal to `self` if the sign of `self` and `sign` are the same, otherwise
+/// equal to `-self`. If `self` is a NaN, then a NaN with the sign bit of `sign`
+/// is returned.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 3.5_f64;
+///
+/// assert_eq!(f.copysign(0.42), 3.5_f64);
+/// assert_eq!(f.copysign(-0.42), -3.5_f64);
+/// assert_eq!((-f).copysign(0.42), 3.5_f64);
+/// assert_eq!((-f).copysign(-0.42), -3.5_f64);
+///
+/// assert!(f64::NAN.copysign(1.0).is_nan());
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn copysign(this: f64, sign: f64) -> f64 {
+    this.copysign(sign)
+}
+
+/// Fused multiply-add. Computes `(self * a) + b` with only one rounding error,
+/// yielding a more accurate result than an unfused multiply-add.
+///
+/// # Examples
+///
+/// ```rune
+/// let m = 10.0_f64;
+/// let x = 4.0_f64;
+/// let b = 60.0_f64;
+///
+/// assert_eq!(m.mul_add(x, b), 100.0);
+/// assert_eq!(m * x + b, 100.0);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn mul_add(this: f64, a: f64, b: f64) -> f64 {
+    this.mul_add(a, b)
+}
+
+/// Calculates Euclidean division, the matching method for `rem_euclid`.
+///
+/// This computes the integer `n` such that `self = n * rhs +
+/// self.rem_euclid(rhs)`. In other words, the result is `self / rhs` rounded
+/// to the integer `n` such that `self >= n * rhs`.
+///
+/// # Examples
+///
+/// ```rune
+/// let a = 7.0_f64;
+/// let b = 4.0_f64;
+///
+/// assert_eq!(a.div_euclid(b), 1.0);
+/// assert_eq!((-a).div_euclid(b), -2.0);
+/// assert_eq!(a.div_euclid(-b), -1.0);
+/// assert_eq!((-a).div_euclid(-b), 2.0);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn div_euclid(this: f64, rhs: f64) -> f64 {
+    this.div_euclid(rhs)
+}
+
+/// Calculates the least nonnegative remainder of `self (mod rhs)`.
+///
+/// In particular, the return value `r` satisfies `0.0 <= r < rhs.abs()` in
+/// most cases.
+///
+/// # Examples
+///
+/// ```rune
+/// let a = 7.0_f64;
+/// let b = 4.0_f64;
+///
+/// assert_eq!(a.rem_euclid(b), 3.0);
+/// assert_eq!((-a).rem_euclid(b), 1.0);
+/// assert_eq!(a.rem_euclid(-b), 3.0);
+/// assert_eq!((-a).rem_euclid(-b), 1.0);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn rem_euclid(this: f64, rhs: f64) -> f64 {
+    this.rem_euclid(rhs)
+}
+
+/// Returns `e^(self)`, (the exponential function).
+///
+/// # Examples
+///
+/// ```rune
+/// let one = 1.0_f64;
+/// let e = one.exp();
+///
+/// let abs_difference = (e.ln() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn exp(this: f64) -> f64 {
+    this.exp()
+}
+
+/// Returns `2^(self)`.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 2.0_f64;
+///
+/// let abs_difference = (f.exp2() - 4.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn exp2(this: f64) -> f64 {
+    this.exp2()
+}
+
+/// Returns the natural logarithm of the number.
+///
+/// # Examples
+///
+/// ```rune
+/// let one = 1.0_f64;
+/// let e = one.exp();
+///
+/// let abs_difference = (e.ln() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn ln(this: f64) -> f64 {
+    this.ln()
+}
+
+/// Returns the logarithm of the number with respect to an arbitrary base.
+///
+/// The result might not be correctly rounded owing to implementation details;
+/// `self.log2()` can produce more accurate results for base 2, and
+/// `self.log10()` can produce more accurate results for base 10.
+///
+/// # Examples
+///
+/// ```rune
+/// let twenty_five = 25.0_f64;
+///
+/// let abs_difference = (twenty_five.log(5.0) - 2.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn log(this: f64, base: f64) -> f64 {
+    this.log(base)
+}
+
+/// Returns the base 2 logarithm of the number.
+///
+/// # Examples
+///
+/// ```rune
+/// let four = 4.0_f64;
+///
+/// let abs_difference = (four.log2() - 2.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn log2(this: f64) -> f64 {
+    this.log2()
+}
+
+/// Returns the base 10 logarithm of the number.
+///
+/// # Examples
+///
+/// ```rune
+/// let hundred = 100.0_f64;
+///
+/// let abs_difference = (hundred.log10() - 2.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn log10(this: f64) -> f64 {
+    this.log10()
+}
+
+/// Returns the cube root of a number.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 8.0_f64;
+///
+/// let abs_difference = (x.cbrt() - 2.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn cbrt(this: f64) -> f64 {
+    this.cbrt()
+}
+
+/// Compute the distance between the origin and a point (`x`, `y`) on the
+/// Euclidean plane. Equivalently, compute the length of the hypotenuse of a
+/// right-angle triangle with other sides having length `x.abs()` and
+/// `y.abs()`.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 2.0_f64;
+/// let y = 3.0_f64;
+///
+/// let abs_difference = (x.hypot(y) - (x.powi(2) + y.powi(2)).sqrt()).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn hypot(this: f64, other: f64) -> f64 {
+    this.hypot(other)
+}
+
+/// Computes the sine of a number (in radians).
+///
+/// # Examples
+///
+/// ```rune
+/// let x = f64::FRAC_PI_2;
+///
+/// let abs_difference = (x.sin() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn sin(this: f64) -> f64 {
+    this.sin()
+}
+
+/// Computes the cosine of a number (in radians).
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 2.0 * f64::PI;
+///
+/// let abs_difference = (x.cos() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn cos(this: f64) -> f64 {
+    this.cos()
+}
+
+/// Computes the tangent of a number (in radians).
+///
+/// # Examples
+///
+/// ```rune
+/// let x = f64::FRAC_PI_4;
+///
+/// let abs_difference = (x.tan() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-14);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn tan(this: f64) -> f64 {
+    this.tan()
+}
+
+/// Computes the arcsine of a number. Return value is in radians in the range
+/// [-pi/2, pi/2] or NaN if the number is outside the range [-1, 1].
+///
+/// # Examples
+///
+/// ```rune
+/// let f = f64::FRAC_PI_2;
+///
+/// // asin(sin(pi/2))
+/// let abs_difference = (f.sin().asin() - f64::FRAC_PI_2).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn asin(this: f64) -> f64 {
+    this.asin()
+}
+
+/// Computes the arccosine of a number. Return value is in radians in the range
+/// [0, pi] or NaN if the number is outside the range [-1, 1].
+///
+/// # Examples
+///
+/// ```rune
+/// let f = f64::FRAC_PI_4;
+///
+/// // acos(cos(pi/4))
+/// let abs_difference = (f.cos().acos() - f64::FRAC_PI_4).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn acos(this: f64) -> f64 {
+    this.acos()
+}
+
+/// Computes the arctangent of a number. Return value is in radians in the range
+/// [-pi/2, pi/2].
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 1.0_f64;
+///
+/// // atan(tan(1))
+/// let abs_difference = (f.tan().atan() - 1.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn atan(this: f64) -> f64 {
+    this.atan()
+}
+
+/// Computes the four quadrant arctangent of `self` (`y`) and `other` (`x`) in
+/// radians.
+///
+/// * `x = 0`, `y = 0`: `0`
+/// * `x >= 0`: `arctan(y/x)` -> `[-pi/2, pi/2]`
+/// * `y >= 0`: `arctan(y/x) + pi` -> `(pi/2, pi]`
+/// * `y < 0`: `arctan(y/x) - pi` -> `(-pi, -pi/2)`
+///
+/// # Examples
+///
+/// ```rune
+/// // Positive angles measured counter-clockwise from positive x axis
+/// // -pi/4 radians (45 deg clockwise)
+/// let x1 = 3.0_f64;
+/// let y1 = -3.0_f64;
+///
+/// // 3pi/4 radians (135 deg counter-clockwise)
+/// let x2 = -3.0_f64;
+/// let y2 = 3.0_f64;
+///
+/// let abs_difference_1 = (y1.atan2(x1) - (-f64::FRAC_PI_4)).abs();
+/// let abs_difference_2 = (y2.atan2(x2) - (3.0 * f64::FRAC_PI_4)).abs();
+///
+/// assert!(abs_difference_1 < 1e-10);
+/// assert!(abs_difference_2 < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn atan2(this: f64, other: f64) -> f64 {
+    this.atan2(other)
+}
+
+/// Simultaneously computes the sine and cosine of the number, `x`. Returns
+/// `(sin(x), cos(x))`.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = f64::FRAC_PI_4;
+/// let (sin, cos) = x.sin_cos();
+///
+/// let abs_difference_0 = (sin - x.sin()).abs();
+/// let abs_difference_1 = (cos - x.cos()).abs();
+///
+/// assert!(abs_difference_0 < 1e-10);
+/// assert!(abs_difference_1 < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn sin_cos(this: f64) -> (f64, f64) {
+    this.sin_cos()
+}
+
+/// Returns `e^(self) - 1` in a way that is accurate even if the number is close
+/// to zero.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 1e-16_f64;
+///
+/// // for very small x, e^x is approximately 1 + x + x^2 / 2
+/// let approx = x + x * x / 2.0;
+/// let abs_difference = (x.exp_m1() - approx).abs();
+///
+/// assert!(abs_difference < 1e-20);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn exp_m1(this: f64) -> f64 {
+    this.exp_m1()
+}
+
+/// Returns `ln(1+n)` (natural logarithm) more accurately than if the operations
+/// were performed separately.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 1e-16_f64;
+///
+/// // for very small x, ln(1 + x) is approximately x - x^2 / 2
+/// let approx = x - x * x / 2.0;
+/// let abs_difference = (x.ln_1p() - approx).abs();
+///
+/// assert!(abs_difference < 1e-20);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn ln_1p(this: f64) -> f64 {
+    this.ln_1p()
+}
+
+/// Hyperbolic sine function.
+///
+/// # Examples
+///
+/// ```rune
+/// let e = f64::E;
+/// let x = 1.0_f64;
+///
+/// let f = x.sinh();
+/// // Solving sinh() at 1 gives `(e^2-1)/(2e)`
+/// let g = ((e * e) - 1.0) / (2.0 * e);
+/// let abs_difference = (f - g).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn sinh(this: f64) -> f64 {
+    this.sinh()
+}
+
+/// Hyperbolic cosine function.
+///
+/// # Examples
+///
+/// ```rune
+/// let e = f64::E;
+/// let x = 1.0_f64;
+///
+/// let f = x.cosh();
+/// // Solving cosh() at 1 gives this result
+/// let g = ((e * e) + 1.0) / (2.0 * e);
+/// let abs_difference = (f - g).abs();
+///
+/// assert!(abs_difference < 1.0e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn cosh(this: f64) -> f64 {
+    this.cosh()
+}
+
+/// Hyperbolic tangent function.
+///
+/// # Examples
+///
+/// ```rune
+/// let e = f64::E;
+/// let x = 1.0_f64;
+///
+/// let f = x.tanh();
+/// // Solving tanh() at 1 gives `(1 - e^(-2))/(1 + e^(-2))`
+/// let g = (1.0 - e.powi(-2)) / (1.0 + e.powi(-2));
+/// let abs_difference = (f - g).abs();
+///
+/// assert!(abs_difference < 1.0e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn tanh(this: f64) -> f64 {
+    this.tanh()
+}
+
+/// Inverse hyperbolic sine function.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 1.0_f64;
+/// let f = x.sinh().asinh();
+///
+/// let abs_difference = (f - x).abs();
+///
+/// assert!(abs_difference < 1.0e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn asinh(this: f64) -> f64 {
+    this.asinh()
+}
+
+/// Inverse hyperbolic cosine function.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 1.0_f64;
+/// let f = x.cosh().acosh();
+///
+/// let abs_difference = (f - x).abs();
+///
+/// assert!(abs_difference < 1.0e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn acosh(this: f64) -> f64 {
+    this.acosh()
+}
+
+/// Inverse hyperbolic tangent function.
+///
+/// # Examples
+///
+/// ```rune
+/// let e = f64::E;
+/// let f = e.tanh().atanh();
+///
+/// let abs_difference = (f - e).abs();
+///
+/// assert!(abs_difference < 1.0e-10);
+/// ```
+#[rune::function(instance)]
+#[cfg(feature = "std")]
+fn atanh(this: f64) -> f64 {
+    this.atanh()
+}
+
+/// Returns `true` if `self` has a positive sign, including `+0.0`, NaNs with
+/// positive sign bit and positive infinity.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 7.0_f64;
+/// let g = -7.0_f64;
+///
+/// assert!(f.is_sign_positive());
+/// assert!(!g.is_sign_positive());
+/// ```
+#[rune::function(instance)]
+fn is_sign_positive(this: f64) -> bool {
+    this.is_sign_positive()
+}
+
+/// Returns `true` if `self` has a negative sign, including `-0.0`, NaNs with
+/// negative sign bit and negative infinity.
+///
+/// # Examples
+///
+/// ```rune
+/// let f = 7.0_f64;
+/// let g = -7.0_f64;
+///
+/// assert!(!f.is_sign_negative());
+/// assert!(g.is_sign_negative());
+/// ```
+#[rune::function(instance)]
+fn is_sign_negative(this: f64) -> bool {
+    this.is_sign_negative()
+}
+
+/// Takes the reciprocal (inverse) of a number, `1/x`.
+///
+/// # Examples
+///
+/// ```rune
+/// let x = 2.0_f64;
+/// let abs_difference = (x.recip() - (1.0 / x)).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+fn recip(this: f64) -> f64 {
+    this.recip()
+}
+
+/// Converts radians to degrees.
+///
+/// # Examples
+///
+/// ```rune
+/// let angle = f64::PI;
+///
+/// let abs_difference = (angle.to_degrees() - 180.0).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+fn to_degrees(this: f64) -> f64 {
+    this.to_degrees()
+}
+
+/// Converts degrees to radians.
+///
+/// # Examples
+///
+/// ```rune
+/// let angle = 180.0_f64;
+///
+/// let abs_difference = (angle.to_radians() - f64::PI).abs();
+///
+/// assert!(abs_difference < 1e-10);
+/// ```
+#[rune::function(instance)]
+fn to_radians(this: f64) -> f64 {
+    this.to_radians()
+}
+
+/// Restrict a value to a certain interval unless it is NaN.
+///
+/// Returns `max` if `self` is greater than `max`, and `min` if `self` is less
+/// than `min`. Otherwise this returns `self`.
+///
+/// Note that this function returns NaN if the initial value was NaN as well.
+///
+/// # Panics
+///
+/// Panics if `min > max`, `min` is NaN, or `max` is NaN.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!((-3.0_f64).clamp(-2.0, 1.0) == -2.0);
+/// assert!((0.0_f64).clamp(-2.0, 1.0) == 0.0);
+/// assert!((2.0_f64).clamp(-2.0, 1.0) == 1.0);
+/// assert!((f64::NAN).clamp(-2.0, 1.0).is_nan());
+/// ```
+#[rune::function(instance)]
+fn clamp(this: f64, min: f64, max: f64) -> VmResult<f64> {
+    if min > max || min.is_nan() || max.is_nan() {
+        return VmResult::panic("min > max, or either was NaN");
+    }
+
+    VmResult::Ok(this.clamp(min, max))
+}
+
+/// Raw transmutation to `u64`.
+///
+/// This is currently identical to `transmute::<f64, u64>(self)` on all
+/// platforms.
+///
+/// # Examples
+///
+/// ```rune
+/// assert!(1.0_f64.to_bits() != 1.0_f64.to::<i64>());
+/// assert_eq!(12.5_f64.to_bits(), 0x4029000000000000u64);
+/// ```
+#[rune::function(instance)]
+fn to_bits(this: f64) -> u64 {
+    this.to_bits()
+}
+
+/// Raw transmutation from `u64`.
+///
+/// This is currently identical to `transmute::<u64, f64>(v)` on all platforms.
+///
+/// # Examples
+///
+/// ```rune
+/// let v = f64::from_bits(0x4029000000000000u64);
+/// assert_eq!(v, 12.5);
+/// ```
+#[rune::function]
+fn from_bits(v: u64) -> f64 {
+    f64::from_bits(v)
+}
+
+/// Return the ordering between `self` and `other`.
+///
+/// Unlike the standard partial comparison between floating point numbers, this
+/// comparison always produces an ordering in accordance to the `totalOrder`
+/// predicate as defined in the IEEE 754 (2008 revision) floating point
+/// standard.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(1.0.total_cmp(2.0), Ordering::Less);
+/// assert_eq!((-0.0).total_cmp(0.0), Ordering::Less);
+/// assert_eq!(f64::NAN.total_cmp(f64::INFINITY), Ordering::Greater);
+/// ```
+#[rune::function(instance)]
+fn total_cmp(this: f64, other: f64) -> Ordering {
+    this.total_cmp(&other)
+}
+
 /// Clone a `f64`.
 ///
 /// Note that since the type is copy, cloning has the same effect as assigning
@@ -1,5 +1,10 @@
 //! I/O functions.
 
+#[cfg(all(feature = "std", feature = "tokio"))]
+mod buf;
+#[cfg(all(feature = "std", feature = "tokio"))]
+pub use self::buf::{BufReader, BufWriter, Lines, Stdin};
+
 #[cfg(feature = "std")]
 use std::io::{self, Write as _};
 
@@ -10,7 +15,7 @@ use crate::compile;
 use crate::macros::{quote, FormatArgs, MacroContext, TokenStream};
 use crate::parse::Parser;
 #[cfg(feature = "std")]
-use crate::runtime::{Formatter, InstAddress, Memory, Output, Panic, VmResult};
+use crate::runtime::{Bytes, Formatter, InstAddress, Memory, Output, Panic, Ref, Value, VmResult};
 use crate::{ContextError, Module};
 
 /// I/O functions.
@@ -34,6 +39,10 @@ pub fn module(
         /// Their definitions can be omitted from the built-in standard library, and
         /// can then easily be defined by third party modules allowing for printing
         /// to be hooked up to whatever system you want.
+        ///
+        /// Input can be read line by line through [stdin()], and the
+        /// [BufReader] and [BufWriter] types provide buffered reading and
+        /// writing for other sources, like the pipes of a child process.
     })?;
 
     #[cfg(feature = "std")]
@@ -42,6 +51,8 @@ pub fn module(
     module.function_meta(io_error_display_fmt)?;
     #[cfg(feature = "std")]
     module.function_meta(io_error_debug_fmt)?;
+    #[cfg(all(feature = "std", feature = "tokio"))]
+    buf::install(&mut module, stdio)?;
 
     #[cfg(feature = "std")]
     if stdio {
@@ -92,6 +103,21 @@ fn io_error_debug_fmt(error: &io::Error, f: &mut Formatter) -> VmResult<()> {
     vm_write!(f, "{error:?}")
 }
 
+/// Convert a value which is either a string or bytes into the bytes it
+/// contains.
+///
+/// This is used by functions which write data, so that they accept both
+/// `"text"` and `b"bytes"`.
+#[cfg(feature = "std")]
+pub(crate) fn contents_to_vec(value: Value) -> VmResult<::rust_alloc::vec::Vec<u8>> {
+    if let Ok(string) = crate::from_value::<Ref<str>>(value.clone()) {
+        return VmResult::Ok(string.as_bytes().to_vec());
+    }
+
+    let bytes = vm_try!(crate::from_value::<Ref<Bytes>>(value));
+    VmResult::Ok(bytes.as_slice().to_vec())
+}
+
 #[cfg(feature = "std")]
 fn dbg_impl(stack: &mut dyn Memory, addr: InstAddress, args: usize, out: Output) -> VmResult<()> {
     let stdout = io::stdout();
@@ -0,0 +1,431 @@
+//! Buffered asynchronous readers and writers.
+
+use core::pin::Pin;
+
+use ::rust_alloc::boxed::Box;
+use ::rust_alloc::sync::Arc;
+use std::io;
+use std::sync::OnceLock;
+
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
+use tokio::sync::Mutex;
+
+use crate as rune;
+use crate::alloc::String;
+use crate::runtime::{Mut, Ref, Value, VmResult};
+use crate::{Any, ContextError, Module};
+
+use super::contents_to_vec;
+
+/// Install the buffered reader and writer types.
+pub(super) fn install(module: &mut Module, stdio: bool) -> Result<(), ContextError> {
+    if stdio {
+        module.function_meta(stdin)?;
+    }
+
+    module.ty::<Stdin>()?;
+    module.function_meta(Stdin::read_line__meta)?;
+    module.function_meta(Stdin::read_to_string__meta)?;
+    module.function_meta(Stdin::lines__meta)?;
+
+    module.ty::<BufReader>()?;
+    module.function_meta(BufReader::read_line__meta)?;
+    module.function_meta(BufReader::read_to_string__meta)?;
+    module.function_meta(BufReader::lines__meta)?;
+
+    module.ty::<Lines>()?;
+    module.function_meta(Lines::next_line__meta)?;
+
+    module.ty::<BufWriter>()?;
+    module.function_meta(BufWriter::write_all__meta)?;
+    module.function_meta(BufWriter::flush__meta)?;
+    module.function_meta(BufWriter::shutdown__meta)?;
+    Ok(())
+}
+
+/// Get a handle to the standard input of the current process.
+///
+/// Input is buffered, and the buffer is shared by all handles.
+///
+/// # Examples
+///
+/// ```rune,no_run
+/// let name = std::io::stdin().read_line().await?;
+/// println!("Hello {}!", name.trim());
+/// ```
+#[rune::function]
+fn stdin() -> Stdin {
+    static STDIN: OnceLock<Stdin> = OnceLock::new();
+    STDIN.get_or_init(|| Stdin::new(tokio::io::stdin())).clone()
+}
+
+/// A buffered reader which can be shared between handles.
+#[derive(Clone)]
+struct Shared {
+    inner: Arc<Mutex<Pin<Box<dyn AsyncBufRead + Send>>>>,
+}
+
+impl Shared {
+    fn new<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self {
+            inner: Arc::new(Mutex::new(Box::pin(tokio::io::BufReader::new(reader)))),
+        }
+    }
+
+    async fn read_line(&self) -> VmResult<io::Result<String>> {
+        let mut line = std::string::String::new();
+
+        if let Err(error) = self.inner.lock().await.read_line(&mut line).await {
+            return VmResult::Ok(Err(error));
+        }
+
+        VmResult::Ok(Ok(vm_try!(String::try_from(line))))
+    }
+
+    async fn read_to_string(&self) -> VmResult<io::Result<String>> {
+        let mut string = std::string::String::new();
+
+        if let Err(error) = self.inner.lock().await.read_to_string(&mut string).await {
+            return VmResult::Ok(Err(error));
+        }
+
+        VmResult::Ok(Ok(vm_try!(String::try_from(string))))
+    }
+
+    async fn next_line(&self) -> VmResult<io::Result<Option<String>>> {
+        let mut line = vm_try!(self.read_line().await);
+
+        if let Ok(line) = &mut line {
+            if line.is_empty() {
+                return VmResult::Ok(Ok(None));
+            }
+
+            if line.ends_with('\n') {
+                line.pop();
+
+                if line.ends_with('\r') {
+                    line.pop();
+                }
+            }
+        }
+
+        VmResult::Ok(line.map(Some))
+    }
+}
+
+/// A handle to the standard input of the current process, which is
+/// constructed through [`stdin()`].
+#[derive(Any, Clone)]
+#[rune(item = ::std::io)]
+pub struct Stdin {
+    inner: Shared,
+}
+
+impl Stdin {
+    /// Construct a standard input handle which reads from the given reader.
+    ///
+    /// This can be used to provide the input of scripts which use
+    /// [`stdin()`], see [`capture_io`].
+    ///
+    /// [`capture_io`]: crate::modules::capture_io
+    pub(crate) fn new<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self {
+            inner: Shared::new(reader),
+        }
+    }
+
+    /// Read a line from standard input, including the trailing newline if
+    /// there is one.
+    ///
+    /// An empty string is returned once the end of input has been reached.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let stdin = std::io::stdin();
+    ///
+    /// loop {
+    ///     let line = stdin.read_line().await?;
+    ///
+    ///     if line.is_empty() {
+    ///         break;
+    ///     }
+    ///
+    ///     print!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_line)]
+    async fn read_line(this: Ref<Self>) -> VmResult<io::Result<String>> {
+        let inner = this.inner.clone();
+        drop(this);
+        inner.read_line().await
+    }
+
+    /// Read all remaining input until the end of standard input.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let input = std::io::stdin().read_to_string().await?;
+    /// println!("read {} bytes", input.len());
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_to_string)]
+    async fn read_to_string(this: Ref<Self>) -> VmResult<io::Result<String>> {
+        let inner = this.inner.clone();
+        drop(this);
+        inner.read_to_string().await
+    }
+
+    /// Construct a stream over the lines of standard input.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let lines = std::io::stdin().lines();
+    ///
+    /// while let Some(line) = lines.next_line().await? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep)]
+    fn lines(&self) -> Lines {
+        Lines {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+/// An asynchronous buffered reader.
+///
+/// Buffered readers are constructed from other types, like the standard output
+/// of a child process through `process::ChildStdout::into_buf_reader`.
+#[derive(Any)]
+#[rune(item = ::std::io)]
+pub struct BufReader {
+    inner: Shared,
+}
+
+impl BufReader {
+    /// Construct a buffered reader from an asynchronous reader.
+    pub fn new<R>(reader: R) -> Self
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        Self {
+            inner: Shared::new(reader),
+        }
+    }
+
+    /// Read a line, including the trailing newline if there is one.
+    ///
+    /// An empty string is returned once the end of the reader has been
+    /// reached.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("ls");
+    /// command.stdout(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let reader = child.stdout().unwrap().into_buf_reader();
+    /// let first = reader.read_line().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_line)]
+    async fn read_line(this: Ref<Self>) -> VmResult<io::Result<String>> {
+        let inner = this.inner.clone();
+        drop(this);
+        inner.read_line().await
+    }
+
+    /// Read everything until the end of the reader.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("ls");
+    /// command.stdout(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let reader = child.stdout().unwrap().into_buf_reader();
+    /// let output = reader.read_to_string().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::read_to_string)]
+    async fn read_to_string(this: Ref<Self>) -> VmResult<io::Result<String>> {
+        let inner = this.inner.clone();
+        drop(this);
+        inner.read_to_string().await
+    }
+
+    /// Convert the reader into a stream over its lines.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("ls");
+    /// command.stdout(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let lines = child.stdout().unwrap().into_buf_reader().lines();
+    ///
+    /// while let Some(line) = lines.next_line().await? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep)]
+    fn lines(self) -> Lines {
+        Lines { inner: self.inner }
+    }
+}
+
+/// A stream over the lines of a reader, constructed through
+/// [`BufReader::lines`] or [`Stdin::lines`].
+#[derive(Any)]
+#[rune(item = ::std::io)]
+pub struct Lines {
+    inner: Shared,
+}
+
+impl Lines {
+    /// Get the next line without its trailing newline, or `None` once the end
+    /// of the reader has been reached.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// let lines = std::io::stdin().lines();
+    ///
+    /// while let Some(line) = lines.next_line().await? {
+    ///     println!("{line}");
+    /// }
+    /// ```
+    #[rune::function(keep, instance, path = Self::next_line)]
+    async fn next_line(this: Ref<Self>) -> VmResult<io::Result<Option<String>>> {
+        let inner = this.inner.clone();
+        drop(this);
+        inner.next_line().await
+    }
+}
+
+/// An asynchronous buffered writer.
+///
+/// Buffered writers are constructed from other types, like the standard input
+/// of a child process through `process::ChildStdin::into_buf_writer`.
+///
+/// Buffered data is not written when the writer is dropped, so it has to be
+/// flushed with [`BufWriter::flush`] or [`BufWriter::shutdown`].
+#[derive(Any)]
+#[rune(item = ::std::io)]
+pub struct BufWriter {
+    inner: Option<Pin<Box<dyn AsyncWrite + Send>>>,
+}
+
+impl BufWriter {
+    /// Construct a buffered writer from an asynchronous writer.
+    pub fn new<W>(writer: W) -> Self
+    where
+        W: AsyncWrite + Send + 'static,
+    {
+        Self {
+            inner: Some(Box::pin(tokio::io::BufWriter::new(writer))),
+        }
+    }
+
+    fn writer(&mut self) -> io::Result<&mut Pin<Box<dyn AsyncWrite + Send>>> {
+        match &mut self.inner {
+            Some(inner) => Ok(inner),
+            None => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "writer has been shut down",
+            )),
+        }
+    }
+
+    /// Write all of the given string or bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("cat");
+    /// command.stdin(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let writer = child.stdin().unwrap().into_buf_writer();
+    /// writer.write_all("Hello ").await?;
+    /// writer.write_all(b"World").await?;
+    /// writer.shutdown().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::write_all)]
+    async fn write_all(mut this: Mut<Self>, value: Value) -> VmResult<io::Result<()>> {
+        let contents = vm_try!(contents_to_vec(value));
+
+        let writer = match this.writer() {
+            Ok(writer) => writer,
+            Err(error) => return VmResult::Ok(Err(error)),
+        };
+
+        VmResult::Ok(writer.write_all(&contents).await)
+    }
+
+    /// Flush buffered data to the underlying writer.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("cat");
+    /// command.stdin(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let writer = child.stdin().unwrap().into_buf_writer();
+    /// writer.write_all("Hello World\n").await?;
+    /// writer.flush().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::flush)]
+    async fn flush(mut this: Mut<Self>) -> io::Result<()> {
+        this.writer()?.flush().await
+    }
+
+    /// Flush buffered data, shut down and close the underlying writer.
+    ///
+    /// Closing the standard input of a child process signals the end of its
+    /// input. Writing to the writer after it has been shut down results in an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```rune,no_run
+    /// use process::{Command, Stdio};
+    ///
+    /// let command = Command::new("cat");
+    /// command.stdin(Stdio::piped());
+    ///
+    /// let child = command.spawn()?;
+    /// let writer = child.stdin().unwrap().into_buf_writer();
+    /// writer.write_all("Hello World\n").await?;
+    /// writer.shutdown().await?;
+    /// ```
+    #[rune::function(keep, instance, path = Self::shutdown)]
+    async fn shutdown(mut this: Mut<Self>) -> io::Result<()> {
+        this.writer()?.shutdown().await?;
+        this.inner = None;
+        Ok(())
+    }
+}
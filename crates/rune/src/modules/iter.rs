@@ -5,7 +5,7 @@ use crate::alloc;
 use crate::alloc::prelude::*;
 use crate::modules::collections::VecDeque;
 #[cfg(feature = "alloc")]
-use crate::modules::collections::{HashMap, HashSet};
+use crate::modules::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
 use crate::runtime::range::RangeIter;
 use crate::runtime::{
     FromValue, Function, Inline, InstAddress, Object, Output, OwnedTuple, Protocol, Repr, TypeHash,
@@ -407,6 +407,42 @@ pub fn module() -> Result<Module, ContextError> {
                 )?;
             }
 
+            {
+                let next = next.clone();
+                let size_hint = size_hint.clone();
+
+                cx.function(
+                    Params::new("collect", [BinaryHeap::HASH]),
+                    move |iter: Value| {
+                        let (cap, _) = vm_try!(size_hint.call((&iter,)));
+                        let mut heap = vm_try!(BinaryHeap::with_capacity(cap));
+
+                        while let Some(value) = vm_try!(next.call((iter.clone(),))) {
+                            vm_try!(heap.push(value));
+                        }
+
+                        VmResult::Ok(heap)
+                    },
+                )?;
+            }
+
+            {
+                let next = next.clone();
+
+                cx.function(
+                    Params::new("collect", [BTreeSet::HASH]),
+                    move |iter: Value| {
+                        let mut set = BTreeSet::new();
+
+                        while let Some(value) = vm_try!(next.call((iter.clone(),))) {
+                            vm_try!(set.insert(value));
+                        }
+
+                        VmResult::Ok(set)
+                    },
+                )?;
+            }
+
             {
                 let next = next.with_return::<Option<(Value, Value)>>();
                 let size_hint = size_hint.clone();
@@ -426,6 +462,23 @@ pub fn module() -> Result<Module, ContextError> {
                 )?;
             }
 
+            {
+                let next = next.with_return::<Option<(Value, Value)>>();
+
+                cx.function(
+                    Params::new("collect", [BTreeMap::HASH]),
+                    move |iter: Value| {
+                        let mut map = BTreeMap::new();
+
+                        while let Some((key, value)) = vm_try!(next.call((iter.clone(),))) {
+                            vm_try!(map.insert(key, value));
+                        }
+
+                        VmResult::Ok(map)
+                    },
+                )?;
+            }
+
             {
                 let next = next.with_return::<Option<(String, Value)>>();
                 let size_hint = size_hint.clone();
@@ -1507,6 +1560,61 @@ pub fn module() -> Result<Module, ContextError> {
                 /// ```
             })?;
 
+        t.function(Params::new("collect", [BinaryHeap::HASH]))?
+            .return_type::<BinaryHeap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BinaryHeap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BinaryHeap;
+                ///
+                /// let heap = [2, 0, 1].iter().collect::<BinaryHeap>();
+                ///
+                /// assert_eq!(heap.into_sorted_vec(), [0, 1, 2]);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [BTreeSet::HASH]))?
+            .return_type::<BTreeSet>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BTreeSet`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BTreeSet;
+                ///
+                /// let a = [2, 0, 1, 0].iter().collect::<BTreeSet>();
+                /// let b = BTreeSet::from_iter([0, 1, 2]);
+                ///
+                /// assert_eq!(a, b);
+                /// ```
+            })?;
+
+        t.function(Params::new("collect", [BTreeMap::HASH]))?
+            .return_type::<BTreeMap>()?
+            .docs(docstring! {
+                /// Collect the iterator as a [`BTreeMap`].
+                ///
+                /// # Examples
+                ///
+                /// ```rune
+                /// use std::collections::BTreeMap;
+                ///
+                /// let actual = (0..3).iter().map(|n| (n, n.to_string())).collect::<BTreeMap>();
+                ///
+                /// let expected = BTreeMap::from_iter([
+                ///     (0, "0"),
+                ///     (1, "1"),
+                ///     (2, "2"),
+                /// ]);
+                ///
+                /// assert_eq!(actual, expected);
+                /// ```
+            })?;
+
         t.function(Params::new("collect", [Object::HASH]))?
             .return_type::<HashMap>()?
             .docs(docstring! {
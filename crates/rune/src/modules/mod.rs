@@ -42,6 +42,10 @@ pub mod result;
 pub mod slice;
 pub mod stream;
 pub mod string;
+#[cfg(feature = "tokio")]
+pub mod sync;
+#[cfg(feature = "tokio")]
+pub mod task;
 pub mod test;
 pub mod tuple;
 pub mod u64;
@@ -36,7 +36,8 @@ pub fn module() -> Result<Module, ContextError> {
     m.ty::<String>()?;
 
     m.function_meta(string_from)?;
-    m.function_meta(string_from_str)?;
+    m.function_meta(string_from_str)?
+        .replaced_by("String::from")?;
     m.function_meta(string_new)?;
     m.function_meta(string_with_capacity)?;
     m.function_meta(len)?;
@@ -0,0 +1,86 @@
+//! Synchronization primitives for communicating between tasks.
+
+pub mod broadcast;
+pub mod mpsc;
+pub mod oneshot;
+
+use crate as rune;
+use crate::alloc::Box;
+use crate::runtime::{ConstValue, FromValue, ToValue, Value, VmResult};
+use crate::{ContextError, Module, TypeHash};
+
+/// Synchronization primitives for communicating between tasks.
+///
+/// Values which are sent through a channel or moved into a task are copied,
+/// so that they can safely be used on a different thread. This is supported
+/// for unit, booleans, numbers, characters, strings, bytes, options, results
+/// and vectors, tuples and objects of such values. The endpoints of channels
+/// can be moved between tasks as well, in which case they are no longer usable
+/// from the task that moved them.
+#[rune::module(::std::sync)]
+pub fn module() -> Result<Module, ContextError> {
+    Module::from_meta(self::module_meta)
+}
+
+/// A value which can be sent to a different task.
+pub(crate) enum Sendable {
+    Const(ConstValue),
+    Result(Result<Box<Sendable>, Box<Sendable>>),
+    MpscSender(mpsc::Sender),
+    MpscReceiver(mpsc::Receiver),
+    OneshotSender(oneshot::Sender),
+    OneshotReceiver(oneshot::Receiver),
+    BroadcastSender(broadcast::Sender),
+    BroadcastReceiver(broadcast::Receiver),
+}
+
+impl Sendable {
+    /// Take a value so that it can be sent to a different task.
+    ///
+    /// Channel endpoints are moved out of the value, any other value is
+    /// copied which errors if it can't be converted to a constant value.
+    pub(crate) fn from_value(value: Value) -> VmResult<Self> {
+        let sendable = match value.type_hash() {
+            Result::<Value, Value>::HASH => {
+                let result = match vm_try!(Result::<Value, Value>::from_value(value)) {
+                    Ok(value) => Ok(vm_try!(Box::try_new(vm_try!(Self::from_value(value))))),
+                    Err(value) => Err(vm_try!(Box::try_new(vm_try!(Self::from_value(value))))),
+                };
+
+                Self::Result(result)
+            }
+            mpsc::Sender::HASH => Self::MpscSender(vm_try!(FromValue::from_value(value))),
+            mpsc::Receiver::HASH => Self::MpscReceiver(vm_try!(FromValue::from_value(value))),
+            oneshot::Sender::HASH => Self::OneshotSender(vm_try!(FromValue::from_value(value))),
+            oneshot::Receiver::HASH => Self::OneshotReceiver(vm_try!(FromValue::from_value(value))),
+            broadcast::Sender::HASH => Self::BroadcastSender(vm_try!(FromValue::from_value(value))),
+            broadcast::Receiver::HASH => {
+                Self::BroadcastReceiver(vm_try!(FromValue::from_value(value)))
+            }
+            _ => Self::Const(vm_try!(ConstValue::from_value(value))),
+        };
+
+        VmResult::Ok(sendable)
+    }
+
+    /// Convert into a value owned by the receiving task.
+    pub(crate) fn into_value(self) -> VmResult<Value> {
+        let value = match self {
+            Self::Const(value) => value.to_value(),
+            Self::Result(Ok(value)) => {
+                Ok::<_, Value>(vm_try!(Box::into_inner(value).into_value())).to_value()
+            }
+            Self::Result(Err(value)) => {
+                Err::<Value, _>(vm_try!(Box::into_inner(value).into_value())).to_value()
+            }
+            Self::MpscSender(value) => value.to_value(),
+            Self::MpscReceiver(value) => value.to_value(),
+            Self::OneshotSender(value) => value.to_value(),
+            Self::OneshotReceiver(value) => value.to_value(),
+            Self::BroadcastSender(value) => value.to_value(),
+            Self::BroadcastReceiver(value) => value.to_value(),
+        };
+
+        VmResult::Ok(vm_try!(value))
+    }
+}
@@ -0,0 +1,205 @@
+//! The multi-producer, multi-consumer broadcast channel.
+
+use ::rust_alloc::sync::Arc;
+
+use tokio::sync::broadcast;
+
+use crate as rune;
+use crate::alloc::prelude::*;
+use crate::runtime::{ConstValue, FromValue, Mut, ToValue, Value, VmResult};
+use crate::{Any, ContextError, Module};
+
+/// A multi-producer, multi-consumer channel where every value is received by
+/// all receivers.
+#[rune::module(::std::sync::broadcast)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::subscribe__meta)?;
+    m.function_meta(Sender::receiver_count__meta)?;
+    m.function_meta(Sender::clone__meta)?;
+    m.implement_trait::<Sender>(rune::item!(::std::clone::Clone))?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::recv__meta)?;
+
+    Ok(m)
+}
+
+/// Create a broadcast channel which retains at most `capacity` values for
+/// receivers which have not yet received them.
+///
+/// Unlike other channels, only values which can be copied are supported. Channel
+/// endpoints can not be broadcast.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::sync::broadcast;
+///
+/// let (tx, rx1) = broadcast::channel(16);
+/// let rx2 = tx.subscribe();
+///
+/// tx.send("hello")?;
+///
+/// assert_eq!(rx1.recv().await, Some("hello"));
+/// assert_eq!(rx2.recv().await, Some("hello"));
+/// ```
+#[rune::function]
+fn channel(capacity: usize) -> VmResult<(Sender, Receiver)> {
+    if capacity == 0 {
+        return VmResult::panic("broadcast::channel: capacity must be greater than zero");
+    }
+
+    let (sender, receiver) = broadcast::channel(capacity);
+    VmResult::Ok((Sender { inner: sender }, Receiver { inner: receiver }))
+}
+
+/// The sending half of a broadcast channel.
+#[derive(Any)]
+#[rune(item = ::std::sync::broadcast)]
+pub(crate) struct Sender {
+    inner: broadcast::Sender<Arc<ConstValue>>,
+}
+
+impl Sender {
+    /// Send a value to all active receivers, returning the number of receivers
+    /// which will see it.
+    ///
+    /// Returns the value back as an error if there are no active receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(1);
+    /// assert_eq!(tx.send(1), Ok(1));
+    ///
+    /// drop(rx);
+    /// assert_eq!(tx.send(2), Err(2));
+    /// ```
+    #[rune::function(keep)]
+    fn send(&self, value: Value) -> VmResult<Result<usize, Value>> {
+        let value = vm_try!(ConstValue::from_value(value));
+
+        match self.inner.send(Arc::new(value)) {
+            Ok(count) => VmResult::Ok(Ok(count)),
+            Err(error) => VmResult::Ok(Err(vm_try!(vm_try!((*error.0).try_clone()).to_value()))),
+        }
+    }
+
+    /// Create a new receiver which receives all values sent after this call.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(2);
+    /// tx.send(1)?;
+    ///
+    /// let late = tx.subscribe();
+    /// tx.send(2)?;
+    ///
+    /// assert_eq!(late.recv().await, Some(2));
+    /// ```
+    #[rune::function(keep)]
+    fn subscribe(&self) -> Receiver {
+        Receiver {
+            inner: self.inner.subscribe(),
+        }
+    }
+
+    /// Get the number of active receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(1);
+    /// let rx2 = tx.subscribe();
+    /// assert_eq!(tx.receiver_count(), 2);
+    /// ```
+    #[rune::function(keep)]
+    fn receiver_count(&self) -> usize {
+        self.inner.receiver_count()
+    }
+
+    /// Clone the sender, allowing multiple tasks to send to the same channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(2);
+    /// let tx2 = tx.clone();
+    ///
+    /// tx.send(1)?;
+    /// tx2.send(2)?;
+    ///
+    /// assert_eq!(rx.recv().await, Some(1));
+    /// assert_eq!(rx.recv().await, Some(2));
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &Sender) -> Sender {
+        Self {
+            inner: this.inner.clone(),
+        }
+    }
+}
+
+/// The receiving half of a broadcast channel.
+#[derive(Any)]
+#[rune(item = ::std::sync::broadcast)]
+pub(crate) struct Receiver {
+    inner: broadcast::Receiver<Arc<ConstValue>>,
+}
+
+impl Receiver {
+    /// Receive the next value, waiting until one is available.
+    ///
+    /// Returns `None` once all senders have been dropped and there are no
+    /// remaining values. If the receiver has fallen so far behind that values
+    /// were discarded before it could receive them, those values are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::broadcast;
+    ///
+    /// let (tx, rx) = broadcast::channel(2);
+    ///
+    /// for n in 0..3 {
+    ///     tx.send(n)?;
+    /// }
+    ///
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.recv().await, Some(1));
+    /// assert_eq!(rx.recv().await, Some(2));
+    /// assert_eq!(rx.recv().await, None);
+    /// ```
+    #[rune::function(keep, instance, path = Self::recv)]
+    async fn recv(mut this: Mut<Self>) -> VmResult<Option<Value>> {
+        loop {
+            match this.inner.recv().await {
+                Ok(value) => {
+                    return VmResult::Ok(Some(vm_try!(vm_try!((*value).try_clone()).to_value())))
+                }
+                Err(broadcast::error::RecvError::Lagged(..)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return VmResult::Ok(None),
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+//! The multi-producer, single-consumer channel.
+
+use tokio::sync::mpsc;
+
+use crate as rune;
+use crate::runtime::{Mut, Ref, Value, VmResult};
+use crate::{Any, ContextError, Module};
+
+use super::Sendable;
+
+/// A multi-producer, single-consumer channel for communicating between tasks.
+#[rune::module(::std::sync::mpsc)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::is_closed__meta)?;
+    m.function_meta(Sender::clone__meta)?;
+    m.implement_trait::<Sender>(rune::item!(::std::clone::Clone))?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::recv__meta)?;
+    m.function_meta(Receiver::close__meta)?;
+
+    Ok(m)
+}
+
+/// Create a bounded channel which can hold at most `capacity` messages that
+/// have not yet been received.
+///
+/// Sending to a full channel waits until space is available. All values sent
+/// will be received in the order they were sent.
+///
+/// # Panics
+///
+/// Panics if `capacity` is zero.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::sync::mpsc;
+/// use std::task;
+///
+/// let (tx, rx) = mpsc::channel(16);
+///
+/// let producer = task::spawn(async move || {
+///     for n in 0..3 {
+///         tx.send(n).await?;
+///     }
+/// });
+///
+/// let values = [];
+///
+/// while let Some(value) = rx.recv().await {
+///     values.push(value);
+/// }
+///
+/// producer.await;
+/// assert_eq!(values, [0, 1, 2]);
+/// ```
+#[rune::function]
+fn channel(capacity: usize) -> VmResult<(Sender, Receiver)> {
+    if capacity == 0 {
+        return VmResult::panic("mpsc::channel: capacity must be greater than zero");
+    }
+
+    let (sender, receiver) = mpsc::channel(capacity);
+    VmResult::Ok((Sender { inner: sender }, Receiver { inner: receiver }))
+}
+
+/// The sending half of a multi-producer, single-consumer channel.
+///
+/// Senders can be cloned to send to the same channel from multiple tasks.
+#[derive(Any)]
+#[rune(item = ::std::sync::mpsc)]
+pub(crate) struct Sender {
+    inner: mpsc::Sender<Sendable>,
+}
+
+impl Sender {
+    /// Send a value, waiting until there is capacity in the channel.
+    ///
+    /// Returns the value back as an error if the receiving half of the
+    /// channel has been closed or dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(1);
+    /// tx.send("hello").await?;
+    /// assert_eq!(rx.recv().await, Some("hello"));
+    ///
+    /// rx.close();
+    /// assert_eq!(tx.send("world").await, Err("world"));
+    /// ```
+    #[rune::function(keep, instance, path = Self::send)]
+    async fn send(this: Ref<Self>, value: Value) -> VmResult<Result<(), Value>> {
+        let value = vm_try!(Sendable::from_value(value));
+
+        match this.inner.send(value).await {
+            Ok(()) => VmResult::Ok(Ok(())),
+            Err(error) => VmResult::Ok(Err(vm_try!(error.0.into_value()))),
+        }
+    }
+
+    /// Test if the receiving half of the channel has been closed or dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(1);
+    /// assert!(!tx.is_closed());
+    /// rx.close();
+    /// assert!(tx.is_closed());
+    /// ```
+    #[rune::function(keep)]
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+
+    /// Clone the sender, allowing multiple tasks to send to the same channel.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(2);
+    /// let tx2 = tx.clone();
+    ///
+    /// tx.send(1).await?;
+    /// tx2.send(2).await?;
+    ///
+    /// assert_eq!(rx.recv().await, Some(1));
+    /// assert_eq!(rx.recv().await, Some(2));
+    /// ```
+    #[rune::function(keep, instance, path = Self::clone, protocol = CLONE)]
+    fn clone(this: &Sender) -> Sender {
+        Self {
+            inner: this.inner.clone(),
+        }
+    }
+}
+
+/// The receiving half of a multi-producer, single-consumer channel.
+#[derive(Any)]
+#[rune(item = ::std::sync::mpsc)]
+pub(crate) struct Receiver {
+    inner: mpsc::Receiver<Sendable>,
+}
+
+impl Receiver {
+    /// Receive the next value, waiting until one is available.
+    ///
+    /// Returns `None` once the channel has been closed, or all senders have
+    /// been dropped, and there are no remaining buffered values.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(1);
+    /// tx.send(42).await?;
+    /// drop(tx);
+    ///
+    /// assert_eq!(rx.recv().await, Some(42));
+    /// assert_eq!(rx.recv().await, None);
+    /// ```
+    #[rune::function(keep, instance, path = Self::recv)]
+    async fn recv(mut this: Mut<Self>) -> VmResult<Option<Value>> {
+        match this.inner.recv().await {
+            Some(value) => VmResult::Ok(Some(vm_try!(value.into_value()))),
+            None => VmResult::Ok(None),
+        }
+    }
+
+    /// Close the receiving half of the channel, preventing any further values
+    /// from being sent.
+    ///
+    /// Values which have already been sent can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel(1);
+    /// tx.send(1).await?;
+    /// rx.close();
+    ///
+    /// assert!(tx.send(2).await.is_err());
+    /// assert_eq!(rx.recv().await, Some(1));
+    /// assert_eq!(rx.recv().await, None);
+    /// ```
+    #[rune::function(keep)]
+    fn close(&mut self) {
+        self.inner.close();
+    }
+}
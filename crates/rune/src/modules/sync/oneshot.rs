@@ -0,0 +1,164 @@
+//! The channel for sending a single value.
+
+use tokio::sync::oneshot;
+
+use crate as rune;
+use crate::runtime::{Mut, Value, VmResult};
+use crate::{Any, ContextError, Module};
+
+use super::Sendable;
+
+/// A channel for sending a single value between tasks.
+#[rune::module(::std::sync::oneshot)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.function_meta(channel)?;
+
+    m.ty::<Sender>()?;
+    m.function_meta(Sender::send__meta)?;
+    m.function_meta(Sender::is_closed__meta)?;
+
+    m.ty::<Receiver>()?;
+    m.function_meta(Receiver::into_future__meta)?;
+    m.function_meta(Receiver::close__meta)?;
+
+    Ok(m)
+}
+
+/// Create a channel for sending a single value.
+///
+/// The receiving half is awaited to receive the value.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::sync::oneshot;
+/// use std::task;
+///
+/// let (tx, rx) = oneshot::channel();
+///
+/// task::spawn(async move || {
+///     tx.send(42)?;
+/// });
+///
+/// assert_eq!(rx.await, Some(42));
+/// ```
+#[rune::function]
+fn channel() -> (Sender, Receiver) {
+    let (sender, receiver) = oneshot::channel();
+
+    (
+        Sender { inner: sender },
+        Receiver {
+            inner: Some(receiver),
+        },
+    )
+}
+
+/// The sending half of a oneshot channel.
+#[derive(Any)]
+#[rune(item = ::std::sync::oneshot)]
+pub(crate) struct Sender {
+    inner: oneshot::Sender<Sendable>,
+}
+
+impl Sender {
+    /// Send a value, consuming the sender.
+    ///
+    /// Returns the value back as an error if the receiving half of the
+    /// channel has been closed or dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// rx.close();
+    /// assert_eq!(tx.send(42), Err(42));
+    /// ```
+    #[rune::function(keep, instance, path = Self::send)]
+    fn send(this: Self, value: Value) -> VmResult<Result<(), Value>> {
+        let value = vm_try!(Sendable::from_value(value));
+
+        match this.inner.send(value) {
+            Ok(()) => VmResult::Ok(Ok(())),
+            Err(value) => VmResult::Ok(Err(vm_try!(value.into_value()))),
+        }
+    }
+
+    /// Test if the receiving half of the channel has been closed or dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// assert!(!tx.is_closed());
+    /// drop(rx);
+    /// assert!(tx.is_closed());
+    /// ```
+    #[rune::function(keep)]
+    fn is_closed(&self) -> bool {
+        self.inner.is_closed()
+    }
+}
+
+/// The receiving half of a oneshot channel.
+#[derive(Any)]
+#[rune(item = ::std::sync::oneshot)]
+pub(crate) struct Receiver {
+    inner: Option<oneshot::Receiver<Sendable>>,
+}
+
+impl Receiver {
+    /// Wait for the value to be sent.
+    ///
+    /// Returns `None` if the sender was dropped without sending a value, or if
+    /// the value has already been received.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// drop(tx);
+    /// assert_eq!(rx.await, None);
+    /// ```
+    #[rune::function(keep, instance, path = Self::into_future, protocol = INTO_FUTURE)]
+    async fn into_future(mut this: Mut<Self>) -> VmResult<Option<Value>> {
+        let Some(receiver) = &mut this.inner else {
+            return VmResult::Ok(None);
+        };
+
+        let result = receiver.await;
+        this.inner = None;
+
+        match result {
+            Ok(value) => VmResult::Ok(Some(vm_try!(value.into_value()))),
+            Err(..) => VmResult::Ok(None),
+        }
+    }
+
+    /// Close the receiving half of the channel, preventing a value from being
+    /// sent.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::oneshot;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    /// rx.close();
+    /// assert!(tx.is_closed());
+    /// ```
+    #[rune::function(keep)]
+    fn close(&mut self) {
+        if let Some(receiver) = &mut self.inner {
+            receiver.close();
+        }
+    }
+}
@@ -0,0 +1,309 @@
+//! Spawning and coordinating concurrent tasks.
+
+use tokio::runtime::Handle;
+use tokio::task;
+
+use crate as rune;
+use crate::alloc::{limit, Vec};
+use crate::modules::sync::Sendable;
+use crate::runtime::{budget, Args, Function, Mut, Stack, Value, VmResult, VmSendExecution};
+use crate::{Any, ContextError, Module};
+
+/// Spawning and coordinating concurrent tasks.
+///
+/// Tasks are scheduled on the Tokio runtime the virtual machine is running on,
+/// and run concurrently with the task that spawned them. Values are exchanged
+/// with tasks through the channels in `std::sync`, see its documentation for
+/// which values can be sent between tasks.
+///
+/// If the spawning task is running with a limited instruction budget or memory
+/// limit, half of what remains of each is handed over to the spawned task at
+/// the time it is spawned, so that tasks together never exceed the limits of
+/// the task which spawned them.
+#[rune::module(::std::task)]
+pub fn module() -> Result<Module, ContextError> {
+    let mut m = Module::from_meta(self::module_meta)?;
+
+    m.function_meta(spawn)?;
+    m.function_meta(spawn_blocking)?;
+    m.function_meta(yield_now)?;
+
+    m.ty::<JoinHandle>()?;
+    m.function_meta(JoinHandle::into_future__meta)?;
+    m.function_meta(JoinHandle::abort__meta)?;
+    m.function_meta(JoinHandle::is_finished__meta)?;
+
+    Ok(m)
+}
+
+/// Spawn a function as a new task, returning a [`JoinHandle`] which can be
+/// awaited to get the value it returns.
+///
+/// The function must be defined in a script and take no arguments. Values
+/// captured by a closure are moved into the task, so they must be possible to
+/// send between tasks.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::task;
+///
+/// let n = 20;
+///
+/// let a = task::spawn(async || n + 1);
+/// let b = task::spawn(async || n + 2);
+///
+/// assert_eq!(a.await + b.await, 43);
+/// ```
+#[rune::function]
+fn spawn(function: Function) -> VmResult<JoinHandle> {
+    let runtime = vm_try!(runtime());
+    let execution = vm_try!(prepare(function));
+
+    let task = async move {
+        let value = vm_try!(execution.async_complete().await);
+        Sendable::from_value(value)
+    };
+
+    let task = limit::with(limit::split(), budget::with(budget::split(), task));
+
+    VmResult::Ok(JoinHandle {
+        inner: Some(runtime.spawn(task)),
+    })
+}
+
+/// Spawn a function as a new task on a thread where blocking is acceptable,
+/// returning a [`JoinHandle`] which can be awaited to get the value it
+/// returns.
+///
+/// The function is subject to the same restrictions as the ones passed to
+/// [`spawn`], and additionally it may not use `.await`.
+///
+/// # Panics
+///
+/// Panics if called outside of a Tokio runtime.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::task;
+///
+/// fn fib(n) {
+///     if n < 2 { n } else { fib(n - 1) + fib(n - 2) }
+/// }
+///
+/// let handle = task::spawn_blocking(|| fib(20));
+/// assert_eq!(handle.await, 6765);
+/// ```
+#[rune::function]
+fn spawn_blocking(function: Function) -> VmResult<JoinHandle> {
+    let runtime = vm_try!(runtime());
+    let execution = vm_try!(prepare(function));
+
+    let task = move || {
+        let value = vm_try!(execution.complete());
+        Sendable::from_value(value)
+    };
+
+    let task = limit::with(limit::split(), budget::with(budget::split(), task));
+
+    VmResult::Ok(JoinHandle {
+        inner: Some(runtime.spawn_blocking(move || task.call())),
+    })
+}
+
+/// Yield execution back to the runtime, allowing other tasks to run.
+///
+/// # Examples
+///
+/// ```rune
+/// use std::task;
+///
+/// task::yield_now().await;
+/// ```
+#[rune::function]
+async fn yield_now() {
+    task::yield_now().await;
+}
+
+/// A handle to a spawned task.
+///
+/// Awaiting the handle waits for the task to complete and returns the value it
+/// produced. Errors raised by the task are propagated to the awaiting task.
+///
+/// Dropping the handle detaches the task, which keeps running in the
+/// background until it completes. This is intended, so that tasks can be
+/// spawned without holding on to their handles, and a detached task is still
+/// bound by the instruction budget and memory limit it was handed when it was
+/// spawned. Use [`JoinHandle::abort`] to stop a task early.
+#[derive(Any)]
+#[rune(item = ::std::task)]
+pub(crate) struct JoinHandle {
+    inner: Option<task::JoinHandle<VmResult<Sendable>>>,
+}
+
+impl JoinHandle {
+    /// Wait for the task to complete and return the value it produced.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the task was aborted, if the task has already been joined,
+    /// or if the task itself raised an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::task;
+    ///
+    /// let handle = task::spawn(async || 42);
+    ///
+    /// let value = select {
+    ///     value = handle => value,
+    /// };
+    ///
+    /// assert_eq!(value, 42);
+    /// ```
+    #[rune::function(keep, instance, path = Self::into_future, protocol = INTO_FUTURE)]
+    async fn into_future(mut this: Mut<Self>) -> VmResult<Value> {
+        let Some(handle) = &mut this.inner else {
+            return VmResult::panic("task has already been joined");
+        };
+
+        let result = handle.await;
+        this.inner = None;
+
+        match result {
+            Ok(result) => vm_try!(result).into_value(),
+            Err(error) if error.is_cancelled() => VmResult::panic("task was aborted"),
+            Err(..) => VmResult::panic("task panicked"),
+        }
+    }
+
+    /// Abort the task.
+    ///
+    /// Awaiting an aborted task which had not yet completed panics.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::sync::oneshot;
+    /// use std::task;
+    ///
+    /// let (tx, rx) = oneshot::channel();
+    ///
+    /// let handle = task::spawn(async move || {
+    ///     rx.await
+    /// });
+    ///
+    /// handle.abort();
+    /// task::yield_now().await;
+    /// assert!(tx.is_closed());
+    /// ```
+    #[rune::function(keep)]
+    fn abort(&self) {
+        if let Some(handle) = &self.inner {
+            handle.abort();
+        }
+    }
+
+    /// Test if the task has completed.
+    ///
+    /// # Examples
+    ///
+    /// ```rune
+    /// use std::task;
+    ///
+    /// let handle = task::spawn(async || 42);
+    ///
+    /// while !handle.is_finished() {
+    ///     task::yield_now().await;
+    /// }
+    ///
+    /// assert_eq!(handle.await, 42);
+    /// ```
+    #[rune::function(keep)]
+    fn is_finished(&self) -> bool {
+        self.inner
+            .as_ref()
+            .map_or(true, task::JoinHandle::is_finished)
+    }
+}
+
+/// Get a handle to the runtime which tasks are spawned on.
+fn runtime() -> VmResult<Handle> {
+    match Handle::try_current() {
+        Ok(handle) => VmResult::Ok(handle),
+        Err(..) => VmResult::panic("tasks can only be spawned inside of a Tokio runtime"),
+    }
+}
+
+/// Prepare the execution of a function in a virtual machine which can be sent
+/// to a different thread.
+fn prepare(function: Function) -> VmResult<VmSendExecution> {
+    let Some((vm, hash, environment)) = function.to_unit_entry() else {
+        return VmResult::panic("only functions defined in scripts can be spawned");
+    };
+
+    let environment = match environment {
+        Some(environment) => {
+            let mut sendable = vm_try!(Vec::try_with_capacity(environment.len()));
+
+            for value in environment {
+                vm_try!(sendable.try_push(vm_try!(Sendable::from_value(value.clone()))));
+            }
+
+            Some(sendable)
+        }
+        None => None,
+    };
+
+    VmResult::Ok(vm_try!(vm.send_execute(hash, SendArgs { environment })))
+}
+
+/// Arguments which are sent to a different thread together with the virtual
+/// machine that uses them.
+struct SendArgs {
+    /// The environment of a closure.
+    environment: Option<Vec<Sendable>>,
+}
+
+impl SendArgs {
+    fn into_environment(environment: Vec<Sendable>) -> VmResult<Value> {
+        let mut values = vm_try!(Vec::try_with_capacity(environment.len()));
+
+        for value in environment {
+            vm_try!(values.try_push(vm_try!(value.into_value())));
+        }
+
+        VmResult::Ok(vm_try!(Value::tuple(values)))
+    }
+}
+
+impl Args for SendArgs {
+    fn into_stack(self, stack: &mut Stack) -> VmResult<()> {
+        if let Some(environment) = self.environment {
+            vm_try!(stack.push(vm_try!(Self::into_environment(environment))));
+        }
+
+        VmResult::Ok(())
+    }
+
+    fn try_into_vec(self) -> VmResult<Vec<Value>> {
+        let mut vec = Vec::new();
+
+        if let Some(environment) = self.environment {
+            vm_try!(vec.try_push(vm_try!(Self::into_environment(environment))));
+        }
+
+        VmResult::Ok(vec)
+    }
+
+    #[inline]
+    fn count(&self) -> usize {
+        usize::from(self.environment.is_some())
+    }
+}
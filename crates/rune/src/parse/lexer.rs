@@ -618,11 +618,15 @@ impl<'a> Lexer<'a> {
 
             // Added here specifically to avoid skipping over leading whitespace
             // tokens just below. We only ever want to parse shebangs which are
-            // the first two leading characters in any input.
+            // the first two leading characters in any input, and which aren't
+            // the start of an inner attribute like `#![allow(unused)]`.
             if self.shebang {
                 self.shebang = false;
 
-                if matches!((c, self.iter.peek()), ('#', Some('!'))) {
+                if matches!(
+                    (c, self.iter.peek(), self.iter.peek2()),
+                    ('#', Some('!'), next) if next != Some('[')
+                ) {
                     self.consume_line();
 
                     return Ok(Some(ast::Token {
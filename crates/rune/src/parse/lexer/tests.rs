@@ -652,3 +652,35 @@ fn test_literals() {
         },
     };
 }
+
+#[test]
+fn test_shebang() {
+    let mut it = Lexer::new("#!/usr/bin/env rune\n", SourceId::empty(), true);
+
+    assert_eq!(
+        it.next().unwrap(),
+        Some(ast::Token {
+            kind: ast::Kind::Shebang(ast::LitSource::Text(SourceId::EMPTY)),
+            span: span!(0, 19),
+        })
+    );
+
+    // An inner attribute at the start of the input is not a shebang.
+    let mut it = Lexer::new("#![allow(unused)]", SourceId::empty(), true);
+
+    assert_eq!(
+        it.next().unwrap(),
+        Some(ast::Token {
+            kind: K![#],
+            span: span!(0, 1),
+        })
+    );
+
+    assert_eq!(
+        it.next().unwrap(),
+        Some(ast::Token {
+            kind: K![!],
+            span: span!(1, 2),
+        })
+    );
+}
@@ -215,6 +215,8 @@ pub(crate) enum ImplItemKind {
     Ast {
         /// Non-expanded ast of the path.
         path: Box<ast::Path>,
+        /// Non-expanded ast of the path to the trait being implemented.
+        trait_path: Option<Box<ast::Path>>,
         /// Functions in the impl block.
         functions: Vec<ast::ItemFn>,
     },
@@ -6,8 +6,9 @@ use ::rust_alloc::rc::Rc;
 use ::rust_alloc::sync::Arc;
 
 use crate::alloc::borrow::Cow;
+use crate::alloc::fmt::TryWrite;
 use crate::alloc::prelude::*;
-use crate::alloc::{self, BTreeMap, HashSet, VecDeque};
+use crate::alloc::{self, BTreeMap, Box, HashSet, String, Vec, VecDeque};
 use crate::alloc::{hash_map, HashMap};
 use crate::ast;
 use crate::ast::{Span, Spanned};
@@ -17,18 +18,17 @@ use crate::compile::{
     Located, Location, MetaError, ModId, ModMeta, Names, Pool, Prelude, SourceLoader, SourceMeta,
     UnitBuilder, Visibility, WithSpan,
 };
+use crate::diagnostics::{Lint, LintLevel, LintScopes, Suggestion};
 use crate::grammar::{Ignore, Node, Stream};
 use crate::hir;
 use crate::indexing::{self, FunctionAst, Indexed, Items};
 use crate::item::ComponentRef;
 use crate::item::IntoComponent;
-use crate::macros::Storage;
+use crate::macros::{MacroRules, Storage};
 use crate::parse::{NonZeroId, Resolve};
-#[cfg(feature = "doc")]
-use crate::runtime::Call;
-use crate::runtime::ConstValue;
+use crate::runtime::{Call, ConstValue, OwnedTuple};
 use crate::shared::{Consts, Gen};
-use crate::{Context, Diagnostics, Hash, Item, ItemBuf, Options, SourceId, Sources};
+use crate::{Context, Diagnostics, Hash, Item, ItemBuf, Options, SourceId, Sources, TypeHash};
 
 use super::{
     Build, BuildEntry, BuiltInMacro, ConstFn, DeferEntry, ExpandedMacro, GenericsParameters, Named,
@@ -41,6 +41,50 @@ enum ContextMatch<'this, 'm> {
     None,
 }
 
+/// Test if an item of the given kind can be imported with a `use`.
+fn is_importable(kind: &meta::Kind) -> bool {
+    matches!(
+        kind,
+        meta::Kind::Type { .. }
+            | meta::Kind::Struct { .. }
+            | meta::Kind::Enum { .. }
+            | meta::Kind::Function {
+                associated: None,
+                ..
+            }
+            | meta::Kind::Const
+            | meta::Kind::ConstFn
+            | meta::Kind::Macro
+            | meta::Kind::Module
+    )
+}
+
+/// Name the arguments of a function for documentation, by the source of their
+/// spans.
+#[cfg(feature = "doc")]
+fn to_doc_names(
+    sources: &Sources,
+    source_id: SourceId,
+    args: &[Span],
+) -> alloc::Result<Box<[meta::DocArgument]>> {
+    let mut out = Vec::try_with_capacity(args.len())?;
+
+    for (n, span) in args.iter().enumerate() {
+        let name = match sources.source(source_id, *span) {
+            Some(name) => meta::DocName::Name(name.try_into()?),
+            None => meta::DocName::Index(n),
+        };
+
+        out.try_push(meta::DocArgument {
+            name,
+            base: Hash::EMPTY,
+            generics: Box::default(),
+        })?;
+    }
+
+    Box::try_from(out)
+}
+
 /// The permitted number of import recursions when constructing a path.
 const IMPORT_RECURSION_LIMIT: usize = 128;
 
@@ -63,6 +107,8 @@ pub(crate) struct QueryInner<'arena> {
     internal_macros: HashMap<NonZeroId, Arc<BuiltInMacro>>,
     /// Expanded macros.
     expanded_macros: HashMap<NonZeroId, ExpandedMacro>,
+    /// Declarative macros defined in scripts.
+    macro_rules: HashMap<ItemId, MacroRulesEntry>,
     /// Associated between `id` and `Item`. Use to look up items through
     /// `item_for` with an opaque id.
     ///
@@ -73,6 +119,19 @@ pub(crate) struct QueryInner<'arena> {
     names: Names,
     /// Queue of impl items to process.
     pub(crate) defer_queue: VecDeque<DeferEntry>,
+    /// Traits declared in scripts.
+    traits: HashMap<ItemId, indexing::Trait>,
+    /// Lint levels set by attributes.
+    lints: LintScopes,
+    /// Named fields of private structs declared in scripts.
+    fields: Vec<(Location, String)>,
+    /// Names of fields which are read somewhere.
+    read_fields: HashSet<String>,
+    /// If fields might be read in ways which can't be tracked.
+    fields_read_dynamically: bool,
+    /// The span of the last top-level `use` item in each source, after which
+    /// new imports are suggested.
+    last_imports: HashMap<SourceId, Span>,
 }
 
 impl QueryInner<'_> {
@@ -358,6 +417,49 @@ impl<'a, 'arena> Query<'a, 'arena> {
         ))
     }
 
+    /// Indicate to the visitor which item a path at the given location resolved
+    /// to, such as an import or a macro call.
+    ///
+    /// Only meta matching the given filter is visited. This doesn't build
+    /// anything, so only items which are already known are visited.
+    pub(crate) fn visit_resolved(
+        &mut self,
+        location: &dyn Located,
+        item: ItemId,
+        filter: fn(&meta::Kind) -> bool,
+    ) -> compile::Result<()> {
+        if let Some(meta) = self.inner.meta.get(&(item, Hash::EMPTY)) {
+            if filter(&meta.kind) {
+                self.visitor
+                    .visit_meta(location, meta.as_meta_ref(self.pool))
+                    .with_span(location.as_spanned())?;
+            }
+
+            return Ok(());
+        }
+
+        let Some(meta) = self
+            .context
+            .lookup_meta(self.pool.item(item))
+            .and_then(|mut metas| metas.find(|meta| filter(&meta.kind)))
+        else {
+            return Ok(());
+        };
+
+        let meta = meta::MetaRef {
+            context: true,
+            hash: meta.hash,
+            item: self.pool.item(item),
+            kind: &meta.kind,
+            source: None,
+        };
+
+        self.visitor
+            .visit_meta(location, meta)
+            .with_span(location.as_spanned())?;
+        Ok(())
+    }
+
     /// Access the meta for the given language item.
     #[tracing::instrument(skip_all, fields(item = ?self.pool.item(item), parameters))]
     pub(crate) fn try_lookup_meta(
@@ -446,7 +548,205 @@ impl<'a, 'arena> Query<'a, 'arena> {
             }
         };
 
-        Err(compile::Error::new(location.as_spanned(), kind))
+        let error = compile::Error::new(location.as_spanned(), kind);
+        Err(self.with_import_suggestions(location, error)?)
+    }
+
+    /// Resolve a type annotation into the hash of the type it refers to.
+    ///
+    /// All tuples, including the unit type `()`, share the same type.
+    pub(crate) fn resolve_type(
+        &mut self,
+        source_id: SourceId,
+        ty: &ast::Type,
+    ) -> compile::Result<Hash> {
+        match ty {
+            ast::Type::Path(path) => {
+                let named = self.convert_path(path)?;
+
+                if let Some((span, _)) = named.parameters.iter().flatten().next() {
+                    return Err(compile::Error::msg(
+                        span,
+                        "Generic parameters are not supported in type annotations",
+                    ));
+                }
+
+                let location = DynLocation::new(source_id, path);
+
+                let Some(meta) =
+                    self.try_lookup_meta(&location, named.item, &GenericsParameters::default())?
+                else {
+                    let kind = ErrorKind::MissingItem {
+                        item: self.pool.item(named.item).try_to_owned()?,
+                    };
+
+                    let error = compile::Error::new(path, kind);
+                    return Err(self.with_import_suggestions(&location, error)?);
+                };
+
+                match &meta.kind {
+                    meta::Kind::Type { .. }
+                    | meta::Kind::Struct { .. }
+                    | meta::Kind::Enum { .. } => Ok(meta.hash),
+                    _ => Err(compile::Error::expected_meta(
+                        path,
+                        meta.info(self.pool)?,
+                        "a type",
+                    )),
+                }
+            }
+            ast::Type::Tuple(tuple) => {
+                for (ty, _) in tuple {
+                    self.resolve_type(source_id, ty)?;
+                }
+
+                Ok(OwnedTuple::HASH)
+            }
+        }
+    }
+
+    /// Get the name of the type with the given hash for use in diagnostics.
+    pub(crate) fn type_name(&self, hash: Hash) -> alloc::Result<Box<str>> {
+        let item = match self
+            .context
+            .lookup_meta_by_hash(hash)
+            .find_map(|m| m.item.as_ref())
+        {
+            Some(item) => item.try_to_string()?,
+            None => match self.inner.meta.keys().find(|&&(_, h)| h == hash) {
+                Some(&(item, _)) => self.pool.item(item).try_to_string()?,
+                None => try_format!("{hash}"),
+            },
+        };
+
+        Box::try_from(item)
+    }
+
+    /// Attach suggestions to import items which would make the path at the
+    /// given location resolve.
+    ///
+    /// An item is suggested if its name matches the first component of the
+    /// path and the rest of the path exists inside of it.
+    pub(crate) fn with_import_suggestions(
+        &self,
+        location: &dyn Located,
+        mut error: compile::Error,
+    ) -> compile::Result<compile::Error> {
+        /// The maximum number of imports to suggest.
+        const LIMIT: usize = 5;
+
+        let Location { source_id, span } = location.location();
+
+        let Some(source) = self.sources.get(source_id) else {
+            return Ok(error);
+        };
+
+        let Some(text) = source.get(span.range()) else {
+            return Ok(error);
+        };
+
+        let mut path = Vec::new();
+
+        for component in text.split("::") {
+            let component = component.trim();
+
+            if component.is_empty() || !component.chars().all(|c| c.is_alphanumeric() || c == '_') {
+                return Ok(error);
+            }
+
+            path.try_push(component)?;
+        }
+
+        let [first, rest @ ..] = &path[..] else {
+            return Ok(error);
+        };
+
+        if matches!(*first, "self" | "super" | "crate") {
+            return Ok(error);
+        }
+
+        let mut script = Vec::new();
+
+        for (id, entries) in &self.inner.indexed {
+            let importable = entries.iter().any(|entry| match &entry.indexed {
+                Indexed::Function(f) => f.impl_item.is_none(),
+                Indexed::Enum
+                | Indexed::Struct(..)
+                | Indexed::ConstExpr(..)
+                | Indexed::ConstFn(..)
+                | Indexed::Module => true,
+                _ => false,
+            });
+
+            script.try_push((self.pool.item(*id), importable))?;
+        }
+
+        for ((id, _), meta) in &self.inner.meta {
+            script.try_push((self.pool.item(*id), is_importable(&meta.kind)))?;
+        }
+
+        let mut candidates = Vec::new();
+
+        for meta in self.context.iter_meta() {
+            let Some(item) = &meta.item else {
+                continue;
+            };
+
+            if !is_importable(&meta.kind) || item.last() != Some(ComponentRef::Str(first)) {
+                continue;
+            }
+
+            let full = item.join(rest)?;
+
+            if rest.is_empty() || self.context.lookup_meta(&full).is_some() {
+                let mut path = String::new();
+                write!(path, "{}", item.try_to_string()?.trim_start_matches("::"))?;
+                candidates.try_push(path)?;
+            }
+        }
+
+        for &(item, importable) in &script {
+            if !importable || item.last() != Some(ComponentRef::Str(first)) {
+                continue;
+            }
+
+            let full = item.join(rest)?;
+
+            if rest.is_empty() || script.iter().any(|&(other, _)| *other == *full) {
+                let mut path = String::new();
+                write!(path, "crate::{item}")?;
+                candidates.try_push(path)?;
+            }
+        }
+
+        candidates.sort_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+        candidates.dedup();
+
+        // New imports go after the last top-level `use`, or at the start of
+        // the source if there are none.
+        let last_import = self.inner.last_imports.get(&source_id).copied();
+
+        for candidate in candidates.iter().take(LIMIT) {
+            let mut message = String::new();
+            write!(message, "Import `{candidate}`")?;
+
+            let mut replacement = String::new();
+
+            let span = match last_import {
+                Some(span) => {
+                    write!(replacement, "\nuse {candidate};")?;
+                    span.tail()
+                }
+                None => {
+                    writeln!(replacement, "use {candidate};")?;
+                    Span::empty()
+                }
+            };
+
+            error = error.with_suggestion(Suggestion::new(&message, span, &replacement)?);
+        }
+
+        Ok(error)
     }
 
     pub(crate) fn lookup_deprecation(&self, hash: Hash) -> Option<&str> {
@@ -618,6 +918,60 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Insert a declarative macro defined in a script, replacing any earlier
+    /// definition with the same name.
+    pub(crate) fn insert_macro_rules(
+        &mut self,
+        item: ItemId,
+        module: ModId,
+        location: Location,
+        visibility: Visibility,
+        macro_rules: MacroRules,
+    ) -> compile::Result<()> {
+        let entry = MacroRulesEntry {
+            module,
+            location,
+            visibility,
+            macro_rules: Rc::new(macro_rules),
+        };
+
+        self.inner.macro_rules.try_insert(item, entry)?;
+        Ok(())
+    }
+
+    /// Get the declarative macro defined in a script for the given item,
+    /// checking that it is accessible from the module `from`.
+    pub(crate) fn macro_rules_for(
+        &mut self,
+        span: &dyn Spanned,
+        from: ModId,
+        item: ItemId,
+    ) -> compile::Result<Option<Rc<MacroRules>>> {
+        let Some(entry) = self.inner.macro_rules.get(&item) else {
+            return Ok(None);
+        };
+
+        let module = entry.module;
+        #[cfg(feature = "emit")]
+        let location = entry.location;
+        let visibility = entry.visibility;
+        let macro_rules = entry.macro_rules.clone();
+
+        self.check_access_to(
+            span,
+            from,
+            item,
+            module,
+            #[cfg(feature = "emit")]
+            location,
+            visibility,
+            #[cfg(feature = "emit")]
+            &mut Vec::new(),
+        )?;
+
+        Ok(Some(macro_rules))
+    }
+
     /// Get an expanded macro.
     pub(crate) fn take_expanded_macro(&mut self, id: NonZeroId) -> Option<ExpandedMacro> {
         self.inner.expanded_macros.remove(&id)
@@ -788,6 +1142,173 @@ impl<'a, 'arena> Query<'a, 'arena> {
         Ok(())
     }
 
+    /// Add a new trait item, which is indexed immediately.
+    #[tracing::instrument(skip_all)]
+    pub(crate) fn index_trait(
+        &mut self,
+        span: &dyn Spanned,
+        item_meta: ItemMeta,
+        tr: indexing::Trait,
+    ) -> compile::Result<()> {
+        let trait_hash = self.pool.item_type_hash(item_meta.item);
+
+        // NB: Like the functions of native traits, trait functions are
+        // registered under the associated hash of the trait.
+        for (item_meta, f) in &tr.functions {
+            let name = f.name.resolve(resolve_context!(self))?;
+
+            #[cfg(feature = "doc")]
+            let arguments = {
+                let args = f
+                    .args
+                    .iter()
+                    .map(|(a, _)| match a {
+                        ast::FnArg::Typed(a) => a.pat.span(),
+                        a => a.span(),
+                    })
+                    .try_collect::<Vec<_>>()?;
+
+                to_doc_names(self.sources, tr.source_id, &args)?
+            };
+
+            let source = SourceMeta {
+                location: item_meta.location,
+                path: self
+                    .sources
+                    .path(item_meta.location.source_id)
+                    .map(|p| p.try_into())
+                    .transpose()?,
+            };
+
+            let meta = meta::Meta {
+                context: false,
+                hash: Hash::associated_function(trait_hash, name),
+                item_meta: *item_meta,
+                kind: meta::Kind::Function {
+                    associated: Some(meta::AssociatedKind::Instance(Cow::Owned(name.try_into()?))),
+                    trait_hash: None,
+                    signature: meta::Signature {
+                        is_async: f.async_token.is_some(),
+                        #[cfg(feature = "doc")]
+                        arguments: Some(arguments),
+                        argument_types: Box::default(),
+                        return_type: meta::DocType::empty(),
+                    },
+                    is_test: false,
+                    is_bench: false,
+                    parameters: Hash::EMPTY,
+                    #[cfg(feature = "doc")]
+                    container: Some(trait_hash),
+                    #[cfg(feature = "doc")]
+                    parameter_types: Vec::new(),
+                },
+                source: Some(source),
+                parameters: Hash::EMPTY,
+            };
+
+            self.insert_meta(meta).with_span(f)?;
+        }
+
+        self.inner.traits.try_insert(item_meta.item, tr)?;
+        self.index_meta(span, item_meta, meta::Kind::Trait)?;
+        Ok(())
+    }
+
+    /// Test if the given item is a function declared by a trait, either in a
+    /// script or in the context.
+    pub(crate) fn is_trait_function(&mut self, item: ItemId) -> alloc::Result<bool> {
+        let Some(parent) = self.pool.try_map_alloc(item, Item::parent)? else {
+            return Ok(false);
+        };
+
+        if self.inner.traits.contains_key(&parent) {
+            return Ok(true);
+        }
+
+        Ok(self
+            .context
+            .lookup_meta(self.pool.item(parent))
+            .into_iter()
+            .flatten()
+            .any(|meta| matches!(meta.kind, meta::Kind::Trait)))
+    }
+
+    /// Get the trait declared in a script by its item.
+    pub(crate) fn get_trait(&self, item: ItemId) -> Option<&indexing::Trait> {
+        self.inner.traits.get(&item)
+    }
+
+    /// Set the level of a lint inside of the given span.
+    pub(crate) fn lint_level(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+        lint: Lint,
+        level: LintLevel,
+    ) -> alloc::Result<()> {
+        self.inner.lints.insert(source_id, span, lint, level)
+    }
+
+    /// Resolve the lint levels of all warnings reported since `start`.
+    pub(crate) fn resolve_lints(&mut self, start: usize) -> alloc::Result<()> {
+        let lints = &self.inner.lints;
+        let levels = &self.options.lints;
+
+        self.diagnostics
+            .resolve_lints(start, |warning| lints.level(levels, warning))
+    }
+
+    /// Declare a named field of a private struct, which is warned about
+    /// unless it's read somewhere.
+    pub(crate) fn declare_field(&mut self, location: Location, name: &str) -> alloc::Result<()> {
+        self.inner.fields.try_push((location, name.try_to_owned()?))
+    }
+
+    /// Record the span of the last top-level `use` item in a source.
+    pub(crate) fn insert_last_import(
+        &mut self,
+        source_id: SourceId,
+        span: Span,
+    ) -> alloc::Result<()> {
+        self.inner.last_imports.try_insert(source_id, span)?;
+        Ok(())
+    }
+
+    /// Mark a field with the given name as read.
+    pub(crate) fn field_read(&mut self, name: &str) -> alloc::Result<()> {
+        if !self.inner.read_fields.contains(name) {
+            self.inner.read_fields.try_insert(name.try_to_owned()?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Indicate that fields might be read in ways which can't be tracked, like
+    /// through the `DEBUG_FMT` or `PARTIAL_EQ` protocols, or by native
+    /// functions which are passed a struct.
+    ///
+    /// Since we don't know the type of the values involved, no fields are
+    /// reported as unused after this.
+    pub(crate) fn fields_read_dynamically(&mut self) {
+        self.inner.fields_read_dynamically = true;
+    }
+
+    /// Report declared fields which are never read.
+    pub(crate) fn unused_fields(&mut self) -> alloc::Result<()> {
+        if self.inner.fields_read_dynamically {
+            return Ok(());
+        }
+
+        for (location, name) in &self.inner.fields {
+            if !self.inner.read_fields.contains(name.as_str()) {
+                self.diagnostics
+                    .unused_field(location.source_id, &location.span)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Index meta immediately.
     #[tracing::instrument(skip_all)]
     pub(crate) fn index_meta(
@@ -1566,30 +2087,6 @@ impl<'a, 'arena> Query<'a, 'arena> {
         entry: indexing::Entry,
         used: Used,
     ) -> compile::Result<meta::Meta> {
-        #[cfg(feature = "doc")]
-        fn to_doc_names(
-            sources: &Sources,
-            source_id: SourceId,
-            args: &[Span],
-        ) -> alloc::Result<Box<[meta::DocArgument]>> {
-            let mut out = Vec::try_with_capacity(args.len())?;
-
-            for (n, span) in args.iter().enumerate() {
-                let name = match sources.source(source_id, *span) {
-                    Some(name) => meta::DocName::Name(name.try_into()?),
-                    None => meta::DocName::Index(n),
-                };
-
-                out.try_push(meta::DocArgument {
-                    name,
-                    base: Hash::EMPTY,
-                    generics: Box::default(),
-                })?;
-            }
-
-            Box::try_from(out)
-        }
-
         let indexing::Entry { item_meta, indexed } = entry;
 
         if let Used::Used = used {
@@ -1624,6 +2121,34 @@ impl<'a, 'arena> Query<'a, 'arena> {
                 parameters: Hash::EMPTY,
             },
             Indexed::Function(f) => {
+                let source_id = item_meta.location.source_id;
+                let mut argument_types = Vec::new();
+                let mut return_type = meta::DocType::empty();
+
+                if let FunctionAst::Item(ast, _) = &f.ast {
+                    for (arg, _) in &ast.args {
+                        argument_types.try_push(match arg {
+                            ast::FnArg::Typed(arg) => self.resolve_type(source_id, &arg.ty)?,
+                            _ => Hash::EMPTY,
+                        })?;
+                    }
+
+                    if let Some(ty) = &ast.fn_type {
+                        return_type = meta::DocType::new(self.resolve_type(source_id, ty)?);
+                    }
+                }
+
+                #[cfg(feature = "doc")]
+                let arguments = {
+                    let mut arguments = to_doc_names(self.sources, source_id, &f.args)?;
+
+                    for (doc, &base) in arguments.iter_mut().zip(&argument_types) {
+                        doc.base = base;
+                    }
+
+                    arguments
+                };
+
                 let kind = meta::Kind::Function {
                     associated: match (f.is_instance, &f.ast) {
                         (true, FunctionAst::Item(_, name)) => {
@@ -1642,16 +2167,11 @@ impl<'a, 'arena> Query<'a, 'arena> {
                     is_test: f.is_test,
                     is_bench: f.is_bench,
                     signature: meta::Signature {
-                        #[cfg(feature = "doc")]
                         is_async: matches!(f.call, Call::Async | Call::Stream),
                         #[cfg(feature = "doc")]
-                        arguments: Some(to_doc_names(
-                            self.sources,
-                            item_meta.location.source_id,
-                            &f.args,
-                        )?),
-                        #[cfg(feature = "doc")]
-                        return_type: meta::DocType::empty(),
+                        arguments: Some(arguments),
+                        argument_types: Box::try_from(argument_types)?,
+                        return_type,
                     },
                     parameters: Hash::EMPTY,
                     #[cfg(feature = "doc")]
@@ -2082,6 +2602,19 @@ impl<'a, 'arena> Query<'a, 'arena> {
     }
 }
 
+/// A declarative macro defined in a script.
+struct MacroRulesEntry {
+    /// The module the macro is defined in.
+    module: ModId,
+    /// The location of the definition.
+    #[cfg_attr(not(feature = "emit"), allow(unused))]
+    location: Location,
+    /// The visibility of the macro.
+    visibility: Visibility,
+    /// The compiled rules of the macro.
+    macro_rules: Rc<MacroRules>,
+}
+
 struct FoundImportStep {
     item_meta: ItemMeta,
     import: meta::Import,
@@ -96,6 +96,24 @@ pub fn acquire() -> BudgetGuard {
     BudgetGuard(self::no_std::rune_budget_replace(usize::MAX))
 }
 
+/// Split off half of the budget which remains for the current task, deducting
+/// it from the current task.
+///
+/// The budget of a running virtual machine is only visible here while it calls
+/// a function, see [`BudgetGuard::publish`]. This returns `usize::MAX` if the
+/// current task is not budgeted.
+pub(crate) fn split() -> usize {
+    let budget = self::no_std::rune_budget_get();
+
+    if budget == usize::MAX {
+        return usize::MAX;
+    }
+
+    let half = budget / 2;
+    let _ = self::no_std::rune_budget_replace(budget - half);
+    half
+}
+
 /// A locally acquired budget.
 ///
 /// This guard is acquired by calling [`take`] and can be used to take permits.
@@ -118,6 +136,20 @@ impl BudgetGuard {
         self.0 -= 1;
         true
     }
+
+    /// Publish the remaining budget while calling `f`, so that it can be used
+    /// by functions which take part of it, like when spawning tasks, or by
+    /// virtual machines which are called recursively.
+    pub(crate) fn publish<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        if self.0 == usize::MAX {
+            return f();
+        }
+
+        let previous = self::no_std::rune_budget_replace(self.0);
+        let output = f();
+        self.0 = self::no_std::rune_budget_replace(previous);
+        output
+    }
 }
 
 impl Drop for BudgetGuard {
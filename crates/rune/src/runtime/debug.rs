@@ -23,6 +23,8 @@ pub struct DebugInfo {
     pub functions_rev: HashMap<usize, Hash>,
     /// Hash to identifier.
     pub hash_to_ident: HashMap<Hash, Box<str>>,
+    /// Variables which are defined at the given instruction pointer.
+    pub variables: HashMap<usize, Vec<DebugVariable>>,
 }
 
 impl DebugInfo {
@@ -38,6 +40,14 @@ impl DebugInfo {
         Some((hash, signature))
     }
 
+    /// Get the variables which are defined at the given instruction pointer.
+    pub fn variables_at(&self, ip: usize) -> &[DebugVariable] {
+        self.variables
+            .get(&ip)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// Access an identifier for the given hash - if it exists.
     pub fn ident_for_hash(&self, hash: Hash) -> Option<&str> {
         Some(self.hash_to_ident.get(&hash)?)
@@ -75,6 +85,24 @@ impl DebugInst {
     }
 }
 
+/// Debug information on a named variable.
+#[derive(Debug, TryClone, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct DebugVariable {
+    /// The name of the variable.
+    pub name: Box<str>,
+    /// The stack slot of the variable, relative to the top of the call frame
+    /// it is defined in.
+    pub slot: usize,
+}
+
+impl DebugVariable {
+    /// Construct a new debug variable.
+    pub fn new(name: Box<str>, slot: usize) -> Self {
+        Self { name, slot }
+    }
+}
+
 /// Debug information on function arguments.
 #[derive(Debug, TryClone, Serialize, Deserialize)]
 pub enum DebugArgs {
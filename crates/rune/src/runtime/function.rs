@@ -255,6 +255,24 @@ impl Function {
         Self(FunctionImpl::from_tuple_struct(rtti, args))
     }
 
+    /// Construct a virtual machine for calling this function by its hash if it
+    /// is defined in a unit, along with the environment it captures if it is a
+    /// closure.
+    ///
+    /// Returns `None` for native functions and constructors.
+    pub(crate) fn to_unit_entry(&self) -> Option<(Vm, Hash, Option<&[Value]>)> {
+        let (fn_offset, environment) = match &self.0.inner {
+            Inner::FnOffset(fn_offset) => (fn_offset, None),
+            Inner::FnClosureOffset(closure) => {
+                (&closure.fn_offset, Some(&closure.environment[..]))
+            }
+            _ => return None,
+        };
+
+        let vm = Vm::new(fn_offset.context.clone(), fn_offset.unit.clone());
+        Some((vm, fn_offset.hash, environment))
+    }
+
     /// Type [Hash][struct@Hash] of the underlying function.
     ///
     /// # Examples
@@ -49,7 +49,7 @@ pub use self::const_value::{
 pub(crate) use self::const_value::{ConstContext, ConstValueKind, EmptyConstContext};
 
 pub mod debug;
-pub use self::debug::{DebugInfo, DebugInst};
+pub use self::debug::{DebugInfo, DebugInst, DebugVariable};
 
 mod env;
 
@@ -198,6 +198,9 @@ pub use self::vm_execution::{VmExecution, VmSendExecution};
 mod vm_halt;
 pub(crate) use self::vm_halt::{VmHalt, VmHaltInfo};
 
+mod vm_hook;
+pub use self::vm_hook::{VmHook, VmHookControl};
+
 mod fmt;
 pub use self::fmt::Formatter;
 
@@ -371,7 +371,7 @@ impl Stack {
     /// index is within range.
     ///
     /// [top]: Self::top()
-    #[cfg(feature = "cli")]
+    #[cfg(any(feature = "cli", feature = "debugger"))]
     pub(crate) fn get<I>(&self, index: I) -> Option<&<I as slice::SliceIndex<[Value]>>::Output>
     where
         I: slice::SliceIndex<[Value]>,
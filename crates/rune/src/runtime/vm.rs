@@ -24,8 +24,8 @@ use super::{
     Protocol, ProtocolCaller, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo,
     RangeToInclusive, Repr, RttiKind, RuntimeContext, Select, SelectFuture, Stack, Stream, Type,
     TypeCheck, TypeHash, TypeInfo, TypeOf, Unit, UnitFn, UnitStorage, Value, Vec, VmDiagnostics,
-    VmDiagnosticsObj, VmError, VmErrorKind, VmExecution, VmHalt, VmIntegerRepr, VmResult,
-    VmSendExecution,
+    VmDiagnosticsObj, VmError, VmErrorKind, VmExecution, VmHalt, VmHook, VmHookControl,
+    VmIntegerRepr, VmResult, VmSendExecution,
 };
 
 /// Helper to take a value, replacing the old one with empty.
@@ -125,6 +125,14 @@ pub struct Vm {
     stack: Stack,
     /// Frames relative to the stack.
     call_frames: alloc::Vec<CallFrame>,
+    /// The hook observing execution.
+    hook: Option<Arc<dyn VmHook>>,
+    /// Whether the hook should be notified that a function was entered before
+    /// the next instruction.
+    hook_enter: bool,
+    /// Whether the hook should not be called for the next instruction, because
+    /// execution was paused before it.
+    hook_skip: bool,
 }
 
 impl Vm {
@@ -150,9 +158,27 @@ impl Vm {
             last_ip_len: 0,
             stack,
             call_frames: alloc::Vec::new(),
+            hook: None,
+            hook_enter: true,
+            hook_skip: false,
         }
     }
 
+    /// Construct a virtual machine which runs the function at the given
+    /// offset, inheriting the hook of this one.
+    pub(crate) fn child(
+        &self,
+        context: Arc<RuntimeContext>,
+        unit: Arc<Unit>,
+        stack: Stack,
+        ip: usize,
+    ) -> Self {
+        let mut vm = Self::with_stack(context, unit, stack);
+        vm.ip = ip;
+        vm.hook = self.hook.clone();
+        vm
+    }
+
     /// Construct a vm with a default empty [RuntimeContext]. This is useful
     /// when the [Unit] was constructed with an empty
     /// [Context][crate::compile::Context].
@@ -187,6 +213,18 @@ impl Vm {
         &self.call_frames
     }
 
+    /// Set the hook used to observe execution, see [`VmHook`].
+    #[inline]
+    pub fn set_hook(&mut self, hook: Option<Arc<dyn VmHook>>) {
+        self.hook = hook;
+    }
+
+    /// Get the hook used to observe execution.
+    #[inline]
+    pub fn hook(&self) -> Option<&Arc<dyn VmHook>> {
+        self.hook.as_ref()
+    }
+
     /// Get the stack.
     #[inline]
     pub fn stack(&self) -> &Stack {
@@ -263,6 +301,8 @@ impl Vm {
         self.ip = 0;
         self.stack.clear();
         self.call_frames.clear();
+        self.hook_enter = true;
+        self.hook_skip = false;
     }
 
     /// Look up a function in the virtual machine by its name.
@@ -573,6 +613,8 @@ impl Vm {
         self.ip = offset;
         self.stack.clear();
         self.call_frames.clear();
+        self.hook_enter = true;
+        self.hook_skip = false;
         Ok(())
     }
 
@@ -1096,8 +1138,7 @@ impl Vm {
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
-            let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
-            vm.ip = offset;
+            let vm = self.child(self.context.clone(), self.unit.clone(), stack, offset);
             *self.stack.at_mut(at)? = Value::try_from(Generator::new(vm))?;
         } else {
             values.iter_mut().for_each(consume);
@@ -1118,8 +1159,7 @@ impl Vm {
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
-            let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
-            vm.ip = offset;
+            let vm = self.child(self.context.clone(), self.unit.clone(), stack, offset);
             *self.stack.at_mut(at)? = Value::try_from(Stream::new(vm))?;
         } else {
             values.iter_mut().for_each(consume);
@@ -1140,8 +1180,7 @@ impl Vm {
 
         if let Some(at) = out.as_addr() {
             let stack = values.iter_mut().map(take).try_collect::<Stack>()?;
-            let mut vm = Self::with_stack(self.context.clone(), self.unit.clone(), stack);
-            vm.ip = offset;
+            let vm = self.child(self.context.clone(), self.unit.clone(), stack, offset);
             let mut execution = vm.into_execution();
             let future = Future::new(async move { execution.async_complete().await })?;
             *self.stack.at_mut(at)? = Value::try_from(future)?;
@@ -1270,7 +1309,14 @@ impl Vm {
         let futures = futures_util::stream::FuturesUnordered::new();
 
         for (branch, value) in vm_try!(self.stack.slice_at(addr, len)).iter().enumerate() {
-            let future = vm_try!(value.clone().into_mut::<Future>());
+            // NB: Values which are not futures are converted through the
+            // `INTO_FUTURE` protocol, the same as when they are awaited.
+            let value = match value.type_hash() {
+                Future::HASH => value.clone(),
+                _ => vm_try!(Value::new(vm_try!(value.clone().into_future()))),
+            };
+
+            let future = vm_try!(value.into_mut::<Future>());
 
             if !future.is_completed() {
                 futures.push(SelectFuture::new(self.ip + branch, future));
@@ -3034,6 +3080,7 @@ impl Vm {
         let _guard = runtime::env::Guard::new(self.context.clone(), self.unit.clone(), diagnostics);
 
         let mut budget = budget::acquire();
+        let hook = self.hook.clone();
 
         loop {
             if !budget.take() {
@@ -3049,6 +3096,19 @@ impl Vm {
 
             tracing::trace!(ip = ?self.ip, ?inst);
 
+            // The hook is notified with the instruction pointer and call depth
+            // from before the instruction is executed.
+            let hooked = match &hook {
+                Some(hook) => {
+                    if let Some(halt) = vm_try!(self.hook_before(&**hook, &inst)) {
+                        return VmResult::Ok(halt);
+                    }
+
+                    Some((&**hook, self.ip, self.call_frames.len()))
+                }
+                None => None,
+            };
+
             self.ip = self.ip.wrapping_add(inst_len);
             self.last_ip_len = inst_len as u8;
 
@@ -3076,7 +3136,7 @@ impl Vm {
                     args,
                     out,
                 } => {
-                    vm_try!(self.op_call(hash, addr, args, out));
+                    vm_try!(budget.publish(|| self.op_call(hash, addr, args, out)));
                 }
                 Inst::CallOffset {
                     offset,
@@ -3093,7 +3153,7 @@ impl Vm {
                     args,
                     out,
                 } => {
-                    vm_try!(self.op_call_associated(hash, addr, args, out));
+                    vm_try!(budget.publish(|| self.op_call_associated(hash, addr, args, out)));
                 }
                 Inst::CallFn {
                     function,
@@ -3101,7 +3161,13 @@ impl Vm {
                     args,
                     out,
                 } => {
-                    if let Some(reason) = vm_try!(self.op_call_fn(function, addr, args, out)) {
+                    let reason = budget.publish(|| self.op_call_fn(function, addr, args, out));
+
+                    if let Some(reason) = vm_try!(reason) {
+                        if let Some((_, _, depth)) = hooked {
+                            self.hook_enter = self.call_frames.len() > depth;
+                        }
+
                         return VmResult::Ok(reason);
                     }
                 }
@@ -3140,11 +3206,19 @@ impl Vm {
                 }
                 Inst::Return { addr } => {
                     if let Some(out) = vm_try!(self.op_return(addr)) {
+                        if let Some((hook, at, _)) = hooked {
+                            vm_try!(self.hook_control(hook.exit(self, at)));
+                        }
+
                         return VmResult::Ok(VmHalt::Exited(out.as_addr()));
                     }
                 }
                 Inst::ReturnUnit => {
                     if let Some(out) = vm_try!(self.op_return_unit()) {
+                        if let Some((hook, at, _)) = hooked {
+                            vm_try!(self.hook_control(hook.exit(self, at)));
+                        }
+
                         return VmResult::Ok(VmHalt::Exited(out.as_addr()));
                     }
                 }
@@ -3347,6 +3421,55 @@ impl Vm {
                     });
                 }
             }
+
+            if let Some((hook, at, depth)) = hooked {
+                match self.call_frames.len().cmp(&depth) {
+                    Ordering::Greater => {
+                        self.hook_enter = true;
+                    }
+                    Ordering::Less => {
+                        if let Some(halt) = vm_try!(self.hook_control(hook.exit(self, at))) {
+                            return VmResult::Ok(halt);
+                        }
+                    }
+                    Ordering::Equal => {}
+                }
+            }
+        }
+    }
+
+    /// Notify the hook before the given instruction is executed, returns the
+    /// reason to halt if execution should be paused.
+    fn hook_before(&mut self, hook: &dyn VmHook, inst: &Inst) -> VmResult<Option<VmHalt>> {
+        if replace(&mut self.hook_enter, false) {
+            if let Some(halt) = vm_try!(self.hook_control(hook.enter(self, self.ip))) {
+                return VmResult::Ok(Some(halt));
+            }
+        }
+
+        if replace(&mut self.hook_skip, false) {
+            return VmResult::Ok(None);
+        }
+
+        let mut control = hook.instruction(self, self.ip, inst);
+
+        if control == VmHookControl::Continue
+            && matches!(inst, Inst::Await { .. } | Inst::Select { .. })
+        {
+            control = hook.await_point(self, self.ip);
+        }
+
+        let halt = vm_try!(self.hook_control(control));
+        self.hook_skip = halt.is_some();
+        VmResult::Ok(halt)
+    }
+
+    /// Act on a control value returned by the hook.
+    fn hook_control(&self, control: VmHookControl) -> VmResult<Option<VmHalt>> {
+        match control {
+            VmHookControl::Continue => VmResult::Ok(None),
+            VmHookControl::Pause => VmResult::Ok(Some(VmHalt::Paused)),
+            VmHookControl::Abort => err(VmErrorKind::Aborted),
         }
     }
 }
@@ -3360,6 +3483,9 @@ impl TryClone for Vm {
             last_ip_len: self.last_ip_len,
             stack: self.stack.try_clone()?,
             call_frames: self.call_frames.try_clone()?,
+            hook: self.hook.clone(),
+            hook_enter: self.hook_enter,
+            hook_skip: self.hook_skip,
         })
     }
 }
@@ -90,8 +90,6 @@ impl VmCall {
         let context = self.context.unwrap_or_else(|| vm.context().clone());
         let unit = self.unit.unwrap_or_else(|| vm.unit().clone());
 
-        let mut vm = Vm::with_stack(context, unit, new_stack);
-        vm.set_ip(ip);
-        VmResult::Ok(vm)
+        VmResult::Ok(vm.child(context, unit, new_stack, ip))
     }
 }
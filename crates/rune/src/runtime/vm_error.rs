@@ -72,6 +72,14 @@ impl VmError {
         Self::from(VmErrorKind::Overflow)
     }
 
+    /// Test if the error is caused by a [`VmHook`] pausing execution, in which
+    /// case execution can be continued by resuming it again.
+    ///
+    /// [`VmHook`]: crate::runtime::VmHook
+    pub fn is_paused(&self) -> bool {
+        matches!(self.inner.error.kind, VmErrorKind::Paused)
+    }
+
     /// Get the first error location.
     pub fn first_location(&self) -> Option<&VmErrorLocation> {
         self.inner.stacktrace.first()
@@ -663,6 +671,8 @@ pub(crate) enum VmErrorKind {
     Halted {
         halt: VmHaltInfo,
     },
+    Aborted,
+    Paused,
     Overflow,
     Underflow,
     DivideByZero,
@@ -890,6 +900,8 @@ impl fmt::Display for VmErrorKind {
             VmErrorKind::Panic { reason } => write!(f, "Panicked: {reason}"),
             VmErrorKind::NoRunningVm {} => write!(f, "No running virtual machines"),
             VmErrorKind::Halted { halt } => write!(f, "Halted for unexpected reason `{halt}`"),
+            VmErrorKind::Aborted => write!(f, "Execution was aborted by a hook"),
+            VmErrorKind::Paused => write!(f, "Execution was paused by a hook"),
             VmErrorKind::Overflow {} => write!(f, "Numerical overflow"),
             VmErrorKind::Underflow {} => write!(f, "Numerical underflow"),
             VmErrorKind::DivideByZero {} => write!(f, "Division by zero"),
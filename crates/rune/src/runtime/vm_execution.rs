@@ -227,7 +227,7 @@ where
         &mut self,
         diagnostics: Option<&mut dyn VmDiagnostics>,
     ) -> VmResult<GeneratorState> {
-        if let ExecutionState::Resumed(out) = self.state {
+        if let ExecutionState::Resumed(out) = replace(&mut self.state, ExecutionState::Suspended) {
             vm_try!(out.store(self.head.as_mut().stack_mut(), Value::unit));
         }
 
@@ -268,6 +268,9 @@ where
                     self.state = ExecutionState::Resumed(out);
                     return VmResult::Ok(GeneratorState::Yielded(value));
                 }
+                VmHalt::Paused => {
+                    return VmResult::err(VmErrorKind::Paused);
+                }
                 halt => {
                     return VmResult::err(VmErrorKind::Halted {
                         halt: halt.into_info(),
@@ -357,6 +360,9 @@ where
                     self.state = ExecutionState::Resumed(out);
                     return VmResult::Ok(GeneratorState::Yielded(value));
                 }
+                VmHalt::Paused => {
+                    return VmResult::err(VmErrorKind::Paused);
+                }
                 halt => {
                     return VmResult::err(VmErrorKind::Halted {
                         halt: halt.into_info(),
@@ -389,7 +395,7 @@ where
                 vm_try!(vm_call.into_execution(self));
                 return VmResult::Ok(None);
             }
-            VmHalt::Limited => return VmResult::Ok(None),
+            VmHalt::Limited | VmHalt::Paused => return VmResult::Ok(None),
             halt => {
                 return VmResult::err(VmErrorKind::Halted {
                     halt: halt.into_info(),
@@ -423,7 +429,7 @@ where
                 vm_try!(vm_call.into_execution(self));
                 return VmResult::Ok(None);
             }
-            VmHalt::Limited => return VmResult::Ok(None),
+            VmHalt::Limited | VmHalt::Paused => return VmResult::Ok(None),
             halt => {
                 return VmResult::err(VmErrorKind::Halted {
                     halt: halt.into_info(),
@@ -538,6 +544,18 @@ impl VmSendExecution {
         unsafe { AssertSend::new(future) }
     }
 
+    /// Complete the current execution without support for async instructions.
+    ///
+    /// This is useful for executions sent to a thread which is allowed to
+    /// block, like through [tokio::task::spawn_blocking]. If any async
+    /// instructions are encountered, this will error. This will also error if
+    /// the execution is suspended through yielding.
+    ///
+    /// [tokio::task::spawn_blocking]: https://docs.rs/tokio/1/tokio/task/fn.spawn_blocking.html
+    pub fn complete(mut self) -> VmResult<Value> {
+        self.0.complete()
+    }
+
     /// Complete the current execution with support for async instructions.
     ///
     /// This requires that the result of the Vm is converted into a
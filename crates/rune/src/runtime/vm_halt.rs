@@ -15,6 +15,8 @@ pub(crate) enum VmHalt {
     Awaited(Awaited),
     /// Call into a new virtual machine.
     VmCall(VmCall),
+    /// The virtual machine was paused by its hook.
+    Paused,
 }
 
 impl VmHalt {
@@ -26,6 +28,7 @@ impl VmHalt {
             Self::Yielded(..) => VmHaltInfo::Yielded,
             Self::Awaited(..) => VmHaltInfo::Awaited,
             Self::VmCall(..) => VmHaltInfo::VmCall,
+            Self::Paused => VmHaltInfo::Paused,
         }
     }
 }
@@ -43,6 +46,8 @@ pub(crate) enum VmHaltInfo {
     Awaited,
     /// Received instruction to push the inner virtual machine.
     VmCall,
+    /// The virtual machine was paused by its hook.
+    Paused,
 }
 
 impl fmt::Display for VmHaltInfo {
@@ -53,6 +58,7 @@ impl fmt::Display for VmHaltInfo {
             Self::Yielded => write!(f, "yielded"),
             Self::Awaited => write!(f, "awaited"),
             Self::VmCall => write!(f, "calling into other vm"),
+            Self::Paused => write!(f, "paused"),
         }
     }
 }
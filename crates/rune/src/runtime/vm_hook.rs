@@ -0,0 +1,134 @@
+use core::fmt;
+
+use crate::runtime::{Inst, Vm};
+
+/// The control value returned by the callbacks of a [`VmHook`], which decides
+/// how execution proceeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VmHookControl {
+    /// Continue execution.
+    Continue,
+    /// Pause execution.
+    ///
+    /// The virtual machine halts in the same way as when its [budget] is
+    /// exhausted. [`VmExecution::step`] returns `Ok(None)`, while methods
+    /// which run to completion such as [`VmExecution::complete`] return an
+    /// error for which [`VmError::is_paused`] is `true`. In either case the
+    /// execution is left intact, and picks up where it left off once it is
+    /// resumed.
+    ///
+    /// If this is returned from [`VmHook::instruction`] or
+    /// [`VmHook::await_point`], the instruction is executed when execution is
+    /// resumed, without the hook being called for it again.
+    ///
+    /// [budget]: crate::runtime::budget
+    /// [`VmExecution::step`]: crate::runtime::VmExecution::step
+    /// [`VmExecution::complete`]: crate::runtime::VmExecution::complete
+    /// [`VmError::is_paused`]: crate::runtime::VmError::is_paused
+    Pause,
+    /// Abort execution with an error.
+    Abort,
+}
+
+/// A hook which observes the execution of a [`Vm`].
+///
+/// A hook is installed with [`Vm::set_hook`], and is inherited by virtual
+/// machines which are constructed to run async functions, generators and
+/// streams called from the one it's installed on.
+///
+/// All callbacks default to doing nothing and returning
+/// [`VmHookControl::Continue`].
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use rune::runtime::{Inst, Vm, VmHook, VmHookControl};
+/// use rune::{Context, Diagnostics};
+///
+/// #[derive(Default)]
+/// struct Counter {
+///     instructions: AtomicUsize,
+///     calls: AtomicUsize,
+/// }
+///
+/// impl VmHook for Counter {
+///     fn instruction(&self, _: &Vm, _: usize, _: &Inst) -> VmHookControl {
+///         self.instructions.fetch_add(1, Ordering::Relaxed);
+///         VmHookControl::Continue
+///     }
+///
+///     fn enter(&self, _: &Vm, _: usize) -> VmHookControl {
+///         self.calls.fetch_add(1, Ordering::Relaxed);
+///         VmHookControl::Continue
+///     }
+/// }
+///
+/// let context = Context::with_default_modules()?;
+/// let runtime = Arc::new(context.runtime()?);
+///
+/// let mut sources = rune::sources! {
+///     entry => {
+///         fn add(a, b) { a + b }
+///         pub fn main() { add(1, 2) + add(3, 4) }
+///     }
+/// };
+///
+/// let unit = rune::prepare(&mut sources).build()?;
+///
+/// let counter = Arc::new(Counter::default());
+///
+/// let mut vm = Vm::new(runtime, Arc::new(unit));
+/// vm.set_hook(Some(counter.clone()));
+///
+/// let output: i64 = rune::from_value(vm.call(["main"], ())?)?;
+/// assert_eq!(output, 10);
+///
+/// assert!(counter.instructions.load(Ordering::Relaxed) > 0);
+/// assert_eq!(counter.calls.load(Ordering::Relaxed), 3);
+/// # Ok::<_, rune::support::Error>(())
+/// ```
+pub trait VmHook: Send + Sync {
+    /// Called before the instruction `inst` at `ip` is executed.
+    fn instruction(&self, vm: &Vm, ip: usize, inst: &Inst) -> VmHookControl {
+        _ = (vm, ip, inst);
+        VmHookControl::Continue
+    }
+
+    /// Called when a function starting at `ip` is entered, before its first
+    /// instruction is executed.
+    ///
+    /// The depth of the function is `vm.call_frames().len()`.
+    fn enter(&self, vm: &Vm, ip: usize) -> VmHookControl {
+        _ = (vm, ip);
+        VmHookControl::Continue
+    }
+
+    /// Called after the function has returned through the instruction at
+    /// `ip`.
+    ///
+    /// If execution continues in the caller, [`Vm::ip`] is the instruction it
+    /// continues at. Pausing after the last function of the virtual machine
+    /// returned has no effect.
+    fn exit(&self, vm: &Vm, ip: usize) -> VmHookControl {
+        _ = (vm, ip);
+        VmHookControl::Continue
+    }
+
+    /// Called before the instruction at `ip` awaits a future or selects over
+    /// a collection of futures.
+    fn await_point(&self, vm: &Vm, ip: usize) -> VmHookControl {
+        _ = (vm, ip);
+        VmHookControl::Continue
+    }
+}
+
+impl fmt::Debug for dyn VmHook {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("VmHook")
+    }
+}
@@ -417,6 +417,8 @@ mod attribute;
 #[cfg(not(miri))]
 mod binary;
 #[cfg(not(miri))]
+mod binary_heap;
+#[cfg(not(miri))]
 mod bug_326;
 #[cfg(not(miri))]
 mod bug_344;
@@ -436,6 +438,8 @@ mod bugfixes;
 mod builtin_macros;
 #[cfg(not(miri))]
 mod capture;
+#[cfg(all(not(miri), feature = "cli"))]
+mod cli_output;
 #[cfg(not(miri))]
 mod comments;
 #[cfg(not(miri))]
@@ -455,6 +459,8 @@ mod compiler_patterns;
 #[cfg(not(miri))]
 mod compiler_use;
 #[cfg(not(miri))]
+mod compiler_visitor;
+#[cfg(not(miri))]
 mod compiler_visibility;
 #[cfg(not(miri))]
 mod compiler_warnings;
@@ -486,6 +492,10 @@ mod getter_setter;
 #[cfg(not(miri))]
 mod iterator;
 #[cfg(not(miri))]
+mod lints;
+#[cfg(not(miri))]
+mod macro_rules;
+#[cfg(not(miri))]
 mod macros;
 #[cfg(not(miri))]
 mod moved;
@@ -504,8 +514,14 @@ mod rename_type;
 #[cfg(not(miri))]
 mod result;
 #[cfg(not(miri))]
+mod suggestions;
+#[cfg(not(miri))]
+mod traits;
+#[cfg(not(miri))]
 mod tuple;
 #[cfg(not(miri))]
+mod type_annotations;
+#[cfg(not(miri))]
 mod type_name_native;
 #[cfg(not(miri))]
 mod unit_constants;
@@ -532,11 +548,17 @@ mod vm_function_pointers;
 #[cfg(not(miri))]
 mod vm_general;
 #[cfg(not(miri))]
+mod vm_hook;
+#[cfg(all(not(miri), feature = "tokio", feature = "capture-io"))]
+mod vm_io;
+#[cfg(not(miri))]
 mod vm_literals;
 #[cfg(not(miri))]
 mod vm_not_used;
 #[cfg(not(miri))]
 mod vm_result;
+#[cfg(all(not(miri), feature = "tokio"))]
+mod vm_task;
 #[cfg(not(miri))]
 mod vm_test_from_value_derive;
 #[cfg(not(miri))]
@@ -0,0 +1,71 @@
+prelude!();
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+/// A comparator which errors in the middle of a sift leaves the heap unchanged.
+#[test]
+fn test_comparator_error() -> Result<()> {
+    let mut vm = vm(r#"
+        use std::collections::BinaryHeap;
+
+        pub fn new() {
+            let state = #{ fail: false };
+
+            let heap = BinaryHeap::with_comparator(|a, b| {
+                if state.fail {
+                    return ();
+                }
+
+                a.cmp(b)
+            });
+
+            heap.extend([5, 3, 4, 1, 2]);
+            (heap, state)
+        }
+
+        pub fn fail(heap, fail) {
+            heap.1.fail = fail;
+        }
+
+        pub fn push(heap, value) {
+            heap.0.push(value);
+        }
+
+        pub fn pop(heap) {
+            heap.0.pop()
+        }
+
+        pub fn items(heap) {
+            heap.0.clone().into_vec()
+        }
+    "#)?;
+
+    let heap = vm.call(["new"], ())?;
+    let items =
+        |vm: &mut Vm| -> Result<Vec<i64>> { Ok(from_value(vm.call(["items"], (heap.clone(),))?)?) };
+
+    let before = items(&mut vm)?;
+
+    vm.call(["fail"], (heap.clone(), true))?;
+    assert!(vm.call(["push"], (heap.clone(), 6i64)).is_err());
+    assert!(vm.call(["pop"], (heap.clone(),)).is_err());
+    vm.call(["fail"], (heap.clone(), false))?;
+
+    assert_eq!(items(&mut vm)?, before);
+
+    let mut popped = Vec::new();
+
+    while let Some(value) = from_value::<Option<i64>>(vm.call(["pop"], (heap.clone(),))?)? {
+        popped.push(value);
+    }
+
+    assert_eq!(popped, [5, 4, 3, 2, 1]);
+    Ok(())
+}
@@ -0,0 +1,115 @@
+prelude!();
+
+use std::string::String;
+use std::vec::Vec;
+
+use crate::cli::coverage::{Coverage, Report};
+use crate::cli::profile::Profiler;
+use crate::runtime::{RuntimeContext, VmHook};
+use crate::Unit;
+
+const SCRIPT: &str = r#"
+fn add(a, b) {
+    a + b
+}
+
+fn sign(n) {
+    if n < 0 {
+        return -1;
+    }
+
+    1
+}
+
+pub fn main() {
+    let n = add(1, 2);
+    sign(n) + sign(n)
+}
+"#;
+
+fn build(sources: &mut Sources) -> Result<(Arc<RuntimeContext>, Arc<Unit>)> {
+    let context = Context::with_default_modules()?;
+    let unit = prepare(sources).with_context(&context).build()?;
+    Ok((Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+fn run(runtime: Arc<RuntimeContext>, unit: Arc<Unit>, hook: Arc<dyn VmHook>) -> Result<i64> {
+    let mut vm = Vm::new(runtime, unit);
+    vm.set_hook(Some(hook));
+    Ok(from_value(vm.call(["main"], ())?)?)
+}
+
+#[test]
+fn test_profile_collapsed() -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("script.rn", SCRIPT)?)?;
+    let (runtime, unit) = build(&mut sources)?;
+
+    let profiler = Arc::new(Profiler::new(&unit));
+    assert_eq!(run(runtime, unit.clone(), profiler.clone())?, 2);
+
+    let mut output = Vec::new();
+    profiler.collapsed(&mut output, &unit)?;
+    let output = String::from_utf8(output)?;
+
+    // NB: the weights are the time spent in each stack, which is not stable.
+    let mut stacks = Vec::new();
+
+    for line in output.lines() {
+        let (stack, weight) = line.rsplit_once(' ').expect("missing weight");
+        assert!(weight.parse::<u128>().is_ok(), "{line}");
+        stacks.push(stack);
+    }
+
+    assert_eq!(stacks, ["main", "main;add", "main;sign"]);
+    Ok(())
+}
+
+#[test]
+fn test_coverage_lcov() -> Result<()> {
+    let mut sources = Sources::new();
+    sources.insert(Source::new("script.rn", SCRIPT)?)?;
+    let (runtime, unit) = build(&mut sources)?;
+
+    let coverage = Arc::new(Coverage::new(unit.clone(), Arc::new(sources)));
+    assert_eq!(run(runtime, unit, coverage.clone())?, 2);
+
+    let mut report = Report::default();
+    report.add(&coverage);
+
+    let mut output = Vec::new();
+    report.lcov(&mut output)?;
+    let output = String::from_utf8(output)?;
+
+    let expected = [
+        "TN:",
+        "SF:script.rn",
+        "FN:2,add",
+        "FN:14,main",
+        "FN:6,sign",
+        "FNDA:1,add",
+        "FNDA:1,main",
+        "FNDA:2,sign",
+        "FNF:3",
+        "FNH:3",
+        "BRDA:7,16,0,0",
+        "BRDA:7,16,1,2",
+        "BRF:2",
+        "BRH:1",
+        "DA:2,1",
+        "DA:3,1",
+        "DA:6,2",
+        "DA:7,2",
+        "DA:8,0",
+        "DA:11,2",
+        "DA:14,1",
+        "DA:15,1",
+        "DA:16,1",
+        "LF:9",
+        "LH:8",
+        "end_of_record",
+    ];
+
+    assert_eq!(output.lines().collect::<Vec<_>>(), expected);
+    Ok(())
+}
@@ -0,0 +1,252 @@
+prelude!();
+
+use ast::{Span, Spanned};
+use compile::MetaError;
+
+/// Collects the span of every location passed to the visitor along with the
+/// kind of visit.
+struct Visitor {
+    source: &'static str,
+    visited: Vec<(Span, &'static str)>,
+    /// The type hash each field declaration or use is attributed to.
+    owners: Vec<(Span, Option<Hash>)>,
+    /// The names of types which declare fields.
+    types: Vec<(Hash, String)>,
+}
+
+impl Visitor {
+    fn new(source: &'static str) -> Self {
+        Self {
+            source,
+            visited: Vec::new(),
+            owners: Vec::new(),
+            types: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, location: &dyn Located, kind: &'static str) {
+        self.visited.push((location.location().span, kind));
+    }
+
+    /// Build the source and only keep visits of the given kinds.
+    fn build_only(self, kinds: &[&str]) -> Result<Vec<(&'static str, &'static str)>> {
+        let mut visited = self.build()?;
+        visited.retain(|(kind, _)| kinds.contains(kind));
+        Ok(visited)
+    }
+
+    /// Build the source and return the text of every field declaration or use
+    /// together with the name of the type it's attributed to.
+    fn build_owners(mut self) -> Result<Vec<(&'static str, Option<String>)>> {
+        self.compile()?;
+        self.owners.sort_by_key(|(span, _)| span.start);
+        self.owners.dedup();
+
+        let mut output = Vec::new();
+
+        for &(span, hash) in &self.owners {
+            let owner = hash.map(|hash| {
+                self.types
+                    .iter()
+                    .find(|(other, _)| *other == hash)
+                    .map(|(_, name)| name.clone())
+                    .unwrap_or_else(|| format!("{hash}"))
+            });
+
+            output.push((&self.source[span.range()], owner));
+        }
+
+        Ok(output)
+    }
+
+    /// Build the source and return the kind and text of every visit, in the
+    /// order in which they appear in the source.
+    fn build(mut self) -> Result<Vec<(&'static str, &'static str)>> {
+        self.compile()?;
+        self.visited.sort_by_key(|(span, _)| span.start);
+        self.visited.dedup();
+
+        Ok(self
+            .visited
+            .iter()
+            .map(|&(span, kind)| (kind, &self.source[span.range()]))
+            .collect())
+    }
+}
+
+impl Visitor {
+    fn compile(&mut self) -> Result<()> {
+        let mut sources = crate::tests::sources(self.source);
+        let mut diagnostics = Diagnostics::new();
+        let context = Context::default();
+
+        let _ = prepare(&mut sources)
+            .with_context(&context)
+            .with_diagnostics(&mut diagnostics)
+            .with_visitor(self)?
+            .build()?;
+
+        Ok(())
+    }
+}
+
+impl compile::CompileVisitor for Visitor {
+    fn visit_variable_use(
+        &mut self,
+        _: rune::SourceId,
+        var_span: &dyn Spanned,
+        span: &dyn Spanned,
+    ) -> Result<(), MetaError> {
+        self.visited.push((var_span.span(), "variable"));
+        self.visited.push((span.span(), "variable use"));
+        Ok(())
+    }
+
+    fn visit_fn_arg(&mut self, location: &dyn Located) -> Result<(), MetaError> {
+        self.push(location, "argument");
+        Ok(())
+    }
+
+    fn visit_field(
+        &mut self,
+        location: &dyn Located,
+        item: &Item,
+        hash: Hash,
+        field: &str,
+    ) -> Result<(), MetaError> {
+        assert_eq!(field, &self.source[location.location().span.range()]);
+        self.push(location, "field");
+        self.owners.push((location.location().span, Some(hash)));
+        self.types.push((hash, item.to_string()));
+        Ok(())
+    }
+
+    fn visit_field_use(
+        &mut self,
+        location: &dyn Located,
+        hash: Option<Hash>,
+        field: &str,
+        shorthand: bool,
+    ) -> Result<(), MetaError> {
+        assert_eq!(field, &self.source[location.location().span.range()]);
+        self.push(location, if shorthand { "shorthand" } else { "use" });
+        self.owners.push((location.location().span, hash));
+        Ok(())
+    }
+}
+
+#[test]
+fn visit_fields() -> Result<()> {
+    let visited = Visitor::new(
+        r#"
+        struct Point { x: i64, y: i64 }
+        enum Shape { Circle { radius: i64 } }
+
+        pub fn main() {
+            let p = Point { x: 1, y: 2 };
+            let radius = p.x;
+            let Point { x, y: z } = p;
+            let c = Shape::Circle { radius };
+            let o = #{ x: 1 };
+            x + z + c.radius + o.y
+        }
+        "#,
+    )
+    .build_only(&["field", "use", "shorthand"])?;
+
+    assert_eq!(
+        visited,
+        [
+            ("field", "x"),
+            ("field", "y"),
+            ("field", "radius"),
+            ("use", "x"),
+            ("use", "y"),
+            ("use", "x"),
+            ("shorthand", "x"),
+            ("use", "y"),
+            ("shorthand", "radius"),
+            ("use", "radius"),
+            ("use", "y"),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn visit_field_owners() -> Result<()> {
+    let owners = Visitor::new(
+        r#"
+        struct Point { x: i64 }
+        struct Size { x: i64 }
+        enum Shape { Circle { x: i64 } }
+
+        pub fn main() {
+            let p = Point { x: 1 };
+            let Size { x } = Size { x: 2 };
+            let c = Shape::Circle { x };
+            let Shape::Circle { x: r } = c;
+            p.x + r
+        }
+        "#,
+    )
+    .build_owners()?;
+
+    let owner = |name: &str| Some(String::from(name));
+
+    assert_eq!(
+        owners,
+        [
+            ("x", owner("Point")),
+            ("x", owner("Size")),
+            ("x", owner("Shape::Circle")),
+            ("x", owner("Point")),
+            ("x", owner("Size")),
+            ("x", owner("Size")),
+            ("x", owner("Shape::Circle")),
+            ("x", owner("Shape::Circle")),
+            ("x", None),
+        ]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn visit_variables() -> Result<()> {
+    let visited = Visitor::new(
+        r#"
+        pub fn sum(first, second) {
+            let total = first + second;
+            let add = |a, b| a + b;
+            (add, total)
+        }
+        "#,
+    )
+    .build_only(&["argument", "variable", "variable use"])?;
+
+    assert_eq!(
+        visited,
+        [
+            ("argument", "first"),
+            ("variable", "first"),
+            ("argument", "second"),
+            ("variable", "second"),
+            ("variable", "total"),
+            ("variable use", "first"),
+            ("variable use", "second"),
+            ("variable", "add"),
+            ("argument", "a"),
+            ("variable", "a"),
+            ("argument", "b"),
+            ("variable", "b"),
+            ("variable use", "a"),
+            ("variable use", "b"),
+            ("variable use", "add"),
+            ("variable use", "total"),
+        ]
+    );
+
+    Ok(())
+}
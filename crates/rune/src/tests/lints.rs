@@ -0,0 +1,252 @@
+prelude!();
+
+use diagnostics::{Diagnostic, FatalDiagnosticKind, Lint, LintLevel};
+use ErrorKind::*;
+use WarningDiagnosticKind::*;
+
+/// Compile the given script with the specified options.
+fn compile(source: &str, options: &Options) -> Result<Diagnostics> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new("main", source)?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(options)
+        .build();
+
+    Ok(diagnostics)
+}
+
+fn script() -> Options {
+    let mut options = Options::default();
+    options.script(true);
+    options
+}
+
+#[test]
+fn unused_imports() {
+    assert_warnings! {
+        "use std::iter::range;",
+        span!(4, 20), UnusedImport { .. }
+    };
+}
+
+#[test]
+fn unused_fields() {
+    assert_warnings! {
+        "struct Point { x: i64, y: i64 } let p = Point { x: 1, y: 2 }; p.x",
+        span!(23, 24), UnusedField { .. }
+    };
+
+    assert_no_warnings(
+        "struct Point { x: i64 } let p = Point { x: 1 }; match p { Point { x } => x }",
+    );
+    assert_no_warnings("pub struct Point { x: i64 } Point { x: 1 }");
+
+    // Fields might be read through protocols or by native functions.
+    assert_no_warnings(
+        "struct Point { x: i64, y: i64 } let p = Point { x: 1, y: 2 }; format!(\"{:?}\", p)",
+    );
+    assert_no_warnings(
+        "struct Point { x: i64, y: i64 } Point { x: 1, y: 2 } == Point { x: 1, y: 2 }",
+    );
+    assert_no_warnings("struct Point { x: i64, y: i64 } dbg(Point { x: 1, y: 2 })");
+}
+
+#[test]
+fn shadowed_bindings() {
+    assert_no_warnings("let a = 1; let a = a + 1; a");
+
+    assert_warnings! {
+        "#![warn(shadowed_bindings)] let a = 1; let a = a + 1; a",
+        span!(43, 44), ShadowedBinding { .. }
+    };
+
+    assert_no_warnings("#![warn(shadowed_bindings)] let _a = 1; let _a = 2; _a");
+}
+
+#[test]
+fn unused_variables() {
+    let diagnostics = compile("let a = 1; let b = 2; b", &script()).unwrap();
+
+    let [Diagnostic::Warning(warning)] = diagnostics.diagnostics() else {
+        panic!("expected a single warning: {diagnostics:?}");
+    };
+
+    assert_eq!(warning.lint(), Lint::UnusedVariables);
+    assert_eq!(warning.suggestions().len(), 1);
+    assert_eq!(warning.suggestions()[0].span(), span!(4, 4));
+    assert_eq!(warning.suggestions()[0].replacement(), "_");
+
+    assert_warnings! {
+        "fn f(a, b) { b } f(1, 2)",
+        span!(5, 6), UnusedVariable { .. }
+    };
+
+    assert_no_warnings("let _a = 1;");
+    assert_no_warnings("let a = 1; let f = || a; f()");
+    assert_no_warnings("let a = 1; `${a}`");
+    assert_no_warnings("#[allow(unused_variables)] fn f(a) {} f(1)");
+}
+
+#[test]
+fn needless_clone() {
+    assert_warnings! {
+        "[1, 2].clone()",
+        span!(0, 14), NeedlessClone { .. }
+    };
+
+    assert_no_warnings("let a = [1, 2]; a.clone()");
+}
+
+#[test]
+fn bool_comparison() {
+    let diagnostics = compile("let a = true; a == false", &script()).unwrap();
+
+    let [Diagnostic::Warning(warning)] = diagnostics.diagnostics() else {
+        panic!("expected a single warning: {diagnostics:?}");
+    };
+
+    assert_eq!(warning.lint(), Lint::BoolComparison);
+    assert_eq!(warning.suggestions().len(), 1);
+    assert_eq!(warning.suggestions()[0].replacement(), "!a");
+
+    assert_warnings! {
+        "let a = true; true != a",
+        span!(14, 23), BoolComparison { .. }
+    };
+}
+
+#[test]
+fn allow_attributes() {
+    assert_no_warnings("#![allow(needless_clone)] [1, 2].clone()");
+    assert_no_warnings("#[allow(needless_clone)] { [1, 2].clone() }");
+    assert_no_warnings("#[allow(unused_imports)] use std::iter::range;");
+
+    assert_no_warnings(
+        "#[allow(unused_fields)] struct Point { x: i64, y: i64 } Point { x: 1, y: 2 }",
+    );
+
+    assert_warnings! {
+        "#[allow(needless_clone)] fn f() { [1].clone() } f(); [2].clone()",
+        span!(53, 64), NeedlessClone { .. }
+    };
+}
+
+#[test]
+fn innermost_attribute_wins() {
+    assert_no_warnings(
+        "#![warn(shadowed_bindings)] let a = 1; #[allow(shadowed_bindings)] { let a = a + 1; a }",
+    );
+
+    assert_warnings! {
+        "#![allow(needless_clone)] #[warn(needless_clone)] { [1].clone() }",
+        span!(52, 63), NeedlessClone { .. }
+    };
+}
+
+#[test]
+fn deny_attribute() {
+    let diagnostics = compile("#![deny(unused_imports)] use std::iter::range;", &script()).unwrap();
+
+    assert!(diagnostics.has_error());
+    assert!(!diagnostics.has_warning());
+
+    let [Diagnostic::Fatal(error)] = diagnostics.diagnostics() else {
+        panic!("expected a single error: {diagnostics:?}");
+    };
+
+    let FatalDiagnosticKind::Lint(warning) = error.kind() else {
+        panic!("expected a denied lint: {error:?}");
+    };
+
+    assert_eq!(warning.lint(), Lint::UnusedImports);
+}
+
+#[test]
+fn options() {
+    let mut options = script();
+    options.lint(Lint::UnusedImports, LintLevel::Allow);
+
+    let diagnostics = compile("use std::iter::range;", &options).unwrap();
+    assert!(diagnostics.is_empty());
+
+    let mut options = script();
+    options.parse_option("lint.needless_clone=deny").unwrap();
+
+    let diagnostics = compile("[1].clone()", &options).unwrap();
+    assert!(diagnostics.has_error());
+
+    // Attributes take precedence over options.
+    let diagnostics = compile("#![allow(needless_clone)] [1].clone()", &options).unwrap();
+    assert!(diagnostics.is_empty());
+
+    assert!(script().parse_option("lint.not_a_lint=deny").is_err());
+    assert!(script()
+        .parse_option("lint.needless_clone=sometimes")
+        .is_err());
+}
+
+#[test]
+fn warnings_disabled() {
+    let mut diagnostics = Diagnostics::without_warnings();
+
+    let _ = crate::tests::compile_helper(
+        "#![deny(needless_clone)] [1].clone(); use std::iter::range;",
+        &mut diagnostics,
+    );
+
+    assert!(diagnostics.has_error());
+    assert!(!diagnostics.has_warning());
+    assert_eq!(diagnostics.diagnostics().len(), 1);
+}
+
+#[test]
+fn unknown_lint() {
+    assert_errors! {
+        "#[allow(not_a_lint)] fn foo() {}",
+        span!(8, 18), UnknownLint { name } => {
+            assert_eq!(&*name, "not_a_lint");
+        }
+    };
+}
+
+#[test]
+fn emit_lint_name() -> Result<()> {
+    let context = Context::with_default_modules()?;
+
+    let mut sources = Sources::new();
+    sources.insert(Source::new(
+        "main",
+        "struct Point { x: i64, y: i64 } let p = Point { x: 1, y: 2 }; p.x",
+    )?)?;
+
+    let mut diagnostics = Diagnostics::new();
+
+    let _ = prepare(&mut sources)
+        .with_context(&context)
+        .with_diagnostics(&mut diagnostics)
+        .with_options(&script())
+        .build();
+
+    let mut out = crate::termcolor::Buffer::no_color();
+    diagnostics.emit(&mut out, &sources)?;
+    let out = core::str::from_utf8(out.as_slice())?;
+
+    assert!(
+        out.contains("warning[unused_fields]: Field is never read"),
+        "{out}"
+    );
+    assert!(out.contains("`#[allow(unused_fields)]`"), "{out}");
+    Ok(())
+}
+
+fn assert_no_warnings(source: &str) {
+    let diagnostics = compile(source, &script()).unwrap();
+    assert!(diagnostics.is_empty(), "{source}: {diagnostics:?}");
+}
@@ -0,0 +1,196 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn test_fragments() {
+    let out: i64 = rune! {
+        macro_rules! square {
+            ($e:expr) => { $e * $e };
+        }
+
+        square!(1 + 2)
+    };
+    assert_eq!(out, 9);
+
+    let out: i64 = rune! {
+        macro_rules! object {
+            ($name:ident = $value:lit) => { #{ $name: $value } };
+        }
+
+        object!(a = 42).a
+    };
+    assert_eq!(out, 42);
+
+    let out: bool = rune! {
+        macro_rules! matches {
+            ($value:expr, $pat:pat) => {
+                match $value { $pat => true, _ => false }
+            };
+        }
+
+        matches!(Some(1), Some(_)) && !matches!(None, Some(_))
+    };
+    assert!(out);
+
+    let out: (i64, String) = rune! {
+        macro_rules! first {
+            ($first:tt $($rest:tt)*) => { $first };
+        }
+
+        (first!(1 2 3), first!("a" [b c]))
+    };
+    assert_eq!(out, (1, String::from("a")));
+}
+
+#[test]
+fn test_literal_tokens() {
+    let out: (i64, i64) = rune! {
+        macro_rules! pick {
+            (left $a:expr, $b:expr) => { $a };
+            (right $a:expr, $b:expr) => { $b };
+        }
+
+        (pick!(left 1, 2), pick!(right 1, 2))
+    };
+    assert_eq!(out, (1, 2));
+}
+
+#[test]
+fn test_repetition() {
+    let out: i64 = rune! {
+        macro_rules! sum {
+            () => { 0 };
+            ($first:expr $(, $rest:expr)*) => { $first + sum!($($rest),*) };
+        }
+
+        sum!(1, 2, 3, 4)
+    };
+    assert_eq!(out, 10);
+
+    let out: Vec<(i64, i64)> = rune! {
+        macro_rules! pairs {
+            ($($k:expr => [$($v:expr),*]);* $(;)?) => { [$($(($k, $v)),*),*] };
+        }
+
+        pairs!(1 => [2, 3]; 4 => [5];)
+    };
+    assert_eq!(out, [(1, 2), (1, 3), (4, 5)]);
+
+    let out: Vec<i64> = rune! {
+        macro_rules! optional {
+            ($a:expr $(, $b:expr)?) => { [$a $(, $b)?] };
+        }
+
+        let a = optional!(1);
+        let b = optional!(1, 2);
+        [a.len(), b.len()]
+    };
+    assert_eq!(out, [1, 2]);
+}
+
+#[test]
+fn test_item_macros() {
+    let out: i64 = rune_n! {
+        (),
+        macro_rules! constant {
+            ($name:ident, $value:expr) => {
+                fn $name() { $value }
+            };
+        }
+
+        constant!(answer, 40 + 2);
+
+        pub fn main() {
+            answer()
+        }
+    };
+    assert_eq!(out, 42);
+}
+
+#[test]
+fn test_statements() {
+    let out: (i64, i64) = rune! {
+        macro_rules! swap {
+            ($a:ident, $b:ident) => {
+                let t = $a;
+                $a = $b;
+                $b = t;
+            };
+        }
+
+        let x = 1;
+        let y = 2;
+        swap!(x, y);
+        (x, y)
+    };
+    assert_eq!(out, (2, 1));
+
+    let out: i64 = rune! {
+        macro_rules! add_to {
+            ($a:ident, $e:expr) => { $a = $a + $e; $a };
+        }
+
+        let x = 1;
+        add_to!(x, 2);
+        add_to!(x, 3)
+    };
+    assert_eq!(out, 6);
+}
+
+#[test]
+fn test_nested_module() {
+    let out: i64 = rune_n! {
+        (),
+        mod inner {
+            macro_rules! double {
+                ($e:expr) => { $e * 2 };
+            }
+
+            pub macro_rules! triple {
+                ($e:expr) => { $e * 3 };
+            }
+
+            pub fn call() {
+                double!(4)
+            }
+        }
+
+        pub fn main() {
+            inner::call() + inner::triple!(1)
+        }
+    };
+    assert_eq!(out, 11);
+}
+
+#[test]
+fn test_errors() {
+    assert_errors! {
+        "macro_rules! m { ($a:expr) => { $b }; } m!(1)",
+        span!(32, 34), MissingMacroVariable { name } => {
+            assert_eq!(&*name, "b");
+        }
+    };
+
+    assert_errors! {
+        "macro_rules! m { ($a:foo) => { $a }; }",
+        span!(21, 24), UnsupportedMacroFragment { name } => {
+            assert_eq!(&*name, "foo");
+        }
+    };
+
+    assert_errors! {
+        "macro_rules! one { (one) => { 1 }; } one!(two)",
+        span!(37, 46), NoMatchingMacroRule { .. }
+    };
+
+    assert_errors! {
+        "macro_rules! forever { ($e:expr) => { forever!($e) }; } forever!(1)",
+        span!(38, 50), MaxMacroRecursion { depth: 64, max: 64 }
+    };
+
+    assert_errors! {
+        "mod inner { macro_rules! m { () => { 1 }; } } inner::m!()",
+        span!(46, 54), NotVisible { .. }
+    };
+}
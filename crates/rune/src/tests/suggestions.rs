@@ -0,0 +1,112 @@
+prelude!();
+
+use ast::Span;
+use diagnostics::Diagnostic;
+use ErrorKind::*;
+
+/// A suggestion as its message, span and replacement.
+type Found = (String, Span, String);
+
+/// Compile the given source and collect the suggestions of every diagnostic.
+fn suggestions(source: &str) -> Vec<Found> {
+    let mut diagnostics = Diagnostics::new();
+    let _ = crate::tests::compile_helper(source, &mut diagnostics);
+
+    let mut suggestions = Vec::new();
+
+    for diagnostic in diagnostics.diagnostics() {
+        let found = match diagnostic {
+            Diagnostic::Fatal(error) => error.suggestions(),
+            Diagnostic::Warning(warning) => warning.suggestions(),
+            _ => &[],
+        };
+
+        for s in found {
+            suggestions.push((
+                String::from(s.message()),
+                s.span(),
+                String::from(s.replacement()),
+            ));
+        }
+    }
+
+    suggestions
+}
+
+#[test]
+fn prefix_unused_variable() {
+    assert_eq!(
+        suggestions("let value = 1;"),
+        [(
+            String::from("Prefix with an underscore"),
+            span!(4, 4),
+            String::from("_")
+        )]
+    );
+}
+
+#[test]
+fn add_missing_await() {
+    let source = "async fn f() -> i64 { 1 } async fn g() { let n: i64 = f(); n } g()";
+
+    assert_eq!(
+        suggestions(source),
+        [(
+            String::from("Add `.await`"),
+            span!(57, 57),
+            String::from(".await")
+        )]
+    );
+
+    assert!(suggestions(
+        "async fn f() -> i64 { 1 } async fn g() { let n: i64 = f().await; n } g()"
+    )
+    .is_empty());
+
+    assert_errors! {
+        "async fn f() -> String { `a` } async fn g() { let n: i64 = f(); n } g()",
+        span!(59, 62), TypeMismatch { actual, .. } => {
+            assert_eq!(&*actual, "::std::future::Future");
+        }
+    };
+}
+
+#[test]
+fn replace_deprecated() {
+    assert_eq!(
+        suggestions("String::from_str(`a`)"),
+        [(
+            String::from("Replace with `String::from`"),
+            span!(0, 16),
+            String::from("String::from")
+        )]
+    );
+}
+
+#[test]
+fn import_after_last_use() {
+    let import = |source| {
+        suggestions(source)
+            .into_iter()
+            .find(|(message, ..)| message == "Import `std::collections::hash_map::HashMap`")
+            .map(|(_, span, replacement)| (span, replacement))
+    };
+
+    assert_eq!(
+        import("HashMap::new()"),
+        Some((
+            span!(0, 0),
+            String::from("use std::collections::hash_map::HashMap;\n")
+        ))
+    );
+
+    assert_eq!(
+        import(
+            "use std::iter::range;\nuse std::iter::empty;\nrange(0, 1); empty(); HashMap::new()"
+        ),
+        Some((
+            span!(43, 43),
+            String::from("\nuse std::collections::hash_map::HashMap;")
+        ))
+    );
+}
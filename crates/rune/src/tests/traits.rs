@@ -0,0 +1,215 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn default_functions() {
+    let out: (i64, i64) = rune! {
+        trait Shape {
+            fn area(self);
+
+            fn scaled(self, factor) {
+                self.area() * factor
+            }
+        }
+
+        struct Square { side: i64 }
+        struct Rect { w: i64, h: i64 }
+
+        impl Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        impl Shape for Rect {
+            fn area(self) {
+                self.w * self.h
+            }
+
+            fn scaled(self, factor) {
+                self.area() * factor + 1
+            }
+        }
+
+        (Square { side: 3 }.scaled(2), Rect { w: 2, h: 5 }.scaled(2))
+    };
+
+    assert_eq!(out, (18, 21));
+}
+
+#[test]
+fn self_in_default_function() {
+    let out: i64 = rune! {
+        mod shapes {
+            pub trait Shape {
+                fn area(self);
+
+                fn doubled(self) {
+                    Self::area(self) * 2
+                }
+            }
+        }
+
+        struct Square { side: i64 }
+
+        impl shapes::Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        Square::doubled(Square { side: 4 })
+    };
+
+    assert_eq!(out, 32);
+}
+
+#[test]
+fn missing_trait_function() {
+    assert_errors! {
+        r#"trait Shape { fn area(self); } struct Circle; impl Shape for Circle {}"#,
+        span!(51, 56), MissingTraitFunction { name, item } => {
+            assert_eq!(&*name, "area");
+            assert_eq!(item, ItemBuf::with_item(["Shape"]).unwrap());
+        }
+    };
+}
+
+#[test]
+fn not_trait_function() {
+    assert_errors! {
+        r#"trait Shape {} struct Circle; impl Shape for Circle { fn area(self) {} }"#,
+        span!(57, 61), NotTraitFunction { name, .. } => {
+            assert_eq!(&*name, "area");
+        }
+    };
+}
+
+#[test]
+fn duplicate_trait_function() {
+    assert_errors! {
+        r#"trait Shape { fn area(self) {} fn area(self) {} }"#,
+        span!(34, 38), Custom { .. }
+    };
+}
+
+#[test]
+fn not_a_trait() {
+    assert_errors! {
+        r#"struct Circle; impl Circle for Circle {}"#,
+        span!(20, 26), ExpectedMeta { .. }
+    };
+}
+
+#[test]
+fn default_function_in_trait_scope() {
+    let out: i64 = rune! {
+        mod shapes {
+            fn unit() {
+                10
+            }
+
+            pub trait Shape {
+                fn area(self);
+
+                fn scaled(self) {
+                    self.area() * unit()
+                }
+            }
+        }
+
+        fn unit() {
+            1
+        }
+
+        struct Square { side: i64 }
+
+        impl shapes::Shape for Square {
+            fn area(self) {
+                self.side * self.side * unit()
+            }
+        }
+
+        Square { side: 2 }.scaled()
+    };
+
+    assert_eq!(out, 40);
+}
+
+#[test]
+fn call_through_trait_path() {
+    let out: (i64, i64, i64) = rune! {
+        mod shapes {
+            pub trait Shape {
+                fn area(self);
+
+                fn scaled(self, factor) {
+                    Shape::area(self) * factor
+                }
+            }
+        }
+
+        struct Square { side: i64 }
+        struct Rect { w: i64, h: i64 }
+
+        impl shapes::Shape for Square {
+            fn area(self) {
+                self.side * self.side
+            }
+        }
+
+        impl shapes::Shape for Rect {
+            fn area(self) {
+                self.w * self.h
+            }
+        }
+
+        let square = Square { side: 3 };
+        let rect = Rect { w: 2, h: 5 };
+
+        (
+            shapes::Shape::area(square),
+            shapes::Shape::area(rect),
+            shapes::Shape::scaled(rect, 2),
+        )
+    };
+
+    assert_eq!(out, (9, 10, 20));
+}
+
+#[test]
+fn native_trait() {
+    let out: i64 = rune! {
+        struct Counter { n: i64 }
+
+        impl std::clone::Clone for Counter {
+            fn clone(self) {
+                Counter { n: self.n + 1 }
+            }
+        }
+
+        std::clone::Clone::clone(Counter { n: 1 }).n
+    };
+
+    assert_eq!(out, 2);
+}
+
+#[test]
+fn not_native_trait_function() {
+    assert_errors! {
+        r#"struct Counter; impl std::clone::Clone for Counter { fn copy(self) {} }"#,
+        span!(56, 60), NotTraitFunction { name, item } => {
+            assert_eq!(&*name, "copy");
+            assert_eq!(item, ItemBuf::with_crate_item("std", ["clone", "Clone"]).unwrap());
+        }
+    };
+}
+
+#[test]
+fn trait_function_without_receiver() {
+    assert_errors! {
+        r#"trait Shape { fn area(self); } Shape::area()"#,
+        span!(42, 44), Custom { .. }
+    };
+}
@@ -0,0 +1,180 @@
+prelude!();
+
+use ErrorKind::*;
+
+#[test]
+fn annotations_are_ignored_at_runtime() {
+    let out: i64 = rune! {
+        struct Point { x: i64, y: i64 }
+
+        fn add(a: i64, b: i64) -> i64 {
+            a + b
+        }
+
+        fn point(p: Point) -> Point {
+            p
+        }
+
+        let n: i64 = add(1, 2);
+        let p: Point = point(Point { x: n, y: 4 });
+        let v: Vec = [p.x, p.y];
+        let (a, b): (i64, i64) = (v[0], v[1]);
+        let f = |c: i64| -> i64 { c * 2 };
+        f(a + b)
+    };
+
+    assert_eq!(out, 14);
+}
+
+#[test]
+fn let_mismatch() {
+    assert_errors! {
+        r#"let s: String = 1;"#,
+        span!(16, 17), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::string::String");
+            assert_eq!(&*actual, "::std::i64");
+        }
+    };
+}
+
+#[test]
+fn assign_mismatch() {
+    assert_errors! {
+        r#"let n: i64 = 1; n = "hello";"#,
+        span!(20, 27), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::i64");
+            assert_eq!(&*actual, "::std::string::String");
+        }
+    };
+}
+
+#[test]
+fn return_mismatch() {
+    assert_errors! {
+        r#"fn f() -> String { return 1; } f()"#,
+        span!(26, 27), TypeMismatch { .. }
+    };
+
+    assert_errors! {
+        r#"fn f() -> String { 1 } f()"#,
+        span!(19, 20), TypeMismatch { .. }
+    };
+}
+
+#[test]
+fn script_call_mismatch() {
+    assert_errors! {
+        r#"fn add(a: i64, b: i64) -> i64 { a + b } let s: String = add(1, 2);"#,
+        span!(56, 65), TypeMismatch { .. }
+    };
+}
+
+#[test]
+fn integer_types_are_compatible() {
+    let out: u64 = rune! {
+        let n: u64 = 1;
+        n
+    };
+
+    assert_eq!(out, 1);
+}
+
+#[test]
+fn not_a_type() {
+    assert_errors! {
+        r#"fn f() -> add {} fn add() {} f()"#,
+        span!(10, 13), ExpectedMeta { .. }
+    };
+}
+
+#[test]
+fn native_call_mismatch() {
+    assert_errors! {
+        r#"std::string::String::from(42)"#,
+        span!(26, 28), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::string::String");
+            assert_eq!(&*actual, "::std::i64");
+        }
+    };
+
+    assert_errors! {
+        r#"let v: Vec = []; let n: String = v.len();"#,
+        span!(33, 40), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::string::String");
+            assert_eq!(&*actual, "::std::u64");
+        }
+    };
+}
+
+#[test]
+fn closure_return_mismatch() {
+    assert_errors! {
+        r#"let f = |a: i64| -> String { a }; f(1)"#,
+        span!(27, 32), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::string::String");
+            assert_eq!(&*actual, "::std::i64");
+        }
+    };
+
+    assert_errors! {
+        r#"let f = |a| -> String { return 1; }; f(1)"#,
+        span!(31, 32), TypeMismatch { .. }
+    };
+}
+
+#[test]
+fn argument_mismatch() {
+    assert_errors! {
+        r#"fn f(s: String) {} f(1)"#,
+        span!(21, 22), TypeMismatch { expected, actual } => {
+            assert_eq!(&*expected, "::std::string::String");
+            assert_eq!(&*actual, "::std::i64");
+        }
+    };
+}
+
+#[test]
+fn missing_type() {
+    assert_errors! {
+        r#"let n: u8 = 1;"#,
+        span!(7, 9), MissingItem { item } => {
+            assert_eq!(item.to_string(), "::std::u8");
+        }
+    };
+
+    assert_errors! {
+        r#"fn f(a: Missing) {} f(1)"#,
+        span!(8, 15), MissingItem { .. }
+    };
+
+    assert_errors! {
+        r#"let n: (i64, Missing) = (1, 2);"#,
+        span!(13, 20), MissingItem { .. }
+    };
+}
+
+#[test]
+fn not_a_type_in_annotations() {
+    assert_errors! {
+        r#"fn add() {} let n: add = 1;"#,
+        span!(19, 22), ExpectedMeta { .. }
+    };
+
+    assert_errors! {
+        r#"fn add() {} fn f(a: add) {} f(1)"#,
+        span!(20, 23), ExpectedMeta { .. }
+    };
+
+    assert_errors! {
+        r#"fn add() {} let f = || -> add { 1 }; f()"#,
+        span!(26, 29), ExpectedMeta { .. }
+    };
+}
+
+#[test]
+fn generic_parameters_are_rejected() {
+    assert_errors! {
+        r#"let v: Vec::<i64> = [];"#,
+        span!(12, 17), Custom { .. }
+    };
+}
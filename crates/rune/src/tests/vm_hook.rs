@@ -0,0 +1,211 @@
+prelude!();
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::runtime::{Inst, VmHook, VmHookControl};
+
+fn vm(source: &str) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let mut sources = Sources::new();
+    sources.insert(Source::memory(source)?)?;
+
+    let unit = prepare(&mut sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[derive(Default)]
+struct Events {
+    events: Mutex<Vec<&'static str>>,
+}
+
+impl Events {
+    fn push(&self, event: &'static str) -> VmHookControl {
+        self.events.lock().unwrap().push(event);
+        VmHookControl::Continue
+    }
+
+    fn take(&self) -> Vec<&'static str> {
+        core::mem::take(&mut *self.events.lock().unwrap())
+    }
+}
+
+impl VmHook for Events {
+    fn enter(&self, _: &Vm, _: usize) -> VmHookControl {
+        self.push("enter")
+    }
+
+    fn exit(&self, _: &Vm, _: usize) -> VmHookControl {
+        self.push("exit")
+    }
+
+    fn await_point(&self, _: &Vm, _: usize) -> VmHookControl {
+        self.push("await")
+    }
+}
+
+#[test]
+fn test_enter_exit() -> Result<()> {
+    let mut vm = vm(r#"
+        fn inner(n) { n + 1 }
+        fn outer(n) { inner(n) * 2 }
+        pub fn main() { outer(1) + inner(2) }
+    "#)?;
+
+    let events = Arc::new(Events::default());
+    vm.set_hook(Some(events.clone()));
+
+    let output: i64 = from_value(vm.call(["main"], ())?)?;
+    assert_eq!(output, 7);
+
+    assert_eq!(
+        events.take(),
+        ["enter", "enter", "enter", "exit", "exit", "enter", "exit", "exit"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_await_is_inherited() -> Result<()> {
+    let mut vm = vm(r#"
+        async fn inner() { 42 }
+        pub async fn main() { inner().await }
+    "#)?;
+
+    let events = Arc::new(Events::default());
+    vm.set_hook(Some(events.clone()));
+
+    let output: i64 = from_value(block_on(vm.async_call(["main"], ()))?)?;
+    assert_eq!(output, 42);
+
+    // The async function is called on a separate virtual machine, which
+    // inherits the hook.
+    assert_eq!(events.take(), ["enter", "await", "enter", "exit", "exit"]);
+    Ok(())
+}
+
+struct Breakpoint {
+    at: AtomicUsize,
+    hits: AtomicUsize,
+}
+
+impl VmHook for Breakpoint {
+    fn instruction(&self, _: &Vm, ip: usize, inst: &Inst) -> VmHookControl {
+        if self.at.load(Ordering::Relaxed) == usize::MAX && matches!(inst, Inst::Call { .. }) {
+            self.at.store(ip, Ordering::Relaxed);
+        }
+
+        if self.at.load(Ordering::Relaxed) == ip {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return VmHookControl::Pause;
+        }
+
+        VmHookControl::Continue
+    }
+}
+
+#[test]
+fn test_pause() -> Result<()> {
+    let mut vm = vm(r#"
+        fn add(a, b) { a + b }
+        pub fn main() { let n = 0; for i in 0..3 { n = add(n, i); } n }
+    "#)?;
+
+    let breakpoint = Arc::new(Breakpoint {
+        at: AtomicUsize::new(usize::MAX),
+        hits: AtomicUsize::new(0),
+    });
+
+    vm.set_hook(Some(breakpoint.clone()));
+
+    let mut execution = vm.execute(["main"], ())?;
+    let mut pauses = 0;
+
+    let output = loop {
+        let result = execution.complete();
+
+        match result.into_result() {
+            Ok(output) => break output,
+            Err(error) => {
+                assert!(error.is_paused(), "{error}");
+                pauses += 1;
+
+                let vm = execution.vm();
+                assert_eq!(vm.ip(), breakpoint.at.load(Ordering::Relaxed));
+            }
+        }
+    };
+
+    assert_eq!(from_value::<i64>(output)?, 3);
+    assert_eq!(pauses, 3);
+    assert_eq!(breakpoint.hits.load(Ordering::Relaxed), 3);
+    Ok(())
+}
+
+struct Abort {
+    aborted: AtomicBool,
+}
+
+impl VmHook for Abort {
+    fn enter(&self, vm: &Vm, _: usize) -> VmHookControl {
+        if vm.call_frames().len() > 4 {
+            self.aborted.store(true, Ordering::Relaxed);
+            return VmHookControl::Abort;
+        }
+
+        VmHookControl::Continue
+    }
+}
+
+#[test]
+fn test_abort() -> Result<()> {
+    let mut vm = vm(r#"
+        fn recurse(n) { recurse(n + 1) }
+        pub fn main() { recurse(0) }
+    "#)?;
+
+    let abort = Arc::new(Abort {
+        aborted: AtomicBool::new(false),
+    });
+
+    vm.set_hook(Some(abort.clone()));
+
+    let error = vm.call(["main"], ()).unwrap_err();
+    assert!(error.to_string().contains("aborted"), "{error}");
+    assert!(abort.aborted.load(Ordering::Relaxed));
+    Ok(())
+}
+
+#[test]
+fn test_async_pause() -> Result<()> {
+    let mut vm = vm(r#"
+        fn add(a, b) { a + b }
+        async fn value(n) { n }
+        pub async fn main() { let n = 0; for i in 0..3 { n = add(n, value(i).await); } n }
+    "#)?;
+
+    let breakpoint = Arc::new(Breakpoint {
+        at: AtomicUsize::new(usize::MAX),
+        hits: AtomicUsize::new(0),
+    });
+
+    vm.set_hook(Some(breakpoint.clone()));
+
+    let mut execution = vm.execute(["main"], ())?;
+    let mut pauses = 0;
+
+    let output = loop {
+        match block_on(execution.async_complete()).into_result() {
+            Ok(output) => break output,
+            Err(error) => {
+                assert!(error.is_paused(), "{error}");
+                pauses += 1;
+            }
+        }
+    };
+
+    assert_eq!(from_value::<i64>(output)?, 3);
+    assert_eq!(pauses, 3);
+    Ok(())
+}
@@ -0,0 +1,47 @@
+prelude!();
+
+use crate::modules::capture_io::{self, CaptureIo};
+
+fn vm(io: &CaptureIo, sources: &mut Sources) -> Result<Vm> {
+    let mut context = Context::with_config(false)?;
+    context.install(capture_io::module(io)?)?;
+    let unit = prepare(sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[tokio::test]
+async fn test_stdin() -> Result<()> {
+    let io = CaptureIo::new();
+    io.push_stdin(b"first\nsecond\r\nthird\nrest\n");
+
+    let mut sources = sources! {
+        entry => {
+            use std::io::stdin;
+
+            pub async fn main() {
+                let first = stdin().read_line().await?;
+
+                let lines = stdin().lines();
+                let second = lines.next_line().await?;
+                let third = lines.next_line().await?;
+
+                let rest = stdin().read_to_string().await?;
+                let end = stdin().read_line().await?;
+                let none = lines.next_line().await?;
+
+                println!("{first:?} {second:?} {third:?} {rest:?} {end:?} {none:?}");
+                Ok(())
+            }
+        }
+    };
+
+    let mut vm = vm(&io, &mut sources)?;
+    let output: Result<(), Value> = from_value(vm.async_call(["main"], ()).await?)?;
+    assert!(output.is_ok());
+
+    assert_eq!(
+        io.drain_utf8()?,
+        "\"first\\n\" Some(\"second\") Some(\"third\") \"rest\\n\" \"\" None\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,166 @@
+prelude!();
+
+use crate::alloc::limit;
+use crate::runtime::budget;
+
+fn vm(sources: &mut Sources) -> Result<Vm> {
+    let context = Context::with_default_modules()?;
+    let unit = prepare(sources).with_context(&context).build()?;
+    Ok(Vm::new(Arc::new(context.runtime()?), Arc::new(unit)))
+}
+
+#[tokio::test]
+async fn test_spawn_with_channels() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            use std::sync::mpsc;
+            use std::task;
+
+            pub async fn main() {
+                let (tx, rx) = mpsc::channel(1);
+
+                let producers = [];
+
+                for n in 0..4 {
+                    let tx = tx.clone();
+                    producers.push(task::spawn(async || tx.send(n).await));
+                }
+
+                drop(tx);
+
+                let sum = 0;
+
+                while let Some(n) = rx.recv().await {
+                    sum += n;
+                }
+
+                for producer in producers {
+                    producer.await?;
+                }
+
+                sum
+            }
+        }
+    };
+
+    let mut vm = vm(&mut sources)?;
+    let output: i64 = from_value(vm.async_call(["main"], ()).await?)?;
+    assert_eq!(output, 6);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_task_inherits_budget() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            use std::task;
+
+            pub async fn main() {
+                let handle = task::spawn(async || {
+                    loop {}
+                });
+
+                handle.await
+            }
+        }
+    };
+
+    let mut vm = vm(&mut sources)?;
+    let error = budget::with(1000, vm.async_call(["main"], ()))
+        .await
+        .unwrap_err();
+
+    assert!(
+        error.to_string().contains("limited"),
+        "unexpected error: {error}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_tasks_split_budget() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            use std::task;
+
+            fn count() {
+                let n = 0;
+
+                while n < 100 {
+                    n += 1;
+                }
+
+                n
+            }
+
+            pub async fn main() {
+                let handles = [];
+
+                for _ in 0..8 {
+                    handles.push(task::spawn(async || count()));
+                }
+
+                let sum = 0;
+
+                for handle in handles {
+                    sum += handle.await;
+                }
+
+                sum
+            }
+        }
+    };
+
+    let mut unlimited = vm(&mut sources)?;
+    let output: i64 = from_value(unlimited.async_call(["main"], ()).await?)?;
+    assert_eq!(output, 800);
+
+    // Each task needs fewer instructions than the budget, but all of them
+    // together need more.
+    let mut vm = vm(&mut sources)?;
+    let error = budget::with(2000, vm.async_call(["main"], ()))
+        .await
+        .unwrap_err();
+
+    assert!(
+        error.to_string().contains("limited"),
+        "unexpected error: {error}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_spawned_task_inherits_memory_limit() -> Result<()> {
+    let mut sources = sources! {
+        entry => {
+            use std::task;
+
+            pub async fn main() {
+                let handle = task::spawn(async || {
+                    let values = [];
+
+                    for n in 0..10000 {
+                        values.push(n);
+                    }
+
+                    values.len()
+                });
+
+                handle.await
+            }
+        }
+    };
+
+    let mut unlimited = vm(&mut sources)?;
+    let output: usize = from_value(unlimited.async_call(["main"], ()).await?)?;
+    assert_eq!(output, 10000);
+
+    let mut vm = vm(&mut sources)?;
+    assert!(limit::with(64 * 1024, vm.async_call(["main"], ()))
+        .await
+        .is_err());
+
+    Ok(())
+}
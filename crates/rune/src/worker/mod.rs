@@ -7,11 +7,11 @@ mod wildcard_import;
 use rust_alloc::rc::Rc;
 
 use crate::alloc::prelude::*;
-use crate::alloc::{self, HashMap, Vec, VecDeque};
+use crate::alloc::{self, HashMap, HashSet, Vec, VecDeque};
 use crate::ast::{self, Kind, Span, Spanned};
-use crate::compile::{self, ItemId, ModId, WithSpan};
+use crate::compile::{self, meta, DynLocation, ItemId, ItemMeta, Location, ModId, WithSpan};
 use crate::grammar::{Node, Stream};
-use crate::indexing::{index, index2};
+use crate::indexing::{index, index2, IndexItem, Indexer, Items, Scopes};
 use crate::macros::{MacroContext, TokenStream};
 use crate::parse::Resolve;
 use crate::query::{
@@ -267,7 +267,11 @@ impl<'a, 'arena> Worker<'a, 'arena> {
         // we might introduce bounds which would not be communicated
         // through `Self`.
         match this.kind {
-            ImplItemKind::Ast { path, functions } => {
+            ImplItemKind::Ast {
+                path,
+                trait_path,
+                functions,
+            } => {
                 let named = self
                     .q
                     .convert_path_with(&path, true, Used::Used, Used::Unused)?;
@@ -287,12 +291,67 @@ impl<'a, 'arena> Worker<'a, 'arena> {
                     GenericsParameters::default(),
                 )?;
 
+                let defaults = match trait_path {
+                    Some(trait_path) => {
+                        self.trait_defaults(this.location, &trait_path, &functions)?
+                    }
+                    None => None,
+                };
+
                 let empty = Rc::default();
                 let mut idx = indexer!(&empty, named, meta);
 
                 for f in functions {
                     index::item_fn(&mut idx, f)?;
                 }
+
+                if let Some((source_id, trait_meta, defaults)) = defaults {
+                    // Default implementations are indexed in the scope of the
+                    // trait, with `Self` referring to the implementing type,
+                    // and are made available as items of the implementing
+                    // type through imports.
+                    let items = Items::new(self.q.pool.item(trait_meta.item))?;
+
+                    let mut idx = Indexer {
+                        q: self.q.borrow(),
+                        root: this.root.as_deref(),
+                        source_id,
+                        items,
+                        scopes: Scopes::new()?,
+                        item: IndexItem::with_impl_item(
+                            trait_meta.module,
+                            trait_meta.item,
+                            meta.item_meta.item,
+                        ),
+                        nested_item: this.nested_item,
+                        macro_depth: this.macro_depth,
+                        loaded: Some(&mut self.loaded),
+                        queue: Some(&mut self.queue),
+                        tree: &empty,
+                    };
+
+                    let mut targets = Vec::new();
+
+                    for f in defaults {
+                        let location = DynLocation::new(source_id, f.span());
+                        let target = index::trait_default_fn(&mut idx, f)?;
+                        targets.try_push((location, target))?;
+                    }
+
+                    let at = self.q.pool.item(meta.item_meta.item).try_to_owned()?;
+
+                    for (location, target) in targets {
+                        self.q.insert_import(
+                            &location,
+                            trait_meta.module,
+                            trait_meta.visibility,
+                            &at,
+                            &target,
+                            None,
+                            false,
+                        )?;
+                    }
+                }
             }
             ImplItemKind::Node { path, functions } => {
                 let named =
@@ -324,6 +383,122 @@ impl<'a, 'arena> Worker<'a, 'arena> {
         Ok(())
     }
 
+    /// Check the functions of a trait implementation against the trait being
+    /// implemented, returning the default implementations of functions which
+    /// are not implemented along with the source they are declared in and the
+    /// trait they belong to.
+    ///
+    /// Native traits don't have default implementations which can be
+    /// provided by scripts, so for them `None` is returned.
+    fn trait_defaults(
+        &mut self,
+        location: Location,
+        trait_path: &ast::Path,
+        functions: &[ast::ItemFn],
+    ) -> compile::Result<Option<(SourceId, ItemMeta, Vec<ast::ItemFn>)>> {
+        let named = self
+            .q
+            .convert_path_with(trait_path, true, Used::Used, Used::Unused)?;
+
+        if let Some((spanned, _)) = named.parameters.into_iter().flatten().next() {
+            return Err(compile::Error::new(
+                spanned.span(),
+                compile::ErrorKind::UnsupportedGenerics,
+            ));
+        }
+
+        let location = DynLocation::new(location.source_id, trait_path);
+        let meta = self
+            .q
+            .lookup_meta(&location, named.item, GenericsParameters::default())?;
+
+        if !matches!(meta.kind, meta::Kind::Trait) {
+            return Err(compile::Error::expected_meta(
+                trait_path,
+                meta.info(self.q.pool)?,
+                "a trait",
+            ));
+        }
+
+        let item = self.q.pool.item(meta.item_meta.item);
+
+        let Some(tr) = self.q.get_trait(meta.item_meta.item) else {
+            for f in functions {
+                let name = f.name.resolve(resolve_context!(self.q))?;
+
+                let declared = self
+                    .q
+                    .context
+                    .lookup_meta(&item.extended(name)?)
+                    .into_iter()
+                    .flatten()
+                    .any(|m| matches!(m.kind, meta::Kind::Function { .. }));
+
+                if !declared {
+                    return Err(compile::Error::new(
+                        f.name,
+                        compile::ErrorKind::NotTraitFunction {
+                            name: name.try_into()?,
+                            item: item.try_to_owned()?,
+                        },
+                    ));
+                }
+            }
+
+            return Ok(None);
+        };
+
+        let tr = tr.try_clone()?;
+
+        let mut declared = HashSet::new();
+
+        for (_, f) in &tr.functions {
+            declared.try_insert(f.name.resolve(resolve_context!(self.q))?)?;
+        }
+
+        let mut implemented = HashSet::new();
+
+        for f in functions {
+            let name = f.name.resolve(resolve_context!(self.q))?;
+
+            if !declared.contains(name) {
+                return Err(compile::Error::new(
+                    f.name,
+                    compile::ErrorKind::NotTraitFunction {
+                        name: name.try_into()?,
+                        item: item.try_to_owned()?,
+                    },
+                ));
+            }
+
+            implemented.try_insert(name)?;
+        }
+
+        let mut defaults = Vec::new();
+
+        for (_, f) in &tr.functions {
+            let name = f.name.resolve(resolve_context!(self.q))?;
+
+            if implemented.contains(name) {
+                continue;
+            }
+
+            let Some(f) = f.to_item_fn()? else {
+                return Err(compile::Error::new(
+                    trait_path,
+                    compile::ErrorKind::MissingTraitFunction {
+                        name: name.try_into()?,
+                        item: item.try_to_owned()?,
+                    },
+                ));
+            };
+
+            defaults.try_push(f)?;
+        }
+
+        Ok(Some((tr.source_id, meta.item_meta, defaults)))
+    }
+
     #[tracing::instrument(skip_all)]
     fn expand_macro_builtin(&mut self, mut this: ExpandMacroBuiltin) -> compile::Result<()> {
         let (name, stream) = this.node.parse(|p| {
@@ -119,6 +119,12 @@ pub(crate) enum WorkspaceErrorKind {
     UnsupportedKey {
         key: String,
     },
+    UnknownLint {
+        name: String,
+    },
+    UnknownLintLevel {
+        level: String,
+    },
     AllocError {
         error: alloc::Error,
     },
@@ -169,6 +175,11 @@ impl fmt::Display for WorkspaceErrorKind {
             ),
             WorkspaceErrorKind::ExpectedTable {} => write!(f, "Expected table"),
             WorkspaceErrorKind::UnsupportedKey { key } => write!(f, "Key `{key}` not supported",),
+            WorkspaceErrorKind::UnknownLint { name } => write!(f, "Unknown lint `{name}`"),
+            WorkspaceErrorKind::UnknownLintLevel { level } => write!(
+                f,
+                "Unknown lint level `{level}`, expected one of `allow`, `warn` or `deny`"
+            ),
             WorkspaceErrorKind::AllocError { error } => error.fmt(f),
         }
     }
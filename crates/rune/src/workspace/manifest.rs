@@ -16,6 +16,7 @@ use crate as rune;
 use crate::alloc::prelude::*;
 use crate::alloc::{self, String, Vec};
 use crate::ast::{Span, Spanned};
+use crate::diagnostics::{Lint, LintLevel};
 use crate::workspace::spanned_value::{Array, SpannedValue, Table, Value};
 use crate::workspace::{
     glob, Diagnostics, SourceLoader, WorkspaceError, WorkspaceErrorKind, MANIFEST_FILE,
@@ -183,6 +184,8 @@ pub struct Package {
     pub auto_examples: bool,
     /// Automatically detect benches.
     pub auto_benches: bool,
+    /// Lint levels configured in the `[lints]` table.
+    pub lints: Vec<(Lint, LintLevel)>,
 }
 
 impl Package {
@@ -309,7 +312,11 @@ impl<'a> Loader<'a> {
             .transpose()?
             .flatten()
         {
-            if let Some(package) = self.load_package(package, span, root)? {
+            if let Some(mut package) = self.load_package(package, span, root)? {
+                if let Some(lints) = table.remove("lints") {
+                    package.lints = self.load_lints(lints)?;
+                }
+
                 self.manifest.packages.try_push(package)?;
             }
         }
@@ -469,9 +476,55 @@ impl<'a> Loader<'a> {
             auto_tests: true,
             auto_examples: true,
             auto_benches: true,
+            lints: Vec::new(),
         }))
     }
 
+    /// Load the `[lints]` table, which maps lint names to their levels.
+    fn load_lints(&mut self, value: SpannedValue) -> alloc::Result<Vec<(Lint, LintLevel)>> {
+        let mut lints = Vec::new();
+
+        let Some((table, _)) = self.ensure_table(value)? else {
+            return Ok(lints);
+        };
+
+        for (key, value) in table {
+            let span = Spanned::span(&value);
+
+            let Some(lint) = Lint::from_name(key.get_ref()) else {
+                self.fatal(WorkspaceError::new(
+                    Spanned::span(&key),
+                    WorkspaceErrorKind::UnknownLint {
+                        name: key.get_ref().as_str().try_into()?,
+                    },
+                ))?;
+                continue;
+            };
+
+            let level = match deserialize::<std::string::String>(value) {
+                Ok(level) => level,
+                Err(error) => {
+                    self.fatal(error)?;
+                    continue;
+                }
+            };
+
+            let Some(level) = LintLevel::from_name(&level) else {
+                self.fatal(WorkspaceError::new(
+                    span,
+                    WorkspaceErrorKind::UnknownLintLevel {
+                        level: level.as_str().try_into()?,
+                    },
+                ))?;
+                continue;
+            };
+
+            lints.try_push((lint, level))?;
+        }
+
+        Ok(lints)
+    }
+
     /// Ensure that a table is empty and mark any additional elements as erroneous.
     fn ensure_empty(&mut self, table: Table) -> alloc::Result<()> {
         for (key, _) in table {